@@ -163,6 +163,7 @@ group "rust-services" {
   # NOTE: Please keep this list sorted in alphabetical order
   targets = [
     "analyzer-dispatcher",
+    "analyzer-execution-sidecar",
     "event-source",
     "generator-dispatcher",
     "generator-execution-sidecar",
@@ -357,6 +358,14 @@ target "event-source" {
   ]
 }
 
+target "analyzer-execution-sidecar" {
+  inherits = ["_rust-base"]
+  target   = "analyzer-execution-sidecar-deploy"
+  tags = [
+    upstream_aware_tag("analyzer-execution-sidecar")
+  ]
+}
+
 target "generator-execution-sidecar" {
   inherits = ["_rust-base"]
   target   = "generator-execution-sidecar-deploy"
@@ -66,7 +66,9 @@ def _container_images(artifacts: ArtifactGetter) -> Mapping[str, DockerImageId]:
 
     return {
         "analyzer-dispatcher": builder.build_with_tag("analyzer-dispatcher"),
-        "analyzer-execution-sidecar": DockerImageId("TODO implement analzyer executor"),
+        "analyzer-execution-sidecar": builder.build_with_tag(
+            "analyzer-execution-sidecar"
+        ),
         "dgraph": DockerImageId("dgraph/dgraph:v21.03.1"),
         "event-source": builder.build_with_tag("event-source"),
         "generator-dispatcher": builder.build_with_tag("generator-dispatcher"),
@@ -189,6 +191,7 @@ def main() -> None:
     kafka_services = (
         "analyzer-dispatcher",
         "analyzer-dispatcher-retry",
+        "analyzer-execution-sidecar",
         "generator-dispatcher",
         "generator-dispatcher-retry",
         "graph-generator",
@@ -243,7 +246,6 @@ def main() -> None:
         rust_log=log_levels.RUST_LOG_LEVELS,
         schema_properties_table_name=dynamodb_tables.schema_properties_table.name,
         schema_table_name=dynamodb_tables.schema_table.name,
-        session_table_name=dynamodb_tables.dynamic_session_table.name,
         test_user_name=config.GRAPL_TEST_USER_NAME,
         user_auth_table=dynamodb_tables.user_auth_table.name,
         user_session_table=dynamodb_tables.user_session_table.name,
@@ -492,6 +494,7 @@ def main() -> None:
 
     grapl_core_vars: Final[NomadVars] = dict(
         event_source_db=event_source_db.to_nomad_service_db_args(),
+        graph_db=graph_db.to_nomad_scylla_args(),
         organization_management_db=organization_management_db.to_nomad_service_db_args(),
         plugin_registry_db=plugin_registry_db.to_nomad_service_db_args(),
         plugin_work_queue_db=plugin_work_queue_db.to_nomad_service_db_args(),
@@ -96,17 +96,6 @@ class DynamoDB(pulumi.ComponentResource):
             hash_key="session_token",
             opts=pulumi.ResourceOptions(parent=self),
         )
-        self.dynamic_session_table = DynamoDBTable(
-            f"{STACK_NAME}-dynamic_session_table",
-            attrs=[
-                {"name": "pseudo_key", "type": "S"},
-                {"name": "create_time", "type": "N"},
-            ],
-            hash_key="pseudo_key",
-            range_key="create_time",
-            opts=pulumi.ResourceOptions(parent=self),
-        )
-        pulumi.export("dynamic-session-table", self.dynamic_session_table.name)
 
         self.register_outputs({})
 
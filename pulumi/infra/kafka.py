@@ -141,6 +141,7 @@ class Kafka(pulumi.ComponentResource):
                 "raw-logs-retry",
                 "generated-graphs",
                 "identified-graphs",
+                "node-validation-reports",
                 "merged-graphs",
                 "merged-graphs-retry",
                 "analyzer-executions",
@@ -41,6 +41,5 @@ def wipe_state(ctx: click.Context, graplctl_state: State) -> None:
         dynamodb=graplctl_state.dynamodb,
         schema_table_name=graplctl_state.schema_table,
         schema_properties_table_name=graplctl_state.schema_properties_table,
-        dynamic_session_table_name=graplctl_state.dynamic_session_table,
     )
     click.echo("Wiped dynamodb")
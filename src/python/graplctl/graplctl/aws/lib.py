@@ -46,12 +46,10 @@ def wipe_dynamodb(
     dynamodb: DynamoDBServiceResource,
     schema_table_name: str,
     schema_properties_table_name: str,
-    dynamic_session_table_name: str,
 ) -> None:
-    session_table = dynamodb.Table(dynamic_session_table_name)
     schema_table = dynamodb.Table(schema_table_name)
     schema_properties_table = dynamodb.Table(schema_properties_table_name)
-    for table in (session_table, schema_table, schema_properties_table):
+    for table in (schema_table, schema_properties_table):
         LOGGER.info(f"Wiping {table}")
         _wipe_dynamodb_table(table)
         LOGGER.info(f"Wiped {table}")
@@ -66,12 +66,6 @@ SUPPORTED_REGIONS = list(
     envvar="GRAPL_SCHEMA_PROPERTIES_TABLE",
     help="The name of the DynamoDB table that holds the schema properties",
 )
-@click.option(
-    "--dynamic-session-table",
-    type=click.STRING,
-    envvar="GRAPL_DYNAMIC_SESSION_TABLE",
-    help="The name of the DynamoDB table that holds dynamic session information",
-)
 @click.pass_context
 def main(
     ctx: click.Context,
@@ -79,7 +73,6 @@ def main(
     stack_name: str,
     schema_table: str,
     schema_properties_table: str,
-    dynamic_session_table: str,
 ) -> None:
     session = boto3.session.Session()
     config = Config(region_name=grapl_region)
@@ -96,7 +89,6 @@ def main(
         ssm=SSMClientFactory(session).from_env(config=config),
         schema_table=schema_table,
         schema_properties_table=schema_properties_table,
-        dynamic_session_table=dynamic_session_table,
     )
 
 
@@ -41,7 +41,6 @@ class State:
     stack_name: str
     schema_table: str
     schema_properties_table: str
-    dynamic_session_table: str
 
     cloudwatch: CloudWatchClient
     dynamodb: DynamoDBServiceResource
@@ -37,6 +37,14 @@ pub struct File {
     file_directory: String,
     #[grapl(immutable)]
     file_inode: u64,
+    #[grapl(immutable)]
+    md5_hash: String,
+    #[grapl(immutable)]
+    sha1_hash: String,
+    #[grapl(immutable)]
+    sha256_hash: String,
+    #[grapl(immutable)]
+    stream_name: String,
 }
 
 impl IFileNode for FileNode {
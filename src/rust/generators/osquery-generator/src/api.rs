@@ -0,0 +1,79 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+
+use crate::{
+    error::OsqueryGeneratorError,
+    parsers,
+};
+
+pub struct OsqueryGenerator {}
+
+#[async_trait::async_trait]
+impl GeneratorApi for OsqueryGenerator {
+    type Error = OsqueryGeneratorError;
+
+    /// Generates a single graph from the osquery result logs in the request.
+    ///
+    /// The request may contain any number of newline-delimited log lines, in either the event,
+    /// batch or snapshot formats. Lines that fail to parse are logged and skipped so that one bad
+    /// line doesn't drop the rest of the batch. An error is only returned if the request contains
+    /// no log lines, or if every line in it failed.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let input_utf8 = std::str::from_utf8(&request.data)?;
+
+        let mut graph_description = GraphDescription::new();
+        let mut line_count = 0;
+        let mut failed_count = 0;
+        let mut first_error = None;
+
+        for line in input_utf8.lines().filter(|line| !line.trim().is_empty()) {
+            line_count += 1;
+
+            match parsers::parse_log_line(line) {
+                Ok(events) => {
+                    for event in events {
+                        let graph: GraphDescription = event.into();
+                        graph_description.merge(&graph);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        message = "skipping osquery log line that failed to parse",
+                        line_number = line_count,
+                        error = %e,
+                    );
+
+                    failed_count += 1;
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        tracing::debug!(
+            message = "completed batch",
+            line_count = line_count,
+            failed_count = failed_count,
+            node_count = graph_description.nodes.len(),
+            edge_count = graph_description.edges.len(),
+        );
+
+        match first_error {
+            None if line_count == 0 => Err(OsqueryGeneratorError::EventNotFound),
+            Some(e) if failed_count == line_count => Err(e.into()),
+            _ => Ok(RunGeneratorResponse {
+                generated_graph: GeneratedGraph { graph_description },
+            }),
+        }
+    }
+}
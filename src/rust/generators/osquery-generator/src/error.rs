@@ -0,0 +1,25 @@
+use rust_proto::protocol::status::Status;
+use thiserror::Error;
+
+/// This represents all possible errors that can occur in this generator.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum OsqueryGeneratorError {
+    #[error("error parsing osquery log {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("no osquery events found")]
+    EventNotFound,
+
+    #[error("error converting bytes to utf-8 {0}")]
+    Utf8Error(#[from] std::str::Utf8Error),
+
+    #[error("error configuring tracing {0}")]
+    SetupTracingError(#[from] grapl_tracing::SetupTracingError),
+}
+
+impl From<OsqueryGeneratorError> for Status {
+    fn from(e: OsqueryGeneratorError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
@@ -1,34 +1,17 @@
-use grapl_tracing::setup_tracing;
-use rust_proto::graplinc::grapl::{
-    api::graph::v1beta1::GraphDescription,
-    pipeline::v1beta1::{
-        Envelope,
-        RawLog,
-    },
+use generator_sdk::server::{
+    self,
+    GeneratorServiceConfig,
 };
-use thiserror::Error;
-
-mod parsers;
-
-#[non_exhaustive]
-#[derive(Debug, Error)]
-pub enum OsqueryGeneratorError {}
+use grapl_tracing::setup_tracing;
+use osquery_generator::api;
 
 const SERVICE_NAME: &'static str = "osquery-generator";
 
 #[tokio::main]
-#[tracing::instrument]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _guard = setup_tracing(SERVICE_NAME)?;
 
-    // TODO: actually do something here
-    Ok(())
-}
-
-// TODO: when we have a plugin SDK, hook this binary into it here
-#[allow(dead_code)]
-async fn event_handler(
-    _event: Envelope<RawLog>,
-) -> Result<Envelope<GraphDescription>, OsqueryGeneratorError> {
-    todo!();
+    let config = GeneratorServiceConfig::from_env_vars();
+    let generator = api::OsqueryGenerator {};
+    server::exec_service(generator, config).await
 }
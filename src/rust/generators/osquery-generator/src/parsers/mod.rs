@@ -1,5 +1,9 @@
 use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
 use serde::Deserialize;
+use serde_json::{
+    Map,
+    Value,
+};
 
 mod grapl_pack;
 
@@ -19,6 +23,7 @@ pub enum OSQueryEvent {
 pub(self) enum OSQueryAction {
     Added,
     Removed,
+    Snapshot,
     Other(String),
 }
 
@@ -31,3 +36,95 @@ impl From<OSQueryEvent> for GraphDescription {
         }
     }
 }
+
+/// Parses a single line of osquery result log output into the events it contains.
+///
+/// osquery writes scheduled query results in one of the following formats, depending on how the
+/// query is configured:
+/// * Event format - one differential result per line, with `columns` and an `added` or `removed`
+///   `action`.
+/// * Batch format - the differential results of a query run, as `diffResults.added` and
+///   `diffResults.removed` lists of rows.
+/// * Snapshot format - every row returned by a query run, as a `snapshot` list of rows.
+///
+/// Batches and snapshots are split into one event per row.
+/// See https://osquery.readthedocs.io/en/stable/deployment/logging/
+pub(crate) fn parse_log_line(line: &str) -> Result<Vec<OSQueryEvent>, serde_json::Error> {
+    let mut log: Map<String, Value> = serde_json::from_str(line)?;
+
+    if log.contains_key("columns") {
+        return Ok(vec![serde_json::from_value(Value::Object(log))?]);
+    }
+
+    let rows: Vec<(&str, Value)> = if let Some(snapshot) = log.remove("snapshot") {
+        rows_with_action("snapshot", snapshot)?
+    } else if let Some(mut diff_results) = log.remove("diffResults") {
+        let added = diff_results
+            .get_mut("added")
+            .map(Value::take)
+            .unwrap_or_default();
+        let removed = diff_results
+            .get_mut("removed")
+            .map(Value::take)
+            .unwrap_or_default();
+
+        let mut rows = rows_with_action("added", added)?;
+        rows.extend(rows_with_action("removed", removed)?);
+        rows
+    } else {
+        return Err(serde::de::Error::custom(
+            "expected one of `columns`, `snapshot` or `diffResults`",
+        ));
+    };
+
+    rows.into_iter()
+        .map(|(action, columns)| {
+            let mut event = log.clone();
+            event.insert("action".to_string(), Value::from(action));
+            event.insert("columns".to_string(), columns);
+
+            serde_json::from_value(Value::Object(event))
+        })
+        .collect()
+}
+
+/// Pairs each row in a list of query results with the `action` it should be reported as.
+fn rows_with_action(action: &str, rows: Value) -> Result<Vec<(&str, Value)>, serde_json::Error> {
+    match rows {
+        Value::Array(rows) => Ok(rows.into_iter().map(|row| (action, row)).collect()),
+        Value::Null => Ok(vec![]),
+        _ => Err(serde::de::Error::custom("expected a list of rows")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_snapshot_log_line() {
+        let line = r#"{"snapshot":[{"cmdline":"","parent":"1","path":"/usr/bin/bash","pid":"9355","time":"1603399763"},{"cmdline":"sleep 1","parent":"9355","path":"/usr/bin/sleep","pid":"9356","time":"1603399764"}],"action":"snapshot","name":"pack_grapl_processes","hostIdentifier":"host","calendarTime":"Thu Oct 22 20:54:42 2020 UTC","unixTime":1603400082,"epoch":0,"counter":0,"numerics":false}"#;
+
+        let events = parse_log_line(line).expect("failed to parse snapshot");
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| matches!(e, OSQueryEvent::Process(_))));
+    }
+
+    #[test]
+    fn parse_batch_log_line() {
+        let line = r#"{"diffResults":{"added":[{"fd":"3","path":"/tmp/a","pid":"9355"}],"removed":[{"fd":"4","path":"/tmp/b","pid":"9355"}]},"name":"pack_grapl_process-files","hostIdentifier":"host","calendarTime":"Thu Oct 22 20:54:42 2020 UTC","unixTime":1603400082,"epoch":0,"counter":1,"numerics":false}"#;
+
+        let events = parse_log_line(line).expect("failed to parse batch");
+
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|e| matches!(e, OSQueryEvent::ProcessFileAction(_))));
+    }
+
+    #[test]
+    fn parse_unknown_log_line() {
+        assert!(parse_log_line(r#"{"name":"pack_grapl_processes"}"#).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use bytes::Bytes;
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use osquery_generator::api::OsqueryGenerator;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        ImmutableUintProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+
+fn find_node<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Option<&'a NodeDescription> {
+    graph.nodes.values().find(|n| {
+        n.properties.iter().any(|(p_name, p_value)| {
+            p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+        })
+    })
+}
+
+/// Reads a sample osquery log and writes it out on a single line, the way osquery logs it.
+fn sample_log_line(path: &str) -> String {
+    let sample = std::fs::read_to_string(path).expect("unable to read sample file");
+    let value: serde_json::Value = serde_json::from_str(&sample).expect("invalid sample file");
+
+    value.to_string()
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_differential_logs_produce_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(OsqueryGenerator {}).await;
+
+    let data = [
+        sample_log_line("sample_data/unit/pack_grapl_processes.json"),
+        sample_log_line("sample_data/unit/pack_grapl_files.json"),
+        sample_log_line("sample_data/unit/pack_grapl_process-files.json"),
+    ]
+    .join("\n");
+
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: Bytes::from(data),
+        })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    let parent_process = find_node(
+        &generated_graph,
+        "process_id",
+        ImmutableUintProp { prop: 8753 }.into(),
+    )
+    .expect("parent process missing");
+
+    let child_process = find_node(
+        &generated_graph,
+        "process_id",
+        ImmutableUintProp { prop: 9355 }.into(),
+    )
+    .expect("child process missing");
+
+    let parent_to_child_edge = generated_graph
+        .edges
+        .get(parent_process.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .find(|edge| edge.to_node_key == child_process.get_node_key())
+        .expect("missing edge from parent to child");
+
+    assert_eq!(parent_to_child_edge.edge_name, "children");
+
+    find_node(
+        &generated_graph,
+        "file_path",
+        ImmutableStrProp {
+            prop: "/usr/bin/bash".to_string(),
+        }
+        .into(),
+    )
+    .expect("process binary missing");
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_snapshot_log_produces_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(OsqueryGenerator {}).await;
+
+    let data = r#"{"snapshot":[{"cmdline":"","parent":"1","path":"/usr/bin/bash","pid":"9355","time":"1603399763"},{"cmdline":"sleep 1","parent":"9355","path":"/usr/bin/sleep","pid":"9356","time":"1603399764"}],"action":"snapshot","name":"pack_grapl_processes","hostIdentifier":"host","calendarTime":"Thu Oct 22 20:54:42 2020 UTC","unixTime":1603400082,"epoch":0,"counter":0,"numerics":false}"#;
+
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: Bytes::from(data),
+        })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    for pid in [9355, 9356] {
+        find_node(
+            &generated_graph,
+            "process_id",
+            ImmutableUintProp { prop: pid }.into(),
+        )
+        .expect("process missing");
+    }
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_invalid_logs_are_rejected(ctx: &mut GeneratorTestContext) -> eyre::Result<()> {
+    let mut client = ctx.get_client(OsqueryGenerator {}).await;
+
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: Bytes::from("not json"),
+        })
+        .await;
+
+    assert!(result.is_err());
+    Ok(())
+}
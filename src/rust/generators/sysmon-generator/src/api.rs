@@ -1,10 +1,12 @@
-use rust_proto::graplinc::grapl::api::plugin_sdk::generators::v1beta1::{
-    server::GeneratorApi,
-    GeneratedGraph,
-    RunGeneratorRequest,
-    RunGeneratorResponse,
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
 };
-use sysmon_parser::SysmonEvent;
 
 use crate::{
     error::SysmonGeneratorError,
@@ -17,41 +19,75 @@ pub struct SysmonGenerator {}
 impl GeneratorApi for SysmonGenerator {
     type Error = SysmonGeneratorError;
 
+    /// Generates a single graph from every Sysmon event in the request.
+    ///
+    /// Events that fail to parse, or that a graph can't be generated from, are logged and skipped
+    /// so that one bad event doesn't drop the rest of the batch. An error is only returned if the
+    /// request contains no events, or if every event in it failed.
     #[tracing::instrument(skip(self, request), err)]
     async fn run_generator(
         &self,
         request: RunGeneratorRequest,
     ) -> Result<RunGeneratorResponse, Self::Error> {
         let input_utf8 = std::str::from_utf8(&request.data)?;
-        let events: Vec<_> = sysmon_parser::parse_events(input_utf8).collect();
-        let sysmon_event: SysmonEvent = expect_one_event(events)?;
+        // Sysmon for Linux may write control characters (ex: from command lines) that aren't
+        // valid XML 1.0, so escape them rather than dropping the event.
+        let input_utf8 = sysmon_parser::escape_invalid_xml_chars(input_utf8);
 
-        match models::generate_graph_from_event(&sysmon_event)? {
-            Some(graph_description) => Ok(RunGeneratorResponse {
-                generated_graph: GeneratedGraph { graph_description },
-            }),
-            None => {
+        let mut graph_description = GraphDescription::new();
+        let mut event_count = 0;
+        let mut failed_count = 0;
+        let mut first_error = None;
+
+        for result in sysmon_parser::parse_events_recovering(&input_utf8) {
+            event_count += 1;
+
+            let sysmon_event = match result {
+                Ok(sysmon_event) => sysmon_event,
+                Err(e) => {
+                    tracing::warn!(
+                        message = "skipping sysmon event that failed to parse",
+                        position = e.position,
+                        error = %e.source,
+                    );
+
+                    failed_count += 1;
+                    first_error.get_or_insert(SysmonGeneratorError::from(e.source));
+                    continue;
+                }
+            };
+
+            match models::generate_graph_from_event(&sysmon_event) {
+                Ok(Some(graph)) => graph_description.merge(&graph),
                 // We do not expect to handle all Sysmon event types.
-                // So we'd just return an empty Graph Description.
-                Ok(RunGeneratorResponse::default())
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        message = "skipping sysmon event that failed graph generation",
+                        event_record_id = sysmon_event.system.event_record_id,
+                        error = %e,
+                    );
+
+                    failed_count += 1;
+                    first_error.get_or_insert(e);
+                }
             }
         }
-    }
-}
 
-pub fn expect_one_event(
-    events: Vec<Result<sysmon_parser::SysmonEvent, sysmon_parser::Error>>,
-) -> Result<sysmon_parser::SysmonEvent, sysmon_parser::Error> {
-    if events.len() != 1 {
-        tracing::warn!(
-            message =
-                "sysmon-generator expects inputs of exactly 1 event - dropping all other events!",
-            found_events = events.len()
+        tracing::debug!(
+            message = "completed batch",
+            event_count = event_count,
+            failed_count = failed_count,
+            node_count = graph_description.nodes.len(),
+            edge_count = graph_description.edges.len(),
         );
+
+        match first_error {
+            None if event_count == 0 => Err(sysmon_parser::Error::SysmonEventNotFound.into()),
+            Some(e) if failed_count == event_count => Err(e),
+            _ => Ok(RunGeneratorResponse {
+                generated_graph: GeneratedGraph { graph_description },
+            }),
+        }
     }
-    // Take first, or EventNotFound
-    events
-        .into_iter()
-        .next()
-        .unwrap_or_else(|| Err(sysmon_parser::Error::SysmonEventNotFound))
 }
@@ -30,20 +30,26 @@ pub(crate) fn generate_graph_from_event(
 
             Some(graph)
         }
-        EventData::NetworkConnect(event_data) => {
-            if event_data.initiated {
-                let graph = network::generate_outbound_connection_subgraph(
-                    &sysmon_event.system,
-                    event_data,
-                )?;
+        EventData::FileCreateStreamHash(event_data) => {
+            let graph =
+                file::generate_file_create_stream_hash_subgraph(&sysmon_event.system, event_data)?;
+
+            Some(graph)
+        }
+        EventData::ProcessTerminate(event_data) => {
+            let graph =
+                process::generate_process_terminate_subgraph(&sysmon_event.system, event_data)?;
 
-                Some(graph)
+            Some(graph)
+        }
+        EventData::NetworkConnect(event_data) => {
+            let graph = if event_data.initiated {
+                network::generate_outbound_connection_subgraph(&sysmon_event.system, event_data)?
             } else {
-                // TODO(inickles): fix graph model for networking and support this
-                tracing::warn!("found inbound connection, which is not currenlty supported");
+                network::generate_inbound_connection_subgraph(&sysmon_event.system, event_data)?
+            };
 
-                None
-            }
+            Some(graph)
         }
         // We do not expect to handle all Sysmon event types
         _ => None,
@@ -77,6 +83,39 @@ fn strip_file_zone_identifier(path: &str) -> &str {
     }
 }
 
+/// File hashes as reported by Sysmon, ex: `MD5=...,SHA256=...,IMPHASH=...`.
+///
+/// Only the algorithms that Sysmon was configured to compute are present.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Hashes<'a> {
+    md5: Option<&'a str>,
+    sha1: Option<&'a str>,
+    sha256: Option<&'a str>,
+}
+
+/// Parses the value of a Sysmon `Hash` or `Hashes` field.
+///
+/// Sysmon reports `-` when no hashes were computed, and unrecognized algorithms (such as
+/// `IMPHASH`) are ignored.
+fn parse_hashes(hashes: &str) -> Hashes<'_> {
+    let mut result = Hashes::default();
+
+    for (algorithm, value) in hashes
+        .split(',')
+        .filter_map(|hash| hash.trim().split_once('='))
+    {
+        if algorithm.eq_ignore_ascii_case("MD5") {
+            result.md5 = Some(value);
+        } else if algorithm.eq_ignore_ascii_case("SHA1") {
+            result.sha1 = Some(value);
+        } else if algorithm.eq_ignore_ascii_case("SHA256") {
+            result.sha256 = Some(value);
+        }
+    }
+
+    result
+}
+
 // TODO(inickles): delete this, do not strip full path and update analyzers accordingly.
 /// Gets the name of the process given a path to the executable.
 fn get_image_name(image_path: &str) -> String {
@@ -103,3 +142,39 @@ fn utc_to_epoch(utc: &DateTime<Utc>) -> Result<u64, SysmonGeneratorError> {
         Ok(ts as u64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hashes_reads_known_algorithms() {
+        assert_eq!(
+            parse_hashes("MD5=A6177D080759CF4A03EF837A38F62401,SHA1=B2,SHA256=79D1,IMPHASH=0000"),
+            Hashes {
+                md5: Some("A6177D080759CF4A03EF837A38F62401"),
+                sha1: Some("B2"),
+                sha256: Some("79D1"),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_hashes_is_case_insensitive_and_trims() {
+        assert_eq!(
+            parse_hashes("md5=AA, sha256=BB"),
+            Hashes {
+                md5: Some("AA"),
+                sha1: None,
+                sha256: Some("BB"),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_hashes_without_hashes() {
+        assert_eq!(parse_hashes("-"), Hashes::default());
+        assert_eq!(parse_hashes(""), Hashes::default());
+        assert_eq!(parse_hashes("IMPHASH=0000"), Hashes::default());
+    }
+}
@@ -1,3 +1,5 @@
 mod create;
+mod create_stream_hash;
 
 pub(crate) use create::generate_file_create_subgraph;
+pub(crate) use create_stream_hash::generate_file_create_stream_hash_subgraph;
@@ -0,0 +1,230 @@
+use endpoint_plugin::{
+    AssetNode,
+    FileNode,
+    IAssetNode,
+    IFileNode,
+    IProcessNode,
+    ProcessNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use sysmon_parser::{
+    event_data::FileCreateStreamHashEventData,
+    System,
+};
+
+use crate::{
+    error::SysmonGeneratorError,
+    models::{
+        get_image_name,
+        parse_hashes,
+        utc_to_epoch,
+    },
+};
+
+/// Creates a subgraph describing a `FileCreateStreamHashEvent`
+///
+/// The subgraph generation for a `FileCreateStreamHashEvent` includes the following:
+/// * A creator `Process` node - denotes the process that created the file stream
+/// * A subject `File` node - the file the stream was created on, with the hashes and stream name
+///   reported by Sysmon
+#[tracing::instrument]
+pub(crate) fn generate_file_create_stream_hash_subgraph(
+    system: &System,
+    event_data: &FileCreateStreamHashEventData<'_>,
+) -> Result<GraphDescription, SysmonGeneratorError> {
+    tracing::trace!("generating graph from event");
+
+    let timestamp = utc_to_epoch(&event_data.utc_time)?;
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(&system.computer)
+        .with_hostname(&system.computer);
+
+    let mut creator = ProcessNode::new(ProcessNode::session_strategy());
+    creator
+        .with_asset_id(&system.computer)
+        .with_process_id(event_data.process_id)
+        .with_process_name(get_image_name(&event_data.image))
+        .with_last_seen_timestamp(timestamp);
+
+    let (file_path, stream_name) = split_stream_name(&event_data.target_filename);
+
+    let mut file = FileNode::new(FileNode::session_strategy());
+    file.with_asset_id(&system.computer)
+        .with_file_path(file_path)
+        .with_last_seen_timestamp(timestamp);
+
+    if let Some(stream_name) = stream_name {
+        file.with_stream_name(stream_name);
+    }
+
+    let hashes = parse_hashes(&event_data.hash);
+
+    if let Some(md5) = hashes.md5 {
+        file.with_md5_hash(md5);
+    }
+
+    if let Some(sha1) = hashes.sha1 {
+        file.with_sha1_hash(sha1);
+    }
+
+    if let Some(sha256) = hashes.sha256 {
+        file.with_sha256_hash(sha256);
+    }
+
+    graph.add_edge(
+        "process_asset",
+        creator.clone_node_key(),
+        asset.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "created_files",
+        creator.clone_node_key(),
+        file.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "files_on_asset",
+        asset.clone_node_key(),
+        file.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(creator);
+    graph.add_node(file);
+
+    Ok(graph)
+}
+
+/// Splits a path to an alternate data stream, ex: `C:\\file.exe:Zone.Identifier`, into the path
+/// of the file and the name of the stream.
+fn split_stream_name(path: &str) -> (&str, Option<&str>) {
+    // only look for the stream separator in the file name so that drive letters are left alone
+    let file_name_start = path
+        .rfind(|c: char| c == '\\' || c == '/')
+        .map_or(0, |i| i + 1);
+
+    match path[file_name_start..].find(':') {
+        Some(i) => {
+            let separator = file_name_start + i;
+
+            (&path[..separator], Some(&path[separator + 1..]))
+        }
+        None => (path, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        ImmutableStrProp,
+        NodeDescription,
+        Property,
+    };
+    use sysmon_parser::EventData;
+
+    use super::*;
+
+    fn find_node<'a>(
+        graph: &'a GraphDescription,
+        o_p_name: &str,
+        o_p_value: Property,
+    ) -> Option<&'a NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.properties.iter().any(|(p_name, p_value)| {
+                p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+            })
+        })
+    }
+
+    #[test]
+    fn file_create_stream_hash() {
+        // Given - A sysmon file stream creation event for a downloaded file
+        // When - We generate a graph from the event
+        // Then - We expect the file node to have the stream name and hashes
+
+        let event = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System><Provider Name='Microsoft-Windows-Sysmon' Guid='{5770385F-C22A-43E0-BF4C-06F5698FFBD9}'/><EventID>15</EventID><Version>2</Version><Level>4</Level><Task>15</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime='2019-07-24T18:05:14.402156600Z'/><EventRecordID>552</EventRecordID><Correlation/><Execution ProcessID='3324' ThreadID='3220'/><Channel>Microsoft-Windows-Sysmon/Operational</Channel><Computer>DESKTOP-FVSHABR</Computer><Security UserID='S-1-5-18'/></System><EventData><Data Name='RuleName'></Data><Data Name='UtcTime'>2019-07-24 18:05:14.399</Data><Data Name='ProcessGuid'>{87E8D3BD-9DDA-5D38-0000-0010A3941D00}</Data><Data Name='ProcessId'>5752</Data><Data Name='Image'>C:\Program Files\Mozilla Firefox\firefox.exe</Data><Data Name='TargetFilename'>C:\Users\grapltest\Downloads\svchost.exe:Zone.Identifier</Data><Data Name='CreationUtcTime'>2019-07-24 18:05:13.101</Data><Data Name='Hash'>MD5=A6177D080759CF4A03EF837A38F62401,SHA256=79D1FFABDD7841D9043D4DDF1F93721BCD35D823614411FD4EAB5D2C16A86F35,IMPHASH=00000000000000000000000000000000</Data><Data Name='Contents'>-</Data><Data Name='User'>DESKTOP-FVSHABR\grapltest</Data></EventData></Event>"#;
+        let event = sysmon_parser::SysmonEvent::from_str(event).unwrap();
+
+        let event_data = match event.event_data {
+            EventData::FileCreateStreamHash(event_data) => event_data,
+            _ => panic!("must be FileCreateStreamHash"),
+        };
+
+        let graph: GraphDescription =
+            generate_file_create_stream_hash_subgraph(&event.system, &event_data)
+                .expect("failed to generate graph");
+
+        let file = find_node(
+            &graph,
+            "file_path",
+            ImmutableStrProp {
+                prop: r"C:\Users\grapltest\Downloads\svchost.exe".to_string(),
+            }
+            .into(),
+        )
+        .expect("file missing");
+
+        let get_str = |name: &str| {
+            file.get_property(name)
+                .map(|p| p.property.clone())
+                .expect("property missing")
+        };
+
+        assert_eq!(
+            get_str("stream_name"),
+            ImmutableStrProp {
+                prop: "Zone.Identifier".to_string()
+            }
+            .into()
+        );
+        assert_eq!(
+            get_str("md5_hash"),
+            ImmutableStrProp {
+                prop: "A6177D080759CF4A03EF837A38F62401".to_string()
+            }
+            .into()
+        );
+        assert_eq!(
+            get_str("sha256_hash"),
+            ImmutableStrProp {
+                prop: "79D1FFABDD7841D9043D4DDF1F93721BCD35D823614411FD4EAB5D2C16A86F35"
+                    .to_string()
+            }
+            .into()
+        );
+        assert!(file.get_property("sha1_hash").is_none());
+    }
+
+    #[test]
+    fn split_stream_name_separates_stream() {
+        assert_eq!(
+            split_stream_name(r"C:\Users\grapltest\Downloads\svchost.exe:Zone.Identifier"),
+            (
+                r"C:\Users\grapltest\Downloads\svchost.exe",
+                Some("Zone.Identifier")
+            )
+        );
+    }
+
+    #[test]
+    fn split_stream_name_ignores_drive_letter() {
+        assert_eq!(
+            split_stream_name(r"C:\Users\grapltest\file.txt"),
+            (r"C:\Users\grapltest\file.txt", None)
+        );
+        assert_eq!(split_stream_name(r"C:\"), (r"C:\", None));
+    }
+
+    #[test]
+    fn split_stream_name_without_directory() {
+        assert_eq!(
+            split_stream_name("file.txt:secret:$DATA"),
+            ("file.txt", Some("secret:$DATA"))
+        );
+        assert_eq!(split_stream_name("file.txt"), ("file.txt", None));
+    }
+}
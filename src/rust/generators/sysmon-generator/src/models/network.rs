@@ -1,3 +1,5 @@
+mod inbound;
 mod outbound;
 
+pub(crate) use inbound::generate_inbound_connection_subgraph;
 pub(crate) use outbound::generate_outbound_connection_subgraph;
@@ -0,0 +1,268 @@
+use endpoint_plugin::{
+    AssetNode,
+    IAssetNode,
+    IIpAddressNode,
+    IIpConnectionNode,
+    IIpPortNode,
+    INetworkConnectionNode,
+    IProcessInboundConnectionNode,
+    IProcessNode,
+    IpAddressNode,
+    IpConnectionNode,
+    IpPortNode,
+    NetworkConnectionNode,
+    ProcessInboundConnectionNode,
+    ProcessNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use sysmon_parser::{
+    event_data::NetworkConnectionEventData,
+    System,
+};
+
+use crate::{
+    error::SysmonGeneratorError,
+    models::utc_to_epoch,
+};
+
+/// Creates a subgraph describing an inbound `NetworkEvent`
+///
+/// For inbound connections Sysmon reports the remote peer as the source and the local process's
+/// address as the destination.
+///
+/// Subgraph generation for an inbound `NetworkEvent` includes the following:
+/// * An `Asset` node - indicating the asset in which the inbound `NetworkEvent` occurred
+/// * A `Process` node - indicating the process which accepted the inbound `NetworkEvent`
+/// * A subject `InboundConnection` node - indicating the network connection accepted by the process
+/// * Source and Destination IP Address and Port nodes
+/// * IP connection and Network connection nodes
+#[tracing::instrument]
+pub(crate) fn generate_inbound_connection_subgraph(
+    system: &System,
+    event_data: &NetworkConnectionEventData<'_>,
+) -> Result<GraphDescription, SysmonGeneratorError> {
+    tracing::trace!("generating graph from event");
+
+    let timestamp = utc_to_epoch(&event_data.utc_time)?;
+
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(&system.computer)
+        .with_hostname(&system.computer);
+
+    // A process accepts an inbound connection on dst_port
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(&system.computer)
+        .with_process_id(event_data.process_id)
+        .with_last_seen_timestamp(timestamp);
+
+    let mut inbound =
+        ProcessInboundConnectionNode::new(ProcessInboundConnectionNode::identity_strategy());
+    inbound
+        .with_asset_id(&system.computer)
+        .with_ip_address(event_data.destination_ip.to_string())
+        .with_protocol(&event_data.protocol)
+        .with_port(event_data.destination_port)
+        .with_created_timestamp(timestamp);
+
+    let mut src_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+    src_ip
+        .with_ip_address(event_data.source_ip.to_string())
+        .with_last_seen_timestamp(timestamp);
+
+    let mut dst_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+    dst_ip
+        .with_ip_address(event_data.destination_ip.to_string())
+        .with_last_seen_timestamp(timestamp);
+
+    let mut src_port = IpPortNode::new(IpPortNode::identity_strategy());
+    src_port
+        .with_ip_address(event_data.source_ip.to_string())
+        .with_port(event_data.source_port)
+        .with_protocol(&event_data.protocol);
+
+    let mut dst_port = IpPortNode::new(IpPortNode::identity_strategy());
+    dst_port
+        .with_ip_address(event_data.destination_ip.to_string())
+        .with_port(event_data.destination_port)
+        .with_protocol(&event_data.protocol);
+
+    let mut network_connection =
+        NetworkConnectionNode::new(NetworkConnectionNode::identity_strategy());
+    network_connection
+        .with_src_ip_address(event_data.source_ip.to_string())
+        .with_src_port(event_data.source_port)
+        .with_dst_ip_address(event_data.destination_ip.to_string())
+        .with_dst_port(event_data.destination_port)
+        .with_protocol(&event_data.protocol)
+        .with_created_timestamp(timestamp);
+
+    let mut ip_connection = IpConnectionNode::new(IpConnectionNode::identity_strategy());
+    ip_connection
+        .with_src_ip_address(event_data.source_ip.to_string())
+        .with_dst_ip_address(event_data.destination_ip.to_string())
+        .with_protocol(&event_data.protocol)
+        .with_created_timestamp(timestamp);
+
+    // An asset is assigned an IP
+    graph.add_edge("asset_ip", asset.clone_node_key(), dst_ip.clone_node_key());
+
+    // A process spawns on an asset
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        process.clone_node_key(),
+    );
+
+    // A process accepts a connection
+    graph.add_edge(
+        "inbound_connections",
+        process.clone_node_key(),
+        inbound.clone_node_key(),
+    );
+
+    // The connection is bound to the local IP + Port
+    graph.add_edge(
+        "bound_port",
+        inbound.clone_node_key(),
+        dst_port.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "bound_ip",
+        inbound.clone_node_key(),
+        dst_ip.clone_node_key(),
+    );
+
+    // There is also a connection between the two IP addresses
+
+    graph.add_edge(
+        "ip_connections",
+        src_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "ip_connections",
+        dst_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        src_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        dst_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(process);
+    graph.add_node(inbound);
+    graph.add_node(src_ip);
+    graph.add_node(dst_ip);
+    graph.add_node(src_port);
+    graph.add_node(dst_port);
+    graph.add_node(network_connection);
+    graph.add_node(ip_connection);
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        ImmutableStrProp,
+        ImmutableUintProp,
+        NodeDescription,
+        Property,
+    };
+    use sysmon_parser::EventData;
+
+    use super::*;
+
+    fn find_node<'a>(
+        graph: &'a GraphDescription,
+        node_type: &str,
+        o_p_name: &str,
+        o_p_value: Property,
+    ) -> Option<&'a NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.node_type == node_type
+                && n.properties.iter().any(|(p_name, p_value)| {
+                    p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+                })
+        })
+    }
+
+    #[test]
+    fn inbound_connection() {
+        // Given - A sysmon network connection event that was not initiated by the local process
+        // When - We generate a graph from the event
+        // Then - We expect the process to have an inbound connection bound to the local port
+
+        let event = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System><Provider Name='Microsoft-Windows-Sysmon' Guid='{5770385F-C22A-43E0-BF4C-06F5698FFBD9}'/><EventID>3</EventID><Version>5</Version><Level>4</Level><Task>3</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime='2019-07-24T18:05:14.402156600Z'/><EventRecordID>551</EventRecordID><Correlation/><Execution ProcessID='3324' ThreadID='3220'/><Channel>Microsoft-Windows-Sysmon/Operational</Channel><Computer>DESKTOP-FVSHABR</Computer><Security UserID='S-1-5-18'/></System><EventData><Data Name='RuleName'></Data><Data Name='UtcTime'>2019-07-24 18:05:14.399</Data><Data Name='ProcessGuid'>{87E8D3BD-9DDA-5D38-0000-0010A3941D00}</Data><Data Name='ProcessId'>4</Data><Data Name='Image'>System</Data><Data Name='User'>NT AUTHORITY\SYSTEM</Data><Data Name='Protocol'>tcp</Data><Data Name='Initiated'>false</Data><Data Name='SourceIsIpv6'>false</Data><Data Name='SourceIp'>10.0.0.12</Data><Data Name='SourceHostname'></Data><Data Name='SourcePort'>50412</Data><Data Name='SourcePortName'></Data><Data Name='DestinationIsIpv6'>false</Data><Data Name='DestinationIp'>10.0.0.5</Data><Data Name='DestinationHostname'></Data><Data Name='DestinationPort'>445</Data><Data Name='DestinationPortName'>microsoft-ds</Data></EventData></Event>"#;
+        let event = sysmon_parser::SysmonEvent::from_str(event).unwrap();
+
+        let event_data = match event.event_data {
+            EventData::NetworkConnect(event_data) => event_data,
+            _ => panic!("must be NetworkConnect"),
+        };
+
+        let graph: GraphDescription =
+            generate_inbound_connection_subgraph(&event.system, &event_data)
+                .expect("failed to generate graph");
+
+        let process = find_node(
+            &graph,
+            "Process",
+            "process_id",
+            ImmutableUintProp { prop: 4 }.into(),
+        )
+        .expect("process missing");
+
+        let inbound = find_node(
+            &graph,
+            "ProcessInboundConnection",
+            "port",
+            ImmutableUintProp { prop: 445 }.into(),
+        )
+        .expect("inbound connection missing");
+
+        let local_port = find_node(
+            &graph,
+            "IpPort",
+            "ip_address",
+            ImmutableStrProp {
+                prop: "10.0.0.5".to_string(),
+            }
+            .into(),
+        )
+        .expect("local port missing");
+
+        let edge_to_inbound = graph
+            .edges
+            .get(process.get_node_key())
+            .iter()
+            .flat_map(|e| e.edges.iter())
+            .find(|e| e.to_node_key == inbound.get_node_key())
+            .expect("missing edge to inbound connection");
+        assert_eq!(edge_to_inbound.edge_name, "inbound_connections");
+
+        let edge_to_port = graph
+            .edges
+            .get(inbound.get_node_key())
+            .iter()
+            .flat_map(|e| e.edges.iter())
+            .find(|e| e.to_node_key == local_port.get_node_key())
+            .expect("missing edge to local port");
+        assert_eq!(edge_to_port.edge_name, "bound_port");
+    }
+}
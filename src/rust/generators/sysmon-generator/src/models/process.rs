@@ -1,3 +1,5 @@
 mod create;
+mod terminate;
 
 pub(crate) use create::generate_process_create_subgraph;
+pub(crate) use terminate::generate_process_terminate_subgraph;
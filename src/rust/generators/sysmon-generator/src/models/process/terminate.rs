@@ -0,0 +1,152 @@
+use endpoint_plugin::{
+    AssetNode,
+    IAssetNode,
+    IProcessNode,
+    ProcessNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use sysmon_parser::{
+    event_data::ProcessTerminatedEventData,
+    System,
+};
+
+use crate::{
+    error::SysmonGeneratorError,
+    models::{
+        get_image_name,
+        utc_to_epoch,
+    },
+};
+
+/// Creates a graph decribing a `ProcessTerminateEvent`.
+///
+/// Graph generation for a `ProcessTerminateEvent` includes the following:
+/// * An `Asset` node - indicating the asset in which the process was running
+/// * A subject `Process` node - indicating the process that terminated, with its termination time
+#[tracing::instrument]
+pub(crate) fn generate_process_terminate_subgraph(
+    system: &System,
+    event_data: &ProcessTerminatedEventData<'_>,
+) -> Result<GraphDescription, SysmonGeneratorError> {
+    tracing::trace!("generating graph from event");
+
+    let timestamp = utc_to_epoch(&event_data.utc_time)?;
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(&system.computer)
+        .with_hostname(&system.computer);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(&system.computer)
+        .with_process_id(event_data.process_id)
+        .with_process_name(get_image_name(&event_data.image))
+        .with_terminated_timestamp(timestamp);
+
+    graph.add_edge(
+        "process_asset",
+        process.clone_node_key(),
+        asset.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(process);
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        ImmutableUintProp,
+        NodeDescription,
+        Property,
+    };
+    use sysmon_parser::EventData;
+
+    use super::*;
+
+    fn find_node<'a>(
+        graph: &'a GraphDescription,
+        o_p_name: &str,
+        o_p_value: Property,
+    ) -> Option<&'a NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.properties.iter().any(|(p_name, p_value)| {
+                p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+            })
+        })
+    }
+
+    #[test]
+    fn process_terminate() {
+        // Given - A sysmon process termination event
+        // When - We generate a graph from the event
+        // Then - We expect a process with its termination time, and an edge to its asset
+
+        let event = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System><Provider Name='Microsoft-Windows-Sysmon' Guid='{5770385F-C22A-43E0-BF4C-06F5698FFBD9}'/><EventID>5</EventID><Version>3</Version><Level>4</Level><Task>5</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime='2019-07-24T18:05:14.402156600Z'/><EventRecordID>553</EventRecordID><Correlation/><Execution ProcessID='3324' ThreadID='3220'/><Channel>Microsoft-Windows-Sysmon/Operational</Channel><Computer>DESKTOP-FVSHABR</Computer><Security UserID='S-1-5-18'/></System><EventData><Data Name='RuleName'></Data><Data Name='UtcTime'>2019-07-24 18:05:14.399</Data><Data Name='ProcessGuid'>{87E8D3BD-9DDA-5D38-0000-0010A3941D00}</Data><Data Name='ProcessId'>5752</Data><Data Name='Image'>C:\Windows\System32\cmd.exe</Data></EventData></Event>"#;
+        let event = sysmon_parser::SysmonEvent::from_str(event).unwrap();
+
+        let event_data = match event.event_data {
+            EventData::ProcessTerminate(event_data) => event_data,
+            _ => panic!("must be ProcessTerminate"),
+        };
+
+        let graph: GraphDescription =
+            generate_process_terminate_subgraph(&event.system, &event_data)
+                .expect("failed to generate graph");
+
+        let process = find_node(
+            &graph,
+            "process_id",
+            ImmutableUintProp { prop: 5752 }.into(),
+        )
+        .expect("process missing");
+
+        assert_eq!(
+            process
+                .get_property("terminated_timestamp")
+                .map(|p| p.property.clone()),
+            Some(
+                ImmutableUintProp {
+                    prop: 1563991514399
+                }
+                .into()
+            )
+        );
+        assert_eq!(
+            process
+                .get_property("process_name")
+                .map(|p| p.property.clone()),
+            Some(
+                ImmutableStrProp {
+                    prop: "cmd.exe".to_string()
+                }
+                .into()
+            )
+        );
+
+        let asset = find_node(
+            &graph,
+            "hostname",
+            ImmutableStrProp {
+                prop: "DESKTOP-FVSHABR".to_string(),
+            }
+            .into(),
+        )
+        .expect("asset missing");
+
+        let edge_to_asset = graph
+            .edges
+            .get(process.get_node_key())
+            .iter()
+            .flat_map(|e| e.edges.iter())
+            .find(|e| e.to_node_key == asset.get_node_key());
+        let edge_to_asset = edge_to_asset.expect("missing edge to asset");
+        assert_eq!(edge_to_asset.edge_name, "process_asset");
+    }
+}
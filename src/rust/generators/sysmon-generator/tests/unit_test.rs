@@ -109,3 +109,53 @@ async fn test_sysmon_event_produces_expected_graph(
     assert_eq!(parent_to_child_edge.edge_name, "children");
     Ok(())
 }
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_sysmon_event_batch_skips_failed_events(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(SysmonGenerator {}).await;
+
+    // two valid events with a malformed one between them
+    let data: Bytes = [
+        log_bytes(),
+        Bytes::from("<Event><System></System></Event>"),
+        log_bytes(),
+    ]
+    .concat()
+    .into();
+
+    let result = client.run_generator(RunGeneratorRequest { data }).await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    let child_processes = generated_graph
+        .nodes
+        .values()
+        .filter(|n| {
+            n.get_property("process_id")
+                .map(|p| p.property.clone() == ImmutableUintProp { prop: 5752 }.into())
+                .unwrap_or(false)
+        })
+        .count();
+
+    assert_eq!(child_processes, 2);
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_sysmon_event_batch_fails_when_every_event_fails(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(SysmonGenerator {}).await;
+
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: Bytes::from("<Event><System></System></Event>"),
+        })
+        .await;
+
+    assert!(result.is_err());
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
 
 use rust_proto::{
     graplinc::grapl::{
@@ -10,7 +13,11 @@ use rust_proto::{
                     CreateEdgeResponse,
                     CreateNodeRequest,
                     CreateNodeResponse,
+                    ExistingNode,
+                    MutateGraphRequest,
+                    MutateGraphResponse,
                     MutationRedundancy,
+                    NodeRef,
                     SetNodePropertyRequest,
                     SetNodePropertyResponse,
                 },
@@ -28,7 +35,16 @@ use rust_proto::{
     protocol::status::Status,
 };
 use scylla::{
+    batch::{
+        Batch,
+        BatchType,
+    },
+    frame::value::ValueList,
     query::Query,
+    transport::errors::{
+        DbError,
+        QueryError,
+    },
     CachingSession,
 };
 use uid_allocator::client::CachingUidAllocatorServiceClient as UidAllocatorClient;
@@ -47,6 +63,7 @@ use crate::{
         MAX_U_64_TABLE_NAME,
         MIN_I_64_TABLE_NAME,
         MIN_U_64_TABLE_NAME,
+        NODES_BY_TYPE_TABLE_NAME,
     },
     write_dropper::WriteDropper,
 };
@@ -58,9 +75,34 @@ pub enum GraphMutationManagerError {
     #[error("Allocated Zero Uid")]
     ZeroUid,
     #[error("Scylla Error: {0}")]
-    ScyllaError(#[from] scylla::transport::errors::QueryError),
+    ScyllaError(#[from] QueryError),
     #[error("ReverseEdgeResolverError: {0}")]
     ReverseEdgeResolverError(#[from] ReverseEdgeResolverError),
+    #[error("Duplicate node_key in request: {0}")]
+    DuplicateNodeKey(String),
+    #[error("Edge refers to unknown node_key: {0}")]
+    UnknownNodeKey(String),
+}
+
+/// Tenants provisioned before `nodes_by_type` existed don't have the table
+/// until they're provisioned again, which also backfills it from `node_type`.
+/// Until then their nodes are still written, they just can't be found by type.
+fn tolerate_missing_nodes_by_type(
+    result: Result<(), GraphMutationManagerError>,
+) -> Result<(), GraphMutationManagerError> {
+    match result {
+        Err(GraphMutationManagerError::ScyllaError(QueryError::DbError(
+            DbError::Invalid,
+            message,
+        ))) if message.contains("unconfigured table") => {
+            tracing::warn!(
+                message = "nodes_by_type is missing, the tenant needs to be provisioned again",
+                error = %message,
+            );
+            Ok(())
+        }
+        result => result,
+    }
 }
 
 impl From<GraphMutationManagerError> for Status {
@@ -79,11 +121,70 @@ impl From<GraphMutationManagerError> for Status {
                 Status::internal(format!("UidAllocatorClient error: {e:?}"))
             }
             GraphMutationManagerError::ZeroUid => Status::failed_precondition("Allocated Zero Uid"),
+            GraphMutationManagerError::DuplicateNodeKey(_)
+            | GraphMutationManagerError::UnknownNodeKey(_) => {
+                Status::invalid_argument(e.to_string())
+            }
             e => Status::internal(e.to_string()),
         }
     }
 }
 
+/// Upper bound on the statements sent in a single batch, to keep batches
+/// under Scylla's batch size thresholds
+const MAX_BATCH_STATEMENTS: usize = 100;
+
+/// The rows written by a single `MutateGraph` request, grouped by table
+#[derive(Default)]
+struct GraphWrites {
+    node_types: Vec<(i64, String)>,
+    max_u64: Vec<(i64, String, i64, i64)>,
+    min_u64: Vec<(i64, String, i64, i64)>,
+    imm_u64: Vec<(i64, String, i64)>,
+    max_i64: Vec<(i64, String, i64, i64)>,
+    min_i64: Vec<(i64, String, i64, i64)>,
+    imm_i64: Vec<(i64, String, i64)>,
+    imm_string: Vec<(i64, String, String)>,
+    edges: Vec<(i64, i64, String, String)>,
+}
+
+impl GraphWrites {
+    fn add_property(&mut self, uid: Uid, property_name: PropertyName, property: Property) {
+        let uid = uid.as_i64();
+        let property_name = property_name.value;
+        // Timestamps are bound per statement so that, as with the single
+        // property upserts, the largest (or smallest) value always wins
+        match property {
+            Property::IncrementOnlyUintProp(property) => {
+                let value = property.prop as i64;
+                self.max_u64.push((uid, property_name, value, value));
+            }
+            Property::DecrementOnlyUintProp(property) => {
+                let value = property.prop as i64;
+                self.min_u64.push((uid, property_name, value, -value));
+            }
+            Property::ImmutableUintProp(property) => {
+                self.imm_u64
+                    .push((uid, property_name, property.prop as i64));
+            }
+            Property::IncrementOnlyIntProp(property) => {
+                let value = property.prop;
+                self.max_i64.push((uid, property_name, value, value));
+            }
+            Property::DecrementOnlyIntProp(property) => {
+                let value = property.prop;
+                self.min_i64.push((uid, property_name, value, -value));
+            }
+            Property::ImmutableIntProp(property) => {
+                self.imm_i64.push((uid, property_name, property.prop));
+            }
+            Property::ImmutableStrProp(property) => {
+                self.imm_string.push((uid, property_name, property.prop));
+            }
+        }
+    }
+}
+
 pub struct GraphMutationManager {
     scylla_client: Arc<CachingSession>,
     uid_allocator_client: UidAllocatorClient,
@@ -118,6 +219,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_max_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -154,6 +256,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_min_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -191,6 +294,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_imm_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
@@ -225,6 +329,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_max_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -260,6 +365,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_min_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -296,6 +402,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_imm_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
@@ -335,9 +442,23 @@ impl GraphMutationManager {
                 ));
 
                 self.scylla_client
-                    .execute(query, &(uid.as_i64(), node_type.value))
+                    .execute(query, &(uid.as_i64(), &node_type.value))
                     .await?;
-                Ok(())
+
+                let query = Query::new(format!(
+                    r"
+                        INSERT INTO {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME} (uid, node_type)
+                        VALUES (?, ?)
+                    "
+                ));
+
+                tolerate_missing_nodes_by_type(
+                    self.scylla_client
+                        .execute(query, &(uid.as_i64(), node_type.value))
+                        .await
+                        .map(|_| ())
+                        .map_err(GraphMutationManagerError::from),
+                )
             })
             .await
     }
@@ -354,6 +475,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_imm_string(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
@@ -442,6 +564,144 @@ impl GraphMutationManager {
     }
 }
 
+impl GraphMutationManager {
+    /// Executes `statement` once for each of `values`, split across as many
+    /// unlogged batches as needed. Every statement we batch is idempotent.
+    async fn execute_batched<V: ValueList>(
+        &self,
+        statement: &str,
+        values: &[V],
+    ) -> Result<(), GraphMutationManagerError> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        // Statements are prepared through the session's cache, the same one
+        // `execute` uses, so each is only prepared the first time it's seen
+        let prepared = self
+            .scylla_client
+            .add_prepared_statement(&Query::from(statement.to_owned()))
+            .await?;
+
+        for chunk in values.chunks(MAX_BATCH_STATEMENTS) {
+            let mut batch = Batch::new(BatchType::Unlogged);
+            batch.statements.reserve(chunk.len());
+            for _ in chunk {
+                batch.append_statement(prepared.clone());
+            }
+            batch.set_is_idempotent(true);
+
+            self.scylla_client.session.batch(&batch, chunk).await?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, writes), err)]
+    async fn write_graph(
+        &self,
+        tenant_id: uuid::Uuid,
+        writes: GraphWrites,
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        self.execute_batched(
+            &format!("INSERT INTO {tenant_ks}.node_type (uid, node_type) VALUES (?, ?)"),
+            &writes.node_types,
+        )
+        .await?;
+        tolerate_missing_nodes_by_type(
+            self.execute_batched(
+                &format!(
+                    "INSERT INTO {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME} (uid, node_type) VALUES (?, ?)"
+                ),
+                &writes.node_types,
+            )
+            .await,
+        )?;
+
+        for (table_name, values) in [
+            (MAX_U_64_TABLE_NAME, &writes.max_u64),
+            (MIN_U_64_TABLE_NAME, &writes.min_u64),
+            (MAX_I_64_TABLE_NAME, &writes.max_i64),
+            (MIN_I_64_TABLE_NAME, &writes.min_i64),
+        ] {
+            self.execute_batched(
+                &format!(
+                    r"
+                    INSERT INTO {tenant_ks}.{table_name}
+                    (uid, populated_field, value)
+                    VALUES (?, ?, ?)
+                    USING TIMESTAMP ?
+                    "
+                ),
+                values,
+            )
+            .await?;
+        }
+
+        for (table_name, values) in [
+            (IMM_U_64_TABLE_NAME, &writes.imm_u64),
+            (IMM_I_64_TABLE_NAME, &writes.imm_i64),
+        ] {
+            self.execute_batched(
+                &format!(
+                    r"
+                    INSERT INTO {tenant_ks}.{table_name}
+                    (uid, populated_field, value)
+                    VALUES (?, ?, ?)
+                    "
+                ),
+                values,
+            )
+            .await?;
+        }
+
+        self.execute_batched(
+            &format!(
+                r"
+                INSERT INTO {tenant_ks}.{IMM_STRING_TABLE_NAME}
+                (uid, populated_field, value)
+                VALUES (?, ?, ?)
+                "
+            ),
+            &writes.imm_string,
+        )
+        .await?;
+
+        self.execute_batched(
+            &format!(
+                r"
+                INSERT INTO {tenant_ks}.edges (
+                    source_uid,
+                    destination_uid,
+                    f_edge_name,
+                    r_edge_name
+                )
+                VALUES (?, ?, ?, ?)
+                "
+            ),
+            &writes.edges,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Looks up the uid and type of a node referred to by a `MutateGraph` edge
+fn resolve_node_ref(
+    allocated_nodes: &HashMap<String, (Uid, NodeType)>,
+    node_ref: NodeRef,
+) -> Result<(Uid, NodeType), GraphMutationManagerError> {
+    match node_ref {
+        NodeRef::NodeKey(node_key) => allocated_nodes
+            .get(&node_key)
+            .cloned()
+            .ok_or(GraphMutationManagerError::UnknownNodeKey(node_key)),
+        NodeRef::ExistingNode(ExistingNode { uid, node_type }) => Ok((uid, node_type)),
+    }
+}
+
 #[async_trait::async_trait]
 impl GraphMutationApi for GraphMutationManager {
     type Error = GraphMutationManagerError;
@@ -553,4 +813,104 @@ impl GraphMutationApi for GraphMutationManager {
             mutation_redundancy: MutationRedundancy::Maybe,
         })
     }
+
+    /// MutateGraph creates every node in the request, sets the properties of
+    /// both those nodes and any existing nodes in the request, and creates the
+    /// edges between them. Uids are allocated in bulk, reverse edges are
+    /// resolved once per source node type and edge name, and the writes are
+    /// issued as Scylla batches.
+    ///
+    /// Unlike the single-item rpcs, these writes bypass the write dropper,
+    /// which only pays off for writes that are repeated.
+    ///
+    /// A new uid is allocated for every node each time a request is handled,
+    /// so retrying a request that failed partway through writes its nodes
+    /// again under new uids, leaving the partial write in place.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn mutate_graph(
+        &self,
+        request: MutateGraphRequest,
+    ) -> Result<MutateGraphResponse, Self::Error> {
+        let MutateGraphRequest {
+            tenant_id,
+            nodes,
+            edges,
+            existing_nodes,
+        } = request;
+
+        let allocated = self
+            .uid_allocator_client
+            .allocate_ids(tenant_id, nodes.len())
+            .await?;
+
+        let mut writes = GraphWrites::default();
+        let mut allocated_nodes: HashMap<String, (Uid, NodeType)> =
+            HashMap::with_capacity(nodes.len());
+        for (node, uid) in nodes.into_iter().zip(allocated) {
+            let uid = Uid::from_u64(uid).ok_or(GraphMutationManagerError::ZeroUid)?;
+            if allocated_nodes.contains_key(&node.node_key) {
+                return Err(GraphMutationManagerError::DuplicateNodeKey(node.node_key));
+            }
+
+            writes
+                .node_types
+                .push((uid.as_i64(), node.node_type.value.clone()));
+            for property in node.properties {
+                writes.add_property(uid, property.property_name, property.property.property);
+            }
+            allocated_nodes.insert(node.node_key, (uid, node.node_type));
+        }
+
+        for existing_node in existing_nodes {
+            for property in existing_node.properties {
+                writes.add_property(
+                    existing_node.node.uid,
+                    property.property_name,
+                    property.property.property,
+                );
+            }
+        }
+
+        let mut reverse_edge_names: HashMap<(NodeType, EdgeName), EdgeName> = HashMap::new();
+        for edge in edges {
+            let (from_uid, source_node_type) = resolve_node_ref(&allocated_nodes, edge.from)?;
+            let (to_uid, _) = resolve_node_ref(&allocated_nodes, edge.to)?;
+
+            let key = (source_node_type, edge.edge_name);
+            let reverse_edge_name = match reverse_edge_names.get(&key) {
+                Some(reverse_edge_name) => reverse_edge_name.clone(),
+                None => {
+                    let reverse_edge_name = self
+                        .reverse_edge_resolver
+                        .resolve_reverse_edge(tenant_id, key.0.clone(), key.1.clone())
+                        .await?;
+                    reverse_edge_names.insert(key.clone(), reverse_edge_name.clone());
+                    reverse_edge_name
+                }
+            };
+            let edge_name = key.1;
+
+            writes.edges.push((
+                from_uid.as_i64(),
+                to_uid.as_i64(),
+                edge_name.value.clone(),
+                reverse_edge_name.value.clone(),
+            ));
+            writes.edges.push((
+                to_uid.as_i64(),
+                from_uid.as_i64(),
+                reverse_edge_name.value,
+                edge_name.value,
+            ));
+        }
+
+        self.write_graph(tenant_id, writes).await?;
+
+        Ok(MutateGraphResponse {
+            uids: allocated_nodes
+                .into_iter()
+                .map(|(node_key, (uid, _))| (node_key, uid))
+                .collect(),
+        })
+    }
 }
@@ -5,6 +5,8 @@ pub const MAX_U_64_TABLE_NAME: &str = "max_u64";
 pub const MIN_U_64_TABLE_NAME: &str = "min_u64";
 pub const IMM_U_64_TABLE_NAME: &str = "imm_u64";
 pub const IMM_STRING_TABLE_NAME: &str = "imm_string";
+/// The uids of each node type, for scanning the nodes of one type
+pub const NODES_BY_TYPE_TABLE_NAME: &str = "nodes_by_type";
 
 pub fn tenant_keyspace_name(tenant_id: uuid::Uuid) -> String {
     // scylla keyspace names must be alphanumeric + underscores, and max out at 48.
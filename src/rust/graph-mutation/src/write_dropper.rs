@@ -36,6 +36,7 @@ struct NodeTypeKey {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct PropertyKey {
     tenant_id: uuid::Uuid,
+    uid: Uid,
     node_type: NodeType,
     property_name: PropertyName,
 }
@@ -87,6 +88,7 @@ impl WriteDropper {
     pub async fn check_max_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
@@ -97,6 +99,7 @@ impl WriteDropper {
     {
         match self.max_i64.entry(PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         }) {
@@ -119,6 +122,7 @@ impl WriteDropper {
     pub async fn check_min_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
@@ -129,6 +133,7 @@ impl WriteDropper {
     {
         match self.min_i64.entry(PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         }) {
@@ -150,6 +155,7 @@ impl WriteDropper {
     pub async fn check_imm_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -159,6 +165,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -173,6 +180,7 @@ impl WriteDropper {
     pub async fn check_max_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
@@ -183,6 +191,7 @@ impl WriteDropper {
     {
         match self.max_u64.entry(PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         }) {
@@ -205,6 +214,7 @@ impl WriteDropper {
     pub async fn check_min_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
@@ -215,6 +225,7 @@ impl WriteDropper {
     {
         match self.min_u64.entry(PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         }) {
@@ -236,6 +247,7 @@ impl WriteDropper {
     pub async fn check_imm_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -245,6 +257,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -259,6 +272,7 @@ impl WriteDropper {
     pub async fn check_imm_string<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -268,6 +282,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -324,3 +339,81 @@ impl WriteDropper {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    use super::*;
+
+    async fn write_imm_string(
+        write_dropper: &WriteDropper,
+        writes: &AtomicUsize,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) -> Result<(), ()> {
+        write_dropper
+            .check_imm_string(
+                tenant_id,
+                uid,
+                NodeType::try_from("Process").unwrap(),
+                PropertyName::try_from("process_name").unwrap(),
+                || async {
+                    writes.fetch_add(1, Ordering::SeqCst);
+                    Ok::<(), ()>(())
+                },
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_imm_string_is_written_once_per_uid() -> Result<(), ()> {
+        let write_dropper = WriteDropper::new(100);
+        let writes = AtomicUsize::new(0);
+        let tenant_id = uuid::Uuid::new_v4();
+        let first = Uid::from_u64(1).unwrap();
+        let second = Uid::from_u64(2).unwrap();
+
+        write_imm_string(&write_dropper, &writes, tenant_id, first).await?;
+        write_imm_string(&write_dropper, &writes, tenant_id, first).await?;
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+        // A second node of the same type gets its own write
+        write_imm_string(&write_dropper, &writes, tenant_id, second).await?;
+        assert_eq!(writes.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_u64_is_tracked_per_uid() -> Result<(), ()> {
+        let write_dropper = WriteDropper::new(100);
+        let writes = AtomicUsize::new(0);
+        let tenant_id = uuid::Uuid::new_v4();
+
+        for (uid, value) in [(1, 10), (2, 5), (1, 5), (2, 6)] {
+            write_dropper
+                .check_max_u64(
+                    tenant_id,
+                    Uid::from_u64(uid).unwrap(),
+                    NodeType::try_from("Process").unwrap(),
+                    PropertyName::try_from("process_id").unwrap(),
+                    value,
+                    || async {
+                        writes.fetch_add(1, Ordering::SeqCst);
+                        Ok::<(), ()>(())
+                    },
+                )
+                .await?;
+        }
+
+        // (1, 5) is dropped for being below uid 1's max, while (2, 5) and
+        // (2, 6) are not held back by uid 1's
+        assert_eq!(writes.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use std::net::SocketAddr;
 
+use rust_proto::client_factory::services::GraphSchemaManagerClientConfig;
+
 #[derive(clap::Parser, Clone, Debug)]
 #[clap(name = "graph-generator", about = "Graph Generator Service")]
 pub struct GraphDbConfig {
@@ -21,6 +23,24 @@ pub struct GraphQueryServiceConfig {
     /// The address to bind the graph query service to
     pub graph_query_service_bind_address: SocketAddr,
 
+    #[clap(env, long, default_value = "100000")]
+    /// The maximum number of immutable properties of each type (string, int,
+    /// uint) to cache
+    pub graph_query_property_cache_capacity: u64,
+
+    #[clap(env, long, default_value = "600")]
+    /// How long a cached property is kept before being evicted.
+    /// Unit: Seconds
+    pub graph_query_property_cache_ttl_secs: u64,
+
+    #[clap(env, long, default_value = "5000")]
+    /// How long a single query may run before it's abandoned.
+    /// Unit: Milliseconds
+    pub graph_query_timeout_ms: u64,
+
     #[clap(flatten)]
     pub graph_db_config: GraphDbConfig,
+
+    #[clap(flatten)]
+    pub graph_schema_manager_client_config: GraphSchemaManagerClientConfig,
 }
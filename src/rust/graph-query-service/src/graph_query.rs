@@ -1,9 +1,16 @@
-use futures::future::join_all;
+use std::future::Future;
+
+use futures::{
+    stream::FuturesUnordered,
+    StreamExt,
+};
 pub use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages::StringCmp;
 use rust_proto::graplinc::grapl::{
     api::graph_query_service::v1beta1::messages::{
         GraphQuery,
         GraphView,
+        MatchedGraphWithUid,
+        QueryId,
     },
     common::v1beta1::types::Uid,
 };
@@ -13,8 +20,12 @@ use crate::{
         fetch_node_with_edges,
         NodeQueryError,
     },
-    property_query::PropertyQueryExecutor,
+    property_query::{
+        PropertyQueryError,
+        PropertyQueryExecutor,
+    },
     short_circuit::ShortCircuit,
+    traversal_budget::TraversalBudget,
     visited::Visited,
 };
 
@@ -22,8 +33,19 @@ use crate::{
 pub enum GraphQueryError {
     #[error("Node query failed (uid: '{uid:?}'): {source}")]
     NodeQueryError { uid: Uid, source: NodeQueryError },
+    #[error("Failed to scan for candidate nodes: {0}")]
+    CandidateScanError(#[from] PropertyQueryError),
+    #[error("The root query {0:?} isn't one of the query's node queries")]
+    MissingRootQuery(QueryId),
 }
 
+/// Used when a search doesn't specify a page size
+pub const DEFAULT_SEARCH_PAGE_SIZE: u32 = 100;
+/// Upper bound on the candidates a single search page may scan
+pub const MAX_SEARCH_PAGE_SIZE: u32 = 1000;
+/// How many of a search page's candidates are queried at once
+pub const MAX_CONCURRENT_CANDIDATE_QUERIES: usize = 16;
+
 #[tracing::instrument(skip(graph_query, property_query_executor))]
 pub async fn query_graph(
     graph_query: &GraphQuery,
@@ -31,12 +53,16 @@ pub async fn query_graph(
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
 ) -> Result<Option<(GraphView, Uid)>, GraphQueryError> {
-    let mut query_handles = Vec::with_capacity(graph_query.node_property_queries.len());
+    let query_handles = FuturesUnordered::new();
     let x_query_short_circuiter = ShortCircuit::new();
+    // Shared, so that the limits bound the whole query rather than each of
+    // the traversals it starts
+    let budget = TraversalBudget::new(graph_query.limits);
     for node_query in graph_query.node_property_queries.values() {
         let property_query_executor = property_query_executor.clone();
         let node_query = node_query.clone();
         let x_query_short_circuiter = x_query_short_circuiter.clone();
+        let budget = budget.clone();
         query_handles.push(async move {
             let visited = Visited::new();
             let mut root_query_uid = None;
@@ -47,30 +73,49 @@ pub async fn query_graph(
                 tenant_id,
                 property_query_executor,
                 visited,
-                x_query_short_circuiter.clone(),
+                x_query_short_circuiter,
+                budget,
                 &mut root_query_uid,
             )
             .await
             {
-                Ok(Some(g)) => {
-                    x_query_short_circuiter.set_short_circuit();
-                    Ok(Some((g, root_query_uid)))
-                }
+                Ok(Some(g)) => match root_query_uid {
+                    Some(root_uid) => Ok(Some((g, root_uid))),
+                    // A query limit stopped the traversal before it reached
+                    // the root query's node
+                    None if g.truncated => Ok(None),
+                    None => {
+                        tracing::error!(
+                            message =
+                                "Graph query matched without finding root_uid. This is a bug.",
+                        );
+                        Ok(None)
+                    }
+                },
                 Ok(None) => Ok(None),
                 Err(e) => Err(GraphQueryError::NodeQueryError { uid, source: e }),
             }
         });
     }
-    // todo: We don't need to join_all, we can stop polling the other futures
-    //       once one of them matches
-    //       try_select may work better
-    for graph in join_all(query_handles).await {
+
+    first_match(query_handles, &x_query_short_circuiter).await
+}
+
+/// Polls `queries` until the first one matches or fails. Either way
+/// `short_circuit` is set so that the other queries stop fetching, and
+/// returning drops the remaining futures.
+async fn first_match<T, E>(
+    mut queries: FuturesUnordered<impl Future<Output = Result<Option<T>, E>>>,
+    short_circuit: &ShortCircuit,
+) -> Result<Option<T>, E>
+where
+    E: std::fmt::Debug,
+{
+    while let Some(graph) = queries.next().await {
         match graph {
-            Ok(Some((graph, Some(root_uid)))) => return Ok(Some((graph, root_uid))),
-            Ok(Some((_, None))) => {
-                tracing::error!(
-                    message = "Graph query matched without finding root_uid. This is a bug.",
-                )
+            Ok(Some(graph)) => {
+                short_circuit.set_short_circuit();
+                return Ok(Some(graph));
             }
             Ok(None) => continue,
             Err(e) => {
@@ -78,9 +123,181 @@ pub async fn query_graph(
                     message="Graph query failed",
                     error=?e,
                 );
-                return Err(e.into());
+                short_circuit.set_short_circuit();
+                return Err(e);
             }
         }
     }
     Ok(None)
 }
+
+/// Scans one page of nodes with the root query's node type and returns the
+/// graphs rooted at each node that matched, along with the token for the next
+/// page, if there may be one. The query's limits bound the whole page, rather
+/// than each candidate.
+#[tracing::instrument(skip(graph_query, property_query_executor))]
+pub async fn query_graphs_by_type(
+    graph_query: &GraphQuery,
+    tenant_id: uuid::Uuid,
+    page_size: u32,
+    page_token: Option<Uid>,
+    property_query_executor: PropertyQueryExecutor,
+) -> Result<(Vec<MatchedGraphWithUid>, Option<Uid>), GraphQueryError> {
+    let root_query = graph_query
+        .node_property_queries
+        .get(&graph_query.root_query_id)
+        .ok_or(GraphQueryError::MissingRootQuery(graph_query.root_query_id))?;
+    let page_size = match page_size {
+        0 => DEFAULT_SEARCH_PAGE_SIZE,
+        page_size => page_size.min(MAX_SEARCH_PAGE_SIZE),
+    };
+
+    let candidates = property_query_executor
+        .get_uids_by_node_type(tenant_id, &root_query.node_type, page_token, page_size)
+        .await?;
+
+    // A short page means the scan reached the end of the table
+    let next_page_token = if candidates.len() < page_size as usize {
+        None
+    } else {
+        candidates.last().copied()
+    };
+
+    let budget = TraversalBudget::new(graph_query.limits);
+    let query_handles = candidates.into_iter().map(|uid| {
+        let property_query_executor = property_query_executor.clone();
+        let budget = budget.clone();
+        async move {
+            fetch_node_with_edges(
+                root_query,
+                graph_query,
+                uid,
+                tenant_id,
+                property_query_executor,
+                Visited::new(),
+                ShortCircuit::new(),
+                budget,
+                &mut None,
+            )
+            .await
+            .map(|graph| {
+                graph.map(|matched_graph| MatchedGraphWithUid {
+                    matched_graph,
+                    root_uid: uid,
+                })
+            })
+            .map_err(|e| GraphQueryError::NodeQueryError { uid, source: e })
+        }
+    });
+
+    let mut query_results =
+        futures::stream::iter(query_handles).buffer_unordered(MAX_CONCURRENT_CANDIDATE_QUERIES);
+
+    let mut matched_graphs = vec![];
+    while let Some(graph) = query_results.next().await {
+        if let Some(graph) = graph? {
+            matched_graphs.push(graph);
+        }
+    }
+    // candidates finish in any order, but pages are returned in uid order
+    matched_graphs.sort_by_key(|matched_graph| matched_graph.root_uid);
+
+    Ok((matched_graphs, next_page_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering,
+            },
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::future::BoxFuture;
+
+    use super::*;
+    use crate::test_utils::DropFlag;
+
+    fn never_finishing_query(
+        dropped: Arc<AtomicBool>,
+    ) -> BoxFuture<'static, Result<Option<u64>, String>> {
+        let flag = DropFlag(dropped);
+        Box::pin(async move {
+            let _flag = flag;
+            futures::future::pending::<()>().await;
+            Ok(None)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_first_match_returns_without_waiting_for_other_queries() {
+        let short_circuit = ShortCircuit::new();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let queries: FuturesUnordered<BoxFuture<'static, Result<Option<u64>, String>>> =
+            FuturesUnordered::new();
+        queries.push(never_finishing_query(dropped.clone()));
+        queries.push(Box::pin(async { Ok(Some(1)) }));
+
+        let result =
+            tokio::time::timeout(Duration::from_secs(5), first_match(queries, &short_circuit))
+                .await
+                .expect("first_match waited on a query that never finishes");
+
+        assert_eq!(result, Ok(Some(1)));
+        assert!(short_circuit.get_short_circuit());
+        assert!(dropped.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn test_first_match_skips_queries_that_missed() {
+        let short_circuit = ShortCircuit::new();
+
+        let queries: FuturesUnordered<BoxFuture<'static, Result<Option<u64>, String>>> =
+            FuturesUnordered::new();
+        queries.push(Box::pin(async { Ok(None) }));
+        queries.push(Box::pin(async {
+            tokio::task::yield_now().await;
+            Ok(Some(2))
+        }));
+
+        assert_eq!(first_match(queries, &short_circuit).await, Ok(Some(2)));
+        assert!(short_circuit.get_short_circuit());
+    }
+
+    #[tokio::test]
+    async fn test_first_match_stops_on_error() {
+        let short_circuit = ShortCircuit::new();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let queries: FuturesUnordered<BoxFuture<'static, Result<Option<u64>, String>>> =
+            FuturesUnordered::new();
+        queries.push(never_finishing_query(dropped.clone()));
+        queries.push(Box::pin(async { Err("query failed".to_owned()) }));
+
+        assert_eq!(
+            first_match(queries, &short_circuit).await,
+            Err("query failed".to_owned())
+        );
+        assert!(short_circuit.get_short_circuit());
+        assert!(dropped.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn test_first_match_without_a_match() {
+        let short_circuit = ShortCircuit::new();
+
+        let queries: FuturesUnordered<BoxFuture<'static, Result<Option<u64>, String>>> =
+            FuturesUnordered::new();
+        queries.push(Box::pin(async { Ok(None) }));
+        queries.push(Box::pin(async { Ok(None) }));
+
+        assert_eq!(first_match(queries, &short_circuit).await, Ok(None));
+        assert!(!short_circuit.get_short_circuit());
+    }
+}
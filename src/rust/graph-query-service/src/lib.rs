@@ -1,8 +1,13 @@
 pub mod config;
 pub mod graph_query;
 pub mod node_query;
+pub mod property_cache;
 pub mod property_query;
+pub mod property_types;
 pub mod server;
 pub mod short_circuit;
 pub mod table_names;
+#[cfg(test)]
+mod test_utils;
+pub mod traversal_budget;
 pub mod visited;
@@ -6,10 +6,13 @@ use std::{
 use clap::Parser;
 use graph_query::{
     config,
+    property_cache::PropertyCache,
+    property_types::PropertyTypeResolver,
     server,
 };
 use grapl_tracing::setup_tracing;
 use rust_proto::{
+    client_factory::build_grpc_client,
     graplinc::grapl::api::graph_query_service::v1beta1::server::GraphQueryServiceServer,
     protocol::healthcheck::HealthcheckStatus,
 };
@@ -21,6 +24,7 @@ use tokio::net::TcpListener;
 use crate::config::GraphQueryServiceConfig;
 
 const SERVICE_NAME: &'static str = "graph-query";
+const PROPERTY_CACHE_STATS_INTERVAL: Duration = Duration::from_secs(60);
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -41,7 +45,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         scylla::Session::connect(scylla_config).await?,
         10_000,
     ));
-    let graph_query_service = GraphQueryService::new(scylla_client);
+    let property_cache = PropertyCache::with_capacity(
+        config.graph_query_property_cache_capacity,
+        Duration::from_secs(config.graph_query_property_cache_ttl_secs),
+    );
+    // Property types are cached as long as the properties themselves, so a
+    // newly deployed schema is picked up within the same time.
+    let property_type_resolver = PropertyTypeResolver::new(
+        build_grpc_client(config.graph_schema_manager_client_config.clone()).await?,
+        config.graph_query_property_cache_capacity,
+        Duration::from_secs(config.graph_query_property_cache_ttl_secs),
+    );
+    let graph_query_service = GraphQueryService::new(
+        scylla_client,
+        property_cache,
+        property_type_resolver,
+        Duration::from_millis(config.graph_query_timeout_ms),
+    );
+
+    let stats_service = graph_query_service.clone();
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(PROPERTY_CACHE_STATS_INTERVAL);
+        loop {
+            interval.tick().await;
+            let stats = stats_service.cache_stats();
+            tracing::info!(
+                message = "property cache stats",
+                hits = stats.hits,
+                misses = stats.misses,
+            );
+        }
+    });
 
     exec_service(config, graph_query_service).await
 }
@@ -6,15 +6,21 @@ use std::{
 use async_recursion::async_recursion;
 use rust_proto::graplinc::grapl::{
     api::graph_query_service::v1beta1::messages::{
+        AndIntFilters,
         AndStringFilters,
         GraphQuery,
         GraphView,
+        IntFilter,
+        IntOperation,
         NodePropertiesView,
         NodePropertyQuery,
+        OrIntFilters,
         OrStringFilters,
         QueryId,
         StrCmp,
         StringProperties,
+        UidFilter,
+        UidOperation,
     },
     common::v1beta1::types::{
         EdgeName,
@@ -31,11 +37,14 @@ use rustc_hash::{
 use crate::{
     property_query::{
         EdgeRow,
+        IntField,
+        IntValue,
         PropertyQueryError,
         PropertyQueryExecutor,
         StringField,
     },
     short_circuit::ShortCircuit,
+    traversal_budget::TraversalBudget,
     visited::Visited,
 };
 
@@ -74,6 +83,55 @@ pub(crate) fn match_property(
     false
 }
 
+fn match_int_filter(int_filter: &IntFilter, property_value: Option<IntValue>) -> bool {
+    let value = match (&int_filter.operation, property_value) {
+        (IntOperation::Has, value) => return value.is_some() != int_filter.negated,
+        // A comparison against a missing property never matches, negated or not
+        (_, None) => return false,
+        (_, Some(value)) => value.as_i128(),
+    };
+
+    // compared as i128, so that u64 values above i64::MAX compare correctly
+    let filter_value = i128::from(int_filter.value);
+    let matched = match int_filter.operation {
+        IntOperation::Has => true,
+        IntOperation::Equal => value == filter_value,
+        IntOperation::LessThan => value < filter_value,
+        IntOperation::LessThanOrEqual => value <= filter_value,
+        IntOperation::GreaterThan => value > filter_value,
+        IntOperation::GreaterThanOrEqual => value >= filter_value,
+    };
+
+    matched != int_filter.negated
+}
+
+pub(crate) fn match_int_property(
+    node_properties_query: &NodePropertyQuery,
+    property_name: &PropertyName,
+    property_value: Option<IntValue>,
+) -> bool {
+    node_properties_query.int_filters[property_name]
+        .and_int_filters
+        .iter()
+        .any(|and_filters| {
+            and_filters
+                .int_filters
+                .iter()
+                .all(|int_filter| match_int_filter(int_filter, property_value))
+        })
+}
+
+/// Uid filters are OR'd together; a query without any matches every uid.
+pub(crate) fn match_uid(node_properties_query: &NodePropertyQuery, uid: Uid) -> bool {
+    let uid_filters = &node_properties_query.uid_filters.uid_filters;
+    uid_filters.is_empty()
+        || uid_filters
+            .iter()
+            .any(|uid_filter| match uid_filter.operation {
+                UidOperation::Equal => uid_filter.value == uid,
+            })
+}
+
 #[tracing::instrument(skip(node_properties_query, property_query_executor))]
 pub async fn fetch_node_properties(
     node_properties_query: &NodePropertyQuery,
@@ -105,6 +163,33 @@ pub async fn fetch_node_properties(
     Ok(Some(fields))
 }
 
+/// Fetches every int property the query filters on, and returns `None` if any
+/// of them fail their filters. Missing properties are only a match for a
+/// negated `Has`.
+#[tracing::instrument(skip(node_properties_query, property_query_executor))]
+pub async fn fetch_node_int_properties(
+    node_properties_query: &NodePropertyQuery,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+) -> Result<Option<Vec<IntField>>, NodeQueryError> {
+    let mut fields = vec![];
+    for prop_name in node_properties_query.int_filters.keys() {
+        let property = property_query_executor
+            .get_int(tenant_id, uid, &node_properties_query.node_type, prop_name)
+            .await?;
+
+        let property_value = property.as_ref().map(|p| p.value);
+        if !match_int_property(node_properties_query, prop_name, property_value) {
+            return Ok(None);
+        }
+
+        fields.extend(property);
+    }
+
+    Ok(Some(fields))
+}
+
 #[tracing::instrument(skip(node_properties_query, graph_query, property_query_executor))]
 pub async fn fetch_edges(
     node_properties_query: &NodePropertyQuery,
@@ -136,7 +221,21 @@ pub async fn fetch_edges(
     Ok(Some(edge_rows))
 }
 
-#[async_recursion]
+/// The outcome of matching a node query, and the queries reachable from it,
+/// against a single node.
+enum NodeMatch {
+    Matched(GraphView),
+    Missed,
+    /// The traversal budget ran out before the node could be fetched, so it's
+    /// unknown whether it matches.
+    OutOfBudget,
+}
+
+/// Matches `node_properties_query` against the node `uid`, expanding its edges.
+/// If a `QueryLimits` cuts the traversal short, the partial match is returned
+/// with `truncated` set, rather than treating the nodes that weren't fetched
+/// as misses.
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_node_with_edges(
     node_properties_query: &NodePropertyQuery,
     graph_query: &GraphQuery,
@@ -145,10 +244,52 @@ pub async fn fetch_node_with_edges(
     property_query_executor: PropertyQueryExecutor,
     visited: Visited,
     x_short_circuit: ShortCircuit,
+    budget: TraversalBudget,
     root_node_uid: &mut Option<Uid>,
 ) -> Result<Option<GraphView>, NodeQueryError> {
+    let node_match = match_node_with_edges(
+        node_properties_query,
+        graph_query,
+        uid,
+        tenant_id,
+        property_query_executor,
+        visited,
+        x_short_circuit,
+        budget,
+        root_node_uid,
+    )
+    .await?;
+
+    match node_match {
+        NodeMatch::Matched(graph) => Ok(Some(graph)),
+        // Without the starting node there's no partial match to return
+        NodeMatch::Missed | NodeMatch::OutOfBudget => Ok(None),
+    }
+}
+
+#[async_recursion]
+async fn match_node_with_edges(
+    node_properties_query: &NodePropertyQuery,
+    graph_query: &GraphQuery,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    visited: Visited,
+    x_short_circuit: ShortCircuit,
+    budget: TraversalBudget,
+    root_node_uid: &mut Option<Uid>,
+) -> Result<NodeMatch, NodeQueryError> {
     if visited.get_short_circuit() || x_short_circuit.get_short_circuit() {
-        return Ok(None);
+        return Ok(NodeMatch::Missed);
+    }
+
+    if !match_uid(node_properties_query, uid) {
+        visited.set_short_circuit();
+        return Ok(NodeMatch::Missed);
+    }
+
+    if !budget.try_fetch_node() {
+        return Ok(NodeMatch::OutOfBudget);
     }
 
     let mut node = NodePropertiesView::new(
@@ -168,7 +309,7 @@ pub async fn fetch_node_with_edges(
     let node_properties = match node_properties {
         None => {
             visited.set_short_circuit();
-            return Ok(None);
+            return Ok(NodeMatch::Missed);
         }
         Some(node_properties) => node_properties,
     };
@@ -185,10 +326,30 @@ pub async fn fetch_node_with_edges(
             );
         } else {
             visited.set_short_circuit();
-            return Ok(None);
+            return Ok(NodeMatch::Missed);
         }
     }
 
+    let int_properties = fetch_node_int_properties(
+        node_properties_query,
+        uid,
+        tenant_id,
+        property_query_executor.clone(),
+    )
+    .await?;
+
+    let int_properties = match int_properties {
+        None => {
+            visited.set_short_circuit();
+            return Ok(NodeMatch::Missed);
+        }
+        Some(int_properties) => int_properties,
+    };
+
+    for int_property in int_properties {
+        node.add_int_property(int_property.populated_field, int_property.value.into());
+    }
+
     if node_properties_query.query_id == graph_query.root_query_id {
         *root_node_uid = Some(uid);
     }
@@ -202,7 +363,7 @@ pub async fn fetch_node_with_edges(
     );
 
     if x_short_circuit.get_short_circuit() {
-        return Ok(None);
+        return Ok(NodeMatch::Missed);
     }
 
     // fetch the edges for the uid
@@ -218,7 +379,7 @@ pub async fn fetch_node_with_edges(
         Some(edges) => edges,
         None => {
             visited.set_short_circuit();
-            return Ok(None);
+            return Ok(NodeMatch::Missed);
         }
     };
 
@@ -226,7 +387,9 @@ pub async fn fetch_node_with_edges(
         if *src_id != node_properties_query.query_id {
             continue;
         }
-        let edge_rows = &edges[edge_name];
+        let all_edge_rows = &edges[edge_name];
+        let edge_rows = budget.limit_edges(all_edge_rows);
+        let edges_cut = edge_rows.len() < all_edge_rows.len();
 
         for edge_query_id in edge_queries {
             let edge_query = &graph_query.node_property_queries[edge_query_id];
@@ -250,12 +413,18 @@ pub async fn fetch_node_with_edges(
             // When we support 'OR' logic on edges we'll add that logic here
 
             let mut any = false;
+            let mut out_of_budget = false;
             for edge_row in edge_rows {
                 // we can do this in parallel
                 if x_short_circuit.get_short_circuit() {
-                    return Ok(None);
+                    return Ok(NodeMatch::Missed);
+                }
+                // Once this edge has matched, stop expanding it if we're out of nodes
+                if any && budget.is_exhausted() {
+                    budget.set_truncated();
+                    break;
                 }
-                let neighbors = match fetch_node_with_edges(
+                let neighbors = match match_node_with_edges(
                     edge_query,
                     graph_query,
                     edge_row.destination_uid,
@@ -263,12 +432,17 @@ pub async fn fetch_node_with_edges(
                     property_query_executor.clone(),
                     visited.clone(),
                     x_short_circuit.clone(),
+                    budget.descend(),
                     root_node_uid,
                 )
                 .await?
                 {
-                    Some(neighbors) => neighbors,
-                    None => continue,
+                    NodeMatch::Matched(neighbors) => neighbors,
+                    NodeMatch::Missed => continue,
+                    NodeMatch::OutOfBudget => {
+                        out_of_budget = true;
+                        continue;
+                    }
                 };
                 any = true;
                 for neighbor in neighbors.nodes.keys() {
@@ -277,15 +451,18 @@ pub async fn fetch_node_with_edges(
                 }
                 graph.merge(neighbors);
             }
-            if !any {
+            // Neighbors that weren't fetched, or whose edges weren't followed,
+            // may still have matched, so keep the partial match
+            if !any && !out_of_budget && !edges_cut {
                 // if a given query has no matches, return
                 visited.set_short_circuit();
-                return Ok(None);
+                return Ok(NodeMatch::Missed);
             }
         }
     }
 
-    Ok(Some(graph))
+    graph.truncated = budget.is_truncated();
+    Ok(NodeMatch::Matched(graph))
 }
 
 // Note: Different from the rust_proto NodeQuery.
@@ -312,6 +489,7 @@ impl NodeQuery {
             node_property_queries,
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            limits: Default::default(),
         };
 
         Self {
@@ -352,6 +530,45 @@ impl NodeQuery {
             .insert(property_name, comparisons);
     }
 
+    pub fn with_int_comparisons(
+        &mut self,
+        property_name: PropertyName,
+        comparisons: Vec<IntFilter>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .int_filters
+            .entry(property_name)
+            .or_insert_with(|| OrIntFilters {
+                and_int_filters: Vec::with_capacity(1),
+            })
+            .and_int_filters
+            .push(AndIntFilters {
+                int_filters: comparisons,
+            });
+        drop(inner);
+        self
+    }
+
+    pub fn with_uid(&mut self, uid: Uid) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .uid_filters
+            .uid_filters
+            .push(UidFilter {
+                operation: UidOperation::Equal,
+                value: uid,
+            });
+        drop(inner);
+        self
+    }
+
     pub fn with_shared_edge(
         &mut self,
         edge_name: EdgeName,
@@ -447,6 +664,72 @@ impl NodeQuery {
             node_property_queries: Default::default(),
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            limits: Default::default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_filter(operation: IntOperation, value: i64, negated: bool) -> IntFilter {
+        IntFilter {
+            operation,
+            value,
+            negated,
+        }
+    }
+
+    #[test]
+    fn test_u64_above_i64_max_compares_by_value() {
+        let value = Some(IntValue::U64(u64::MAX));
+
+        assert!(match_int_filter(
+            &int_filter(IntOperation::GreaterThan, i64::MAX, false),
+            value
+        ));
+        assert!(!match_int_filter(
+            &int_filter(IntOperation::LessThan, 0, false),
+            value
+        ));
+        assert!(!match_int_filter(
+            &int_filter(IntOperation::Equal, -1, false),
+            value
+        ));
+    }
+
+    #[test]
+    fn test_i64_comparisons() {
+        let value = Some(IntValue::I64(-5));
+
+        assert!(match_int_filter(
+            &int_filter(IntOperation::LessThan, 0, false),
+            value
+        ));
+        assert!(match_int_filter(
+            &int_filter(IntOperation::GreaterThanOrEqual, -5, false),
+            value
+        ));
+        assert!(match_int_filter(
+            &int_filter(IntOperation::Equal, -5, true),
+            Some(IntValue::I64(6))
+        ));
+    }
+
+    #[test]
+    fn test_missing_property_only_matches_negated_has() {
+        assert!(match_int_filter(
+            &int_filter(IntOperation::Has, 0, true),
+            None
+        ));
+        assert!(!match_int_filter(
+            &int_filter(IntOperation::Has, 0, false),
+            None
+        ));
+        assert!(!match_int_filter(
+            &int_filter(IntOperation::Equal, 0, true),
+            None
+        ));
+    }
+}
@@ -1,13 +1,24 @@
 use std::{
     fmt::Debug,
     hash::Hash,
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Duration,
 };
 
 use moka::future::{
     Cache,
     CacheBuilder,
 };
-use rust_proto::graplinc::grapl::common::v1beta1::types::Uid;
+use rust_proto::graplinc::grapl::common::v1beta1::types::{
+    PropertyName,
+    Uid,
+};
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum CacheMatch {
     Matched,
@@ -22,11 +33,47 @@ pub struct Key {
     tenant_id: uuid::Uuid,
 }
 
+impl Key {
+    pub fn new(tenant_id: uuid::Uuid, uid: Uid, property_name: &PropertyName) -> Self {
+        Self {
+            property_name: property_name.value.clone(),
+            uid,
+            tenant_id,
+        }
+    }
+}
+
+/// A point-in-time snapshot of the cache's hit and miss counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheCounters {
+    fn record<V>(&self, value: Option<V>) -> Option<V> {
+        match value {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        value
+    }
+}
+
+/// Caches immutable properties, which never change once written, so they
+/// only have to be read from Scylla once.
 #[derive(Clone)]
 pub struct PropertyCache {
     string_cache: Cache<Key, String>,
     int_cache: Cache<Key, i64>,
     uint_cache: Cache<Key, u64>,
+    counters: Arc<CacheCounters>,
 }
 
 impl PropertyCache {
@@ -39,6 +86,84 @@ impl PropertyCache {
             string_cache,
             int_cache,
             uint_cache,
+            counters: Default::default(),
         }
     }
+
+    /// Builds a cache holding up to `capacity` entries of each property type,
+    /// which are evicted `time_to_live` after being written.
+    pub fn with_capacity(capacity: u64, time_to_live: Duration) -> Self {
+        Self::new(
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+        )
+    }
+
+    pub fn get_string(&self, key: &Key) -> Option<String> {
+        self.counters.record(self.string_cache.get(key))
+    }
+
+    pub async fn insert_string(&self, key: Key, value: String) {
+        self.string_cache.insert(key, value).await
+    }
+
+    pub fn get_int(&self, key: &Key) -> Option<i64> {
+        self.counters.record(self.int_cache.get(key))
+    }
+
+    pub fn get_uint(&self, key: &Key) -> Option<u64> {
+        self.counters.record(self.uint_cache.get(key))
+    }
+
+    pub async fn insert_int(&self, key: Key, value: i64) {
+        self.int_cache.insert(key, value).await
+    }
+
+    pub async fn insert_uint(&self, key: Key, value: u64) {
+        self.uint_cache.insert(key, value).await
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hits_and_misses_are_counted() {
+        let cache = PropertyCache::with_capacity(10, Duration::from_secs(60));
+        let tenant_id = uuid::Uuid::new_v4();
+        let uid = Uid::from_u64(1).unwrap();
+        let process_name = PropertyName::try_from("process_name").unwrap();
+        let process_id = PropertyName::try_from("process_id").unwrap();
+
+        let string_key = Key::new(tenant_id, uid, &process_name);
+        let uint_key = Key::new(tenant_id, uid, &process_id);
+
+        assert_eq!(cache.get_string(&string_key), None);
+        assert_eq!(cache.get_uint(&uint_key), None);
+
+        cache
+            .insert_string(string_key.clone(), "chrome.exe".to_owned())
+            .await;
+        cache.insert_uint(uint_key.clone(), 1234).await;
+
+        assert_eq!(cache.get_string(&string_key).as_deref(), Some("chrome.exe"));
+        assert_eq!(cache.get_uint(&uint_key), Some(1234));
+
+        assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 2 });
+    }
 }
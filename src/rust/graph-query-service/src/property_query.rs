@@ -1,10 +1,20 @@
 use std::sync::Arc;
 
 use rust_proto::{
-    graplinc::grapl::common::v1beta1::types::{
-        EdgeName,
-        PropertyName,
-        Uid,
+    graplinc::grapl::{
+        api::{
+            graph_query_service::v1beta1::messages::IntPropertyValue,
+            graph_schema_manager::v1beta1::{
+                client::GraphSchemaManagerClientError,
+                messages::PropertyType,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
     },
     SerDeError,
 };
@@ -17,11 +27,40 @@ use scylla::{
     CachingSession,
 };
 
-use crate::table_names::{
-    tenant_keyspace_name,
-    IMM_STRING_TABLE_NAME,
+use crate::{
+    property_cache::{
+        CacheStats,
+        Key,
+        PropertyCache,
+    },
+    property_types::PropertyTypeResolver,
+    table_names::{
+        tenant_keyspace_name,
+        IMM_I_64_TABLE_NAME,
+        IMM_STRING_TABLE_NAME,
+        IMM_U_64_TABLE_NAME,
+        MAX_I_64_TABLE_NAME,
+        MAX_U_64_TABLE_NAME,
+        MIN_I_64_TABLE_NAME,
+        MIN_U_64_TABLE_NAME,
+        NODES_BY_TYPE_TABLE_NAME,
+    },
 };
 
+/// The table graph-mutation writes an integer property of the given type to,
+/// or `None` for non-integer types.
+fn int_table_name(property_type: PropertyType) -> Option<&'static str> {
+    match property_type {
+        PropertyType::ImmutableI64 => Some(IMM_I_64_TABLE_NAME),
+        PropertyType::MaxI64 => Some(MAX_I_64_TABLE_NAME),
+        PropertyType::MinI64 => Some(MIN_I_64_TABLE_NAME),
+        PropertyType::ImmutableU64 => Some(IMM_U_64_TABLE_NAME),
+        PropertyType::MaxU64 => Some(MAX_U_64_TABLE_NAME),
+        PropertyType::MinU64 => Some(MIN_U_64_TABLE_NAME),
+        PropertyType::ImmutableString => None,
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum PropertyQueryError {
     #[error("QueryError: {0}")]
@@ -36,8 +75,12 @@ pub enum PropertyQueryError {
         source_uid: Uid,
         f_edge_name: String,
     },
+    #[error("Invalid uid '{uid}' stored for node_type '{node_type}'")]
+    InvalidNodeUidInDb { uid: i64, node_type: String },
     #[error("Invalid stored edge name {0}")]
     InvalidStoredEdgeName(#[from] SerDeError),
+    #[error("Couldn't look up property type: {0}")]
+    GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
 }
 
 #[derive(Debug, Clone)]
@@ -56,16 +99,65 @@ pub struct StringField {
     pub value: String,
 }
 
+/// An integer property's value. graph-mutation stores u64 values as their i64
+/// bit pattern, so they're decoded back to u64 when read, and compare by
+/// their actual value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntValue {
+    I64(i64),
+    U64(u64),
+}
+
+impl IntValue {
+    /// Widens the value to a type that holds both every i64 and every u64.
+    pub fn as_i128(&self) -> i128 {
+        match self {
+            IntValue::I64(value) => i128::from(*value),
+            IntValue::U64(value) => i128::from(*value),
+        }
+    }
+}
+
+impl From<IntValue> for IntPropertyValue {
+    fn from(value: IntValue) -> Self {
+        match value {
+            IntValue::I64(value) => IntPropertyValue::I64(value),
+            IntValue::U64(value) => IntPropertyValue::U64(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IntField {
+    pub uid: Uid,
+    pub populated_field: PropertyName,
+    pub value: IntValue,
+}
+
 // We should push our filtering logic into here
 
 #[derive(Clone)]
 pub struct PropertyQueryExecutor {
     scylla_client: Arc<CachingSession>,
+    property_cache: PropertyCache,
+    property_type_resolver: PropertyTypeResolver,
 }
 
 impl PropertyQueryExecutor {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
-        Self { scylla_client }
+    pub fn new(
+        scylla_client: Arc<CachingSession>,
+        property_cache: PropertyCache,
+        property_type_resolver: PropertyTypeResolver,
+    ) -> Self {
+        Self {
+            scylla_client,
+            property_cache,
+            property_type_resolver,
+        }
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.property_cache.stats()
     }
 
     pub async fn get_immutable_string(
@@ -74,6 +166,15 @@ impl PropertyQueryExecutor {
         uid: Uid,
         property_name: &PropertyName,
     ) -> Result<Option<StringField>, PropertyQueryError> {
+        let cache_key = Key::new(tenant_id, uid, property_name);
+        if let Some(value) = self.property_cache.get_string(&cache_key) {
+            return Ok(Some(StringField {
+                uid,
+                populated_field: property_name.clone(),
+                value,
+            }));
+        }
+
         let tenant_ks = tenant_keyspace_name(tenant_id);
 
         let mut query = scylla::query::Query::from(format!(
@@ -100,6 +201,10 @@ impl PropertyQueryExecutor {
             None => return Ok(None),
         };
 
+        self.property_cache
+            .insert_string(cache_key, row.clone())
+            .await;
+
         Ok(Some(StringField {
             uid,
             populated_field: property_name.clone(),
@@ -107,6 +212,161 @@ impl PropertyQueryExecutor {
         }))
     }
 
+    /// Reads an integer property from the table its type in the tenant's
+    /// schema is written to. Properties the schema doesn't define as an
+    /// integer are treated as missing.
+    pub async fn get_int(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+    ) -> Result<Option<IntField>, PropertyQueryError> {
+        let property_type = match self
+            .property_type_resolver
+            .get_property_type(tenant_id, node_type, property_name)
+            .await?
+        {
+            Some(property_type) => property_type,
+            None => return Ok(None),
+        };
+
+        let table_name = match int_table_name(property_type) {
+            Some(table_name) => table_name,
+            None => return Ok(None),
+        };
+
+        let cache_key = Key::new(tenant_id, uid, property_name);
+        let cached = match property_type {
+            PropertyType::ImmutableI64 => {
+                self.property_cache.get_int(&cache_key).map(IntValue::I64)
+            }
+            PropertyType::ImmutableU64 => {
+                self.property_cache.get_uint(&cache_key).map(IntValue::U64)
+            }
+            _ => None,
+        };
+        if let Some(value) = cached {
+            return Ok(Some(IntField {
+                uid,
+                populated_field: property_name.clone(),
+                value,
+            }));
+        }
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT value
+            FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            LIMIT 1
+            ALLOW FILTERING;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self
+            .scylla_client
+            .execute(query, &(uid.as_i64(), &property_name.value))
+            .await?;
+
+        let stored = match query_result.maybe_first_row_typed::<(i64,)>()? {
+            Some((stored,)) => stored,
+            None => return Ok(None),
+        };
+
+        let value = match property_type {
+            PropertyType::MaxU64 | PropertyType::MinU64 | PropertyType::ImmutableU64 => {
+                IntValue::U64(stored as u64)
+            }
+            _ => IntValue::I64(stored),
+        };
+
+        // max/min properties change over time, so only immutable ones are cached
+        match (property_type, value) {
+            (PropertyType::ImmutableI64, IntValue::I64(value)) => {
+                self.property_cache.insert_int(cache_key, value).await
+            }
+            (PropertyType::ImmutableU64, IntValue::U64(value)) => {
+                self.property_cache.insert_uint(cache_key, value).await
+            }
+            _ => (),
+        }
+
+        Ok(Some(IntField {
+            uid,
+            populated_field: property_name.clone(),
+            value,
+        }))
+    }
+
+    /// Scans up to `limit` uids of nodes with the given type, in uid order,
+    /// starting after `after_uid`.
+    pub async fn get_uids_by_node_type(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        after_uid: Option<Uid>,
+        limit: u32,
+    ) -> Result<Vec<Uid>, PropertyQueryError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+
+        let query_result = match after_uid {
+            Some(after_uid) => {
+                let mut query = scylla::query::Query::from(format!(
+                    r"
+                    SELECT uid
+                    FROM {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME}
+                    WHERE
+                        node_type = ? AND
+                        uid > ?
+                    LIMIT ?;
+                    "
+                ));
+                query.set_is_idempotent(true);
+
+                self.scylla_client
+                    .execute(query, &(&node_type.value, after_uid.as_i64(), limit))
+                    .await?
+            }
+            None => {
+                let mut query = scylla::query::Query::from(format!(
+                    r"
+                    SELECT uid
+                    FROM {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME}
+                    WHERE
+                        node_type = ?
+                    LIMIT ?;
+                    "
+                ));
+                query.set_is_idempotent(true);
+
+                self.scylla_client
+                    .execute(query, &(&node_type.value, limit))
+                    .await?
+            }
+        };
+
+        let mut uids = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(i64,)>() {
+            let (uid,) = row?;
+            uids.push(Uid::from_i64(uid).ok_or_else(|| {
+                PropertyQueryError::InvalidNodeUidInDb {
+                    uid,
+                    node_type: node_type.value.clone(),
+                }
+            })?);
+        }
+
+        Ok(uids)
+    }
+
     pub async fn get_edges(
         &self,
         tenant_id: uuid::Uuid,
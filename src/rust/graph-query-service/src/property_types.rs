@@ -0,0 +1,110 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::Duration,
+};
+
+use moka::future::{
+    Cache,
+    CacheBuilder,
+};
+use rust_proto::{
+    graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::{
+            client::{
+                GraphSchemaManagerClient,
+                GraphSchemaManagerClientError,
+            },
+            messages::{
+                GetNodeSchemaRequest,
+                PropertyType,
+            },
+        },
+        common::v1beta1::types::{
+            NodeType,
+            PropertyName,
+        },
+    },
+    protocol::status::Code,
+};
+
+type PropertyTypes = Arc<HashMap<PropertyName, PropertyType>>;
+
+/// Looks up the types of a node type's properties in graph-schema-manager, so
+/// that a property can be read from the one table it's written to. Schemas
+/// are cached for `time_to_live`, after which a newly deployed schema is
+/// picked up.
+#[derive(Clone)]
+pub struct PropertyTypeResolver {
+    schema_client: GraphSchemaManagerClient,
+    cache: Cache<(uuid::Uuid, NodeType), PropertyTypes>,
+}
+
+impl PropertyTypeResolver {
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        capacity: u64,
+        time_to_live: Duration,
+    ) -> Self {
+        Self {
+            schema_client,
+            cache: CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+        }
+    }
+
+    /// Returns `None` if the node type, or the property, isn't in the
+    /// tenant's schema.
+    pub async fn get_property_type(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+    ) -> Result<Option<PropertyType>, GraphSchemaManagerClientError> {
+        let cache_key = (tenant_id, node_type.clone());
+        let property_types = match self.cache.get(&cache_key) {
+            Some(property_types) => property_types,
+            None => {
+                let property_types = self.fetch_property_types(tenant_id, node_type).await?;
+                self.cache.insert(cache_key, property_types.clone()).await;
+                property_types
+            }
+        };
+
+        Ok(property_types.get(property_name).copied())
+    }
+
+    async fn fetch_property_types(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+    ) -> Result<PropertyTypes, GraphSchemaManagerClientError> {
+        let response = self
+            .schema_client
+            .clone()
+            .get_node_schema(GetNodeSchemaRequest {
+                tenant_id,
+                node_type: node_type.clone(),
+                schema_version: None,
+            })
+            .await;
+
+        let properties = match response {
+            Ok(response) => response.properties,
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                vec![]
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(Arc::new(
+            properties
+                .into_iter()
+                .map(|property| (property.property_name, property.property_type))
+                .collect(),
+        ))
+    }
+}
@@ -1,5 +1,8 @@
 #![allow(warnings)]
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use rust_proto::{
     graplinc::grapl::api::graph_query_service::v1beta1::{
@@ -9,28 +12,41 @@ use rust_proto::{
             MatchedGraphWithUid,
             MaybeMatchWithUid,
             NoMatchWithUid,
+            QueryGraphByTypeRequest,
+            QueryGraphByTypeResponse,
             QueryGraphFromUidRequest,
             QueryGraphFromUidResponse,
             QueryGraphWithUidRequest,
             QueryGraphWithUidResponse,
+            QueryLimits,
         },
         server::GraphQueryApi,
     },
-    protocol::status::Status,
+    protocol::status::{
+        Code,
+        Status,
+    },
 };
 use scylla::CachingSession;
 
 use crate::{
     graph_query::{
         query_graph,
+        query_graphs_by_type,
         GraphQueryError,
     },
     node_query::{
         fetch_node_with_edges,
         NodeQueryError,
     },
+    property_cache::{
+        CacheStats,
+        PropertyCache,
+    },
     property_query::PropertyQueryExecutor,
+    property_types::PropertyTypeResolver,
     short_circuit::ShortCircuit,
+    traversal_budget::TraversalBudget,
     visited::Visited,
 };
 
@@ -40,14 +56,20 @@ pub enum GraphQueryServiceError {
     GraphQueryError(#[from] GraphQueryError),
     #[error("NodeQueryError {0}")]
     NodeQueryError(#[from] NodeQueryError),
+    #[error("Query did not complete within {0:?}")]
+    DeadlineExceeded(Duration),
 }
 
 impl From<GraphQueryServiceError> for Status {
     fn from(gqs_err: GraphQueryServiceError) -> Self {
         type GQSErr = GraphQueryServiceError;
         match gqs_err {
+            e @ GQSErr::GraphQueryError(GraphQueryError::MissingRootQuery(_)) => {
+                Status::invalid_argument(e.to_string())
+            }
             GQSErr::GraphQueryError(e) => Status::unknown(e.to_string()),
             GQSErr::NodeQueryError(e) => Status::unknown(e.to_string()),
+            e @ GQSErr::DeadlineExceeded(_) => Status::new(Code::DeadlineExceeded, e.to_string()),
         }
     }
 }
@@ -55,14 +77,64 @@ impl From<GraphQueryServiceError> for Status {
 #[derive(Clone)]
 pub struct GraphQueryService {
     property_query_executor: PropertyQueryExecutor,
+    query_timeout: Duration,
 }
 
 impl GraphQueryService {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
+    pub fn new(
+        scylla_client: Arc<CachingSession>,
+        property_cache: PropertyCache,
+        property_type_resolver: PropertyTypeResolver,
+        query_timeout: Duration,
+    ) -> Self {
         Self {
-            property_query_executor: PropertyQueryExecutor::new(scylla_client),
+            property_query_executor: PropertyQueryExecutor::new(
+                scylla_client,
+                property_cache,
+                property_type_resolver,
+            ),
+            query_timeout,
         }
     }
+
+    async fn with_deadline<T, E>(
+        &self,
+        limits: QueryLimits,
+        query: impl std::future::Future<Output = Result<T, E>>,
+    ) -> Result<T, GraphQueryServiceError>
+    where
+        GraphQueryServiceError: From<E>,
+    {
+        with_deadline(query_timeout(limits, self.query_timeout), query).await
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.property_query_executor.cache_stats()
+    }
+}
+
+/// A query may ask for a shorter timeout than the service's, but not a longer
+/// one.
+fn query_timeout(limits: QueryLimits, service_timeout: Duration) -> Duration {
+    limits
+        .timeout
+        .map_or(service_timeout, |timeout| timeout.min(service_timeout))
+}
+
+/// Bounds how long a query may spend expanding the graph, e.g. when it
+/// walks into a hub node with a huge number of edges. Dropping the query
+/// future cancels any fetches still in flight.
+async fn with_deadline<T, E>(
+    timeout: Duration,
+    query: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, GraphQueryServiceError>
+where
+    GraphQueryServiceError: From<E>,
+{
+    match tokio::time::timeout(timeout, query).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(GraphQueryServiceError::DeadlineExceeded(timeout)),
+    }
 }
 
 #[async_trait::async_trait]
@@ -76,13 +148,17 @@ impl GraphQueryApi for GraphQueryService {
         let node_uid = request.node_uid;
 
         let graph_query: GraphQuery = request.graph_query;
-        let graph = query_graph(
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
-        )
-        .await?;
+        let graph = self
+            .with_deadline(
+                graph_query.limits,
+                query_graph(
+                    &graph_query,
+                    node_uid,
+                    request.tenant_id,
+                    self.property_query_executor.clone(),
+                ),
+            )
+            .await?;
 
         let (matched_graph, root_uid) = match graph {
             Some((graph, root_uid)) => (graph, root_uid),
@@ -108,27 +184,142 @@ impl GraphQueryApi for GraphQueryService {
         let node_uid = request.node_uid;
 
         let graph_query: GraphQuery = request.graph_query;
-        let node_query = &graph_query
+        let node_query = graph_query
             .node_property_queries
             .get(&graph_query.root_query_id)
-            .unwrap();
+            .ok_or(GraphQueryError::MissingRootQuery(graph_query.root_query_id))?;
 
         let visited = Visited::new();
         let x_short_circuit = ShortCircuit::new();
-        let graph = fetch_node_with_edges(
-            &node_query,
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
-            visited,
-            x_short_circuit,
-            &mut None,
-        )
-        .await?;
+        let graph = self
+            .with_deadline(
+                graph_query.limits,
+                fetch_node_with_edges(
+                    node_query,
+                    &graph_query,
+                    node_uid,
+                    request.tenant_id,
+                    self.property_query_executor.clone(),
+                    visited,
+                    x_short_circuit,
+                    TraversalBudget::new(graph_query.limits),
+                    &mut None,
+                ),
+            )
+            .await?;
 
         Ok(QueryGraphFromUidResponse {
             matched_graph: graph,
         })
     }
+
+    async fn query_graph_by_type(
+        &self,
+        request: QueryGraphByTypeRequest,
+    ) -> Result<QueryGraphByTypeResponse, GraphQueryServiceError> {
+        let (matched_graphs, next_page_token) = self
+            .with_deadline(
+                request.graph_query.limits,
+                query_graphs_by_type(
+                    &request.graph_query,
+                    request.tenant_id,
+                    request.page_size,
+                    request.page_token,
+                    self.property_query_executor.clone(),
+                ),
+            )
+            .await?;
+
+        Ok(QueryGraphByTypeResponse {
+            matched_graphs,
+            next_page_token,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    };
+
+    use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages::QueryId;
+
+    use super::*;
+    use crate::test_utils::DropFlag;
+
+    #[tokio::test]
+    async fn test_deadline_expiry_cancels_query() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+
+        let result = with_deadline(Duration::from_millis(10), async move {
+            let _flag = flag;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<_, GraphQueryError>(())
+        })
+        .await;
+
+        match result {
+            Err(GraphQueryServiceError::DeadlineExceeded(timeout)) => {
+                assert_eq!(timeout, Duration::from_millis(10))
+            }
+            other => panic!("expected DeadlineExceeded, got {other:?}"),
+        }
+        assert!(dropped.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn test_query_within_deadline_returns_its_result() {
+        let result = with_deadline(Duration::from_secs(60), async {
+            Ok::<_, GraphQueryError>(Some(1))
+        })
+        .await;
+
+        assert!(matches!(result, Ok(Some(1))));
+    }
+
+    #[test]
+    fn test_deadline_exceeded_maps_to_deadline_exceeded_status() {
+        let status = Status::from(GraphQueryServiceError::DeadlineExceeded(
+            Duration::from_millis(10),
+        ));
+
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+    }
+
+    #[test]
+    fn test_missing_root_query_maps_to_invalid_argument_status() {
+        let status = Status::from(GraphQueryServiceError::GraphQueryError(
+            GraphQueryError::MissingRootQuery(QueryId::default()),
+        ));
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_query_timeout_is_capped_by_service_timeout() {
+        let service_timeout = Duration::from_secs(30);
+        let limits = |timeout| QueryLimits {
+            timeout,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            query_timeout(limits(None), service_timeout),
+            service_timeout
+        );
+        assert_eq!(
+            query_timeout(limits(Some(Duration::from_secs(5))), service_timeout),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            query_timeout(limits(Some(Duration::from_secs(300))), service_timeout),
+            service_timeout
+        );
+    }
 }
@@ -0,0 +1,164 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        AtomicU32,
+        Ordering,
+    },
+    Arc,
+};
+
+use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages::QueryLimits;
+
+/// TraversalBudget tracks how much of the graph a single query has traversed
+/// against its `QueryLimits`. Clones share the count of fetched nodes and the
+/// truncated flag, but each clone tracks its own depth.
+#[derive(Clone)]
+pub struct TraversalBudget {
+    limits: QueryLimits,
+    depth: u32,
+    nodes_fetched: Arc<AtomicU32>,
+    truncated: Arc<AtomicBool>,
+}
+
+impl TraversalBudget {
+    pub fn new(limits: QueryLimits) -> Self {
+        Self {
+            limits,
+            depth: 0,
+            nodes_fetched: Arc::new(AtomicU32::new(0)),
+            truncated: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the budget for a neighbor one hop further away.
+    pub fn descend(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Claims one node from the budget. Returns false, and marks the query as
+    /// truncated, if the node is too deep or too many have been fetched.
+    pub fn try_fetch_node(&self) -> bool {
+        if matches!(self.limits.max_depth, Some(max_depth) if self.depth > max_depth) {
+            self.set_truncated();
+            return false;
+        }
+
+        let nodes_fetched = self.nodes_fetched.fetch_add(1, Ordering::AcqRel);
+        if matches!(self.limits.max_nodes, Some(max_nodes) if nodes_fetched >= max_nodes) {
+            self.set_truncated();
+            return false;
+        }
+
+        true
+    }
+
+    /// Whether every node in the budget has been claimed.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(
+            self.limits.max_nodes,
+            Some(max_nodes) if self.nodes_fetched.load(Ordering::Acquire) >= max_nodes
+        )
+    }
+
+    /// Caps the edges followed from one node, for one edge name.
+    pub fn limit_edges<'a, T>(&self, edges: &'a [T]) -> &'a [T] {
+        match self.limits.max_edges_per_hop {
+            Some(max_edges) if edges.len() > max_edges as usize => {
+                self.set_truncated();
+                &edges[..max_edges as usize]
+            }
+            _ => edges,
+        }
+    }
+
+    pub fn set_truncated(&self) {
+        self.truncated.store(true, Ordering::Release)
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_budget_is_never_exhausted() {
+        let budget = TraversalBudget::new(QueryLimits::default());
+
+        for _ in 0..1000 {
+            assert!(budget.try_fetch_node());
+        }
+        assert!(!budget.is_exhausted());
+        assert_eq!(budget.limit_edges(&[1, 2, 3]), &[1, 2, 3]);
+        assert!(!budget.is_truncated());
+    }
+
+    #[test]
+    fn test_max_nodes_is_shared_between_clones() {
+        let budget = TraversalBudget::new(QueryLimits {
+            max_nodes: Some(2),
+            ..Default::default()
+        });
+        let neighbor_budget = budget.descend();
+
+        assert!(budget.try_fetch_node());
+        assert!(!budget.is_exhausted());
+        assert!(neighbor_budget.try_fetch_node());
+        assert!(budget.is_exhausted());
+        assert!(!budget.is_truncated());
+
+        assert!(!budget.clone().try_fetch_node());
+        assert!(neighbor_budget.is_truncated());
+    }
+
+    #[test]
+    fn test_zero_max_nodes_fetches_nothing() {
+        let budget = TraversalBudget::new(QueryLimits {
+            max_nodes: Some(0),
+            ..Default::default()
+        });
+
+        assert!(budget.is_exhausted());
+        assert!(!budget.try_fetch_node());
+        assert!(budget.is_truncated());
+    }
+
+    #[test]
+    fn test_max_depth_limits_each_descent() {
+        let budget = TraversalBudget::new(QueryLimits {
+            max_depth: Some(1),
+            ..Default::default()
+        });
+
+        assert!(budget.try_fetch_node());
+        let one_hop = budget.descend();
+        assert!(one_hop.try_fetch_node());
+        assert!(!budget.is_truncated());
+
+        assert!(!one_hop.descend().try_fetch_node());
+        assert!(budget.is_truncated());
+
+        // A sibling at the same depth is unaffected by the deeper descent
+        assert!(budget.descend().try_fetch_node());
+    }
+
+    #[test]
+    fn test_limit_edges_truncates_only_when_edges_are_dropped() {
+        let budget = TraversalBudget::new(QueryLimits {
+            max_edges_per_hop: Some(2),
+            ..Default::default()
+        });
+
+        assert_eq!(budget.limit_edges(&[1, 2]), &[1, 2]);
+        assert!(!budget.is_truncated());
+
+        assert_eq!(budget.limit_edges(&[1, 2, 3]), &[1, 2]);
+        assert!(budget.is_truncated());
+    }
+}
@@ -17,16 +17,22 @@ use rust_proto::{
         api::{
             graph::v1beta1::{
                 ImmutableStrProp,
+                ImmutableUintProp,
                 NodeProperty,
                 Property,
             },
             graph_mutation::v1beta1::messages as mutation,
             graph_query_service::v1beta1::messages::{
+                IntFilter,
+                IntOperation,
+                IntPropertyValue,
                 MatchedGraphWithUid,
                 MaybeMatchWithUid,
                 NodePropertyQuery,
+                QueryGraphByTypeRequest,
                 QueryGraphFromUidRequest,
                 QueryGraphWithUidRequest,
+                QueryLimits,
                 StringCmp,
             },
             graph_schema_manager::v1beta1::messages as graph_schema_manager_api,
@@ -36,6 +42,7 @@ use rust_proto::{
         common::v1beta1::types::{
             EdgeName,
             NodeType,
+            PropertyName,
         },
     },
 };
@@ -57,6 +64,7 @@ async fn provision_example_graph_schema(tenant_id: uuid::Uuid) -> eyre::Result<(
             schema: get_example_graphql_schema().unwrap(),
             schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
             schema_version: 0,
+            force: false,
         })
         .await?;
     Ok(())
@@ -249,4 +257,674 @@ async fn test_query_two_attached_nodes() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test_log::test(tokio::test)]
+async fn test_query_int_and_uid_filters() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let query_client_config = GraphQueryClientConfig::parse();
+    let mut graph_query_client = build_grpc_client(query_client_config).await?;
+
+    let mutation_client_config = GraphMutationClientConfig::parse();
+    let mut graph_mutation_client = build_grpc_client(mutation_client_config).await?;
+
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client = build_grpc_client(provisioner_client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    _span.record("tenant_id", &format!("{tenant_id}"));
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let mut uid_allocator_client = build_grpc_client(UidAllocatorClientConfig::parse()).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    provision_example_graph_schema(tenant_id).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: process_uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_id".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableUintProp(ImmutableUintProp { prop: 1234 }),
+            },
+        })
+        .await?;
+
+    let pid_filter = |operation, value, negated| IntFilter {
+        operation,
+        value,
+        negated,
+    };
+
+    // Each case is (filters, expected to match)
+    let cases = [
+        (vec![pid_filter(IntOperation::Has, 0, false)], true),
+        (vec![pid_filter(IntOperation::Equal, 1234, false)], true),
+        (vec![pid_filter(IntOperation::Equal, 1234, true)], false),
+        (
+            vec![
+                pid_filter(IntOperation::GreaterThan, 1000, false),
+                pid_filter(IntOperation::LessThan, 2000, false),
+            ],
+            true,
+        ),
+        (
+            vec![pid_filter(IntOperation::LessThanOrEqual, 1233, false)],
+            false,
+        ),
+    ];
+
+    for (filters, expect_match) in cases {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_int_comparisons("process_id".try_into()?, filters.clone())
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid: process_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(matched, expect_match, "filters: {filters:?}");
+    }
+
+    // The matched int properties are returned with the node
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_int_comparisons(
+            "process_id".try_into()?,
+            vec![pid_filter(IntOperation::Equal, 1234, false)],
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+
+    let matched_graph = match response.maybe_match {
+        MaybeMatchWithUid::Matched(MatchedGraphWithUid { matched_graph, .. }) => matched_graph,
+        MaybeMatchWithUid::Missed(_) => panic!("Expected a match"),
+    };
+    let returned_node = &matched_graph.nodes[&process_uid];
+    assert_eq!(
+        returned_node.int_properties.prop_map[&PropertyName::try_from("process_id")?],
+        IntPropertyValue::U64(1234)
+    );
+
+    // A missing property only matches a negated `Has`
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_int_comparisons(
+            "created_at".try_into()?,
+            vec![pid_filter(IntOperation::Has, 0, true)],
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+    assert!(matches!(
+        response.maybe_match,
+        MaybeMatchWithUid::Matched(_)
+    ));
+
+    // u64 values above i64::MAX compare by their actual value
+    let mutation::CreateNodeResponse { uid: big_pid_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: big_pid_uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_id".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableUintProp(ImmutableUintProp { prop: u64::MAX }),
+            },
+        })
+        .await?;
+
+    let cases = [
+        (pid_filter(IntOperation::GreaterThan, i64::MAX, false), true),
+        (pid_filter(IntOperation::LessThan, 0, false), false),
+        (pid_filter(IntOperation::Equal, -1, false), false),
+    ];
+
+    for (filter, expect_match) in cases {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_int_comparisons("process_id".try_into()?, vec![filter.clone()])
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid: big_pid_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(matched, expect_match, "filter: {filter:?}");
+    }
+
+    // Uid filters only match the given node
+    let mutation::CreateNodeResponse { uid: other_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    for (uid_filter, expect_match) in [(process_uid, true), (other_uid, false)] {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_uid(uid_filter)
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid: process_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(matched, expect_match, "uid filter: {uid_filter:?}");
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_graph_by_type() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let query_client_config = GraphQueryClientConfig::parse();
+    let mut graph_query_client = build_grpc_client(query_client_config).await?;
+
+    let mutation_client_config = GraphMutationClientConfig::parse();
+    let mut graph_mutation_client = build_grpc_client(mutation_client_config).await?;
+
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client = build_grpc_client(provisioner_client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    _span.record("tenant_id", &format!("{tenant_id}"));
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let mut uid_allocator_client = build_grpc_client(UidAllocatorClientConfig::parse()).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    provision_example_graph_schema(tenant_id).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    // Three processes, two of which are chrome.exe
+    let mut chrome_uids = vec![];
+    for process_name in ["chrome.exe", "svchost.exe", "chrome.exe"] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.into(),
+                    }),
+                },
+            })
+            .await?;
+
+        if process_name == "chrome.exe" {
+            chrome_uids.push(uid);
+        }
+    }
+
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_string_comparisons(
+            "process_name".try_into()?,
+            vec![StringCmp::Eq("chrome.exe".to_owned(), false)],
+        )
+        .build();
+
+    // Page through one candidate at a time
+    let mut matched_uids = vec![];
+    let mut page_token = None;
+    let mut pages = 0;
+    loop {
+        let response = graph_query_client
+            .query_graph_by_type(QueryGraphByTypeRequest {
+                tenant_id,
+                graph_query: graph_query.clone(),
+                page_size: 1,
+                page_token,
+            })
+            .await?;
+        pages += 1;
+
+        assert!(response.matched_graphs.len() <= 1);
+        for matched in response.matched_graphs {
+            assert_eq!(matched.matched_graph.nodes.len(), 1);
+            matched_uids.push(matched.root_uid);
+        }
+
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+        assert!(pages <= 4, "expected the scan to end after every node");
+    }
+
+    matched_uids.sort();
+    chrome_uids.sort();
+    assert_eq!(matched_uids, chrome_uids);
+
+    // The same query in a single page
+    let response = graph_query_client
+        .query_graph_by_type(QueryGraphByTypeRequest {
+            tenant_id,
+            graph_query,
+            page_size: 0,
+            page_token: None,
+        })
+        .await?;
+    assert_eq!(response.matched_graphs.len(), 2);
+    assert!(response.next_page_token.is_none());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_limits_truncate_graph() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let query_client_config = GraphQueryClientConfig::parse();
+    let mut graph_query_client = build_grpc_client(query_client_config).await?;
+
+    let mutation_client_config = GraphMutationClientConfig::parse();
+    let mut graph_mutation_client = build_grpc_client(mutation_client_config).await?;
+
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client = build_grpc_client(provisioner_client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    _span.record("tenant_id", &format!("{tenant_id}"));
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let mut uid_allocator_client = build_grpc_client(UidAllocatorClientConfig::parse()).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    provision_example_graph_schema(tenant_id).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // The process created two files
+    let forward_edge_name = EdgeName::try_from("created_file")?;
+    let reverse_edge_name = EdgeName::try_from("created_by_process")?;
+    for _ in 0..2 {
+        let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: file_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uid,
+                to_uid: file_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    let mut graph_query = NodeQuery::root(process_node_type.clone())
+        .with_shared_edge(
+            forward_edge_name.clone(),
+            reverse_edge_name.clone(),
+            NodePropertyQuery::new(file_node_type.clone()),
+            |_| {},
+        )
+        .build();
+
+    // Without limits, both files are returned
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query: graph_query.clone(),
+        })
+        .await?;
+
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+    assert_eq!(matched_graph.nodes.len(), 3);
+    assert!(!matched_graph.truncated);
+
+    // Following one edge per hop still matches, but only returns one file
+    graph_query.limits = QueryLimits {
+        max_edges_per_hop: Some(1),
+        ..Default::default()
+    };
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query: graph_query.clone(),
+        })
+        .await?;
+
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+    assert_eq!(matched_graph.nodes.len(), 2);
+    assert!(matched_graph.truncated);
+
+    // Not being able to leave the root node leaves the file query unchecked,
+    // so only the partial match is returned
+    graph_query.limits = QueryLimits {
+        max_depth: Some(0),
+        ..Default::default()
+    };
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query: graph_query.clone(),
+        })
+        .await?;
+
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+    assert_eq!(matched_graph.nodes.len(), 1);
+    assert!(matched_graph.get_node(process_uid).is_some());
+    assert!(matched_graph.truncated);
+
+    // Not being able to fetch any node means there's nothing to return
+    graph_query.limits = QueryLimits {
+        max_nodes: Some(0),
+        ..Default::default()
+    };
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+
+    assert!(response.matched_graph.is_none());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mutate_graph_creates_nodes_and_edges() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let query_client_config = GraphQueryClientConfig::parse();
+    let mut graph_query_client = build_grpc_client(query_client_config).await?;
+
+    let mutation_client_config = GraphMutationClientConfig::parse();
+    let mut graph_mutation_client = build_grpc_client(mutation_client_config).await?;
+
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client = build_grpc_client(provisioner_client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    _span.record("tenant_id", &format!("{tenant_id}"));
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let mut uid_allocator_client = build_grpc_client(UidAllocatorClientConfig::parse()).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    provision_example_graph_schema(tenant_id).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let forward_edge_name = EdgeName {
+        value: "binary_file".to_string(),
+    };
+    let reverse_edge_name = EdgeName {
+        value: "executed_as_processes".to_string(),
+    };
+
+    let response = graph_mutation_client
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            nodes: vec![
+                mutation::NodeMutation {
+                    node_key: "process".to_owned(),
+                    node_type: process_node_type.clone(),
+                    properties: vec![mutation::NodePropertyMutation {
+                        property_name: "process_name".try_into()?,
+                        property: NodeProperty {
+                            property: Property::ImmutableStrProp(ImmutableStrProp {
+                                prop: "chrome.exe".into(),
+                            }),
+                        },
+                    }],
+                },
+                mutation::NodeMutation {
+                    node_key: "file".to_owned(),
+                    node_type: file_node_type.clone(),
+                    properties: vec![],
+                },
+            ],
+            edges: vec![mutation::EdgeMutation {
+                edge_name: forward_edge_name.clone(),
+                from: mutation::NodeRef::NodeKey("process".to_owned()),
+                to: mutation::NodeRef::NodeKey("file".to_owned()),
+            }],
+            existing_nodes: vec![],
+        })
+        .await?;
+
+    assert_eq!(response.uids.len(), 2);
+    let process_uid = response.uids["process"];
+    let file_uid = response.uids["file"];
+    assert_ne!(process_uid, file_uid);
+
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_string_comparisons(
+            "process_name".try_into()?,
+            vec![StringCmp::Eq("chrome.exe".to_owned(), false)],
+        )
+        .with_shared_edge(
+            forward_edge_name.clone(),
+            reverse_edge_name.clone(),
+            NodePropertyQuery::new(file_node_type.clone()),
+            |_| {},
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id: tenant_id.into(),
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+    assert_eq!(matched_graph.nodes.len(), 2);
+    assert_eq!(matched_graph.edges.len(), 2); // forward and reverse edge
+    assert!(matched_graph.edges[&(process_uid, forward_edge_name)].contains(&file_uid));
+    assert!(matched_graph.edges[&(file_uid, reverse_edge_name.clone())].contains(&process_uid));
+
+    // Later requests can attach new nodes to, and set properties on, the
+    // nodes that already exist
+    let created_file_edge_name = EdgeName::try_from("created_file")?;
+    let created_by_process_edge_name = EdgeName::try_from("created_by_process")?;
+    let existing_process = mutation::ExistingNode {
+        uid: process_uid,
+        node_type: process_node_type.clone(),
+    };
+    let response = graph_mutation_client
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            nodes: vec![mutation::NodeMutation {
+                node_key: "created_file".to_owned(),
+                node_type: file_node_type.clone(),
+                properties: vec![],
+            }],
+            edges: vec![mutation::EdgeMutation {
+                edge_name: created_file_edge_name.clone(),
+                from: mutation::NodeRef::ExistingNode(existing_process.clone()),
+                to: mutation::NodeRef::NodeKey("created_file".to_owned()),
+            }],
+            existing_nodes: vec![mutation::ExistingNodeMutation {
+                node: mutation::ExistingNode {
+                    uid: file_uid,
+                    node_type: file_node_type.clone(),
+                },
+                properties: vec![mutation::NodePropertyMutation {
+                    property_name: "file_path".try_into()?,
+                    property: NodeProperty {
+                        property: Property::ImmutableStrProp(ImmutableStrProp {
+                            prop: "/usr/bin/chrome.exe".into(),
+                        }),
+                    },
+                }],
+            }],
+        })
+        .await?;
+
+    assert_eq!(response.uids.len(), 1);
+    let created_file_uid = response.uids["created_file"];
+
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_shared_edge(
+            forward_edge_name.clone(),
+            reverse_edge_name,
+            NodePropertyQuery::new(file_node_type.clone()),
+            |binary_file| {
+                binary_file.with_string_comparisons(
+                    "file_path".try_into().unwrap(),
+                    vec![StringCmp::Eq("/usr/bin/chrome.exe".to_owned(), false)],
+                );
+            },
+        )
+        .with_shared_edge(
+            created_file_edge_name.clone(),
+            created_by_process_edge_name,
+            NodePropertyQuery::new(file_node_type.clone()),
+            |_| {},
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id: tenant_id.into(),
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+    assert_eq!(matched_graph.nodes.len(), 3);
+    assert!(matched_graph.edges[&(process_uid, forward_edge_name)].contains(&file_uid));
+    assert!(
+        matched_graph.edges[&(process_uid, created_file_edge_name.clone())]
+            .contains(&created_file_uid)
+    );
+
+    // Node keys only refer to nodes in the same request
+    let result = graph_mutation_client
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            nodes: vec![],
+            edges: vec![mutation::EdgeMutation {
+                edge_name: created_file_edge_name,
+                from: mutation::NodeRef::ExistingNode(existing_process),
+                to: mutation::NodeRef::NodeKey("created_file".to_owned()),
+            }],
+            existing_nodes: vec![],
+        })
+        .await;
+    assert!(result.is_err());
+
+    drop(_span);
+    Ok(())
+}
+
 // TODO: test `with_edge_to`
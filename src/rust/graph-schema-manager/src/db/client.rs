@@ -4,6 +4,8 @@ use rust_proto::graplinc::grapl::common::v1beta1::types::{
     NodeType,
 };
 use sqlx::{
+    pool::PoolConnection,
+    PgConnection,
     Postgres,
     Transaction,
 };
@@ -13,6 +15,15 @@ use crate::{
     config::SchemaDbConfig,
     db::models::{
         GetEdgeSchemaRequestRow,
+        ListNodeSchemasRow,
+        NodeEdgeSchemaRow,
+        NodeIdentityAlgorithmRow,
+        NodePropertySchemaRow,
+        NodeSchemaVersionRow,
+        NodeTypeRow,
+        RawSchemaRow,
+        SessionIdentityArgumentsRow,
+        StaticIdentityArgumentsRow,
         StoredEdgeCardinality,
     },
 };
@@ -45,6 +56,34 @@ impl SchemaDbClient {
         self.pool.begin().await
     }
 
+    pub async fn acquire(&self) -> Result<PoolConnection<Postgres>, sqlx::Error> {
+        self.pool.acquire().await
+    }
+
+    /// Locks every schema row `tenant_id` has deployed until `txn` ends, so
+    /// that concurrent deployments check compatibility against each other's
+    /// versions in turn rather than against the same previous version.
+    pub async fn lock_deployed_schemas(
+        &self,
+        txn: &mut Txn<'_>,
+        tenant_id: uuid::Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query_as!(
+            NodeSchemaVersionRow,
+            r#"select
+                schema_version
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1
+             FOR UPDATE;
+                 "#,
+            tenant_id,
+        )
+        .fetch_all(&mut *txn)
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_edge_schema(
         &self,
         tenant_id: uuid::Uuid,
@@ -73,6 +112,270 @@ impl SchemaDbClient {
         .await
     }
 
+    /// Returns the identity algorithm of `node_type` at `schema_version`, or at
+    /// the most recently deployed version if `schema_version` is `None`.
+    /// Returns `None` if no such schema defines the node type.
+    pub async fn get_node_identity_algorithm(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: Option<i16>,
+    ) -> Result<Option<NodeIdentityAlgorithmRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeIdentityAlgorithmRow,
+            r#"select
+                identity_algorithm,
+                schema_version
+             FROM graph_schema_manager.node_identity_algorithm
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 ($3::smallint IS NULL OR schema_version = $3)
+             ORDER BY schema_version DESC
+             LIMIT 1;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_optional(&mut *conn)
+        .await
+    }
+
+    pub async fn get_session_identity_args(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<SessionIdentityArgumentsRow, sqlx::Error> {
+        sqlx::query_as!(
+            SessionIdentityArgumentsRow,
+            r#"select
+                pseudo_key_properties,
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property
+             FROM graph_schema_manager.session_identity_arguments
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    pub async fn get_static_identity_args(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<StaticIdentityArgumentsRow, sqlx::Error> {
+        sqlx::query_as!(
+            StaticIdentityArgumentsRow,
+            r#"select
+                static_key_properties
+             FROM graph_schema_manager.static_identity_arguments
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Returns `schema_version` if it defines `node_type`, or the most recently
+    /// deployed version that does if `schema_version` is `None`. Returns
+    /// `None` if no such schema defines the node type.
+    pub async fn get_node_schema_version(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: Option<i16>,
+    ) -> Result<Option<NodeSchemaVersionRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeSchemaVersionRow,
+            r#"select
+                schema_version
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 ($3::smallint IS NULL OR schema_version = $3)
+             ORDER BY schema_version DESC
+             LIMIT 1;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Returns the most recently deployed schema version that precedes
+    /// `schema_version`, or `None` if there isn't one.
+    pub async fn get_previous_schema_version(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        schema_version: i16,
+    ) -> Result<Option<NodeSchemaVersionRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeSchemaVersionRow,
+            r#"select
+                schema_version
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 schema_version < $2
+             ORDER BY schema_version DESC
+             LIMIT 1;
+                 "#,
+            tenant_id,
+            schema_version,
+        )
+        .fetch_optional(&mut *conn)
+        .await
+    }
+
+    pub async fn list_node_schemas(
+        &self,
+        tenant_id: uuid::Uuid,
+    ) -> Result<Vec<ListNodeSchemasRow>, sqlx::Error> {
+        sqlx::query_as!(
+            ListNodeSchemasRow,
+            r#"select
+                node_type,
+                schema_version,
+                deployment_timestamp
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1
+             ORDER BY node_type, schema_version;
+                 "#,
+            tenant_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn get_raw_schema(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<Option<RawSchemaRow>, sqlx::Error> {
+        sqlx::query_as!(
+            RawSchemaRow,
+            r#"select
+                raw_schema,
+                schema_type
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Returns every node type defined by `schema_version`.
+    pub async fn get_node_types(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        schema_version: i16,
+    ) -> Result<Vec<NodeTypeRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeTypeRow,
+            r#"select
+                node_type
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 schema_version = $2
+             ORDER BY node_type;
+                 "#,
+            tenant_id,
+            schema_version,
+        )
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    pub async fn get_node_property_schemas(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<Vec<NodePropertySchemaRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodePropertySchemaRow,
+            r#"select
+                property_name,
+                property_type as "property_type: StoredPropertyType",
+                identity_only
+             FROM graph_schema_manager.property_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    pub async fn get_node_edge_schemas(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<Vec<NodeEdgeSchemaRow>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeEdgeSchemaRow,
+            r#"select
+                forward_edge_name,
+                reverse_edge_name,
+                forward_edge_cardinality as "forward_edge_cardinality: StoredEdgeCardinality",
+                reverse_edge_cardinality as "reverse_edge_cardinality: StoredEdgeCardinality"
+             FROM graph_schema_manager.edge_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_all(&mut *conn)
+        .await
+    }
+
     pub async fn insert_node_identity_algorithm(
         &self,
         txn: &mut Txn<'_>,
@@ -1,4 +1,7 @@
-use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::EdgeCardinality;
+use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
+    EdgeCardinality,
+    PropertyType,
+};
 
 #[derive(sqlx::Type, Copy, Clone, Debug)]
 #[sqlx(type_name = "edge_cardinality")]
@@ -23,6 +26,25 @@ impl From<StoredEdgeCardinality> for EdgeCardinality {
     }
 }
 
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct NodeIdentityAlgorithmRow {
+    pub identity_algorithm: String,
+    pub schema_version: i16,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct SessionIdentityArgumentsRow {
+    pub pseudo_key_properties: Vec<String>,
+    pub creation_timestamp_property: String,
+    pub last_seen_timestamp_property: String,
+    pub termination_timestamp_property: String,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct StaticIdentityArgumentsRow {
+    pub static_key_properties: Vec<String>,
+}
+
 #[derive(sqlx::Type, Copy, Clone, Debug)]
 #[sqlx(type_name = "property_type")]
 pub enum StoredPropertyType {
@@ -35,6 +57,58 @@ pub enum StoredPropertyType {
     MinU64,
 }
 
+impl From<StoredPropertyType> for PropertyType {
+    fn from(p: StoredPropertyType) -> Self {
+        match p {
+            StoredPropertyType::ImmutableString => PropertyType::ImmutableString,
+            StoredPropertyType::ImmutableI64 => PropertyType::ImmutableI64,
+            StoredPropertyType::MaxI64 => PropertyType::MaxI64,
+            StoredPropertyType::MinI64 => PropertyType::MinI64,
+            StoredPropertyType::ImmutableU64 => PropertyType::ImmutableU64,
+            StoredPropertyType::MaxU64 => PropertyType::MaxU64,
+            StoredPropertyType::MinU64 => PropertyType::MinU64,
+        }
+    }
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct NodeSchemaVersionRow {
+    pub schema_version: i16,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct ListNodeSchemasRow {
+    pub node_type: String,
+    pub schema_version: i16,
+    pub deployment_timestamp: sqlx::types::time::PrimitiveDateTime,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct RawSchemaRow {
+    pub raw_schema: Vec<u8>,
+    pub schema_type: String,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct NodeTypeRow {
+    pub node_type: String,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct NodePropertySchemaRow {
+    pub property_name: String,
+    pub property_type: StoredPropertyType,
+    pub identity_only: bool,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct NodeEdgeSchemaRow {
+    pub forward_edge_name: String,
+    pub reverse_edge_name: String,
+    pub forward_edge_cardinality: StoredEdgeCardinality,
+    pub reverse_edge_cardinality: StoredEdgeCardinality,
+}
+
 #[derive(sqlx::Type, Clone, Debug)]
 struct NodeIdentityRow {
     identity_algorithm: String,
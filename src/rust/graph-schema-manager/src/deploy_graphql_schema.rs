@@ -11,23 +11,45 @@ use grapl_graphql_codegen::{
     Document,
     ParseError,
 };
+use rust_proto::{
+    graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::messages::{
+            EdgeSchema,
+            IdentityAlgorithm,
+            PropertySchema,
+            SessionIdentityArguments,
+            StaticIdentityArguments,
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            PropertyName,
+        },
+    },
+    SerDeError,
+};
 use sqlx::{
     Postgres,
     Transaction,
 };
 
-use crate::db::{
-    client::{
-        SchemaDbClient,
-        Txn,
+use crate::{
+    db::{
+        client::{
+            SchemaDbClient,
+            Txn,
+        },
+        models::{
+            StoredEdgeCardinality,
+            StoredPropertyType,
+        },
     },
-    models::{
-        StoredEdgeCardinality,
-        StoredPropertyType,
+    schema_diff::{
+        NodeSchemaSnapshot,
+        SchemaSnapshot,
     },
 };
 
-const SCHEMA_TYPE: &str = "Graphql_V0";
+pub(crate) const SCHEMA_TYPE: &str = "Graphql_V0";
 
 #[derive(thiserror::Error, Debug)]
 pub enum DeployGraphqlError {
@@ -39,28 +61,83 @@ pub enum DeployGraphqlError {
     GraphqlParseError(#[from] ParseError),
     #[error("Schema is invalid {0}")]
     InvalidSchema(&'static str),
+    #[error("Schema contains an invalid name {0}")]
+    InvalidName(#[from] SerDeError),
+}
+
+pub fn parse_graphql_schema(raw_schema: &str) -> Result<Vec<NodeType>, DeployGraphqlError> {
+    let document: Document<String> = parse_schema(raw_schema)?;
+    let document = document.into_static();
+
+    node_type::parse_into_node_types(document)
+        .map_err(|e| DeployGraphqlError::ParseError(e.to_string()))
+}
+
+/// Describes `node_types` the same way a deployed schema version is read back
+/// from the database, so that the two can be diffed.
+pub fn graphql_schema_snapshot(
+    node_types: &[NodeType],
+) -> Result<SchemaSnapshot, DeployGraphqlError> {
+    let mut snapshot = SchemaSnapshot::new();
+    for node_type in node_types {
+        let properties = node_type
+            .predicates
+            .iter()
+            .map(|property| {
+                Ok(PropertySchema {
+                    property_name: PropertyName::try_from(property.predicate_name.clone())?,
+                    property_type: get_predicate_type_name(
+                        property.predicate_type,
+                        property.conflict_resolution,
+                    )?
+                    .into(),
+                    identity_only: false,
+                })
+            })
+            .collect::<Result<Vec<_>, DeployGraphqlError>>()?;
+
+        let edges = node_type
+            .edges
+            .iter()
+            .map(|edge| {
+                let (cardinality, reverse_cardinality) = get_edge_cardinalities(edge);
+                Ok(EdgeSchema {
+                    edge_name: EdgeName::try_from(edge.edge_name.clone())?,
+                    reverse_edge_name: EdgeName::try_from(edge.reverse_edge_name.clone())?,
+                    cardinality: cardinality.into(),
+                    reverse_cardinality: reverse_cardinality.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, DeployGraphqlError>>()?;
+
+        snapshot.insert(
+            node_type.type_name.clone(),
+            NodeSchemaSnapshot {
+                identity_algorithm: get_identity_algorithm(node_type)?,
+                properties,
+                edges,
+            },
+        );
+    }
+
+    Ok(snapshot)
 }
 
+/// Inserts `node_types` as `schema_version` within `txn`. Committing `txn` is
+/// left to the caller.
 pub async fn deploy_graphql_schema(
+    txn: &mut Txn<'_>,
     tenant_id: uuid::Uuid,
     raw_schema: &str,
+    node_types: &[NodeType],
     schema_version: u32,
     db_client: &SchemaDbClient,
 ) -> Result<(), DeployGraphqlError> {
-    let document: Document<String> = parse_schema(raw_schema)?;
-    let document = document.into_static();
-
-    let node_types = node_type::parse_into_node_types(document)
-        .map_err(|e| DeployGraphqlError::ParseError(e.to_string()))?;
-
-    let mut txn = db_client.begin_txn().await?;
-
     for node_type in node_types.iter() {
-        deploy_identity_algorithm(&mut txn, db_client, tenant_id, node_type, schema_version)
-            .await?;
+        deploy_identity_algorithm(txn, db_client, tenant_id, node_type, schema_version).await?;
 
         deploy_node_type(
-            &mut txn,
+            txn,
             db_client,
             tenant_id,
             node_type,
@@ -71,7 +148,7 @@ pub async fn deploy_graphql_schema(
 
         for property in node_type.predicates.iter() {
             deploy_node_property(
-                &mut txn,
+                txn,
                 db_client,
                 tenant_id,
                 node_type,
@@ -82,20 +159,10 @@ pub async fn deploy_graphql_schema(
         }
 
         for edge in node_type.edges.iter() {
-            deploy_edge(
-                &mut txn,
-                db_client,
-                tenant_id,
-                node_type,
-                edge,
-                schema_version,
-            )
-            .await?;
+            deploy_edge(txn, db_client, tenant_id, node_type, edge, schema_version).await?;
         }
     }
 
-    txn.commit().await?;
-
     Ok(())
 }
 
@@ -150,27 +217,59 @@ async fn deploy_identity_algorithm(
         )
         .await?;
 
+    match get_identity_algorithm(node_type)? {
+        IdentityAlgorithm::Session(args) => {
+            db_client
+                .insert_session_identity_args(
+                    txn,
+                    tenant_id,
+                    node_type_name,
+                    schema_version,
+                    args.pseudo_key_properties
+                        .into_iter()
+                        .map(|p| p.value)
+                        .collect(),
+                    &args.creation_timestamp_property.value,
+                    &args.last_seen_timestamp_property.value,
+                    &args.termination_timestamp_property.value,
+                )
+                .await?;
+        }
+        IdentityAlgorithm::Static(args) => {
+            db_client
+                .insert_static_identity_args(
+                    txn,
+                    tenant_id,
+                    node_type_name,
+                    schema_version,
+                    args.static_key_properties
+                        .into_iter()
+                        .map(|p| p.value)
+                        .collect(),
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the identity algorithm `node_type` declares from its identity
+/// predicates, validating that they're consistent with it.
+fn get_identity_algorithm(node_type: &NodeType) -> Result<IdentityAlgorithm, DeployGraphqlError> {
     match node_type.identification_algorithm {
         IdentificationAlgorithm::Session => {
-            deploy_session_identity(txn, db_client, tenant_id, node_type, schema_version).await?;
+            Ok(IdentityAlgorithm::Session(get_session_identity(node_type)?))
         }
         IdentificationAlgorithm::Static => {
-            deploy_static_identity(txn, db_client, tenant_id, node_type, schema_version).await?;
+            Ok(IdentityAlgorithm::Static(get_static_identity(node_type)?))
         }
     }
-
-    Ok(())
 }
 
-async fn deploy_session_identity(
-    txn: &mut Transaction<'_, Postgres>,
-    db_client: &SchemaDbClient,
-    tenant_id: uuid::Uuid,
+fn get_session_identity(
     node_type: &NodeType,
-    schema_version: u32,
-) -> Result<(), DeployGraphqlError> {
-    let node_type_name = &node_type.type_name;
-
+) -> Result<SessionIdentityArguments, DeployGraphqlError> {
     let mut pseudo_keys = Vec::with_capacity(1);
 
     let mut creation_timestamp_property: Option<String> = None;
@@ -180,7 +279,7 @@ async fn deploy_session_identity(
     for field in node_type.predicates.iter() {
         match field.identity_predicate_type {
             Some(IdentityPredicateType::SessionPseudoKey) => {
-                pseudo_keys.push(field.predicate_name.clone());
+                pseudo_keys.push(PropertyName::try_from(field.predicate_name.clone())?);
             }
             Some(IdentityPredicateType::SessionCreateTime) => {
                 creation_timestamp_property = Some(field.predicate_name.to_string());
@@ -216,36 +315,22 @@ async fn deploy_session_identity(
         DeployGraphqlError::InvalidSchema("termination_timestamp_property must be present")
     })?;
 
-    db_client
-        .insert_session_identity_args(
-            txn,
-            tenant_id,
-            node_type_name,
-            schema_version,
-            pseudo_keys,
-            &creation_timestamp_property,
-            &last_seen_timestamp_property,
-            &termination_timestamp_property,
-        )
-        .await?;
-
-    Ok(())
+    Ok(SessionIdentityArguments {
+        pseudo_key_properties: pseudo_keys,
+        creation_timestamp_property: PropertyName::try_from(creation_timestamp_property)?,
+        last_seen_timestamp_property: PropertyName::try_from(last_seen_timestamp_property)?,
+        termination_timestamp_property: PropertyName::try_from(termination_timestamp_property)?,
+    })
 }
 
-async fn deploy_static_identity(
-    txn: &mut Transaction<'_, Postgres>,
-    db_client: &SchemaDbClient,
-    tenant_id: uuid::Uuid,
+fn get_static_identity(
     node_type: &NodeType,
-    schema_version: u32,
-) -> Result<(), DeployGraphqlError> {
-    let node_type_name = &node_type.type_name;
-
+) -> Result<StaticIdentityArguments, DeployGraphqlError> {
     let mut static_keys = Vec::with_capacity(1);
     for field in node_type.predicates.iter() {
         match field.identity_predicate_type {
             Some(IdentityPredicateType::StaticId) => {
-                static_keys.push(field.predicate_name.clone());
+                static_keys.push(PropertyName::try_from(field.predicate_name.clone())?);
             }
             Some(IdentityPredicateType::SessionPseudoKey) => {
                 return Err(DeployGraphqlError::InvalidSchema(
@@ -277,11 +362,9 @@ async fn deploy_static_identity(
         ));
     }
 
-    db_client
-        .insert_static_identity_args(txn, tenant_id, node_type_name, schema_version, static_keys)
-        .await?;
-
-    Ok(())
+    Ok(StaticIdentityArguments {
+        static_key_properties: static_keys,
+    })
 }
 
 async fn deploy_node_property(
@@ -317,17 +400,7 @@ async fn deploy_edge(
     edge: &Edge,
     schema_version: u32,
 ) -> Result<(), DeployGraphqlError> {
-    let forward_edge_cardinality = if edge.relationship.to_one() {
-        StoredEdgeCardinality::ToOne
-    } else {
-        StoredEdgeCardinality::ToMany
-    };
-
-    let reverse_edge_cardinality = if edge.relationship.reverse().to_one() {
-        StoredEdgeCardinality::ToOne
-    } else {
-        StoredEdgeCardinality::ToMany
-    };
+    let (forward_edge_cardinality, reverse_edge_cardinality) = get_edge_cardinalities(edge);
 
     db_client
         .insert_edge_schema(
@@ -344,6 +417,23 @@ async fn deploy_edge(
     Ok(())
 }
 
+/// Returns the (forward, reverse) cardinality of `edge`
+fn get_edge_cardinalities(edge: &Edge) -> (StoredEdgeCardinality, StoredEdgeCardinality) {
+    let forward_edge_cardinality = if edge.relationship.to_one() {
+        StoredEdgeCardinality::ToOne
+    } else {
+        StoredEdgeCardinality::ToMany
+    };
+
+    let reverse_edge_cardinality = if edge.relationship.reverse().to_one() {
+        StoredEdgeCardinality::ToOne
+    } else {
+        StoredEdgeCardinality::ToMany
+    };
+
+    (forward_edge_cardinality, reverse_edge_cardinality)
+}
+
 fn get_predicate_type_name(
     predicate_type: PredicateType,
     conflict_resolution: ConflictResolution,
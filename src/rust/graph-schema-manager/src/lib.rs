@@ -1,4 +1,6 @@
 pub mod config;
 pub mod db;
 pub mod deploy_graphql_schema;
+pub mod schema_diff;
 pub mod server;
+pub mod validation;
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+
+use rust_proto::graplinc::grapl::{
+    api::graph_schema_manager::v1beta1::messages::{
+        DiffSchemasResponse,
+        EdgeSchema,
+        EdgeSchemaChange,
+        IdentityAlgorithm,
+        NodeSchemaDiff,
+        PropertySchema,
+        PropertySchemaChange,
+        SchemaCompatibilityViolation,
+        SchemaCompatibilityViolationKind,
+    },
+    common::v1beta1::types::NodeType,
+};
+
+/// Everything a single schema version defines for one node type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSchemaSnapshot {
+    pub identity_algorithm: IdentityAlgorithm,
+    pub properties: Vec<PropertySchema>,
+    pub edges: Vec<EdgeSchema>,
+}
+
+/// Every node type defined by a schema version, keyed by node type name.
+pub type SchemaSnapshot = BTreeMap<String, NodeSchemaSnapshot>;
+
+/// Structurally diffs two schema versions. Node types present in both
+/// versions are only reported as changed if something about them differs.
+pub fn diff_schemas(from: &SchemaSnapshot, to: &SchemaSnapshot) -> DiffSchemasResponse {
+    let added_node_types = to
+        .keys()
+        .filter(|node_type| !from.contains_key(*node_type))
+        .map(|node_type| to_node_type(node_type))
+        .collect();
+
+    let removed_node_types = from
+        .keys()
+        .filter(|node_type| !to.contains_key(*node_type))
+        .map(|node_type| to_node_type(node_type))
+        .collect();
+
+    let changed_node_types = from
+        .iter()
+        .filter_map(|(node_type, before)| {
+            let after = to.get(node_type)?;
+            diff_node_schema(node_type, before, after)
+        })
+        .collect();
+
+    DiffSchemasResponse {
+        added_node_types,
+        removed_node_types,
+        changed_node_types,
+    }
+}
+
+/// Returns the changes in `diff` that would make data written under the
+/// earlier schema version unreadable or incorrect under the later one.
+/// Adding node types, properties and edges, and removing node types and
+/// properties, is always compatible. Removing an edge removes its reverse too,
+/// leaving the stored edges between existing nodes unreachable from one side.
+pub fn compatibility_violations(diff: &DiffSchemasResponse) -> Vec<SchemaCompatibilityViolation> {
+    let mut violations = Vec::new();
+
+    for node_diff in diff.changed_node_types.iter() {
+        let violation = |kind, description| SchemaCompatibilityViolation {
+            node_type: node_diff.node_type.clone(),
+            kind,
+            description,
+        };
+
+        if node_diff.identity_algorithm_changed {
+            violations.push(violation(
+                SchemaCompatibilityViolationKind::IdentityAlgorithmChanged,
+                format!(
+                    "identity algorithm of {} changed",
+                    node_diff.node_type.value
+                ),
+            ));
+        }
+
+        for change in node_diff.changed_properties.iter() {
+            if change.before.property_type != change.after.property_type {
+                violations.push(violation(
+                    SchemaCompatibilityViolationKind::PropertyTypeChanged,
+                    format!(
+                        "property {} changed from {:?} to {:?}",
+                        change.before.property_name,
+                        change.before.property_type,
+                        change.after.property_type,
+                    ),
+                ));
+            }
+        }
+
+        for edge in node_diff.removed_edges.iter() {
+            violations.push(violation(
+                SchemaCompatibilityViolationKind::EdgeRemoved,
+                format!("edge {} removed", edge.edge_name),
+            ));
+            violations.push(violation(
+                SchemaCompatibilityViolationKind::ReverseEdgeRemoved,
+                format!(
+                    "reverse edge {} removed along with {}",
+                    edge.reverse_edge_name, edge.edge_name,
+                ),
+            ));
+        }
+
+        for change in node_diff.changed_edges.iter() {
+            let (before, after) = (&change.before, &change.after);
+            if before.reverse_edge_name != after.reverse_edge_name {
+                violations.push(violation(
+                    SchemaCompatibilityViolationKind::ReverseEdgeRenamed,
+                    format!(
+                        "reverse of edge {} renamed from {} to {}",
+                        before.edge_name, before.reverse_edge_name, after.reverse_edge_name,
+                    ),
+                ));
+            }
+            if before.cardinality != after.cardinality
+                || before.reverse_cardinality != after.reverse_cardinality
+            {
+                violations.push(violation(
+                    SchemaCompatibilityViolationKind::EdgeCardinalityChanged,
+                    format!(
+                        "cardinality of edge {} changed from {:?}/{:?} to {:?}/{:?}",
+                        before.edge_name,
+                        before.cardinality,
+                        before.reverse_cardinality,
+                        after.cardinality,
+                        after.reverse_cardinality,
+                    ),
+                ));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Returns `None` if `before` and `after` are equivalent.
+fn diff_node_schema(
+    node_type: &str,
+    before: &NodeSchemaSnapshot,
+    after: &NodeSchemaSnapshot,
+) -> Option<NodeSchemaDiff> {
+    let (added_properties, removed_properties, changed_properties) = diff_by_name(
+        &before.properties,
+        &after.properties,
+        |p| p.property_name.value.clone(),
+        |before, after| PropertySchemaChange { before, after },
+    );
+    let (added_edges, removed_edges, changed_edges) = diff_by_name(
+        &before.edges,
+        &after.edges,
+        |e| e.edge_name.value.clone(),
+        |before, after| EdgeSchemaChange { before, after },
+    );
+
+    let diff = NodeSchemaDiff {
+        node_type: to_node_type(node_type),
+        added_properties,
+        removed_properties,
+        changed_properties,
+        added_edges,
+        removed_edges,
+        changed_edges,
+        identity_algorithm_changed: before.identity_algorithm != after.identity_algorithm,
+    };
+
+    let unchanged = diff.added_properties.is_empty()
+        && diff.removed_properties.is_empty()
+        && diff.changed_properties.is_empty()
+        && diff.added_edges.is_empty()
+        && diff.removed_edges.is_empty()
+        && diff.changed_edges.is_empty()
+        && !diff.identity_algorithm_changed;
+
+    if unchanged {
+        None
+    } else {
+        Some(diff)
+    }
+}
+
+/// Pairs up `before` and `after` by name, returning the (added, removed,
+/// changed) entries ordered by name.
+fn diff_by_name<T, C>(
+    before: &[T],
+    after: &[T],
+    name: impl Fn(&T) -> String,
+    change: impl Fn(T, T) -> C,
+) -> (Vec<T>, Vec<T>, Vec<C>)
+where
+    T: Clone + PartialEq,
+{
+    let before: BTreeMap<String, &T> = before.iter().map(|t| (name(t), t)).collect();
+    let after: BTreeMap<String, &T> = after.iter().map(|t| (name(t), t)).collect();
+
+    let added = after
+        .iter()
+        .filter(|(name, _)| !before.contains_key(*name))
+        .map(|(_, t)| (*t).clone())
+        .collect();
+
+    let removed = before
+        .iter()
+        .filter(|(name, _)| !after.contains_key(*name))
+        .map(|(_, t)| (*t).clone())
+        .collect();
+
+    let changed = before
+        .iter()
+        .filter_map(|(name, b)| {
+            let a = after.get(name)?;
+            if b != a {
+                Some(change((*b).clone(), (*a).clone()))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    (added, removed, changed)
+}
+
+fn to_node_type(node_type: &str) -> NodeType {
+    NodeType {
+        value: node_type.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::messages::{
+            EdgeCardinality,
+            PropertyType,
+            StaticIdentityArguments,
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            PropertyName,
+        },
+    };
+
+    use super::*;
+
+    fn property(name: &str, property_type: PropertyType) -> PropertySchema {
+        PropertySchema {
+            property_name: PropertyName::try_from(name.to_owned()).unwrap(),
+            property_type,
+            identity_only: false,
+        }
+    }
+
+    fn edge(name: &str, cardinality: EdgeCardinality) -> EdgeSchema {
+        EdgeSchema {
+            edge_name: EdgeName::try_from(name).unwrap(),
+            reverse_edge_name: EdgeName::try_from("reverse").unwrap(),
+            cardinality,
+            reverse_cardinality: EdgeCardinality::ToMany,
+        }
+    }
+
+    fn snapshot(properties: Vec<PropertySchema>, edges: Vec<EdgeSchema>) -> NodeSchemaSnapshot {
+        NodeSchemaSnapshot {
+            identity_algorithm: IdentityAlgorithm::Static(StaticIdentityArguments {
+                static_key_properties: vec![PropertyName::try_from("name").unwrap()],
+            }),
+            properties,
+            edges,
+        }
+    }
+
+    #[test]
+    fn test_identical_schemas_have_empty_diff() {
+        let schema: SchemaSnapshot = [(
+            "Process".to_owned(),
+            snapshot(
+                vec![property("name", PropertyType::ImmutableString)],
+                vec![edge("children", EdgeCardinality::ToMany)],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        let diff = diff_schemas(&schema, &schema);
+
+        assert!(diff.added_node_types.is_empty());
+        assert!(diff.removed_node_types.is_empty());
+        assert!(diff.changed_node_types.is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_node_types() {
+        let from: SchemaSnapshot = [("File".to_owned(), snapshot(vec![], vec![]))]
+            .into_iter()
+            .collect();
+        let to: SchemaSnapshot = [("Process".to_owned(), snapshot(vec![], vec![]))]
+            .into_iter()
+            .collect();
+
+        let diff = diff_schemas(&from, &to);
+
+        assert_eq!(diff.added_node_types, vec![to_node_type("Process")]);
+        assert_eq!(diff.removed_node_types, vec![to_node_type("File")]);
+        assert!(diff.changed_node_types.is_empty());
+    }
+
+    #[test]
+    fn test_changed_properties_and_edges() {
+        let from: SchemaSnapshot = [(
+            "Process".to_owned(),
+            snapshot(
+                vec![
+                    property("name", PropertyType::ImmutableString),
+                    property("pid", PropertyType::ImmutableI64),
+                ],
+                vec![edge("parent", EdgeCardinality::ToMany)],
+            ),
+        )]
+        .into_iter()
+        .collect();
+        let to: SchemaSnapshot = [(
+            "Process".to_owned(),
+            snapshot(
+                vec![
+                    property("name", PropertyType::ImmutableString),
+                    property("pid", PropertyType::ImmutableU64),
+                    property("exe", PropertyType::ImmutableString),
+                ],
+                vec![edge("parent", EdgeCardinality::ToOne)],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        let diff = diff_schemas(&from, &to);
+
+        assert_eq!(diff.changed_node_types.len(), 1);
+        let node_diff = &diff.changed_node_types[0];
+        assert_eq!(
+            node_diff.added_properties,
+            vec![property("exe", PropertyType::ImmutableString)]
+        );
+        assert!(node_diff.removed_properties.is_empty());
+        assert_eq!(
+            node_diff.changed_properties,
+            vec![PropertySchemaChange {
+                before: property("pid", PropertyType::ImmutableI64),
+                after: property("pid", PropertyType::ImmutableU64),
+            }]
+        );
+        assert_eq!(
+            node_diff.changed_edges,
+            vec![EdgeSchemaChange {
+                before: edge("parent", EdgeCardinality::ToMany),
+                after: edge("parent", EdgeCardinality::ToOne),
+            }]
+        );
+        assert!(!node_diff.identity_algorithm_changed);
+
+        let kinds: Vec<_> = compatibility_violations(&diff)
+            .into_iter()
+            .map(|v| v.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SchemaCompatibilityViolationKind::PropertyTypeChanged,
+                SchemaCompatibilityViolationKind::EdgeCardinalityChanged,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reverse_edge_rename_is_its_own_change() {
+        let from: SchemaSnapshot = [(
+            "Process".to_owned(),
+            snapshot(vec![], vec![edge("binary_file", EdgeCardinality::ToOne)]),
+        )]
+        .into_iter()
+        .collect();
+        let mut renamed = edge("binary_file", EdgeCardinality::ToOne);
+        renamed.reverse_edge_name = EdgeName::try_from("executed_as").unwrap();
+        let to: SchemaSnapshot = [("Process".to_owned(), snapshot(vec![], vec![renamed]))]
+            .into_iter()
+            .collect();
+
+        let violations = compatibility_violations(&diff_schemas(&from, &to));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].kind,
+            SchemaCompatibilityViolationKind::ReverseEdgeRenamed
+        );
+    }
+
+    #[test]
+    fn test_additions_and_removals_are_compatible() {
+        let from: SchemaSnapshot = [
+            (
+                "File".to_owned(),
+                snapshot(
+                    vec![property("path", PropertyType::ImmutableString)],
+                    vec![],
+                ),
+            ),
+            ("Asset".to_owned(), snapshot(vec![], vec![])),
+        ]
+        .into_iter()
+        .collect();
+        let to: SchemaSnapshot = [
+            (
+                "File".to_owned(),
+                snapshot(
+                    vec![property("size", PropertyType::MaxU64)],
+                    vec![edge("spawned_from", EdgeCardinality::ToOne)],
+                ),
+            ),
+            ("Process".to_owned(), snapshot(vec![], vec![])),
+        ]
+        .into_iter()
+        .collect();
+
+        let diff = diff_schemas(&from, &to);
+
+        assert!(compatibility_violations(&diff).is_empty());
+    }
+
+    #[test]
+    fn test_removed_edge_is_incompatible() {
+        let from: SchemaSnapshot = [(
+            "Process".to_owned(),
+            snapshot(vec![], vec![edge("children", EdgeCardinality::ToMany)]),
+        )]
+        .into_iter()
+        .collect();
+        let to: SchemaSnapshot = [("Process".to_owned(), snapshot(vec![], vec![]))]
+            .into_iter()
+            .collect();
+
+        let violations = compatibility_violations(&diff_schemas(&from, &to));
+
+        let kinds: Vec<_> = violations.iter().map(|v| v.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SchemaCompatibilityViolationKind::EdgeRemoved,
+                SchemaCompatibilityViolationKind::ReverseEdgeRemoved,
+            ]
+        );
+        assert!(violations
+            .iter()
+            .all(|v| v.node_type == to_node_type("Process")));
+    }
+
+    #[test]
+    fn test_identity_algorithm_change_is_incompatible() {
+        let from: SchemaSnapshot = [("Process".to_owned(), snapshot(vec![], vec![]))]
+            .into_iter()
+            .collect();
+        let mut changed = snapshot(vec![], vec![]);
+        changed.identity_algorithm = IdentityAlgorithm::Static(StaticIdentityArguments {
+            static_key_properties: vec![PropertyName::try_from("pid").unwrap()],
+        });
+        let to: SchemaSnapshot = [("Process".to_owned(), changed)].into_iter().collect();
+
+        let violations = compatibility_violations(&diff_schemas(&from, &to));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].node_type, to_node_type("Process"));
+        assert_eq!(
+            violations[0].kind,
+            SchemaCompatibilityViolationKind::IdentityAlgorithmChanged
+        );
+    }
+}
@@ -1,26 +1,62 @@
+use std::time::SystemTime;
+
 use rust_proto::{
     graplinc::grapl::{
         api::graph_schema_manager::v1beta1::{
             messages::{
                 DeploySchemaRequest,
                 DeploySchemaResponse,
+                DiffSchemasRequest,
+                DiffSchemasResponse,
+                EdgeSchema,
                 GetEdgeSchemaRequest,
                 GetEdgeSchemaResponse,
+                GetNodeIdentityAlgorithmRequest,
+                GetNodeIdentityAlgorithmResponse,
+                GetNodeSchemaRequest,
+                GetNodeSchemaResponse,
+                GetRawSchemaRequest,
+                GetRawSchemaResponse,
+                IdentityAlgorithm,
+                ListNodeSchemasRequest,
+                ListNodeSchemasResponse,
+                NodeSchemaVersion,
+                PropertySchema,
+                SchemaCompatibilityViolation,
                 SchemaType,
+                SessionIdentityArguments,
+                StaticIdentityArguments,
             },
             server::GraphSchemaManagerApi,
         },
-        common::v1beta1::types::EdgeName,
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+        },
     },
     protocol::status::Status,
     SerDeError,
 };
+use sqlx::PgConnection;
 
 use crate::{
-    db::client::SchemaDbClient,
+    db::{
+        client::SchemaDbClient,
+        models::NodeIdentityAlgorithmRow,
+    },
     deploy_graphql_schema::{
         deploy_graphql_schema,
+        graphql_schema_snapshot,
+        parse_graphql_schema,
         DeployGraphqlError,
+        SCHEMA_TYPE,
+    },
+    schema_diff::{
+        compatibility_violations,
+        diff_schemas,
+        NodeSchemaSnapshot,
+        SchemaSnapshot,
     },
 };
 
@@ -34,6 +70,34 @@ pub enum GraphSchemaManagerServiceError {
     GetEdgeSchemaSqlxError(sqlx::Error),
     #[error("Invalid ReverseEdgeName: {0}")]
     InvalidReverseEdgeName(SerDeError),
+    #[error("GetNodeIdentityAlgorithm sqlx error {0}")]
+    GetNodeIdentityAlgorithmSqlxError(sqlx::Error),
+    #[error("No identity algorithm deployed for node type {0}")]
+    IdentityAlgorithmNotFound(NodeType),
+    #[error("Unknown identity algorithm: {0}")]
+    UnknownIdentityAlgorithm(String),
+    #[error("Invalid identity PropertyName: {0}")]
+    InvalidIdentityPropertyName(SerDeError),
+    #[error("GetNodeSchema sqlx error {0}")]
+    GetNodeSchemaSqlxError(sqlx::Error),
+    #[error("No schema deployed for node type {0}")]
+    NodeSchemaNotFound(NodeType),
+    #[error("Invalid stored schema name: {0}")]
+    InvalidStoredSchemaName(SerDeError),
+    #[error("ListNodeSchemas sqlx error {0}")]
+    ListNodeSchemasSqlxError(sqlx::Error),
+    #[error("GetRawSchema sqlx error {0}")]
+    GetRawSchemaSqlxError(sqlx::Error),
+    #[error("No schema version {1} deployed for node type {0}")]
+    RawSchemaNotFound(NodeType, u32),
+    #[error("Unknown stored schema type: {0}")]
+    UnknownSchemaType(String),
+    #[error("LoadSchema sqlx error {0}")]
+    LoadSchemaSqlxError(sqlx::Error),
+    #[error("No schema version {0} deployed")]
+    SchemaVersionNotFound(u32),
+    #[error("Schema is incompatible with the previously deployed version: {0:?}")]
+    IncompatibleSchema(Vec<SchemaCompatibilityViolation>),
 }
 
 impl From<GraphSchemaManagerServiceError> for Status {
@@ -48,12 +112,67 @@ impl From<GraphSchemaManagerServiceError> for Status {
             GraphSchemaManagerServiceError::DeployGraphqlError(e) => {
                 Status::invalid_argument(format!("DeployGraphqlError - {}", e))
             }
+            GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError(sqlx::Error::RowNotFound) => {
+                Status::not_found("EdgeSchemaNotFound")
+            }
             GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError(e) => {
                 Status::internal(format!("SqlError during deployment - {}", e))
             }
             GraphSchemaManagerServiceError::InvalidReverseEdgeName(name) => {
                 Status::internal(format!("InvalidReverseEdgeName - {}", name))
             }
+            GraphSchemaManagerServiceError::GetNodeIdentityAlgorithmSqlxError(e) => {
+                Status::internal(format!("SqlError during identity algorithm lookup - {}", e))
+            }
+            GraphSchemaManagerServiceError::IdentityAlgorithmNotFound(node_type) => {
+                Status::not_found(format!("IdentityAlgorithmNotFound - {}", node_type))
+            }
+            GraphSchemaManagerServiceError::UnknownIdentityAlgorithm(name) => {
+                Status::internal(format!("UnknownIdentityAlgorithm - {}", name))
+            }
+            GraphSchemaManagerServiceError::InvalidIdentityPropertyName(e) => {
+                Status::internal(format!("InvalidIdentityPropertyName - {}", e))
+            }
+            GraphSchemaManagerServiceError::GetNodeSchemaSqlxError(e) => {
+                Status::internal(format!("SqlError during node schema lookup - {}", e))
+            }
+            GraphSchemaManagerServiceError::NodeSchemaNotFound(node_type) => {
+                Status::not_found(format!("NodeSchemaNotFound - {}", node_type))
+            }
+            GraphSchemaManagerServiceError::InvalidStoredSchemaName(e) => {
+                Status::internal(format!("InvalidStoredSchemaName - {}", e))
+            }
+            GraphSchemaManagerServiceError::ListNodeSchemasSqlxError(e) => {
+                Status::internal(format!("SqlError during node schema listing - {}", e))
+            }
+            GraphSchemaManagerServiceError::GetRawSchemaSqlxError(e) => {
+                Status::internal(format!("SqlError during raw schema lookup - {}", e))
+            }
+            GraphSchemaManagerServiceError::RawSchemaNotFound(node_type, schema_version) => {
+                Status::not_found(format!(
+                    "RawSchemaNotFound - {} version {}",
+                    node_type, schema_version
+                ))
+            }
+            GraphSchemaManagerServiceError::UnknownSchemaType(name) => {
+                Status::internal(format!("UnknownSchemaType - {}", name))
+            }
+            GraphSchemaManagerServiceError::LoadSchemaSqlxError(e) => {
+                Status::internal(format!("SqlError while loading schema version - {}", e))
+            }
+            GraphSchemaManagerServiceError::SchemaVersionNotFound(schema_version) => {
+                Status::not_found(format!("SchemaVersionNotFound - {}", schema_version))
+            }
+            GraphSchemaManagerServiceError::IncompatibleSchema(violations) => {
+                let violations: Vec<_> = violations
+                    .iter()
+                    .map(|v| format!("{:?} in {}: {}", v.kind, v.node_type, v.description))
+                    .collect();
+                Status::failed_precondition(format!(
+                    "IncompatibleSchema - {}",
+                    violations.join("; ")
+                ))
+            }
         }
     }
 }
@@ -75,14 +194,52 @@ impl GraphSchemaManagerApi for GraphSchemaManager {
                 let schema = String::from_utf8(request.schema.to_vec())
                     .map_err(GraphSchemaManagerServiceError::NonUtf8GraphQLSchema)?;
 
+                let node_types = parse_graphql_schema(&schema)?;
+                let snapshot = graphql_schema_snapshot(&node_types)?;
+
+                let mut txn = self
+                    .db_client
+                    .begin_txn()
+                    .await
+                    .map_err(DeployGraphqlError::from)?;
+                self.db_client
+                    .lock_deployed_schemas(&mut txn, request.tenant_id)
+                    .await
+                    .map_err(DeployGraphqlError::from)?;
+
+                let violations = self
+                    .check_compatibility(
+                        &mut txn,
+                        request.tenant_id,
+                        request.schema_version,
+                        snapshot,
+                    )
+                    .await?;
+
+                if !violations.is_empty() && !request.force {
+                    tracing::warn!(
+                        message = "rejecting incompatible schema",
+                        tenant_id =% request.tenant_id,
+                        schema_version = request.schema_version,
+                        violations = violations.len(),
+                    );
+                    return Err(GraphSchemaManagerServiceError::IncompatibleSchema(
+                        violations,
+                    ));
+                }
+
                 deploy_graphql_schema(
+                    &mut txn,
                     request.tenant_id,
                     &schema,
+                    &node_types,
                     request.schema_version,
                     &self.db_client,
                 )
                 .await?;
-                Ok(DeploySchemaResponse {})
+                txn.commit().await.map_err(DeployGraphqlError::from)?;
+
+                Ok(DeploySchemaResponse { violations })
             }
         }
     }
@@ -110,4 +267,344 @@ impl GraphSchemaManagerApi for GraphSchemaManager {
             reverse_cardinality: response.reverse_edge_cardinality.into(),
         })
     }
+
+    async fn get_node_identity_algorithm(
+        &self,
+        request: GetNodeIdentityAlgorithmRequest,
+    ) -> Result<GetNodeIdentityAlgorithmResponse, Self::Error> {
+        let GetNodeIdentityAlgorithmRequest {
+            tenant_id,
+            node_type,
+        } = request;
+
+        let mut conn = self
+            .db_client
+            .acquire()
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeIdentityAlgorithmSqlxError)?;
+
+        let row = self
+            .db_client
+            .get_node_identity_algorithm(&mut conn, tenant_id, &node_type, None)
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeIdentityAlgorithmSqlxError)?
+            .ok_or_else(|| {
+                GraphSchemaManagerServiceError::IdentityAlgorithmNotFound(node_type.clone())
+            })?;
+
+        let identity_algorithm = self
+            .load_identity_algorithm(&mut conn, tenant_id, &node_type, &row)
+            .await?;
+
+        Ok(GetNodeIdentityAlgorithmResponse {
+            identity_algorithm,
+            schema_version: row.schema_version as u32,
+        })
+    }
+
+    async fn get_node_schema(
+        &self,
+        request: GetNodeSchemaRequest,
+    ) -> Result<GetNodeSchemaResponse, Self::Error> {
+        let GetNodeSchemaRequest {
+            tenant_id,
+            node_type,
+            schema_version,
+        } = request;
+
+        let schema_version = self
+            .db_client
+            .get_node_schema_version(tenant_id, &node_type, schema_version.map(|v| v as i16))
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeSchemaSqlxError)?
+            .ok_or_else(|| GraphSchemaManagerServiceError::NodeSchemaNotFound(node_type.clone()))?
+            .schema_version;
+
+        let mut conn = self
+            .db_client
+            .acquire()
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeSchemaSqlxError)?;
+        let (properties, edges) = self
+            .load_node_schema(&mut conn, tenant_id, &node_type, schema_version)
+            .await?;
+
+        Ok(GetNodeSchemaResponse {
+            properties,
+            edges,
+            schema_version: schema_version as u32,
+        })
+    }
+
+    async fn list_node_schemas(
+        &self,
+        request: ListNodeSchemasRequest,
+    ) -> Result<ListNodeSchemasResponse, Self::Error> {
+        let node_schemas = self
+            .db_client
+            .list_node_schemas(request.tenant_id)
+            .await
+            .map_err(GraphSchemaManagerServiceError::ListNodeSchemasSqlxError)?
+            .into_iter()
+            .map(|row| NodeSchemaVersion {
+                node_type: NodeType {
+                    value: row.node_type,
+                },
+                schema_version: row.schema_version as u32,
+                deployment_timestamp: SystemTime::from(row.deployment_timestamp.assume_utc()),
+            })
+            .collect();
+
+        Ok(ListNodeSchemasResponse { node_schemas })
+    }
+
+    async fn get_raw_schema(
+        &self,
+        request: GetRawSchemaRequest,
+    ) -> Result<GetRawSchemaResponse, Self::Error> {
+        let GetRawSchemaRequest {
+            tenant_id,
+            node_type,
+            schema_version,
+        } = request;
+
+        let row = self
+            .db_client
+            .get_raw_schema(tenant_id, &node_type, schema_version as i16)
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetRawSchemaSqlxError)?
+            .ok_or_else(|| {
+                GraphSchemaManagerServiceError::RawSchemaNotFound(node_type.clone(), schema_version)
+            })?;
+
+        let schema_type = match row.schema_type.as_str() {
+            SCHEMA_TYPE => SchemaType::GraphqlV0,
+            other => {
+                return Err(GraphSchemaManagerServiceError::UnknownSchemaType(
+                    other.to_owned(),
+                ))
+            }
+        };
+
+        Ok(GetRawSchemaResponse {
+            schema: row.raw_schema.into(),
+            schema_type,
+        })
+    }
+
+    async fn diff_schemas(
+        &self,
+        request: DiffSchemasRequest,
+    ) -> Result<DiffSchemasResponse, Self::Error> {
+        let DiffSchemasRequest {
+            tenant_id,
+            from_schema_version,
+            to_schema_version,
+        } = request;
+
+        let mut conn = self
+            .db_client
+            .acquire()
+            .await
+            .map_err(GraphSchemaManagerServiceError::LoadSchemaSqlxError)?;
+        let from = self
+            .load_schema_snapshot(&mut conn, tenant_id, from_schema_version)
+            .await?;
+        let to = self
+            .load_schema_snapshot(&mut conn, tenant_id, to_schema_version)
+            .await?;
+
+        Ok(diff_schemas(&from, &to))
+    }
+}
+
+impl GraphSchemaManager {
+    /// Returns the incompatible changes `schema` makes to the schema version
+    /// deployed before `schema_version`, if any, as seen by `conn`.
+    async fn check_compatibility(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        schema_version: u32,
+        schema: SchemaSnapshot,
+    ) -> Result<Vec<SchemaCompatibilityViolation>, GraphSchemaManagerServiceError> {
+        let previous_version = self
+            .db_client
+            .get_previous_schema_version(&mut *conn, tenant_id, schema_version as i16)
+            .await
+            .map_err(GraphSchemaManagerServiceError::LoadSchemaSqlxError)?;
+
+        match previous_version {
+            Some(previous_version) => {
+                let previous_schema = self
+                    .load_schema_snapshot(conn, tenant_id, previous_version.schema_version as u32)
+                    .await?;
+                let diff = diff_schemas(&previous_schema, &schema);
+                Ok(compatibility_violations(&diff))
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn load_identity_algorithm(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        row: &NodeIdentityAlgorithmRow,
+    ) -> Result<IdentityAlgorithm, GraphSchemaManagerServiceError> {
+        let identity_algorithm = match row.identity_algorithm.as_str() {
+            "session" => {
+                let args = self
+                    .db_client
+                    .get_session_identity_args(&mut *conn, tenant_id, node_type, row.schema_version)
+                    .await
+                    .map_err(GraphSchemaManagerServiceError::GetNodeIdentityAlgorithmSqlxError)?;
+
+                IdentityAlgorithm::Session(SessionIdentityArguments {
+                    pseudo_key_properties: to_property_names(args.pseudo_key_properties)?,
+                    creation_timestamp_property: to_property_name(
+                        args.creation_timestamp_property,
+                    )?,
+                    last_seen_timestamp_property: to_property_name(
+                        args.last_seen_timestamp_property,
+                    )?,
+                    termination_timestamp_property: to_property_name(
+                        args.termination_timestamp_property,
+                    )?,
+                })
+            }
+            "static" => {
+                let args = self
+                    .db_client
+                    .get_static_identity_args(&mut *conn, tenant_id, node_type, row.schema_version)
+                    .await
+                    .map_err(GraphSchemaManagerServiceError::GetNodeIdentityAlgorithmSqlxError)?;
+
+                IdentityAlgorithm::Static(StaticIdentityArguments {
+                    static_key_properties: to_property_names(args.static_key_properties)?,
+                })
+            }
+            other => {
+                return Err(GraphSchemaManagerServiceError::UnknownIdentityAlgorithm(
+                    other.to_owned(),
+                ))
+            }
+        };
+
+        Ok(identity_algorithm)
+    }
+
+    async fn load_node_schema(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<(Vec<PropertySchema>, Vec<EdgeSchema>), GraphSchemaManagerServiceError> {
+        let properties = self
+            .db_client
+            .get_node_property_schemas(&mut *conn, tenant_id, node_type, schema_version)
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeSchemaSqlxError)?
+            .into_iter()
+            .map(|row| {
+                Ok(PropertySchema {
+                    property_name: PropertyName::try_from(row.property_name)
+                        .map_err(GraphSchemaManagerServiceError::InvalidStoredSchemaName)?,
+                    property_type: row.property_type.into(),
+                    identity_only: row.identity_only,
+                })
+            })
+            .collect::<Result<Vec<_>, GraphSchemaManagerServiceError>>()?;
+
+        let edges = self
+            .db_client
+            .get_node_edge_schemas(&mut *conn, tenant_id, node_type, schema_version)
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetNodeSchemaSqlxError)?
+            .into_iter()
+            .map(|row| {
+                Ok(EdgeSchema {
+                    edge_name: EdgeName::try_from(row.forward_edge_name)
+                        .map_err(GraphSchemaManagerServiceError::InvalidStoredSchemaName)?,
+                    reverse_edge_name: EdgeName::try_from(row.reverse_edge_name)
+                        .map_err(GraphSchemaManagerServiceError::InvalidStoredSchemaName)?,
+                    cardinality: row.forward_edge_cardinality.into(),
+                    reverse_cardinality: row.reverse_edge_cardinality.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, GraphSchemaManagerServiceError>>()?;
+
+        Ok((properties, edges))
+    }
+
+    /// Loads every node type defined by `schema_version`.
+    async fn load_schema_snapshot(
+        &self,
+        conn: &mut PgConnection,
+        tenant_id: uuid::Uuid,
+        schema_version: u32,
+    ) -> Result<SchemaSnapshot, GraphSchemaManagerServiceError> {
+        let node_types = self
+            .db_client
+            .get_node_types(&mut *conn, tenant_id, schema_version as i16)
+            .await
+            .map_err(GraphSchemaManagerServiceError::LoadSchemaSqlxError)?;
+
+        if node_types.is_empty() {
+            return Err(GraphSchemaManagerServiceError::SchemaVersionNotFound(
+                schema_version,
+            ));
+        }
+
+        let mut snapshot = SchemaSnapshot::new();
+        for row in node_types {
+            let node_type = NodeType {
+                value: row.node_type,
+            };
+
+            let identity_row = self
+                .db_client
+                .get_node_identity_algorithm(
+                    &mut *conn,
+                    tenant_id,
+                    &node_type,
+                    Some(schema_version as i16),
+                )
+                .await
+                .map_err(GraphSchemaManagerServiceError::LoadSchemaSqlxError)?
+                .ok_or_else(|| {
+                    GraphSchemaManagerServiceError::IdentityAlgorithmNotFound(node_type.clone())
+                })?;
+            let identity_algorithm = self
+                .load_identity_algorithm(&mut *conn, tenant_id, &node_type, &identity_row)
+                .await?;
+            let (properties, edges) = self
+                .load_node_schema(&mut *conn, tenant_id, &node_type, schema_version as i16)
+                .await?;
+
+            snapshot.insert(
+                node_type.value,
+                NodeSchemaSnapshot {
+                    identity_algorithm,
+                    properties,
+                    edges,
+                },
+            );
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn to_property_name(name: String) -> Result<PropertyName, GraphSchemaManagerServiceError> {
+    PropertyName::try_from(name)
+        .map_err(GraphSchemaManagerServiceError::InvalidIdentityPropertyName)
+}
+
+fn to_property_names(
+    names: Vec<String>,
+) -> Result<Vec<PropertyName>, GraphSchemaManagerServiceError> {
+    names.into_iter().map(to_property_name).collect()
 }
@@ -0,0 +1,543 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    sync::Arc,
+    time::Duration,
+};
+
+use moka::future::{
+    Cache,
+    CacheBuilder,
+};
+use rust_proto::{
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                EdgeList,
+                GraphDescription,
+                NodeDescription,
+                Property,
+            },
+            graph_schema_manager::v1beta1::{
+                client::{
+                    GraphSchemaManagerClient,
+                    GraphSchemaManagerClientError,
+                },
+                messages::{
+                    EdgeCardinality,
+                    GetNodeSchemaRequest,
+                    GetNodeSchemaResponse,
+                    NodeValidationReport as PublishedNodeValidationReport,
+                    PropertyType,
+                    SchemaViolation as PublishedSchemaViolation,
+                    SchemaViolationKind,
+                },
+            },
+        },
+        common::v1beta1::types::NodeType,
+    },
+    protocol::status::Code,
+};
+
+/// The properties and edges a node type declares, indexed by name.
+/// `reverse_edges` holds the reverse of each of the type's edges, which
+/// nodes at the other end of the edge may use to point back at this type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSchema {
+    properties: HashMap<String, PropertyType>,
+    edges: HashMap<String, EdgeCardinality>,
+    reverse_edges: HashMap<String, EdgeCardinality>,
+}
+
+impl From<GetNodeSchemaResponse> for NodeSchema {
+    fn from(response: GetNodeSchemaResponse) -> Self {
+        Self {
+            properties: response
+                .properties
+                .into_iter()
+                .map(|p| (p.property_name.value, p.property_type))
+                .collect(),
+            edges: response
+                .edges
+                .iter()
+                .map(|e| (e.edge_name.value.clone(), e.cardinality))
+                .collect(),
+            reverse_edges: response
+                .edges
+                .into_iter()
+                .map(|e| (e.reverse_edge_name.value, e.reverse_cardinality))
+                .collect(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SchemaViolation {
+    #[error("node type is not defined by the deployed schema")]
+    UnknownNodeType,
+    #[error("property {property_name} is not defined for this node type")]
+    UnknownProperty { property_name: String },
+    #[error("property {property_name} is declared as {expected:?} but was written as {actual}")]
+    PropertyTypeMismatch {
+        property_name: String,
+        expected: PropertyType,
+        actual: &'static str,
+    },
+    #[error("edge {edge_name} is not defined for this node type")]
+    UndeclaredEdge { edge_name: String },
+    #[error("edge {edge_name} is ToOne but points to {count} nodes")]
+    EdgeCardinalityViolation { edge_name: String, count: usize },
+}
+
+impl From<SchemaViolation> for PublishedSchemaViolation {
+    fn from(violation: SchemaViolation) -> Self {
+        let kind = match violation {
+            SchemaViolation::UnknownNodeType => SchemaViolationKind::UnknownNodeType,
+            SchemaViolation::UnknownProperty { .. } => SchemaViolationKind::UnknownProperty,
+            SchemaViolation::PropertyTypeMismatch { .. } => {
+                SchemaViolationKind::PropertyTypeMismatch
+            }
+            SchemaViolation::UndeclaredEdge { .. } => SchemaViolationKind::UndeclaredEdge,
+            SchemaViolation::EdgeCardinalityViolation { .. } => {
+                SchemaViolationKind::EdgeCardinalityViolation
+            }
+        };
+        Self {
+            kind,
+            description: violation.to_string(),
+        }
+    }
+}
+
+/// Every schema violation found on a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeValidationReport {
+    pub node_key: String,
+    pub node_type: String,
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl From<NodeValidationReport> for PublishedNodeValidationReport {
+    fn from(report: NodeValidationReport) -> Self {
+        Self {
+            node_key: report.node_key,
+            node_type: NodeType {
+                value: report.node_type,
+            },
+            violations: report.violations.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Checks `node`, and the `edges` leaving it, against the schema of its type.
+///
+/// Reverse edges are declared by the type at the other end of the edge, so an
+/// edge that `schema` doesn't declare is looked up among the reverse edges of
+/// the schema `destination_schema` returns for its destination node key.
+pub fn validate_node<'a>(
+    node: &NodeDescription,
+    edges: Option<&EdgeList>,
+    schema: &NodeSchema,
+    destination_schema: impl Fn(&str) -> Option<&'a NodeSchema>,
+) -> Vec<SchemaViolation> {
+    let mut violations = vec![];
+
+    for (property_name, property) in node.properties.iter() {
+        match schema.properties.get(property_name) {
+            Some(expected) if property_matches(*expected, &property.property) => {}
+            Some(expected) => violations.push(SchemaViolation::PropertyTypeMismatch {
+                property_name: property_name.clone(),
+                expected: *expected,
+                actual: property_kind(&property.property),
+            }),
+            None => violations.push(SchemaViolation::UnknownProperty {
+                property_name: property_name.clone(),
+            }),
+        }
+    }
+
+    let mut edge_destinations: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges.iter().flat_map(|e| e.edges.iter()) {
+        edge_destinations
+            .entry(edge.edge_name.as_str())
+            .or_default()
+            .push(edge.to_node_key.as_str());
+    }
+
+    for (edge_name, to_node_keys) in edge_destinations {
+        let count = to_node_keys.len();
+        let cardinality = schema.edges.get(edge_name).or_else(|| {
+            to_node_keys.iter().find_map(|to_node_key| {
+                destination_schema(to_node_key)
+                    .and_then(|destination| destination.reverse_edges.get(edge_name))
+            })
+        });
+
+        match cardinality {
+            Some(EdgeCardinality::ToOne) if count > 1 => {
+                violations.push(SchemaViolation::EdgeCardinalityViolation {
+                    edge_name: edge_name.to_owned(),
+                    count,
+                })
+            }
+            Some(_) => {}
+            None => violations.push(SchemaViolation::UndeclaredEdge {
+                edge_name: edge_name.to_owned(),
+            }),
+        }
+    }
+
+    violations
+}
+
+fn property_matches(expected: PropertyType, property: &Property) -> bool {
+    matches!(
+        (expected, property),
+        (PropertyType::ImmutableString, Property::ImmutableStrProp(_))
+            | (PropertyType::ImmutableI64, Property::ImmutableIntProp(_))
+            | (PropertyType::MaxI64, Property::IncrementOnlyIntProp(_))
+            | (PropertyType::MinI64, Property::DecrementOnlyIntProp(_))
+            | (PropertyType::ImmutableU64, Property::ImmutableUintProp(_))
+            | (PropertyType::MaxU64, Property::IncrementOnlyUintProp(_))
+            | (PropertyType::MinU64, Property::DecrementOnlyUintProp(_))
+    )
+}
+
+fn property_kind(property: &Property) -> &'static str {
+    match property {
+        Property::IncrementOnlyUintProp(_) => "IncrementOnlyUintProp",
+        Property::DecrementOnlyUintProp(_) => "DecrementOnlyUintProp",
+        Property::ImmutableUintProp(_) => "ImmutableUintProp",
+        Property::IncrementOnlyIntProp(_) => "IncrementOnlyIntProp",
+        Property::DecrementOnlyIntProp(_) => "DecrementOnlyIntProp",
+        Property::ImmutableIntProp(_) => "ImmutableIntProp",
+        Property::ImmutableStrProp(_) => "ImmutableStrProp",
+    }
+}
+
+/// Removes the nodes in `node_keys` from `graph`, along with any edge to or
+/// from them.
+pub fn remove_nodes(graph: &mut GraphDescription, node_keys: &HashSet<String>) {
+    graph
+        .nodes
+        .retain(|node_key, _| !node_keys.contains(node_key));
+    graph
+        .edges
+        .retain(|from_node_key, _| !node_keys.contains(from_node_key));
+    for edge_list in graph.edges.values_mut() {
+        edge_list
+            .edges
+            .retain(|edge| !node_keys.contains(&edge.to_node_key));
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GraphValidatorError {
+    #[error("couldn't fetch node schema from Graph Schema Manager: {0}")]
+    GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
+}
+
+/// Validates generated graphs against the schemas a tenant has deployed.
+/// Node schemas are cached for `time_to_live`, so newly deployed schema
+/// versions are picked up once the cached entry expires.
+#[derive(Clone)]
+pub struct GraphValidator {
+    schema_client: GraphSchemaManagerClient,
+    // `None` records that no schema defines the node type
+    schema_cache: Cache<(uuid::Uuid, NodeType), Option<Arc<NodeSchema>>>,
+}
+
+impl GraphValidator {
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        cache_size: u64,
+        time_to_live: Duration,
+    ) -> Self {
+        let schema_cache = CacheBuilder::new(cache_size)
+            .time_to_live(time_to_live)
+            .build();
+        Self {
+            schema_client,
+            schema_cache,
+        }
+    }
+
+    /// Validates every node in `graph`, returning a report for each node that
+    /// violates its schema. Nodes without violations are not reported.
+    pub async fn validate_graph(
+        &self,
+        tenant_id: uuid::Uuid,
+        graph: &GraphDescription,
+    ) -> Result<Vec<NodeValidationReport>, GraphValidatorError> {
+        let mut schemas: HashMap<&str, Option<Arc<NodeSchema>>> = HashMap::new();
+        for node in graph.nodes.values() {
+            if !schemas.contains_key(node.node_type.as_str()) {
+                let schema = self.node_schema(tenant_id, &node.node_type).await?;
+                schemas.insert(node.node_type.as_str(), schema);
+            }
+        }
+
+        let destination_schema = |node_key: &str| {
+            graph
+                .nodes
+                .get(node_key)
+                .and_then(|node| schemas.get(node.node_type.as_str()))
+                .and_then(|schema| schema.as_deref())
+        };
+
+        let mut reports = vec![];
+
+        for (node_key, node) in graph.nodes.iter() {
+            let violations = match schemas
+                .get(node.node_type.as_str())
+                .and_then(|s| s.as_deref())
+            {
+                Some(schema) => {
+                    validate_node(node, graph.edges.get(node_key), schema, destination_schema)
+                }
+                None => vec![SchemaViolation::UnknownNodeType],
+            };
+
+            if !violations.is_empty() {
+                reports.push(NodeValidationReport {
+                    node_key: node_key.clone(),
+                    node_type: node.node_type.clone(),
+                    violations,
+                });
+            }
+        }
+
+        Ok(reports)
+    }
+
+    async fn node_schema(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &str,
+    ) -> Result<Option<Arc<NodeSchema>>, GraphValidatorError> {
+        let node_type = NodeType {
+            value: node_type.to_owned(),
+        };
+        let key = (tenant_id, node_type.clone());
+
+        if let Some(schema) = self.schema_cache.get(&key) {
+            return Ok(schema);
+        }
+
+        let mut schema_client = self.schema_client.clone();
+        let schema = match schema_client
+            .get_node_schema(GetNodeSchemaRequest {
+                tenant_id,
+                node_type,
+                schema_version: None,
+            })
+            .await
+        {
+            Ok(response) => Some(Arc::new(NodeSchema::from(response))),
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                None
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.schema_cache.insert(key, schema.clone()).await;
+        Ok(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                ImmutableStrProp,
+                ImmutableUintProp,
+            },
+            graph_schema_manager::v1beta1::messages::{
+                EdgeSchema,
+                PropertySchema,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            PropertyName,
+        },
+    };
+
+    use super::*;
+
+    fn process_schema() -> NodeSchema {
+        NodeSchema::from(GetNodeSchemaResponse {
+            properties: vec![
+                PropertySchema {
+                    property_name: PropertyName {
+                        value: "process_name".to_owned(),
+                    },
+                    property_type: PropertyType::ImmutableString,
+                    identity_only: false,
+                },
+                PropertySchema {
+                    property_name: PropertyName {
+                        value: "process_id".to_owned(),
+                    },
+                    property_type: PropertyType::ImmutableU64,
+                    identity_only: false,
+                },
+            ],
+            edges: vec![EdgeSchema {
+                edge_name: EdgeName {
+                    value: "binary_file".to_owned(),
+                },
+                reverse_edge_name: EdgeName {
+                    value: "executed_as_processes".to_owned(),
+                },
+                cardinality: EdgeCardinality::ToOne,
+                reverse_cardinality: EdgeCardinality::ToMany,
+            }],
+            schema_version: 0,
+        })
+    }
+
+    fn process_node() -> NodeDescription {
+        let mut node = NodeDescription {
+            properties: HashMap::new(),
+            node_key: "process".to_owned(),
+            node_type: "Process".to_owned(),
+            id_strategy: vec![],
+        };
+        node.set_property(
+            "process_name",
+            ImmutableStrProp {
+                prop: "cmd.exe".to_owned(),
+            },
+        );
+        node.set_property("process_id", ImmutableUintProp { prop: 4 });
+        node
+    }
+
+    fn edges(edge_name: &str, count: usize) -> EdgeList {
+        let mut graph = GraphDescription::new();
+        for i in 0..count {
+            graph.add_edge(edge_name, "process", format!("file-{}", i));
+        }
+        graph.edges.remove("process").unwrap()
+    }
+
+    #[test]
+    fn test_valid_node() {
+        let edges = edges("binary_file", 1);
+        let violations = validate_node(&process_node(), Some(&edges), &process_schema(), |_| None);
+        assert_eq!(violations, vec![]);
+    }
+
+    #[test]
+    fn test_property_type_mismatch() {
+        let mut node = process_node();
+        node.set_property(
+            "process_id",
+            ImmutableStrProp {
+                prop: "4".to_owned(),
+            },
+        );
+
+        let violations = validate_node(&node, None, &process_schema(), |_| None);
+        assert_eq!(
+            violations,
+            vec![SchemaViolation::PropertyTypeMismatch {
+                property_name: "process_id".to_owned(),
+                expected: PropertyType::ImmutableU64,
+                actual: "ImmutableStrProp",
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unknown_property() {
+        let mut node = process_node();
+        node.set_property("parent_pid", ImmutableUintProp { prop: 1 });
+
+        let violations = validate_node(&node, None, &process_schema(), |_| None);
+        assert_eq!(
+            violations,
+            vec![SchemaViolation::UnknownProperty {
+                property_name: "parent_pid".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_undeclared_edge() {
+        let edges = edges("children", 1);
+        let violations = validate_node(&process_node(), Some(&edges), &process_schema(), |_| None);
+        assert_eq!(
+            violations,
+            vec![SchemaViolation::UndeclaredEdge {
+                edge_name: "children".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_to_one_edge_cardinality() {
+        let edges = edges("binary_file", 2);
+        let violations = validate_node(&process_node(), Some(&edges), &process_schema(), |_| None);
+        assert_eq!(
+            violations,
+            vec![SchemaViolation::EdgeCardinalityViolation {
+                edge_name: "binary_file".to_owned(),
+                count: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reverse_edge_declared_by_destination() {
+        let file_schema = NodeSchema::from(GetNodeSchemaResponse {
+            properties: vec![],
+            edges: vec![],
+            schema_version: 0,
+        });
+        let file_node = NodeDescription {
+            properties: HashMap::new(),
+            node_key: "file".to_owned(),
+            node_type: "File".to_owned(),
+            id_strategy: vec![],
+        };
+        let mut graph = GraphDescription::new();
+        graph.add_edge("executed_as_processes", "file", "process-0");
+        graph.add_edge("executed_as_processes", "file", "process-1");
+        let edges = graph.edges.remove("file").unwrap();
+
+        let process_schema = process_schema();
+        let violations = validate_node(&file_node, Some(&edges), &file_schema, |to_node_key| {
+            to_node_key.starts_with("process").then(|| &process_schema)
+        });
+        assert_eq!(violations, vec![]);
+
+        let violations = validate_node(&file_node, Some(&edges), &file_schema, |_| None);
+        assert_eq!(
+            violations,
+            vec![SchemaViolation::UndeclaredEdge {
+                edge_name: "executed_as_processes".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_remove_nodes_drops_edges() {
+        let mut graph = GraphDescription::new();
+        graph.add_node(process_node());
+        graph.add_edge("binary_file", "process", "file");
+        graph.add_edge("executed_as_processes", "file", "process");
+
+        remove_nodes(&mut graph, &HashSet::from(["process".to_owned()]));
+
+        assert!(graph.nodes.is_empty());
+        assert!(!graph.edges.contains_key("process"));
+        assert!(graph.edges.values().all(|e| e.edges.is_empty()));
+    }
+}
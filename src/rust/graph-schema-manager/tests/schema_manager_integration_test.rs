@@ -11,6 +11,10 @@ use rust_proto::{
         api::graph_schema_manager::v1beta1::messages as sm_api,
         common::v1beta1::types as common_api,
     },
+    protocol::{
+        error::GrpcClientError,
+        status::Code,
+    },
 };
 
 pub fn get_example_graphql_schema() -> Result<Bytes, std::io::Error> {
@@ -32,6 +36,7 @@ async fn test_deploy_schema() -> eyre::Result<()> {
             schema: get_example_graphql_schema()?,
             schema_type: sm_api::SchemaType::GraphqlV0,
             schema_version: 0,
+            force: false,
         })
         .await?;
 
@@ -50,3 +55,303 @@ async fn test_deploy_schema() -> eyre::Result<()> {
     assert_eq!(edge_schema.reverse_edge_name.value, "executed_as_processes");
     Ok(())
 }
+
+#[tokio::test]
+async fn test_get_node_identity_algorithm() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+            force: false,
+        })
+        .await?;
+
+    let response = client
+        .get_node_identity_algorithm(sm_api::GetNodeIdentityAlgorithmRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+        })
+        .await?;
+
+    assert_eq!(response.schema_version, 0);
+    match response.identity_algorithm {
+        sm_api::IdentityAlgorithm::Session(args) => {
+            assert_eq!(
+                args.pseudo_key_properties,
+                vec![common_api::PropertyName {
+                    value: "process_id".to_string(),
+                }]
+            );
+            assert_eq!(args.creation_timestamp_property.value, "created_at");
+            assert_eq!(args.last_seen_timestamp_property.value, "last_seen_at");
+            assert_eq!(args.termination_timestamp_property.value, "terminated_at");
+        }
+        other => panic!("expected a session identity algorithm, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_node_identity_algorithm_unknown_node_type() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let result = client
+        .get_node_identity_algorithm(sm_api::GetNodeIdentityAlgorithmRequest {
+            tenant_id: uuid::Uuid::new_v4(),
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+        })
+        .await;
+
+    match result {
+        Err(GrpcClientError::ErrorStatus(s)) => assert_eq!(s.code(), Code::NotFound),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_node_schema() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+            force: false,
+        })
+        .await?;
+
+    let node_schema = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            schema_version: None,
+        })
+        .await?;
+
+    assert_eq!(node_schema.schema_version, 0);
+
+    let process_id = node_schema
+        .properties
+        .iter()
+        .find(|p| p.property_name.value == "process_id")
+        .expect("process_id property");
+    assert_eq!(process_id.property_type, sm_api::PropertyType::ImmutableU64);
+
+    let binary_file = node_schema
+        .edges
+        .iter()
+        .find(|e| e.edge_name.value == "binary_file")
+        .expect("binary_file edge");
+    assert_eq!(binary_file.reverse_edge_name.value, "executed_as_processes");
+    assert_eq!(binary_file.cardinality, sm_api::EdgeCardinality::ToOne);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_node_schemas_and_get_raw_schema() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let schema = get_example_graphql_schema()?;
+
+    for schema_version in [0, 1] {
+        client
+            .deploy_schema(sm_api::DeploySchemaRequest {
+                tenant_id,
+                schema: schema.clone(),
+                schema_type: sm_api::SchemaType::GraphqlV0,
+                schema_version,
+                force: false,
+            })
+            .await?;
+    }
+
+    let response = client
+        .list_node_schemas(sm_api::ListNodeSchemasRequest { tenant_id })
+        .await?;
+
+    let process_versions: Vec<u32> = response
+        .node_schemas
+        .iter()
+        .filter(|s| s.node_type.value == "Process")
+        .map(|s| s.schema_version)
+        .collect();
+    assert_eq!(process_versions, vec![0, 1]);
+
+    let raw_schema = client
+        .get_raw_schema(sm_api::GetRawSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            schema_version: 1,
+        })
+        .await?;
+
+    assert_eq!(raw_schema.schema, schema);
+    assert_eq!(raw_schema.schema_type, sm_api::SchemaType::GraphqlV0);
+
+    let old_node_schema = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            schema_version: Some(0),
+        })
+        .await?;
+
+    assert_eq!(old_node_schema.schema_version, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_raw_schema_unknown_version() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let result = client
+        .get_raw_schema(sm_api::GetRawSchemaRequest {
+            tenant_id: uuid::Uuid::new_v4(),
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            schema_version: 0,
+        })
+        .await;
+
+    match result {
+        Err(GrpcClientError::ErrorStatus(status)) => assert_eq!(status.code(), Code::NotFound),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_diff_identical_schemas() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    for schema_version in [0, 1] {
+        client
+            .deploy_schema(sm_api::DeploySchemaRequest {
+                tenant_id,
+                schema: get_example_graphql_schema()?,
+                schema_type: sm_api::SchemaType::GraphqlV0,
+                schema_version,
+                force: false,
+            })
+            .await?;
+    }
+
+    let diff = client
+        .diff_schemas(sm_api::DiffSchemasRequest {
+            tenant_id,
+            from_schema_version: 0,
+            to_schema_version: 1,
+        })
+        .await?;
+
+    assert!(diff.added_node_types.is_empty());
+    assert!(diff.removed_node_types.is_empty());
+    assert!(diff.changed_node_types.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deploy_incompatible_schema() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = build_grpc_client(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let schema = get_example_graphql_schema()?;
+    let incompatible_schema = Bytes::from(String::from_utf8(schema.to_vec())?.replace(
+        "process_id: UInt! @pseudo_key",
+        "process_id: Int! @pseudo_key",
+    ));
+
+    let response = client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+            force: false,
+        })
+        .await?;
+    assert!(response.violations.is_empty());
+
+    let rejected = client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: incompatible_schema.clone(),
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 1,
+            force: false,
+        })
+        .await;
+    match rejected {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::FailedPrecondition);
+            assert!(status.message().contains("PropertyTypeChanged in Process"));
+        }
+        other => panic!("expected FailedPrecondition, got {:?}", other),
+    }
+
+    let forced = client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: incompatible_schema,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 1,
+            force: true,
+        })
+        .await?;
+    assert_eq!(forced.violations.len(), 1);
+    assert_eq!(
+        forced.violations[0].kind,
+        sm_api::SchemaCompatibilityViolationKind::PropertyTypeChanged
+    );
+    assert_eq!(forced.violations[0].node_type.value, "Process");
+
+    let node_schema = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            schema_version: None,
+        })
+        .await?;
+    assert_eq!(node_schema.schema_version, 1);
+
+    Ok(())
+}
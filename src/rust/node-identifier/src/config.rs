@@ -0,0 +1,36 @@
+use std::net::SocketAddr;
+
+use scylla::transport::errors::NewSessionError;
+use secrecy::ExposeSecret;
+
+#[derive(clap::Parser, Clone, Debug)]
+pub struct GraphDbConfig {
+    #[clap(long, env, value_delimiter = ',')]
+    /// The address of the graph database
+    pub graph_db_addresses: Vec<SocketAddr>,
+    #[clap(long, env)]
+    /// The username for the graph database
+    pub graph_db_auth_username: String,
+    #[clap(long, env)]
+    /// The password for the graph database
+    pub graph_db_auth_password: secrecy::SecretString,
+}
+
+impl GraphDbConfig {
+    pub async fn connect(&self) -> Result<scylla::Session, NewSessionError> {
+        let mut scylla_config = scylla::SessionConfig::new();
+        scylla_config.add_known_nodes_addr(&self.graph_db_addresses[..]);
+        scylla_config.auth_username = Some(self.graph_db_auth_username.to_owned());
+        scylla_config.auth_password = Some(self.graph_db_auth_password.expose_secret().to_owned());
+
+        scylla::Session::connect(scylla_config).await
+    }
+}
+
+#[derive(clap::Parser, Clone, Debug)]
+pub struct SchemaValidationConfig {
+    #[clap(long, env, parse(try_from_str))]
+    /// Whether to reject nodes whose type isn't defined by a deployed schema.
+    /// When false, those nodes skip validation.
+    pub reject_unknown_node_types: bool,
+}
@@ -7,7 +7,6 @@ use failure::{
     bail,
     Error,
 };
-use rusoto_dynamodb::DynamoDb;
 use rust_proto::graplinc::grapl::api::graph::v1beta1::{
     GraphDescription,
     NodeDescription,
@@ -25,6 +24,7 @@ use sha2::{
 };
 
 use crate::{
+    identity_resolver::IdentityResolver,
     sessiondb::SessionDb,
     sessions::UnidSession,
 };
@@ -40,22 +40,22 @@ pub(crate) struct DirectMapping {
     pub mapping: String,
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct NodeDescriptionIdentifier<D>
-where
-    D: DynamoDb,
-{
-    dyn_session_db: SessionDb<D>,
+#[derive(Clone)]
+pub(crate) struct NodeDescriptionIdentifier {
+    dyn_session_db: SessionDb,
+    identity_resolver: IdentityResolver,
     should_guess: bool,
 }
 
-impl<D> NodeDescriptionIdentifier<D>
-where
-    D: DynamoDb,
-{
-    pub fn new(dyn_session_db: SessionDb<D>, should_guess: bool) -> Self {
+impl NodeDescriptionIdentifier {
+    pub fn new(
+        dyn_session_db: SessionDb,
+        identity_resolver: IdentityResolver,
+        should_guess: bool,
+    ) -> Self {
         Self {
             dyn_session_db,
+            identity_resolver,
             should_guess,
         }
     }
@@ -91,7 +91,7 @@ where
     }
 
     /// Because statically identified nodes are uniquely identifiable based on their static properties
-    /// we can avoid fetching from dynamodb and calculate a node key by hashing the properties deterministically
+    /// we can avoid fetching from the session db and calculate a node key by hashing the properties deterministically
     #[tracing::instrument(skip(self, node, strategy), err)]
     fn get_static_node_key(
         &self,
@@ -156,7 +156,7 @@ where
 
         let session_id = self
             .dyn_session_db
-            .handle_unid_session(unid, self.should_guess)
+            .handle_unid_session(tenant_id, unid, self.should_guess)
             .await?;
 
         attributed_node.node_key = session_id;
@@ -184,9 +184,12 @@ where
         node: &NodeDescription,
     ) -> Result<NodeDescription, Error> {
         let mut attributed_node = node.clone();
-        let strategy = &node.id_strategy[0];
+        let strategy = self
+            .identity_resolver
+            .resolve_strategy(tenant_id, node)
+            .await?;
 
-        match strategy.strategy {
+        match strategy {
             Strategy::Session(ref strategy) => {
                 tracing::info!("Attributing dynamic node via session");
                 attributed_node = self
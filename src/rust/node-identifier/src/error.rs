@@ -21,6 +21,18 @@ pub(crate) enum NodeIdentifierError {
 
     #[error("failed to configure tracing {0}")]
     SetupTracingError(#[from] SetupTracingError),
+
+    #[error("failed to connect to graph db {0}")]
+    GraphDbConnectError(#[from] scylla::transport::errors::NewSessionError),
+
+    #[error("failed to publish validation report {0}")]
+    ValidationReportProducerError(#[from] kafka::ProducerError),
+
+    #[error("failed to validate subgraph {0}")]
+    GraphValidatorError(#[from] graph_schema_manager::validation::GraphValidatorError),
+
+    #[error("failed to connect to graph schema manager {0}")]
+    GraphSchemaManagerConnectError(#[from] rust_proto::protocol::service_client::ConnectError),
 }
 
 impl From<NodeIdentifierError> for kafka::StreamProcessorError {
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use moka::future::{
+    Cache,
+    CacheBuilder,
+};
+use rust_proto::{
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                IdStrategy,
+                NodeDescription,
+                Property,
+                Session,
+                Static,
+                Strategy,
+            },
+            graph_schema_manager::v1beta1::{
+                client::{
+                    GraphSchemaManagerClient,
+                    GraphSchemaManagerClientError,
+                },
+                messages::{
+                    GetNodeIdentityAlgorithmRequest,
+                    IdentityAlgorithm,
+                    SessionIdentityArguments,
+                },
+            },
+        },
+        common::v1beta1::types::NodeType,
+    },
+    protocol::status::Code,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum IdentityResolverError {
+    #[error("couldn't resolve identity algorithm from Graph Schema Manager: {0}")]
+    GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
+    #[error("no identity algorithm deployed for node type {0}, and node has no id_strategy")]
+    MissingIdentityAlgorithm(String),
+}
+
+/// Resolves the identity algorithm for a node from the schema deployed to the
+/// graph-schema-manager. Lookups are cached for `time_to_live`, so newly
+/// deployed schema versions are picked up once the cached entry expires.
+#[derive(Clone)]
+pub struct IdentityResolver {
+    schema_client: GraphSchemaManagerClient,
+    // `None` records that no schema defines the node type
+    identity_cache: Cache<(uuid::Uuid, NodeType), Option<IdentityAlgorithm>>,
+}
+
+impl IdentityResolver {
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        cache_size: u64,
+        time_to_live: Duration,
+    ) -> Self {
+        let identity_cache = CacheBuilder::new(cache_size)
+            .time_to_live(time_to_live)
+            .build();
+        Self {
+            schema_client,
+            identity_cache,
+        }
+    }
+
+    /// Returns the strategy used to identify `node`. The deployed schema takes
+    /// precedence; the node's embedded `id_strategy` is only used for node
+    /// types that have no schema.
+    pub async fn resolve_strategy(
+        &self,
+        tenant_id: uuid::Uuid,
+        node: &NodeDescription,
+    ) -> Result<Strategy, IdentityResolverError> {
+        match self.identity_algorithm(tenant_id, node).await? {
+            Some(IdentityAlgorithm::Static(args)) => Ok(Strategy::Static(Static {
+                primary_key_properties: args
+                    .static_key_properties
+                    .into_iter()
+                    .map(|p| p.value)
+                    .collect(),
+                primary_key_requires_asset_id: false,
+            })),
+            Some(IdentityAlgorithm::Session(args)) => {
+                Ok(Strategy::Session(session_strategy(node, args)))
+            }
+            None => match node.id_strategy.first() {
+                Some(IdStrategy { strategy }) => Ok(strategy.clone()),
+                None => Err(IdentityResolverError::MissingIdentityAlgorithm(
+                    node.node_type.clone(),
+                )),
+            },
+        }
+    }
+
+    async fn identity_algorithm(
+        &self,
+        tenant_id: uuid::Uuid,
+        node: &NodeDescription,
+    ) -> Result<Option<IdentityAlgorithm>, IdentityResolverError> {
+        let node_type = NodeType {
+            value: node.node_type.clone(),
+        };
+        let key = (tenant_id, node_type.clone());
+
+        if let Some(identity_algorithm) = self.identity_cache.get(&key) {
+            return Ok(identity_algorithm);
+        }
+
+        let mut schema_client = self.schema_client.clone();
+        let identity_algorithm = match schema_client
+            .get_node_identity_algorithm(GetNodeIdentityAlgorithmRequest {
+                tenant_id,
+                node_type,
+            })
+            .await
+        {
+            Ok(response) => Some(response.identity_algorithm),
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                None
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.identity_cache
+            .insert(key, identity_algorithm.clone())
+            .await;
+        Ok(identity_algorithm)
+    }
+}
+
+/// Builds a session strategy, reading the session's timestamps from the
+/// properties the schema names for them. Missing timestamps are treated as 0,
+/// matching how generators leave unset times.
+fn session_strategy(node: &NodeDescription, args: SessionIdentityArguments) -> Session {
+    Session {
+        primary_key_properties: args
+            .pseudo_key_properties
+            .into_iter()
+            .map(|p| p.value)
+            .collect(),
+        primary_key_requires_asset_id: false,
+        create_time: timestamp_property(node, &args.creation_timestamp_property.value),
+        last_seen_time: timestamp_property(node, &args.last_seen_timestamp_property.value),
+        terminate_time: timestamp_property(node, &args.termination_timestamp_property.value),
+    }
+}
+
+fn timestamp_property(node: &NodeDescription, property_name: &str) -> u64 {
+    match node.get_property(property_name).map(|p| &p.property) {
+        Some(Property::ImmutableUintProp(p)) => p.prop,
+        Some(Property::IncrementOnlyUintProp(p)) => p.prop,
+        Some(Property::DecrementOnlyUintProp(p)) => p.prop,
+        Some(Property::ImmutableIntProp(p)) => p.prop.max(0) as u64,
+        Some(Property::IncrementOnlyIntProp(p)) => p.prop.max(0) as u64,
+        Some(Property::DecrementOnlyIntProp(p)) => p.prop.max(0) as u64,
+        Some(Property::ImmutableStrProp(_)) | None => 0,
+    }
+}
@@ -1,2 +1,4 @@
+pub mod config;
 pub mod sessiondb;
 pub mod sessions;
+mod table_names;
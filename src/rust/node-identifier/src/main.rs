@@ -1,34 +1,54 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
 use clap::Parser;
 use futures::StreamExt;
-use grapl_config::env_helpers::FromEnv;
+use graph_schema_manager::validation::GraphValidator;
 use grapl_tracing::setup_tracing;
 use kafka::{
     config::{
         ConsumerConfig,
         ProducerConfig,
     },
+    Producer,
     StreamProcessor,
     StreamProcessorError,
 };
-use rusoto_dynamodb::DynamoDbClient;
-use rust_proto::graplinc::grapl::{
-    api::graph::v1beta1::{
-        GraphDescription,
-        IdentifiedGraph,
+use rust_proto::{
+    client_factory::{
+        build_grpc_client,
+        services::GraphSchemaManagerClientConfig,
+    },
+    graplinc::grapl::{
+        api::graph::v1beta1::{
+            GraphDescription,
+            IdentifiedGraph,
+        },
+        pipeline::v1beta1::Envelope,
     },
-    pipeline::v1beta1::Envelope,
 };
+use scylla::CachingSession;
 use tracing::instrument::WithSubscriber;
 
+mod config;
 mod dynamic_sessiondb;
 mod error;
+mod identity_resolver;
 mod node_identifier;
 mod sessiondb;
 mod sessions;
+mod table_names;
 
 use crate::{
+    config::{
+        GraphDbConfig,
+        SchemaValidationConfig,
+    },
     dynamic_sessiondb::NodeDescriptionIdentifier,
     error::NodeIdentifierError,
+    identity_resolver::IdentityResolver,
     node_identifier::NodeIdentifier,
     sessiondb::SessionDb,
 };
@@ -44,12 +64,32 @@ async fn main() -> Result<(), NodeIdentifierError> {
 
 #[tracing::instrument]
 async fn handler() -> Result<(), NodeIdentifierError> {
-    let dynamo = DynamoDbClient::from_env();
-    let dyn_session_db = SessionDb::new(
-        dynamo.clone(),
-        std::env::var("GRAPL_DYNAMIC_SESSION_TABLE")?,
+    let graph_db_config = GraphDbConfig::parse();
+    let scylla_client = Arc::new(CachingSession::from(
+        graph_db_config.connect().await?,
+        10_000,
+    ));
+    let dyn_session_db = SessionDb::new(scylla_client);
+
+    let graph_schema_manager_client =
+        build_grpc_client(GraphSchemaManagerClientConfig::parse()).await?;
+    let identity_resolver = IdentityResolver::new(
+        graph_schema_manager_client.clone(),
+        10_000,
+        Duration::from_secs(60),
+    );
+    let graph_validator =
+        GraphValidator::new(graph_schema_manager_client, 10_000, Duration::from_secs(60));
+    let schema_validation_config = SchemaValidationConfig::parse();
+
+    let validation_report_producer_config =
+        ProducerConfig::with_topic_env_var("VALIDATION_REPORT_KAFKA_PRODUCER_TOPIC");
+    let node_identifier = NodeIdentifier::new(
+        NodeDescriptionIdentifier::new(dyn_session_db, identity_resolver, true),
+        graph_validator,
+        schema_validation_config.reject_unknown_node_types,
+        Producer::new(validation_report_producer_config)?,
     );
-    let node_identifier = NodeIdentifier::new(NodeDescriptionIdentifier::new(dyn_session_db, true));
 
     let consumer_config = ConsumerConfig::parse();
     let producer_config = ProducerConfig::parse();
@@ -79,7 +119,10 @@ async fn handler() -> Result<(), NodeIdentifierError> {
 
                 tracing::debug!("received kafka message");
 
-                match identifier.handle_event(tenant_id, graph_description).await {
+                match identifier
+                    .handle_event(tenant_id, trace_id, event_source_id, graph_description)
+                    .await
+                {
                     Ok(identified_graph) => Ok(Some(Envelope::new(
                         tenant_id,
                         trace_id,
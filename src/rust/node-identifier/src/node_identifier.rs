@@ -1,12 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use failure::Error;
-use grapl_utils::rusoto_ext::dynamodb::GraplDynamoDbClientExt;
-use rust_proto::graplinc::grapl::api::graph::v1beta1::{
-    GraphDescription,
-    IdentifiedGraph,
-    IdentifiedNode,
-    NodeDescription,
+use graph_schema_manager::validation::{
+    remove_nodes,
+    GraphValidator,
+    SchemaViolation,
+};
+use kafka::Producer;
+use rust_proto::graplinc::grapl::{
+    api::{
+        graph::v1beta1::{
+            GraphDescription,
+            IdentifiedGraph,
+            IdentifiedNode,
+            NodeDescription,
+        },
+        graph_schema_manager::v1beta1::messages::NodeValidationReport,
+    },
+    pipeline::v1beta1::Envelope,
 };
 use tap::tap::TapOptional;
 
@@ -27,21 +41,77 @@ use crate::{
 
     * [Static](`graplinc::grapl::api::graph::v1beta1::Static`) - strategy used
       for nodes with canonical and unique identifiers (e.g. aws events)
+
+    Before identification, nodes that violate the tenant's deployed schema are
+    rejected, along with their edges, and a report of each rejected node is
+    published to the validation report topic.
 */
 #[derive(Clone)]
-pub(crate) struct NodeIdentifier<D>
-where
-    D: GraplDynamoDbClientExt,
-{
-    dynamic_identifier: NodeDescriptionIdentifier<D>,
+pub(crate) struct NodeIdentifier {
+    dynamic_identifier: NodeDescriptionIdentifier,
+    graph_validator: GraphValidator,
+    reject_unknown_node_types: bool,
+    validation_report_producer: Producer<NodeValidationReport>,
 }
 
-impl<D> NodeIdentifier<D>
-where
-    D: GraplDynamoDbClientExt,
-{
-    pub(crate) fn new(dynamic_identifier: NodeDescriptionIdentifier<D>) -> Self {
-        Self { dynamic_identifier }
+impl NodeIdentifier {
+    pub(crate) fn new(
+        dynamic_identifier: NodeDescriptionIdentifier,
+        graph_validator: GraphValidator,
+        reject_unknown_node_types: bool,
+        validation_report_producer: Producer<NodeValidationReport>,
+    ) -> Self {
+        Self {
+            dynamic_identifier,
+            graph_validator,
+            reject_unknown_node_types,
+            validation_report_producer,
+        }
+    }
+
+    /// Removes the nodes in `unidentified_subgraph` that violate the tenant's
+    /// deployed schema, publishing a report of the violations for each.
+    #[tracing::instrument(skip(self, unidentified_subgraph), err)]
+    async fn reject_invalid_nodes(
+        &self,
+        tenant_id: uuid::Uuid,
+        trace_id: uuid::Uuid,
+        event_source_id: uuid::Uuid,
+        mut unidentified_subgraph: GraphDescription,
+    ) -> Result<GraphDescription, NodeIdentifierError> {
+        let reports = self
+            .graph_validator
+            .validate_graph(tenant_id, &unidentified_subgraph)
+            .await?;
+
+        let mut rejected_node_keys = HashSet::new();
+        for report in reports {
+            if !self.reject_unknown_node_types
+                && report.violations == [SchemaViolation::UnknownNodeType]
+            {
+                continue;
+            }
+
+            tracing::warn!(
+                message = "Rejecting node that violates schema",
+                node_key = %report.node_key,
+                node_type = %report.node_type,
+                violations = ?report.violations,
+            );
+            rejected_node_keys.insert(report.node_key.clone());
+
+            self.validation_report_producer
+                .send(Envelope::new(
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                    report.into(),
+                ))
+                .await?;
+        }
+
+        remove_nodes(&mut unidentified_subgraph, &rejected_node_keys);
+        Ok(unidentified_subgraph)
     }
 
     // todo: We should be yielding IdentifiedNode's here
@@ -163,6 +233,8 @@ where
     pub(crate) async fn handle_event(
         &self,
         tenant_id: uuid::Uuid,
+        trace_id: uuid::Uuid,
+        event_source_id: uuid::Uuid,
         unidentified_subgraph: GraphDescription,
     ) -> Result<IdentifiedGraph, Result<(IdentifiedGraph, NodeIdentifierError), NodeIdentifierError>>
     {
@@ -192,6 +264,16 @@ where
             return Err(Err(NodeIdentifierError::EmptyGraph));
         }
 
+        let unidentified_subgraph = self
+            .reject_invalid_nodes(tenant_id, trace_id, event_source_id, unidentified_subgraph)
+            .await
+            .map_err(Err)?;
+
+        if unidentified_subgraph.is_empty() {
+            tracing::warn!("All nodes in subgraph were rejected.");
+            return Err(Err(NodeIdentifierError::EmptyGraph));
+        }
+
         let mut identified_graph = IdentifiedGraph::new();
 
         let (identified_nodekey_map, attribution_failure) = self
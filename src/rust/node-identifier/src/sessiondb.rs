@@ -1,23 +1,14 @@
-use std::convert::TryFrom;
+use std::sync::Arc;
 
 use failure::{
     bail,
     Error,
 };
-use hmap::hmap;
-use rusoto_core::RusotoError;
-use rusoto_dynamodb::{
-    AttributeValue,
-    AttributeValueUpdate,
-    Delete,
-    DeleteItemInput,
-    DynamoDb,
-    Put,
-    PutItemInput,
-    QueryInput,
-    TransactWriteItem,
-    TransactWriteItemsInput,
-    UpdateItemInput,
+use scylla::{
+    frame::value::ValueList,
+    query::Query,
+    CachingSession,
+    QueryResult,
 };
 use tracing::{
     info,
@@ -25,118 +16,92 @@ use tracing::{
 };
 use uuid::Uuid;
 
-use crate::sessions::*;
+use crate::{
+    sessions::*,
+    table_names::{
+        tenant_keyspace_name,
+        SESSIONS_TABLE_NAME,
+    },
+};
 
-#[derive(Debug, Clone)]
-pub struct SessionDb<D>
-where
-    D: DynamoDb,
-{
-    dynamo: D,
-    table_name: String,
+/// Stores session timelines in the `sessions` table of each tenant's keyspace,
+/// which is provisioned by the scylla-provisioner. Sessions are partitioned by
+/// `pseudo_key` and clustered by `create_time`.
+#[derive(Clone)]
+pub struct SessionDb {
+    scylla_client: Arc<CachingSession>,
 }
 
-impl<D> SessionDb<D>
-where
-    D: DynamoDb,
-{
+impl SessionDb {
     // exposed for integration tests
-    pub fn new(dynamo: D, table_name: impl Into<String>) -> Self {
-        Self {
-            dynamo,
-            table_name: table_name.into(),
-        }
+    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
+        Self { scylla_client }
     }
 
     #[tracing::instrument(skip(self, unid), err)]
     pub(crate) async fn find_first_session_after(
         &self,
+        tenant_id: Uuid,
         unid: &UnidSession,
     ) -> Result<Option<Session>, Error> {
-        info!(message="Finding first session after", table_name=?&self.table_name);
-        let query = QueryInput {
-            consistent_read: Some(true),
-            limit: Some(1),
-            table_name: self.table_name.clone(),
-            key_condition_expression: Some(
-                "pseudo_key = :pseudo_key AND create_time >= :create_time".into(),
-            ),
-            expression_attribute_values: Some(hmap! {
-                ":pseudo_key".to_owned() => AttributeValue {
-                    s: unid.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                ":create_time".to_owned() => AttributeValue {
-                    n: unid.timestamp.to_string().into(),
-                    ..Default::default()
-                }
-            }),
-            ..Default::default()
-        };
-
-        let res = self.dynamo.query(query).await;
-        if let Err(RusotoError::Unknown(ref e)) = res {
-            bail!("Query failed with error: {:?}", e);
-        };
-
-        if let Some(items) = res?.items {
-            match &items[..] {
-                [] => Ok(None),
-                [item] => Session::try_from(item.clone()).map(Option::from),
-                _ => bail!("Unexpected number of items returned"),
-            }
-        } else {
-            Ok(None)
-        }
+        info!(message = "Finding first session after");
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            SELECT {SESSION_COLUMNS}
+            FROM {tenant_ks}.{SESSIONS_TABLE_NAME}
+            WHERE pseudo_key = ? AND create_time >= ?
+            ORDER BY create_time ASC
+            LIMIT 1
+            "
+        ));
+
+        let session = self
+            .scylla_client
+            .execute(query, &(&unid.pseudo_key, unid.timestamp as i64))
+            .await?
+            .maybe_first_row_typed::<SessionRow>()?;
+
+        Ok(session.map(Session::from))
     }
 
     #[tracing::instrument(skip(self, unid), err)]
     pub(crate) async fn find_last_session_before(
         &self,
+        tenant_id: Uuid,
         unid: &UnidSession,
     ) -> Result<Option<Session>, Error> {
         info!(message = "Finding last session before");
-        let query = QueryInput {
-            consistent_read: Some(true),
-            limit: Some(1),
-            scan_index_forward: Some(false),
-            table_name: self.table_name.clone(),
-            key_condition_expression: Some(
-                "pseudo_key = :pseudo_key AND create_time <= :create_time".into(),
-            ),
-            expression_attribute_values: Some(hmap! {
-                ":pseudo_key".to_owned() => AttributeValue {
-                    s: unid.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                ":create_time".to_owned() => AttributeValue {
-                    n: unid.timestamp.to_string().into(),
-                    ..Default::default()
-                }
-            }),
-            ..Default::default()
-        };
-
-        let res = self.dynamo.query(query).await?;
-
-        if let Some(items) = res.items {
-            match &items[..] {
-                [] => Ok(None),
-                [item] => Session::try_from(item.clone()).map(Option::from),
-                _ => bail!("Unexpected number of items returned"),
-            }
-        } else {
-            Ok(None)
-        }
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            SELECT {SESSION_COLUMNS}
+            FROM {tenant_ks}.{SESSIONS_TABLE_NAME}
+            WHERE pseudo_key = ? AND create_time <= ?
+            ORDER BY create_time DESC
+            LIMIT 1
+            "
+        ));
+
+        let session = self
+            .scylla_client
+            .execute(query, &(&unid.pseudo_key, unid.timestamp as i64))
+            .await?
+            .maybe_first_row_typed::<SessionRow>()?;
+
+        Ok(session.map(Session::from))
     }
 
-    // `create_time` is the sort key in the table, so updating it is not possible.
-    // Instead, in one transaction, the row must be deleted and recreated with the
-    // new create_time
+    // `create_time` is the clustering key in the table, so updating it is not
+    // possible. Instead, the session is recreated with the new create_time and
+    // the old row is deleted. Each step is conditional, like every write to
+    // the table: if the old row changed concurrently, the new row is removed
+    // again and the concurrent change wins.
     // This method assumes that the `session` passed in has already been modified
     #[tracing::instrument(skip(self, session), err)]
     pub(crate) async fn update_session_create_time(
         &self,
+        tenant_id: Uuid,
         session: &Session,
         new_time: u64,
         is_canon: bool,
@@ -148,42 +113,80 @@ where
         new_session.version += 1;
         // Create new session with new create_time, increment version
 
-        let put_req = Put {
-            item: serde_dynamodb::to_hashmap(&new_session).unwrap(),
-            table_name: self.table_name.clone(),
-            ..Default::default()
-        };
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        if new_time == session.create_time {
+            let query = Query::new(format!(
+                r"
+                UPDATE {tenant_ks}.{SESSIONS_TABLE_NAME}
+                SET is_create_canon = ?, version = ?
+                WHERE pseudo_key = ? AND create_time = ?
+                IF version = ?
+                "
+            ));
+            let applied = self
+                .execute_conditional(
+                    query,
+                    (
+                        is_canon,
+                        new_session.version as i64,
+                        &session.pseudo_key,
+                        session.create_time as i64,
+                        session.version as i64,
+                    ),
+                )
+                .await?;
+            if !applied {
+                bail!("Session version changed concurrently: {}", session.version);
+            }
+            return Ok(());
+        }
 
-        let del_req = Delete {
-            key: hmap! {
-                "pseudo_key".to_owned() => AttributeValue {
-                    s: session.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                "create_time".to_owned() => AttributeValue {
-                    n: session.create_time.to_string().into(),
-                    ..Default::default()
-                }
-            },
-            table_name: self.table_name.clone(),
-            ..Default::default()
-        };
+        let insert = Query::new(format!(
+            r"
+            INSERT INTO {tenant_ks}.{SESSIONS_TABLE_NAME} ({SESSION_COLUMNS})
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            IF NOT EXISTS
+            "
+        ));
+        if !self
+            .execute_conditional(insert, new_session.to_row())
+            .await?
+        {
+            bail!(
+                "Session already exists with create_time: {}",
+                new_session.create_time
+            );
+        }
 
-        self.dynamo
-            .transact_write_items(TransactWriteItemsInput {
-                transact_items: vec![
-                    TransactWriteItem {
-                        delete: del_req.into(),
-                        ..Default::default()
-                    },
-                    TransactWriteItem {
-                        put: put_req.into(),
-                        ..Default::default()
-                    },
-                ],
-                ..Default::default()
-            })
+        let delete = format!(
+            r"
+            DELETE FROM {tenant_ks}.{SESSIONS_TABLE_NAME}
+            WHERE pseudo_key = ? AND create_time = ?
+            IF version = ?
+            "
+        );
+        let deleted = self
+            .execute_conditional(
+                Query::new(delete.clone()),
+                (
+                    &session.pseudo_key,
+                    session.create_time as i64,
+                    session.version as i64,
+                ),
+            )
+            .await?;
+        if !deleted {
+            self.execute_conditional(
+                Query::new(delete),
+                (
+                    &new_session.pseudo_key,
+                    new_session.create_time as i64,
+                    new_session.version as i64,
+                ),
+            )
             .await?;
+            bail!("Session version changed concurrently: {}", session.version);
+        }
 
         Ok(())
     }
@@ -192,48 +195,38 @@ where
     // https://github.com/grapl-security/issue-tracker/issues/1002
     #[allow(dead_code)]
     #[tracing::instrument(skip(self, session), err)]
-    pub(crate) async fn make_create_time_canonical(&self, session: &Session) -> Result<(), Error> {
+    pub(crate) async fn make_create_time_canonical(
+        &self,
+        tenant_id: Uuid,
+        session: &Session,
+    ) -> Result<(), Error> {
         info!(message = "Updating session end time");
         // Use version as a constraint
-        let upd_req = UpdateItemInput {
-            key: hmap! {
-                "pseudo_key".to_owned() => AttributeValue {
-                    s: session.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                "create_time".to_owned() => AttributeValue {
-                    n: session.create_time.to_string().into(),
-                    ..Default::default()
-                }
-            },
-            attribute_updates: Some(hmap! {
-                "is_create_canon".to_owned() => AttributeValueUpdate {
-                    value: Some(AttributeValue {
-                            bool: true.into(),
-                            ..Default::default()
-                        }),
-                    ..Default::default()
-                },
-                "version".to_owned() => AttributeValueUpdate {
-                    value: Some(AttributeValue {
-                        n: (session.version + 1).to_string().into(),
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                }
-            }),
-            table_name: self.table_name.clone(),
-            condition_expression: Some("version = :version".into()),
-            expression_attribute_values: Some(hmap! {
-                ":version".to_owned() => AttributeValue {
-                    n: session.version.to_string().into(),
-                    ..Default::default()
-                }
-            }),
-            ..Default::default()
-        };
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            UPDATE {tenant_ks}.{SESSIONS_TABLE_NAME}
+            SET is_create_canon = true, version = ?
+            WHERE pseudo_key = ? AND create_time = ?
+            IF version = ?
+            "
+        ));
+
+        let applied = self
+            .execute_conditional(
+                query,
+                (
+                    (session.version + 1) as i64,
+                    &session.pseudo_key,
+                    session.create_time as i64,
+                    session.version as i64,
+                ),
+            )
+            .await?;
 
-        self.dynamo.update_item(upd_req).await?;
+        if !applied {
+            bail!("Session version changed concurrently: {}", session.version);
+        }
 
         Ok(())
     }
@@ -245,73 +238,62 @@ where
     #[tracing::instrument(skip(self, session), err)]
     pub(crate) async fn update_session_end_time(
         &self,
+        tenant_id: Uuid,
         session: &Session,
         new_time: u64,
         is_canon: bool,
     ) -> Result<(), Error> {
         info!(message = "Updating session end time");
         // Use version as a constraint
-        let upd_req = UpdateItemInput {
-            key: hmap! {
-                "pseudo_key".to_owned() => AttributeValue {
-                    s: session.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                "create_time".to_owned() => AttributeValue {
-                    n: session.create_time.to_string().into(),
-                    ..Default::default()
-                }
-            },
-            attribute_updates: Some(hmap! {
-                "end_time".to_owned() => AttributeValueUpdate {
-                    value: Some(AttributeValue {
-                        n: new_time.to_string().into(),
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                },
-                "is_end_canon".to_owned() => AttributeValueUpdate {
-                    value: Some(AttributeValue {
-                            bool: is_canon.into(),
-                            ..Default::default()
-                        }),
-                    ..Default::default()
-                },
-                "version".to_owned() => AttributeValueUpdate {
-                    value: Some(AttributeValue {
-                        n: (session.version + 1).to_string().into(),
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-
-                }
-            }),
-            table_name: self.table_name.clone(),
-            condition_expression: Some("version = :version".into()),
-            expression_attribute_values: Some(hmap! {
-                ":version".to_owned() => AttributeValue {
-                    n: session.version.to_string().into(),
-                    ..Default::default()
-                }
-            }),
-            ..Default::default()
-        };
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            UPDATE {tenant_ks}.{SESSIONS_TABLE_NAME}
+            SET end_time = ?, is_end_canon = ?, version = ?
+            WHERE pseudo_key = ? AND create_time = ?
+            IF version = ?
+            "
+        ));
+
+        let applied = self
+            .execute_conditional(
+                query,
+                (
+                    new_time as i64,
+                    is_canon,
+                    (session.version + 1) as i64,
+                    &session.pseudo_key,
+                    session.create_time as i64,
+                    session.version as i64,
+                ),
+            )
+            .await?;
 
-        self.dynamo.update_item(upd_req).await?;
+        if !applied {
+            bail!("Session version changed concurrently: {}", session.version);
+        }
 
         Ok(())
     }
 
     // exposed for integration tests
     #[tracing::instrument(skip(self, session), err)]
-    pub async fn create_session(&self, session: &Session) -> Result<(), Error> {
-        let put_req = PutItemInput {
-            item: serde_dynamodb::to_hashmap(session).unwrap(),
-            table_name: self.table_name.clone(),
-            ..Default::default()
-        };
-
-        self.dynamo.put_item(put_req).await?;
+    pub async fn create_session(&self, tenant_id: Uuid, session: &Session) -> Result<(), Error> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            INSERT INTO {tenant_ks}.{SESSIONS_TABLE_NAME} ({SESSION_COLUMNS})
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            IF NOT EXISTS
+            "
+        ));
+
+        if !self.execute_conditional(query, session.to_row()).await? {
+            bail!(
+                "Session already exists with create_time: {}",
+                session.create_time
+            );
+        }
 
         Ok(())
     }
@@ -320,35 +302,63 @@ where
     // https://github.com/grapl-security/issue-tracker/issues/1002
     #[allow(dead_code)]
     #[tracing::instrument(skip(self, session), err)]
-    pub(crate) async fn delete_session(&self, session: &Session) -> Result<(), Error> {
-        let del_req = DeleteItemInput {
-            key: hmap! {
-                "pseudo_key".to_owned() => AttributeValue {
-                    s: session.pseudo_key.clone().into(),
-                    ..Default::default()
-                },
-                "create_time".to_owned() => AttributeValue {
-                    n: session.create_time.to_string().into(),
-                    ..Default::default()
-                }
-            },
-            table_name: self.table_name.clone(),
-            ..Default::default()
-        };
+    pub(crate) async fn delete_session(
+        &self,
+        tenant_id: Uuid,
+        session: &Session,
+    ) -> Result<(), Error> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{SESSIONS_TABLE_NAME}
+            WHERE pseudo_key = ? AND create_time = ?
+            IF version = ?
+            "
+        ));
+
+        let applied = self
+            .execute_conditional(
+                query,
+                (
+                    &session.pseudo_key,
+                    session.create_time as i64,
+                    session.version as i64,
+                ),
+            )
+            .await?;
+
+        if !applied {
+            bail!("Session version changed concurrently: {}", session.version);
+        }
 
-        self.dynamo.delete_item(del_req).await?;
         Ok(())
     }
 
+    /// Executes a conditional (`IF ...`) statement, returning whether it was
+    /// applied. Every write to the sessions table is conditional, since Scylla
+    /// doesn't order plain writes against conditional ones on the same rows.
+    async fn execute_conditional(
+        &self,
+        query: Query,
+        values: impl ValueList,
+    ) -> Result<bool, Error> {
+        let result = self.scylla_client.execute(query, values).await?;
+        Ok(was_applied(&result))
+    }
+
     #[tracing::instrument(skip(self, unid), err)]
-    pub(crate) async fn handle_creation_event(&self, unid: UnidSession) -> Result<String, Error> {
+    pub(crate) async fn handle_creation_event(
+        &self,
+        tenant_id: Uuid,
+        unid: UnidSession,
+    ) -> Result<String, Error> {
         info!(
             message="Handling unid session creation",
             pseudo_key=?unid.pseudo_key, timestamp=?unid.timestamp
         );
 
         // Look for first session where session.create_time >= unid.create_time
-        let session = self.find_first_session_after(&unid).await?;
+        let session = self.find_first_session_after(tenant_id, &unid).await?;
 
         if let Some(session) = session {
             // If session.is_create_canon is false,
@@ -356,7 +366,7 @@ where
             // and we should consider this the canonical ID for that session
             if !session.is_create_canon && session.create_time != unid.timestamp {
                 info!(message = "Extending session create_time");
-                self.update_session_create_time(&session, unid.timestamp, true)
+                self.update_session_create_time(tenant_id, &session, unid.timestamp, true)
                     .await?;
                 return Ok(session.session_id);
             }
@@ -388,7 +398,7 @@ where
         }
 
         // Look for last session where session.create_time <= unid.create_time
-        let session = self.find_last_session_before(&unid).await?;
+        let session = self.find_last_session_before(tenant_id, &unid).await?;
 
         if let Some(session) = session {
             // If session.end_time >= unid.create_time (indicates overlapping sessions, error)
@@ -418,13 +428,14 @@ where
         };
 
         info!(message = "Creating session");
-        self.create_session(&session).await?;
+        self.create_session(tenant_id, &session).await?;
         Ok(session.session_id)
     }
 
     #[tracing::instrument(skip(self, unid), err)]
     pub(crate) async fn handle_last_seen(
         &self,
+        tenant_id: Uuid,
         unid: UnidSession,
         should_default: bool,
     ) -> Result<String, Error> {
@@ -435,7 +446,7 @@ where
 
         // Look for session where session.create_time <= unid.create_time <= session.end_time
         // Look for last session where session.create_time <= unid.create_time
-        let session = self.find_last_session_before(&unid).await?;
+        let session = self.find_last_session_before(tenant_id, &unid).await?;
         if let Some(mut session) = session {
             if unid.timestamp < session.end_time || skewed_cmp(unid.timestamp, session.end_time) {
                 info!(message = "Identified session because it fell within a timeline.");
@@ -451,12 +462,12 @@ where
             }
         }
 
-        let session = self.find_first_session_after(&unid).await?;
+        let session = self.find_first_session_after(tenant_id, &unid).await?;
         if let Some(session) = session {
             if !session.is_create_canon {
                 info!(message = "Found a later, non canonical session. Extending create_time.");
 
-                self.update_session_create_time(&session, unid.timestamp, false)
+                self.update_session_create_time(tenant_id, &session, unid.timestamp, false)
                     .await?;
                 return Ok(session.session_id);
             }
@@ -474,7 +485,7 @@ where
                 version: 0,
                 pseudo_key: unid.pseudo_key,
             };
-            self.create_session(&session).await?;
+            self.create_session(tenant_id, &session).await?;
 
             Ok(session_id)
         } else {
@@ -490,18 +501,31 @@ where
     #[tracing::instrument(skip(self), err)]
     pub async fn handle_unid_session(
         &self,
+        tenant_id: Uuid,
         mut unid: UnidSession,
         should_default: bool,
     ) -> Result<String, Error> {
         unid.timestamp = shave_int(unid.timestamp, 1);
         if unid.is_creation {
-            self.handle_creation_event(unid).await
+            self.handle_creation_event(tenant_id, unid).await
         } else {
-            self.handle_last_seen(unid, should_default).await
+            self.handle_last_seen(tenant_id, unid, should_default).await
         }
     }
 }
 
+/// Whether a conditional (`IF ...`) statement was applied
+fn was_applied(result: &QueryResult) -> bool {
+    result
+        .rows
+        .as_ref()
+        .and_then(|rows| rows.first())
+        .and_then(|row| row.columns.first())
+        .and_then(|column| column.as_ref())
+        .and_then(|applied| applied.as_boolean())
+        .unwrap_or(false)
+}
+
 pub(crate) fn skewed_cmp(ts_1: u64, ts_2: u64) -> bool {
     ts_1 - 10 < ts_2 && ts_1 + 10 > ts_2
 }
@@ -1,10 +1,3 @@
-use std::{
-    collections::HashMap,
-    convert::TryFrom,
-};
-
-use failure::Error;
-use rusoto_dynamodb::AttributeValue;
 use serde::{
     Deserialize,
     Serialize,
@@ -28,10 +21,40 @@ pub struct Session {
     pub version: u64, // This is an atomic version used for transactions
 }
 
-impl TryFrom<HashMap<String, AttributeValue>> for Session {
-    type Error = Error;
-    fn try_from(map: HashMap<String, AttributeValue>) -> Result<Self, Error> {
-        Ok(serde_dynamodb::from_hashmap(map)?)
+/// A row of the sessions table, in the order of `SESSION_COLUMNS`. Scylla has
+/// no unsigned types, so times and versions are stored as bigints.
+pub(crate) type SessionRow = (String, String, i64, i64, bool, bool, i64);
+
+pub(crate) const SESSION_COLUMNS: &str =
+    "session_id, pseudo_key, create_time, end_time, is_create_canon, is_end_canon, version";
+
+impl From<SessionRow> for Session {
+    fn from(row: SessionRow) -> Self {
+        let (session_id, pseudo_key, create_time, end_time, is_create_canon, is_end_canon, version) =
+            row;
+        Self {
+            session_id,
+            pseudo_key,
+            create_time: create_time as u64,
+            end_time: end_time as u64,
+            is_create_canon,
+            is_end_canon,
+            version: version as u64,
+        }
+    }
+}
+
+impl Session {
+    pub(crate) fn to_row(&self) -> SessionRow {
+        (
+            self.session_id.clone(),
+            self.pseudo_key.clone(),
+            self.create_time as i64,
+            self.end_time as i64,
+            self.is_create_canon,
+            self.is_end_canon,
+            self.version as i64,
+        )
     }
 }
 
@@ -0,0 +1,8 @@
+// Copy-pasted from scylla-provisioner, which owns the schema for these tables
+pub const SESSIONS_TABLE_NAME: &str = "sessions";
+
+pub fn tenant_keyspace_name(tenant_id: uuid::Uuid) -> String {
+    // scylla keyspace names must be alphanumeric + underscores, and max out at 48.
+    // fun fact: the result of this is exactly 48
+    format!("tenant_keyspace_{}", tenant_id.simple())
+}
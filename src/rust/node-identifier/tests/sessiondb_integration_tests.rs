@@ -1,93 +1,49 @@
 #![cfg(feature = "integration_tests")]
 
-use std::time::Duration;
+use std::sync::Arc;
 
-use grapl_config::env_helpers::FromEnv;
+use clap::Parser;
 use node_identifier::{
+    config::GraphDbConfig,
     sessiondb::SessionDb,
     sessions::{
         Session,
         UnidSession,
     },
 };
-use rusoto_core::RusotoError;
-use rusoto_dynamodb::{
-    AttributeDefinition,
-    CreateTableError,
-    CreateTableInput,
-    CreateTableOutput,
-    DeleteTableInput,
-    DynamoDb,
-    DynamoDbClient,
-    KeySchemaElement,
-    ProvisionedThroughput,
+use rust_proto::{
+    client_factory::{
+        build_grpc_client,
+        services::ScyllaProvisionerClientConfig,
+    },
+    graplinc::grapl::api::scylla_provisioner::v1beta1::messages::ProvisionGraphForTenantRequest,
 };
-use tokio::runtime::Runtime;
+use scylla::CachingSession;
 use uuid::Uuid;
 
-async fn try_create_table(
-    dynamo: &impl DynamoDb,
-    table_name: String,
-) -> Result<CreateTableOutput, RusotoError<CreateTableError>> {
-    dynamo
-        .create_table(CreateTableInput {
-            table_name,
-            attribute_definitions: vec![
-                AttributeDefinition {
-                    attribute_name: "pseudo_key".into(),
-                    attribute_type: "S".into(),
-                },
-                AttributeDefinition {
-                    attribute_name: "create_time".into(),
-                    attribute_type: "N".into(),
-                },
-            ],
-            key_schema: vec![
-                KeySchemaElement {
-                    attribute_name: "pseudo_key".into(),
-                    key_type: "HASH".into(),
-                },
-                KeySchemaElement {
-                    attribute_name: "create_time".into(),
-                    key_type: "RANGE".into(),
-                },
-            ],
-            provisioned_throughput: Some(ProvisionedThroughput {
-                read_capacity_units: 3,
-                write_capacity_units: 3,
-            }),
-            ..Default::default()
-        })
-        .await
-}
-
-fn create_or_empty_table(dynamo: &impl DynamoDb, table_name: impl Into<String>) {
-    let runtime = Runtime::new().unwrap();
-    let table_name = table_name.into();
+// Each test gets its own tenant, and so its own empty sessions table
+async fn session_db_for_new_tenant() -> eyre::Result<(SessionDb, Uuid)> {
+    let tenant_id = Uuid::new_v4();
 
-    let _ = runtime.block_on(dynamo.delete_table(DeleteTableInput {
-        table_name: table_name.clone(),
-    }));
+    let mut provisioner_client = build_grpc_client(ScyllaProvisionerClientConfig::parse()).await?;
+    provisioner_client
+        .provision_graph_for_tenant(ProvisionGraphForTenantRequest { tenant_id })
+        .await?;
 
-    std::thread::sleep(Duration::from_millis(250));
+    let scylla_client = Arc::new(CachingSession::from(
+        GraphDbConfig::parse().connect().await?,
+        100,
+    ));
 
-    while let Err(_e) = runtime.block_on(try_create_table(dynamo, table_name.clone())) {
-        std::thread::sleep(Duration::from_millis(250));
-    }
+    Ok((SessionDb::new(scylla_client), tenant_id))
 }
 
 // Given an empty timeline
 // When a canonical creation event comes in
 // Then the newly created session should be in the timeline
-#[test]
-fn canon_create_on_empty_timeline() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_canon_create_on_empty_timeline";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
-
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn canon_create_on_empty_timeline() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let unid = UnidSession {
         pseudo_key: format!("{}{}", Uuid::new_v4(), rand::random::<u64>(),),
@@ -95,11 +51,14 @@ fn canon_create_on_empty_timeline() {
         is_creation: true,
     };
 
-    let session_id = runtime
-        .block_on(session_db.handle_unid_session(unid, false))
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, false)
+        .await
         .expect("Failed to create session");
 
     assert!(!session_id.is_empty());
+
+    Ok(())
 }
 
 // Given a timeline with a single session, where that session has a non canon
@@ -107,15 +66,9 @@ fn canon_create_on_empty_timeline() {
 // When a canonical creation event comes in with a creation time of 'Y'
 //      where 'Y' < 'X'
 // Then the session should be updated to have 'Y' as its canonical create time
-#[test]
-fn canon_create_update_existing_non_canon_create() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_canon_create_update_existing_non_canon_create";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
-
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn canon_create_update_existing_non_canon_create() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let asset_id = Uuid::new_v4();
     let pid: u64 = rand::random();
@@ -131,8 +84,9 @@ fn canon_create_update_existing_non_canon_create() {
         version: 0,
     };
 
-    runtime
-        .block_on(session_db.create_session(&session))
+    session_db
+        .create_session(tenant_id, &session)
+        .await
         .expect("Failed to create session");
 
     // When a canonical creation event comes in with a creation time of 'Y'
@@ -143,11 +97,14 @@ fn canon_create_update_existing_non_canon_create() {
         is_creation: true,
     };
 
-    let session_id = runtime
-        .block_on(session_db.handle_unid_session(unid, false))
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, false)
+        .await
         .expect("Failed to handle unid");
 
     assert_eq!(session_id, "SessionId");
+
+    Ok(())
 }
 
 // Given a timeline with a single session, where that session has a non canon
@@ -155,15 +112,9 @@ fn canon_create_update_existing_non_canon_create() {
 // When a noncanonical creation event comes in with a creation time of 'Y'
 //      where 'Y' < 'X'
 // Then the session should be updated to have 'Y' as its noncanonical create time
-#[test]
-fn noncanon_create_update_existing_non_canon_create() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_noncanon_create_update_existing_non_canon_create";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
-
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn noncanon_create_update_existing_non_canon_create() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let asset_id = Uuid::new_v4();
     let pid: u64 = rand::random();
@@ -180,8 +131,9 @@ fn noncanon_create_update_existing_non_canon_create() {
         version: 0,
     };
 
-    runtime
-        .block_on(session_db.create_session(&session))
+    session_db
+        .create_session(tenant_id, &session)
+        .await
         .expect("Failed to create session");
 
     // When a noncanonical creation event comes in with a creation time of 'Y'
@@ -192,26 +144,23 @@ fn noncanon_create_update_existing_non_canon_create() {
         is_creation: false,
     };
 
-    let session_id = runtime
-        .block_on(session_db.handle_unid_session(unid, false))
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, false)
+        .await
         .expect("Failed to handle unid");
 
     // TODO: Assert that the create time was updated correctly
     assert_eq!(session_id, "SessionId");
+
+    Ok(())
 }
 
 // Given an empty timeline
 // When a noncanon create event comes in and 'should_default' is true
 // Then Create the new noncanon session
-#[test]
-fn noncanon_create_on_empty_timeline_with_default() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_noncanon_create_on_empty_timeline_with_default";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
-
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn noncanon_create_on_empty_timeline_with_default() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let asset_id = Uuid::new_v4();
     let pid: u64 = rand::random();
@@ -222,25 +171,22 @@ fn noncanon_create_on_empty_timeline_with_default() {
         is_creation: false,
     };
 
-    let session_id = runtime
-        .block_on(session_db.handle_unid_session(unid, true))
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, true)
+        .await
         .expect("Failed to create session");
 
     assert!(!session_id.is_empty());
+
+    Ok(())
 }
 
 // Given an empty timeline
 // When a noncanon create event comes in and 'should_default' is false
 // Then return an error
-#[test]
-fn noncanon_create_on_empty_timeline_without_default() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_noncanon_create_on_empty_timeline_without_default";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
-
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn noncanon_create_on_empty_timeline_without_default() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let unid = UnidSession {
         pseudo_key: "asset_id_a1234".into(),
@@ -248,19 +194,15 @@ fn noncanon_create_on_empty_timeline_without_default() {
         is_creation: false,
     };
 
-    let session_id = runtime.block_on(session_db.handle_unid_session(unid, false));
+    let session_id = session_db.handle_unid_session(tenant_id, unid, false).await;
     assert!(session_id.is_err());
-}
 
-#[test]
-fn update_end_time() {
-    let runtime = Runtime::new().unwrap();
-    let table_name = "process_history_update_end_time";
-    let dynamo = DynamoDbClient::from_env();
-
-    create_or_empty_table(&dynamo, table_name);
+    Ok(())
+}
 
-    let session_db = SessionDb::new(dynamo, table_name);
+#[tokio::test]
+async fn update_end_time() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
 
     let asset_id = Uuid::new_v4();
     let pid: u64 = rand::random();
@@ -277,8 +219,9 @@ fn update_end_time() {
         version: 0,
     };
 
-    runtime
-        .block_on(session_db.create_session(&session))
+    session_db
+        .create_session(tenant_id, &session)
+        .await
         .expect("Failed to create session");
 
     // When a canonical creation event comes in with an end time of 'Y'
@@ -289,9 +232,63 @@ fn update_end_time() {
         is_creation: false,
     };
 
-    let session_id = runtime
-        .block_on(session_db.handle_unid_session(unid, false))
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, false)
+        .await
+        .expect("Failed to handle unid");
+
+    assert_eq!(session_id, "SessionId");
+
+    Ok(())
+}
+
+// Given a timeline with a single session
+// When another session is created with the same pseudo key and create time
+// Then creating it fails, and the existing session is left as it was
+#[tokio::test]
+async fn create_existing_session_fails() -> eyre::Result<()> {
+    let (session_db, tenant_id) = session_db_for_new_tenant().await?;
+
+    let asset_id = Uuid::new_v4();
+    let pid: u64 = rand::random();
+
+    // Given a timeline with a single session
+    let session = Session {
+        pseudo_key: format!("{}{}", asset_id, pid),
+        create_time: 1_544_301_484_600,
+        is_create_canon: true,
+        session_id: "SessionId".into(),
+        is_end_canon: false,
+        end_time: 1_544_301_484_700,
+        version: 0,
+    };
+
+    session_db
+        .create_session(tenant_id, &session)
+        .await
+        .expect("Failed to create session");
+
+    // When another session is created with the same pseudo key and create time
+    let duplicate = Session {
+        session_id: "OtherSessionId".into(),
+        ..session.clone()
+    };
+    let result = session_db.create_session(tenant_id, &duplicate).await;
+    assert!(result.is_err());
+
+    // Then the existing session is left as it was
+    let unid = UnidSession {
+        pseudo_key: format!("{}{}", asset_id, pid),
+        timestamp: 1_544_301_484_600,
+        is_creation: true,
+    };
+
+    let session_id = session_db
+        .handle_unid_session(tenant_id, unid, false)
+        .await
         .expect("Failed to handle unid");
 
     assert_eq!(session_id, "SessionId");
+
+    Ok(())
 }
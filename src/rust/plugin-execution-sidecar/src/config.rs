@@ -2,4 +2,18 @@
 pub struct PluginExecutorConfig {
     #[clap(long, env = "PLUGIN_EXECUTOR_PLUGIN_ID")]
     pub plugin_id: uuid::Uuid,
+    /// How many jobs to request from plugin-work-queue at once. Jobs in a
+    /// batch are processed one after another, while the leases of those still
+    /// waiting are extended.
+    #[clap(long, env = "PLUGIN_EXECUTOR_MAX_JOBS", default_value = "10")]
+    pub max_jobs: u32,
+    /// How often to extend the leases of a batch's unfinished jobs while one
+    /// of them is being processed. Should be comfortably shorter than the
+    /// plugin's visibility timeout.
+    #[clap(
+        long,
+        env = "PLUGIN_EXECUTOR_LEASE_EXTENSION_INTERVAL_MS",
+        default_value = "3000"
+    )]
+    pub lease_extension_interval_ms: u64,
 }
@@ -1,4 +1,10 @@
-use std::time::Duration;
+use std::{
+    collections::{
+        HashSet,
+        VecDeque,
+    },
+    time::Duration,
+};
 
 use clap::Parser;
 use rust_proto::{
@@ -6,13 +12,23 @@ use rust_proto::{
         build_grpc_client,
         services::PluginWorkQueueClientConfig,
     },
-    graplinc::grapl::api::plugin_work_queue::v1beta1::PluginWorkQueueServiceClient,
+    graplinc::grapl::api::{
+        plugin_registry::v1beta1::PluginType,
+        plugin_work_queue::v1beta1::{
+            AcquiredExecutionJob,
+            ExecutionLease,
+            ExtendLeaseRequest,
+            PluginWorkQueueServiceClient,
+        },
+    },
 };
+use uuid::Uuid;
 
 use crate::{
     config::PluginExecutorConfig,
     work::{
         PluginWorkProcessor,
+        ProcessedJob,
         Workload,
     },
 };
@@ -49,12 +65,52 @@ where
             .get_work(&self.config, &mut self.plugin_work_queue_client)
             .await
         {
-            let request_id = work.request_id();
-            if let Some(job) = work.maybe_job() {
+            let jobs = work.jobs();
+            if jobs.is_empty() {
+                let delay = Duration::from_secs(1);
+                tracing::warn!(
+                    message = "found no execution job",
+                    delay =? delay,
+                );
+                tokio::time::sleep(delay).await; // FIXME: backoff?
+                continue;
+            }
+
+            let plugin_id = self.config.plugin_id;
+            let mut processed_jobs: Vec<ProcessedJob<P::ProducedMessage>> =
+                Vec::with_capacity(jobs.len());
+            let mut lost_request_ids = HashSet::new();
+            let mut jobs = VecDeque::from(jobs);
+
+            // Ticks for as long as the batch is being worked through, so that
+            // jobs waiting their turn keep their leases too
+            let mut lease_extension = tokio::time::interval(Duration::from_millis(
+                self.config.lease_extension_interval_ms,
+            ));
+            lease_extension.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            lease_extension.tick().await; // The first tick completes immediately
+
+            while let Some(AcquiredExecutionJob {
+                execution_job: job,
+                request_id,
+                lease_id,
+            }) = jobs.pop_front()
+            {
                 let tenant_id = job.tenant_id();
                 let trace_id = job.trace_id();
                 let event_source_id = job.event_source_id();
-                let plugin_id = self.config.plugin_id;
+
+                if lost_request_ids.contains(&request_id) {
+                    tracing::warn!(
+                        message = "skipping execution job whose lease was lost",
+                        tenant_id =% tenant_id,
+                        trace_id =% trace_id,
+                        event_source_id =% event_source_id,
+                        plugin_id =% plugin_id,
+                        request_id =? request_id,
+                    );
+                    continue;
+                }
 
                 tracing::debug!(
                     message = "retrieved execution job",
@@ -65,11 +121,64 @@ where
                     request_id =? request_id,
                 );
 
-                // Process the job
-                let process_result = self
-                    .plugin_work_processor
-                    .process_job(&self.config, job)
-                    .await;
+                // The leases we still hold: this job's, those of the jobs
+                // waiting behind it, and those of the jobs already processed,
+                // which aren't acknowledged until the batch is done
+                let leases: Vec<ExecutionLease> = std::iter::once((request_id, lease_id))
+                    .chain(jobs.iter().map(|job| (job.request_id, job.lease_id)))
+                    .chain(
+                        processed_jobs
+                            .iter()
+                            .map(|job| (job.request_id, job.lease_id)),
+                    )
+                    .filter(|(request_id, _)| !lost_request_ids.contains(request_id))
+                    .map(|(request_id, lease_id)| ExecutionLease {
+                        request_id,
+                        lease_id,
+                    })
+                    .collect();
+
+                // Process the job, extending the leases until it's done. If
+                // this job's lease is lost, there's no point in finishing it.
+                let process_result = {
+                    let process = self.plugin_work_processor.process_job(&self.config, job);
+                    tokio::pin!(process);
+
+                    loop {
+                        tokio::select! {
+                            process_result = &mut process => break Some(process_result),
+                            _ = lease_extension.tick() => {
+                                lost_request_ids.extend(
+                                    extend_leases(
+                                        &mut self.plugin_work_queue_client,
+                                        P::PLUGIN_TYPE,
+                                        plugin_id,
+                                        leases.clone(),
+                                    )
+                                    .await,
+                                );
+                                if lost_request_ids.contains(&request_id) {
+                                    break None;
+                                }
+                            }
+                        }
+                    }
+                };
+
+                let process_result = match process_result {
+                    Some(process_result) => process_result,
+                    None => {
+                        tracing::warn!(
+                            message = "abandoning execution job whose lease was lost",
+                            tenant_id =% tenant_id,
+                            trace_id =% trace_id,
+                            event_source_id =% event_source_id,
+                            plugin_id =% plugin_id,
+                            request_id =? request_id,
+                        );
+                        continue;
+                    }
+                };
 
                 if let Err(e) = process_result.as_ref() {
                     tracing::error!(
@@ -94,7 +203,7 @@ where
 
                 let should_ack = match process_result.as_ref() {
                     // If it's retriable, just don't ack - PWQ will make the message
-                    // available again in 10 seconds.
+                    // available again once its visibility timeout passes.
                     Err(e) if e.is_retriable() => false,
                     // Otherwise, it's a perma-fail error or a success, so inform PWQ
                     Err(_) => true,
@@ -102,29 +211,69 @@ where
                 };
 
                 if should_ack {
-                    self.plugin_work_processor
-                        .ack_work(
-                            &self.config,
-                            &mut self.plugin_work_queue_client,
-                            process_result,
-                            request_id,
-                            tenant_id,
-                            trace_id,
-                            event_source_id,
-                        )
-                        .await?;
+                    processed_jobs.push(ProcessedJob {
+                        process_result,
+                        request_id,
+                        lease_id,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                    });
                 }
-            } else {
-                let delay = Duration::from_secs(1);
+            }
+
+            // A job whose lease was lost after it was processed has been
+            // handed to someone else, who will settle it
+            processed_jobs.retain(|job| !lost_request_ids.contains(&job.request_id));
+
+            // Settle the whole batch in one round trip
+            if !processed_jobs.is_empty() {
+                self.plugin_work_processor
+                    .ack_work(
+                        &self.config,
+                        &mut self.plugin_work_queue_client,
+                        processed_jobs,
+                    )
+                    .await?;
+            }
+        }
+        Err("Unable to get new work".into())
+    }
+}
+
+/// Extends `leases`, returning the request ids of those that were already
+/// lost. A failure to reach plugin-work-queue isn't fatal: the leases may yet
+/// be extended on the next attempt.
+async fn extend_leases(
+    pwq_client: &mut PluginWorkQueueServiceClient,
+    plugin_type: PluginType,
+    plugin_id: Uuid,
+    leases: Vec<ExecutionLease>,
+) -> Vec<i64> {
+    let request = ExtendLeaseRequest {
+        plugin_type,
+        plugin_id,
+        leases,
+    };
+
+    match pwq_client.extend_lease(request).await {
+        Ok(response) => {
+            if !response.lost_request_ids.is_empty() {
                 tracing::warn!(
-                    message = "found no execution job",
-                    request_id =% request_id,
-                    delay =? delay,
+                    message = "lost leases on execution jobs",
+                    plugin_id =% plugin_id,
+                    lost_request_ids =? response.lost_request_ids,
                 );
-                tokio::time::sleep(delay).await; // FIXME: backoff?
-                continue;
             }
+            response.lost_request_ids
+        }
+        Err(e) => {
+            tracing::error!(
+                message = "error extending leases on execution jobs",
+                plugin_id =% plugin_id,
+                error = ?e,
+            );
+            Vec::new()
         }
-        Err("Unable to get new work".into())
     }
 }
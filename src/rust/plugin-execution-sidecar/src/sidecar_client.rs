@@ -1 +1,9 @@
+pub(crate) mod analyzer_client;
 pub(crate) mod generator_client;
+
+fn get_plugin_upstream_address(plugin_id: uuid::Uuid) -> String {
+    let upstream_addr_env_var = format!("NOMAD_UPSTREAM_ADDR_plugin-{plugin_id}");
+    let upstream_addr = std::env::var(&upstream_addr_env_var).expect(&upstream_addr_env_var);
+    let address = format!("http://{upstream_addr}");
+    address
+}
@@ -0,0 +1,21 @@
+use rust_proto::{
+    client_factory::{
+        build_grpc_client,
+        services::AnalyzerClientConfig,
+    },
+    graplinc::grapl::api::plugin_sdk::analyzers::v1beta1::client::AnalyzerServiceClient,
+    protocol::service_client::ConnectError,
+};
+
+use super::get_plugin_upstream_address;
+
+/// Create a client from environment
+pub async fn get_analyzer_client(
+    plugin_id: uuid::Uuid,
+) -> Result<AnalyzerServiceClient, ConnectError> {
+    let address = get_plugin_upstream_address(plugin_id);
+    let client_config = AnalyzerClientConfig {
+        analyzer_client_address: address.parse().expect("analyzer_client_address"),
+    };
+    build_grpc_client(client_config).await
+}
@@ -7,12 +7,7 @@ use rust_proto::{
     protocol::service_client::ConnectError,
 };
 
-fn get_plugin_upstream_address(plugin_id: uuid::Uuid) -> String {
-    let upstream_addr_env_var = format!("NOMAD_UPSTREAM_ADDR_plugin-{plugin_id}");
-    let upstream_addr = std::env::var(&upstream_addr_env_var).expect(&upstream_addr_env_var);
-    let address = format!("http://{upstream_addr}");
-    address
-}
+use super::get_plugin_upstream_address;
 
 /// Create a client from environment
 pub async fn get_generator_client(
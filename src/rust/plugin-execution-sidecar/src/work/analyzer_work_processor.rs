@@ -0,0 +1,696 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use clap::Parser;
+use kafka::{
+    config::ProducerConfig,
+    Producer,
+};
+use rust_proto::{
+    client_factory::{
+        build_grpc_client,
+        services::GraphSchemaManagerClientConfig,
+    },
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                MergedGraph,
+                Property,
+            },
+            graph_schema_manager::v1beta1::{
+                client::{
+                    GraphSchemaManagerClient,
+                    GraphSchemaManagerClientError,
+                },
+                messages::GetEdgeSchemaRequest,
+            },
+            plugin_registry::v1beta1::PluginType,
+            plugin_sdk::analyzers::v1beta1::{
+                client::AnalyzerServiceClient,
+                messages::{
+                    EdgeUpdate,
+                    ExecutionHit,
+                    ExecutionResult,
+                    Int64PropertyUpdate,
+                    RunAnalyzerRequest,
+                    StringPropertyUpdate,
+                    UInt64PropertyUpdate,
+                    Update,
+                },
+            },
+            plugin_work_queue::v1beta1::{
+                AcknowledgeAnalyzerBatchRequest,
+                AcknowledgeAnalyzerRequest,
+                AcquiredExecutionJob,
+                ExecutionJob,
+                GetExecuteAnalyzerRequest,
+                GetExecuteAnalyzerResponse,
+                PluginWorkQueueServiceClient,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
+        pipeline::v1beta1::Envelope,
+    },
+    protocol::status::Code,
+    SerDe,
+};
+
+use super::{
+    plugin_work_processor::{
+        PluginWorkProcessorError,
+        ProcessedJob,
+        Workload,
+    },
+    PluginWorkProcessor,
+};
+use crate::{
+    config::PluginExecutorConfig,
+    sidecar_client::analyzer_client::get_analyzer_client,
+};
+
+impl Workload for GetExecuteAnalyzerResponse {
+    fn jobs(self) -> Vec<AcquiredExecutionJob> {
+        self.jobs()
+    }
+}
+
+/// The reverse edge names of the edges in a merged graph, keyed by the type of the node each edge
+/// starts from and the edge's name.
+type ReverseEdgeNames = HashMap<(String, String), EdgeName>;
+
+/// How long an edge the schema doesn't know stays unknown before it's looked up again, so that a
+/// newly deployed schema is picked up.
+const UNKNOWN_EDGE_TTL: Duration = Duration::from_secs(60);
+
+/// A reverse edge name from graph-schema-manager, or the absence of one
+struct CachedReverseEdgeName {
+    reverse_edge_name: Option<EdgeName>,
+    fetched_at: Instant,
+}
+
+impl CachedReverseEdgeName {
+    fn is_fresh(&self) -> bool {
+        self.reverse_edge_name.is_some() || self.fetched_at.elapsed() < UNKNOWN_EDGE_TTL
+    }
+}
+
+pub struct AnalyzerWorkProcessor {
+    analyzer_service_client: AnalyzerServiceClient,
+    graph_schema_manager_client: GraphSchemaManagerClient,
+    execution_hit_producer: Producer<ExecutionHit>,
+    reverse_edge_name_cache: HashMap<(uuid::Uuid, String, String), CachedReverseEdgeName>,
+}
+
+impl AnalyzerWorkProcessor {
+    pub async fn new(config: &PluginExecutorConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let analyzer_service_client = get_analyzer_client(config.plugin_id).await?;
+        let graph_schema_manager_client =
+            build_grpc_client(GraphSchemaManagerClientConfig::parse()).await?;
+        let execution_hit_producer = Producer::new(ProducerConfig::parse())?;
+        Ok(AnalyzerWorkProcessor {
+            analyzer_service_client,
+            graph_schema_manager_client,
+            execution_hit_producer,
+            reverse_edge_name_cache: HashMap::new(),
+        })
+    }
+
+    /// Looks up the reverse name of every edge in `merged_graph` in the tenant's schema.
+    ///
+    /// Graph schema manager stores both directions of an edge under the type of the node the
+    /// forward edge starts from, so a reverse edge is looked up under the type of the node it
+    /// points to. Edges the schema doesn't know are left out.
+    async fn resolve_reverse_edge_names(
+        &mut self,
+        tenant_id: uuid::Uuid,
+        merged_graph: &MergedGraph,
+    ) -> Result<ReverseEdgeNames, PluginWorkProcessorError> {
+        let mut reverse_edge_names = ReverseEdgeNames::new();
+
+        for edge in merged_graph
+            .edges
+            .values()
+            .flat_map(|edge_list| edge_list.edges.iter())
+        {
+            let (from_node, to_node) = match (
+                merged_graph.nodes.get(&edge.from_node_key),
+                merged_graph.nodes.get(&edge.to_node_key),
+            ) {
+                (Some(from_node), Some(to_node)) => (from_node, to_node),
+                _ => continue,
+            };
+
+            let key = (from_node.node_type.clone(), edge.edge_name.clone());
+            if reverse_edge_names.contains_key(&key) {
+                continue;
+            }
+
+            let mut reverse_edge_name = None;
+            for node_type in [&from_node.node_type, &to_node.node_type] {
+                reverse_edge_name = self
+                    .get_reverse_edge_name(tenant_id, node_type, &edge.edge_name)
+                    .await?;
+                if reverse_edge_name.is_some() {
+                    break;
+                }
+            }
+
+            match reverse_edge_name {
+                Some(reverse_edge_name) => {
+                    reverse_edge_names.insert(key, reverse_edge_name);
+                }
+                None => {
+                    tracing::debug!(
+                        message = "skipping edge that isn't in the schema",
+                        node_type = from_node.node_type.as_str(),
+                        edge_name = edge.edge_name.as_str(),
+                    );
+                }
+            }
+        }
+
+        Ok(reverse_edge_names)
+    }
+
+    async fn get_reverse_edge_name(
+        &mut self,
+        tenant_id: uuid::Uuid,
+        node_type: &str,
+        edge_name: &str,
+    ) -> Result<Option<EdgeName>, PluginWorkProcessorError> {
+        let cache_key = (tenant_id, node_type.to_owned(), edge_name.to_owned());
+        if let Some(cached) = self.reverse_edge_name_cache.get(&cache_key) {
+            if cached.is_fresh() {
+                return Ok(cached.reverse_edge_name.clone());
+            }
+        }
+
+        let edge_name = match EdgeName::try_from(edge_name) {
+            Ok(edge_name) => edge_name,
+            Err(_) => return Ok(None),
+        };
+
+        let response = self
+            .graph_schema_manager_client
+            .get_edge_schema(GetEdgeSchemaRequest {
+                tenant_id,
+                node_type: NodeType {
+                    value: node_type.to_owned(),
+                },
+                edge_name,
+            })
+            .await;
+
+        let reverse_edge_name = match response {
+            Ok(response) => Some(response.reverse_edge_name),
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                None
+            }
+            Err(e) => {
+                return Err(PluginWorkProcessorError::ProcessingJobFailedRetriable(
+                    e.to_string(),
+                ))
+            }
+        };
+
+        self.reverse_edge_name_cache.insert(
+            cache_key,
+            CachedReverseEdgeName {
+                reverse_edge_name: reverse_edge_name.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(reverse_edge_name)
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginWorkProcessor for AnalyzerWorkProcessor {
+    const PLUGIN_TYPE: PluginType = PluginType::Analyzer;
+
+    type Work = GetExecuteAnalyzerResponse;
+    type ProducedMessage = Vec<ExecutionHit>;
+
+    async fn get_work(
+        &self,
+        config: &PluginExecutorConfig,
+        pwq_client: &mut PluginWorkQueueServiceClient,
+    ) -> Result<Self::Work, PluginWorkProcessorError> {
+        let plugin_id = config.plugin_id;
+        let jobs = pwq_client
+            .get_execute_analyzer(GetExecuteAnalyzerRequest {
+                plugin_id,
+                max_jobs: config.max_jobs,
+            })
+            .await?
+            .jobs();
+
+        tracing::debug!(
+            message = "retrieved execution jobs",
+            plugin_id =% plugin_id,
+            count = jobs.len(),
+        );
+
+        Ok(GetExecuteAnalyzerResponse::new(jobs))
+    }
+
+    async fn ack_work(
+        &self,
+        config: &PluginExecutorConfig,
+        pwq_client: &mut PluginWorkQueueServiceClient,
+        processed_jobs: Vec<ProcessedJob<Self::ProducedMessage>>,
+    ) -> Result<(), PluginWorkProcessorError> {
+        let plugin_id = config.plugin_id;
+
+        let acknowledgements = processed_jobs
+            .into_iter()
+            .map(|processed_job| {
+                tracing::debug!(
+                    message = "acknowledging analyzer work",
+                    tenant_id =% processed_job.tenant_id,
+                    trace_id =% processed_job.trace_id,
+                    event_source_id =% processed_job.event_source_id,
+                    plugin_id =% plugin_id,
+                    request_id =? processed_job.request_id,
+                    execution_hits =? processed_job
+                        .process_result
+                        .as_ref()
+                        .map(|hits| hits.len())
+                        .ok(),
+                );
+
+                AcknowledgeAnalyzerRequest::new(
+                    processed_job.request_id,
+                    Some(processed_job.lease_id),
+                    processed_job.process_result.is_ok(),
+                    plugin_id,
+                    processed_job.tenant_id,
+                    processed_job.trace_id,
+                    processed_job.event_source_id,
+                )
+            })
+            .collect();
+
+        let rejected_request_ids = pwq_client
+            .acknowledge_analyzer_batch(AcknowledgeAnalyzerBatchRequest { acknowledgements })
+            .await?
+            .rejected_request_ids;
+
+        if !rejected_request_ids.is_empty() {
+            tracing::warn!(
+                message = "lease was lost before analyzer work was acknowledged",
+                plugin_id =% plugin_id,
+                rejected_request_ids =? rejected_request_ids,
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn process_job(
+        &mut self,
+        _config: &PluginExecutorConfig,
+        job: ExecutionJob,
+    ) -> Result<Self::ProducedMessage, PluginWorkProcessorError> {
+        let tenant_id = job.tenant_id();
+        let trace_id = job.trace_id();
+        let event_source_id = job.event_source_id();
+
+        let merged_graph = MergedGraph::deserialize(job.data())
+            .map_err(|e| PluginWorkProcessorError::ProcessingJobFailed(e.to_string()))?;
+
+        let reverse_edge_names = self
+            .resolve_reverse_edge_names(tenant_id, &merged_graph)
+            .await?;
+
+        let mut execution_hits = Vec::new();
+        for update in updates_from_merged_graph(&merged_graph, &reverse_edge_names) {
+            let run_analyzer_response = self
+                .analyzer_service_client
+                .run_analyzer(RunAnalyzerRequest { tenant_id, update })
+                .await?;
+
+            match run_analyzer_response.execution_result {
+                ExecutionResult::ExecutionHit(execution_hit) => execution_hits.push(execution_hit),
+                ExecutionResult::ExecutionMiss(_) => {}
+            }
+        }
+
+        for execution_hit in execution_hits.iter() {
+            // If publishing fails partway through, the job is retried and the hits that were
+            // already published are sent again. Consumers dedupe on the idempotency key.
+            self.execution_hit_producer
+                .send(Envelope::new(
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                    execution_hit.clone(),
+                ))
+                .await
+                .map_err(|e| {
+                    PluginWorkProcessorError::ProcessingJobFailedRetriable(e.to_string())
+                })?;
+        }
+
+        Ok(execution_hits)
+    }
+}
+
+/// Breaks a merged graph into the individual updates that an analyzer is run against: one for
+/// each node property, and one for each edge.
+///
+/// `MergedEdge`s don't carry their reverse edge name, so it's taken from `reverse_edge_names`.
+/// A merged graph usually holds both directions of an edge; only one of them is kept. Edges
+/// without a known reverse edge name are skipped.
+fn updates_from_merged_graph(
+    merged_graph: &MergedGraph,
+    reverse_edge_names: &ReverseEdgeNames,
+) -> Vec<Update> {
+    let mut updates = Vec::new();
+
+    for node in merged_graph.nodes.values() {
+        let uid = match Uid::from_u64(node.uid) {
+            Some(uid) => uid,
+            None => continue,
+        };
+
+        for (property_name, property) in node.properties.iter() {
+            let property_name = PropertyName::new_unchecked(property_name.clone());
+
+            let update = match &property.property {
+                Property::ImmutableStrProp(_) => {
+                    Update::StringProperty(StringPropertyUpdate { uid, property_name })
+                }
+                Property::IncrementOnlyUintProp(_)
+                | Property::DecrementOnlyUintProp(_)
+                | Property::ImmutableUintProp(_) => {
+                    Update::Uint64Property(UInt64PropertyUpdate { uid, property_name })
+                }
+                Property::IncrementOnlyIntProp(_)
+                | Property::DecrementOnlyIntProp(_)
+                | Property::ImmutableIntProp(_) => {
+                    Update::Int64Property(Int64PropertyUpdate { uid, property_name })
+                }
+            };
+            updates.push(update);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for edge in merged_graph
+        .edges
+        .values()
+        .flat_map(|edge_list| edge_list.edges.iter())
+    {
+        let (from_uid, to_uid) = match (
+            edge.from_uid.parse().ok().and_then(Uid::from_u64),
+            edge.to_uid.parse().ok().and_then(Uid::from_u64),
+        ) {
+            (Some(from_uid), Some(to_uid)) => (from_uid, to_uid),
+            _ => continue,
+        };
+
+        let from_node_type = match merged_graph.nodes.get(&edge.from_node_key) {
+            Some(from_node) => from_node.node_type.clone(),
+            None => continue,
+        };
+
+        let reverse_edge_name =
+            match reverse_edge_names.get(&(from_node_type, edge.edge_name.clone())) {
+                Some(reverse_edge_name) => reverse_edge_name.clone(),
+                None => continue,
+            };
+
+        let forward_edge_name = match EdgeName::try_from(edge.edge_name.as_str()) {
+            Ok(forward_edge_name) => forward_edge_name,
+            Err(_) => {
+                tracing::warn!(
+                    message = "skipping edge with an invalid name",
+                    edge_name = edge.edge_name.as_str(),
+                );
+                continue;
+            }
+        };
+
+        // the same edge seen from the other end has its names swapped
+        if seen.contains(&(to_uid, from_uid, reverse_edge_name.clone())) {
+            continue;
+        }
+        if !seen.insert((from_uid, to_uid, forward_edge_name.clone())) {
+            continue;
+        }
+
+        updates.push(Update::Edge(EdgeUpdate {
+            src_uid: from_uid,
+            dst_uid: to_uid,
+            forward_edge_name,
+            reverse_edge_name,
+        }));
+    }
+
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        ImmutableIntProp,
+        ImmutableStrProp,
+        ImmutableUintProp,
+        MergedEdge,
+        MergedEdgeList,
+        MergedNode,
+        NodeProperty,
+    };
+
+    use super::*;
+
+    fn add_node(
+        merged_graph: &mut MergedGraph,
+        node_key: &str,
+        node_type: &str,
+        uid: u64,
+        properties: Vec<(&str, Property)>,
+    ) {
+        merged_graph.nodes.insert(
+            node_key.to_owned(),
+            MergedNode {
+                properties: properties
+                    .into_iter()
+                    .map(|(name, property)| (name.to_owned(), NodeProperty { property }))
+                    .collect(),
+                uid,
+                node_key: node_key.to_owned(),
+                node_type: node_type.to_owned(),
+            },
+        );
+    }
+
+    fn add_edge(merged_graph: &mut MergedGraph, from: &str, to: &str, edge_name: &str) {
+        let from_uid = merged_graph.nodes[from].uid.to_string();
+        let to_uid = merged_graph.nodes[to].uid.to_string();
+        merged_graph
+            .edges
+            .entry(from.to_owned())
+            .or_insert_with(|| MergedEdgeList { edges: vec![] })
+            .edges
+            .push(MergedEdge {
+                from_uid,
+                from_node_key: from.to_owned(),
+                to_uid,
+                to_node_key: to.to_owned(),
+                edge_name: edge_name.to_owned(),
+            });
+    }
+
+    fn edge_update(src: u64, dst: u64, forward: &str, reverse: &str) -> Update {
+        Update::Edge(EdgeUpdate {
+            src_uid: Uid::from_u64(src).unwrap(),
+            dst_uid: Uid::from_u64(dst).unwrap(),
+            forward_edge_name: EdgeName::try_from(forward).unwrap(),
+            reverse_edge_name: EdgeName::try_from(reverse).unwrap(),
+        })
+    }
+
+    fn edge_updates(updates: &[Update]) -> Vec<&Update> {
+        updates
+            .iter()
+            .filter(|update| matches!(update, Update::Edge(_)))
+            .collect()
+    }
+
+    #[test]
+    fn properties_become_typed_updates() {
+        // Given a node with one property of each kind
+        let mut merged_graph = MergedGraph::new();
+        add_node(
+            &mut merged_graph,
+            "process-1",
+            "Process",
+            1,
+            vec![
+                (
+                    "process_name",
+                    Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: "cmd.exe".to_owned(),
+                    }),
+                ),
+                (
+                    "process_id",
+                    Property::ImmutableUintProp(ImmutableUintProp { prop: 4 }),
+                ),
+                (
+                    "exit_code",
+                    Property::ImmutableIntProp(ImmutableIntProp { prop: -1 }),
+                ),
+            ],
+        );
+
+        // When it is broken into updates
+        let updates: HashSet<_> =
+            updates_from_merged_graph(&merged_graph, &ReverseEdgeNames::new())
+                .into_iter()
+                .collect();
+
+        // Then each property gets an update of its own type
+        let uid = Uid::from_u64(1).unwrap();
+        let expected: HashSet<_> = [
+            Update::StringProperty(StringPropertyUpdate {
+                uid,
+                property_name: PropertyName::new_unchecked("process_name".to_owned()),
+            }),
+            Update::Uint64Property(UInt64PropertyUpdate {
+                uid,
+                property_name: PropertyName::new_unchecked("process_id".to_owned()),
+            }),
+            Update::Int64Property(Int64PropertyUpdate {
+                uid,
+                property_name: PropertyName::new_unchecked("exit_code".to_owned()),
+            }),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(updates, expected);
+    }
+
+    #[test]
+    fn edge_and_its_reverse_become_one_update() {
+        // Given an edge stored in both directions
+        let mut merged_graph = MergedGraph::new();
+        add_node(&mut merged_graph, "process-1", "Process", 1, vec![]);
+        add_node(&mut merged_graph, "file-2", "File", 2, vec![]);
+        add_edge(&mut merged_graph, "process-1", "file-2", "created_files");
+        add_edge(&mut merged_graph, "file-2", "process-1", "created_by");
+
+        let mut reverse_edge_names = ReverseEdgeNames::new();
+        reverse_edge_names.insert(
+            ("Process".to_owned(), "created_files".to_owned()),
+            EdgeName::try_from("created_by").unwrap(),
+        );
+        reverse_edge_names.insert(
+            ("File".to_owned(), "created_by".to_owned()),
+            EdgeName::try_from("created_files").unwrap(),
+        );
+
+        // When it is broken into updates
+        let updates = updates_from_merged_graph(&merged_graph, &reverse_edge_names);
+
+        // Then the edge is only sent once, in either direction
+        let edges = edge_updates(&updates);
+        assert_eq!(edges.len(), 1);
+        assert!(
+            edges[0] == &edge_update(1, 2, "created_files", "created_by")
+                || edges[0] == &edge_update(2, 1, "created_by", "created_files")
+        );
+    }
+
+    #[test]
+    fn reverse_edge_name_comes_from_the_schema() {
+        // Given two different edges between the same pair of nodes
+        let mut merged_graph = MergedGraph::new();
+        add_node(&mut merged_graph, "process-1", "Process", 1, vec![]);
+        add_node(&mut merged_graph, "process-2", "Process", 2, vec![]);
+        add_edge(&mut merged_graph, "process-1", "process-2", "children");
+        add_edge(&mut merged_graph, "process-1", "process-2", "injected_into");
+
+        let mut reverse_edge_names = ReverseEdgeNames::new();
+        reverse_edge_names.insert(
+            ("Process".to_owned(), "children".to_owned()),
+            EdgeName::try_from("parent").unwrap(),
+        );
+        reverse_edge_names.insert(
+            ("Process".to_owned(), "injected_into".to_owned()),
+            EdgeName::try_from("injected_by").unwrap(),
+        );
+
+        // When it is broken into updates
+        let updates = updates_from_merged_graph(&merged_graph, &reverse_edge_names);
+
+        // Then each edge is paired with its own reverse edge name
+        let edges: HashSet<_> = edge_updates(&updates).into_iter().cloned().collect();
+        let expected: HashSet<_> = [
+            edge_update(1, 2, "children", "parent"),
+            edge_update(1, 2, "injected_into", "injected_by"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn edge_without_a_reverse_edge_name_is_skipped() {
+        // Given an edge the schema doesn't know
+        let mut merged_graph = MergedGraph::new();
+        add_node(&mut merged_graph, "process-1", "Process", 1, vec![]);
+        add_node(&mut merged_graph, "file-2", "File", 2, vec![]);
+        add_edge(&mut merged_graph, "process-1", "file-2", "unknown_edge");
+
+        // When it is broken into updates
+        let updates = updates_from_merged_graph(&merged_graph, &ReverseEdgeNames::new());
+
+        // Then no edge update is produced
+        assert!(edge_updates(&updates).is_empty());
+    }
+
+    #[test]
+    fn unknown_edges_are_looked_up_again_once_stale() {
+        let long_ago = Instant::now()
+            .checked_sub(UNKNOWN_EDGE_TTL * 2)
+            .expect("the clock has been running for longer than the ttl");
+
+        // An edge the schema knows stays cached
+        let known = CachedReverseEdgeName {
+            reverse_edge_name: Some(EdgeName::try_from("parent").unwrap()),
+            fetched_at: long_ago,
+        };
+        assert!(known.is_fresh());
+
+        // while one it doesn't know is only cached for a while
+        let recently_unknown = CachedReverseEdgeName {
+            reverse_edge_name: None,
+            fetched_at: Instant::now(),
+        };
+        assert!(recently_unknown.is_fresh());
+
+        let long_unknown = CachedReverseEdgeName {
+            reverse_edge_name: None,
+            fetched_at: long_ago,
+        };
+        assert!(!long_unknown.is_fresh());
+    }
+}
@@ -1,23 +1,25 @@
 use rust_proto::graplinc::grapl::api::{
     graph::v1beta1::GraphDescription,
+    plugin_registry::v1beta1::PluginType,
     plugin_sdk::generators::v1beta1::{
         client::GeneratorServiceClient,
         RunGeneratorRequest,
     },
     plugin_work_queue::v1beta1::{
+        AcknowledgeGeneratorBatchRequest,
         AcknowledgeGeneratorRequest,
+        AcquiredExecutionJob,
         ExecutionJob,
         GetExecuteGeneratorRequest,
         GetExecuteGeneratorResponse,
         PluginWorkQueueServiceClient,
     },
 };
-use uuid::Uuid;
 
 use super::{
     plugin_work_processor::{
         PluginWorkProcessorError,
-        RequestId,
+        ProcessedJob,
         Workload,
     },
     PluginWorkProcessor,
@@ -28,12 +30,8 @@ use crate::{
 };
 
 impl Workload for GetExecuteGeneratorResponse {
-    fn request_id(&self) -> i64 {
-        self.request_id()
-    }
-
-    fn maybe_job(self) -> Option<ExecutionJob> {
-        self.execution_job()
+    fn jobs(self) -> Vec<AcquiredExecutionJob> {
+        self.jobs()
     }
 }
 
@@ -52,6 +50,8 @@ impl GeneratorWorkProcessor {
 
 #[async_trait::async_trait]
 impl PluginWorkProcessor for GeneratorWorkProcessor {
+    const PLUGIN_TYPE: PluginType = PluginType::Generator;
+
     type Work = GetExecuteGeneratorResponse;
     type ProducedMessage = GraphDescription;
 
@@ -61,67 +61,65 @@ impl PluginWorkProcessor for GeneratorWorkProcessor {
         pwq_client: &mut PluginWorkQueueServiceClient,
     ) -> Result<Self::Work, PluginWorkProcessorError> {
         let plugin_id = config.plugin_id;
-        let response = pwq_client
-            .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id))
-            .await?;
-        let request_id = response.request_id();
-
-        let response_retval = response.clone();
-
-        if let Some(execution_job) = response.execution_job() {
-            let tenant_id = execution_job.tenant_id();
-            let trace_id = execution_job.trace_id();
-            let event_source_id = execution_job.event_source_id();
+        let jobs = pwq_client
+            .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, config.max_jobs))
+            .await?
+            .jobs();
 
-            tracing::debug!(
-                message = "retrieved execution job",
-                tenant_id =% tenant_id,
-                trace_id =% trace_id,
-                event_source_id =% event_source_id,
-                plugin_id =% plugin_id,
-                request_id =? request_id,
-            );
-        } else {
-            tracing::debug!(
-                message = "found no execution jobs",
-                plugin_id =% plugin_id,
-            );
-        }
+        tracing::debug!(
+            message = "retrieved execution jobs",
+            plugin_id =% plugin_id,
+            count = jobs.len(),
+        );
 
-        Ok(response_retval)
+        Ok(GetExecuteGeneratorResponse::new(jobs))
     }
 
     async fn ack_work(
         &self,
         config: &PluginExecutorConfig,
         pwq_client: &mut PluginWorkQueueServiceClient,
-        process_result: Result<Self::ProducedMessage, PluginWorkProcessorError>,
-        request_id: RequestId,
-        tenant_id: Uuid,
-        trace_id: Uuid,
-        event_source_id: Uuid,
+        processed_jobs: Vec<ProcessedJob<Self::ProducedMessage>>,
     ) -> Result<(), PluginWorkProcessorError> {
         let plugin_id = config.plugin_id;
 
-        tracing::debug!(
-            message = "acknowledging generator work",
-            tenant_id =% tenant_id,
-            trace_id =% trace_id,
-            event_source_id =% event_source_id,
-            plugin_id =% plugin_id,
-            request_id =? request_id,
-        );
+        let acknowledgements = processed_jobs
+            .into_iter()
+            .map(|processed_job| {
+                tracing::debug!(
+                    message = "acknowledging generator work",
+                    tenant_id =% processed_job.tenant_id,
+                    trace_id =% processed_job.trace_id,
+                    event_source_id =% processed_job.event_source_id,
+                    plugin_id =% plugin_id,
+                    request_id =? processed_job.request_id,
+                );
+
+                AcknowledgeGeneratorRequest::new(
+                    processed_job.request_id,
+                    Some(processed_job.lease_id),
+                    processed_job.process_result.ok(),
+                    plugin_id,
+                    processed_job.tenant_id,
+                    processed_job.trace_id,
+                    processed_job.event_source_id,
+                )
+            })
+            .collect();
+
+        let rejected_request_ids = pwq_client
+            .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest { acknowledgements })
+            .await?
+            .rejected_request_ids;
+
+        if !rejected_request_ids.is_empty() {
+            tracing::warn!(
+                message = "lease was lost before generator work was acknowledged",
+                plugin_id =% plugin_id,
+                rejected_request_ids =? rejected_request_ids,
+            );
+        }
 
-        let graph_description = process_result.ok();
-        let ack_request = AcknowledgeGeneratorRequest::new(
-            request_id,
-            graph_description,
-            plugin_id,
-            tenant_id,
-            trace_id,
-            event_source_id,
-        );
-        pwq_client.acknowledge_generator(ack_request).await?;
         Ok(())
     }
 
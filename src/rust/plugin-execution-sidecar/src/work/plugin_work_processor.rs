@@ -1,10 +1,13 @@
 use rust_proto::{
-    graplinc::grapl::api::plugin_work_queue::v1beta1::{
-        ExecutionJob,
-        PluginWorkQueueServiceClient,
+    graplinc::grapl::api::{
+        plugin_registry::v1beta1::PluginType,
+        plugin_work_queue::v1beta1::{
+            AcquiredExecutionJob,
+            ExecutionJob,
+            PluginWorkQueueServiceClient,
+        },
     },
     protocol::error::GrpcClientError,
-    SerDe,
 };
 use uuid::Uuid;
 
@@ -34,14 +37,26 @@ impl PluginWorkProcessorError {
 
 // Abstract out between Get[Generator/Analyzer]ExecutionResponse,
 pub trait Workload {
-    fn request_id(&self) -> RequestId;
-    fn maybe_job(self) -> Option<ExecutionJob>;
+    fn jobs(self) -> Vec<AcquiredExecutionJob>;
+}
+
+/// The outcome of processing a job, to be acknowledged to plugin-work-queue
+pub struct ProcessedJob<M> {
+    pub process_result: Result<M, PluginWorkProcessorError>,
+    pub request_id: RequestId,
+    pub lease_id: Uuid,
+    pub tenant_id: Uuid,
+    pub trace_id: Uuid,
+    pub event_source_id: Uuid,
 }
 
 #[async_trait::async_trait]
 pub trait PluginWorkProcessor {
+    /// The kind of plugin whose work this processes
+    const PLUGIN_TYPE: PluginType;
+
     type Work: Workload;
-    type ProducedMessage: SerDe;
+    type ProducedMessage: Send;
 
     async fn get_work(
         &self,
@@ -55,14 +70,11 @@ pub trait PluginWorkProcessor {
         work: ExecutionJob,
     ) -> Result<Self::ProducedMessage, PluginWorkProcessorError>;
 
+    /// Acknowledges a batch of processed jobs in a single request
     async fn ack_work(
         &self,
         config: &PluginExecutorConfig,
         pwq_client: &mut PluginWorkQueueServiceClient,
-        process_result: Result<Self::ProducedMessage, PluginWorkProcessorError>,
-        request_id: RequestId,
-        tenant_id: Uuid,
-        trace_id: Uuid,
-        event_source_id: Uuid,
+        processed_jobs: Vec<ProcessedJob<Self::ProducedMessage>>,
     ) -> Result<(), PluginWorkProcessorError>;
 }
@@ -82,6 +82,9 @@ pub fn get_job(
                 // Passthrough vars
                 ("rust_log", passthru.rust_log),
                 ("observability_env_vars", passthru.observability_env_vars),
+                ("kafka_bootstrap_servers", passthru.kafka_bootstrap_servers),
+                ("kafka_sasl_username", passthru.kafka_sasl_username),
+                ("kafka_sasl_password", passthru.kafka_sasl_password),
             ]);
             cli.parse_hcl2(job_file_hcl, job_file_vars)
         }
@@ -104,6 +107,10 @@ pub fn get_job(
                 ("plugin_id", plugin.plugin_id.to_string()),
                 ("rootfs_artifact_url", service_config.rootfs_artifact_url),
                 ("tenant_id", plugin.tenant_id.to_string()),
+                // Passthrough vars
+                ("kafka_bootstrap_servers", passthru.kafka_bootstrap_servers),
+                ("kafka_sasl_username", passthru.kafka_sasl_username),
+                ("kafka_sasl_password", passthru.kafka_sasl_password),
             ]);
             cli.parse_hcl2(job_file_hcl, job_file_vars)
         }
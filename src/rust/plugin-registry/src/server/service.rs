@@ -127,6 +127,12 @@ pub struct PluginExecutionPassthroughVars {
     pub generator_sidecar_image: String,
     #[clap(long, env = "PLUGIN_EXECUTION_ANALYZER_SIDECAR_IMAGE")]
     pub analyzer_sidecar_image: String,
+    #[clap(long, env = "PLUGIN_EXECUTION_KAFKA_BOOTSTRAP_SERVERS")]
+    pub kafka_bootstrap_servers: String,
+    #[clap(long, env = "PLUGIN_EXECUTION_KAFKA_SASL_USERNAME")]
+    pub kafka_sasl_username: String,
+    #[clap(long, env = "PLUGIN_EXECUTION_KAFKA_SASL_PASSWORD")]
+    pub kafka_sasl_password: String,
 
     // Pass through a couple env vars also used for the plugin-registry service
     // Since they're used in both ways - locally for this service, and the
@@ -0,0 +1,85 @@
+/// This Analyzer shows a very basic way to build an Analyzer, and will also
+/// help inform Grapl Engineers of ways to simplify this API before we ship to
+/// customers.
+use analyzer_sdk::{
+    execution_hit::{
+        lens_ref,
+        ExecutionHitBuilder,
+    },
+    server::{
+        self,
+        AnalyzerServiceConfig,
+    },
+};
+use grapl_tracing::setup_tracing;
+use rust_proto::{
+    graplinc::grapl::api::{
+        graph_query_service::v1beta1::messages::GraphView,
+        plugin_sdk::analyzers::v1beta1::{
+            messages::{
+                ExecutionMiss,
+                ExecutionResult,
+                RunAnalyzerRequest,
+                RunAnalyzerResponse,
+                Update,
+            },
+            server::AnalyzerApi,
+        },
+    },
+    protocol::status::Status,
+};
+const SERVICE_NAME: &'static str = "example-analyzer";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = AnalyzerServiceConfig::from_env_vars();
+    let analyzer = ExampleAnalyzer {};
+    server::exec_service(analyzer, config).await
+}
+
+/// An example, silly error class
+#[derive(thiserror::Error, Debug)]
+pub enum ExampleAnalyzerError {
+    #[error("Unreachable")]
+    Unreachable,
+}
+
+impl From<ExampleAnalyzerError> for Status {
+    fn from(e: ExampleAnalyzerError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
+
+pub struct ExampleAnalyzer {}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for ExampleAnalyzer {
+    type Error = ExampleAnalyzerError;
+
+    /// Flags every node that has a `process_name` written to it. A real
+    /// analyzer would query graph-query-service here, using a GraphQuery built
+    /// with `analyzer_sdk::query::NodeQuery`.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let execution_result = match request.update {
+            Update::StringProperty(update) if update.property_name.value == "process_name" => {
+                let mut graph_view = GraphView::default();
+                graph_view.new_node(update.uid, "Process".try_into().unwrap());
+
+                ExecutionResult::ExecutionHit(
+                    ExecutionHitBuilder::new(SERVICE_NAME, graph_view)
+                        .with_lens_ref(lens_ref("asset", "example"))
+                        .with_score(1)
+                        .build(),
+                )
+            }
+            _ => ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+        };
+
+        Ok(RunAnalyzerResponse { execution_result })
+    }
+}
@@ -0,0 +1,161 @@
+use std::time::SystemTime;
+
+use blake2::{
+    digest::consts::U8,
+    Blake2b,
+    Digest,
+};
+use rust_proto::graplinc::grapl::api::{
+    graph_query_service::v1beta1::messages::GraphView,
+    plugin_sdk::analyzers::v1beta1::messages::{
+        AnalyzerName,
+        ExecutionHit,
+        LensRef,
+    },
+};
+
+/// Creates a reference to the lens that an `ExecutionHit` should be attached to.
+pub fn lens_ref(lens_namespace: impl Into<String>, lens_name: impl Into<String>) -> LensRef {
+    LensRef {
+        lens_namespace: lens_namespace.into(),
+        lens_name: lens_name.into(),
+    }
+}
+
+/// Builds an `ExecutionHit` for the graph an analyzer matched.
+pub struct ExecutionHitBuilder {
+    analyzer_name: AnalyzerName,
+    graph_view: GraphView,
+    lens_refs: Vec<LensRef>,
+    score: i32,
+    idempotency_key: Option<u64>,
+    time_of_match: Option<SystemTime>,
+}
+
+impl ExecutionHitBuilder {
+    pub fn new(analyzer_name: impl Into<String>, graph_view: GraphView) -> Self {
+        Self {
+            analyzer_name: AnalyzerName {
+                value: analyzer_name.into(),
+            },
+            graph_view,
+            lens_refs: Vec::new(),
+            score: 0,
+            idempotency_key: None,
+            time_of_match: None,
+        }
+    }
+
+    pub fn with_lens_ref(mut self, lens_ref: LensRef) -> Self {
+        self.lens_refs.push(lens_ref);
+        self
+    }
+
+    pub fn with_score(mut self, score: i32) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// Overrides the idempotency key, which otherwise is derived from the analyzer name and the
+    /// uids of the nodes in the matched graph.
+    pub fn with_idempotency_key(mut self, idempotency_key: u64) -> Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+    }
+
+    /// Overrides the time of the match, which otherwise is the time `build` is called.
+    pub fn with_time_of_match(mut self, time_of_match: SystemTime) -> Self {
+        self.time_of_match = Some(time_of_match);
+        self
+    }
+
+    pub fn build(self) -> ExecutionHit {
+        let idempotency_key = self
+            .idempotency_key
+            .unwrap_or_else(|| default_idempotency_key(&self.analyzer_name, &self.graph_view));
+
+        ExecutionHit {
+            graph_view: self.graph_view,
+            lens_refs: self.lens_refs,
+            analyzer_name: self.analyzer_name,
+            time_of_match: self.time_of_match.unwrap_or_else(SystemTime::now),
+            idempotency_key,
+            score: self.score,
+        }
+    }
+}
+
+type Blake2b8 = Blake2b<U8>;
+
+/// The same analyzer matching the same nodes produces the same key, so that retried executions
+/// can be deduplicated downstream. The key has to be stable across processes and releases, so
+/// it's a blake2 digest rather than anything built on `std::hash`.
+fn default_idempotency_key(analyzer_name: &AnalyzerName, graph_view: &GraphView) -> u64 {
+    let mut uids: Vec<_> = graph_view.get_nodes().keys().copied().collect();
+    uids.sort_unstable();
+
+    let mut hasher = Blake2b8::new();
+    hasher.update((analyzer_name.value.len() as u64).to_le_bytes());
+    hasher.update(analyzer_name.value.as_bytes());
+    for uid in uids {
+        hasher.update(uid.as_u64().to_le_bytes());
+    }
+    u64::from_le_bytes(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::common::v1beta1::types::Uid;
+
+    use super::*;
+
+    fn graph_view(uids: &[u64]) -> GraphView {
+        let mut graph_view = GraphView::default();
+        for uid in uids {
+            graph_view.new_node(Uid::from_u64(*uid).unwrap(), "Process".try_into().unwrap());
+        }
+        graph_view
+    }
+
+    #[test]
+    fn idempotency_key_is_stable() {
+        // The key is compared across processes and releases, so it must never change for the
+        // same input.
+        let execution_hit =
+            ExecutionHitBuilder::new("example-analyzer", graph_view(&[1, 2])).build();
+        assert_eq!(execution_hit.idempotency_key, 4081831365032064819);
+    }
+
+    #[test]
+    fn idempotency_key_ignores_node_order() {
+        let a = ExecutionHitBuilder::new("example-analyzer", graph_view(&[1, 2, 3])).build();
+        let b = ExecutionHitBuilder::new("example-analyzer", graph_view(&[3, 1, 2])).build();
+        assert_eq!(a.idempotency_key, b.idempotency_key);
+    }
+
+    #[test]
+    fn idempotency_key_depends_on_analyzer_and_nodes() {
+        let key = |analyzer_name: &str, uids: &[u64]| {
+            ExecutionHitBuilder::new(analyzer_name, graph_view(uids))
+                .build()
+                .idempotency_key
+        };
+
+        assert_ne!(
+            key("example-analyzer", &[1, 2]),
+            key("other-analyzer", &[1, 2])
+        );
+        assert_ne!(
+            key("example-analyzer", &[1, 2]),
+            key("example-analyzer", &[1, 3])
+        );
+    }
+
+    #[test]
+    fn idempotency_key_can_be_overridden() {
+        let execution_hit = ExecutionHitBuilder::new("example-analyzer", graph_view(&[1]))
+            .with_idempotency_key(42)
+            .build();
+        assert_eq!(execution_hit.idempotency_key, 42);
+    }
+}
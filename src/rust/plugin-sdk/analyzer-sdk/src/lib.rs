@@ -0,0 +1,8 @@
+pub mod execution_hit;
+pub mod query;
+pub mod server;
+
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils {
+    pub mod test_ctx;
+}
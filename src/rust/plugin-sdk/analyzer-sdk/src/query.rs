@@ -0,0 +1,178 @@
+use rust_proto::graplinc::grapl::{
+    api::graph_query_service::v1beta1::messages::{
+        AndIntFilters,
+        AndStringFilters,
+        GraphQuery,
+        IntFilter,
+        NodePropertyQuery,
+        OrIntFilters,
+        QueryId,
+        UidFilter,
+        UidOperation,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+        Uid,
+    },
+};
+
+/// Builds a `GraphQuery` out of a tree of nodes, starting from the root node.
+///
+/// Filters on a node are OR'd across calls, and AND'd within a single call:
+///
+/// ```rust,ignore
+/// let query = NodeQuery::new(process_type)
+///     .with_string_filters(process_name, vec![StringCmp::eq("svchost.exe", false)])
+///     .with_edge_to(
+///         children,
+///         parent,
+///         NodeQuery::new(process_type)
+///             .with_string_filters(process_name, vec![StringCmp::eq("cmd.exe", false)]),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeQuery {
+    query: NodePropertyQuery,
+    edges: Vec<(EdgeName, EdgeName, NodeQuery)>,
+}
+
+impl NodeQuery {
+    pub fn new(node_type: NodeType) -> Self {
+        Self {
+            query: NodePropertyQuery::new(node_type),
+            edges: Vec::new(),
+        }
+    }
+
+    /// The id of this node within the built `GraphQuery`
+    pub fn query_id(&self) -> QueryId {
+        self.query.query_id
+    }
+
+    /// Matches nodes where `property_name` matches all of the given filters.
+    pub fn with_string_filters(
+        mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndStringFilters>,
+    ) -> Self {
+        self.query.with_string_filters(property_name, filters);
+        self
+    }
+
+    /// Matches nodes where `property_name` matches all of the given filters.
+    pub fn with_int_filters(
+        mut self,
+        property_name: PropertyName,
+        filters: Vec<IntFilter>,
+    ) -> Self {
+        self.query
+            .int_filters
+            .entry(property_name)
+            .or_insert_with(|| OrIntFilters {
+                and_int_filters: Vec::with_capacity(1),
+            })
+            .and_int_filters
+            .push(AndIntFilters {
+                int_filters: filters,
+            });
+        self
+    }
+
+    /// Matches the node with the given uid.
+    pub fn with_uid(mut self, uid: Uid) -> Self {
+        self.query.uid_filters.uid_filters.push(UidFilter {
+            operation: UidOperation::Equal,
+            value: uid,
+        });
+        self
+    }
+
+    /// Matches nodes connected to a node matching `neighbor` over `edge_name`.
+    pub fn with_edge_to(
+        mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        neighbor: NodeQuery,
+    ) -> Self {
+        self.edges.push((edge_name, reverse_edge_name, neighbor));
+        self
+    }
+
+    /// Builds a `GraphQuery` with this node as the root.
+    pub fn build(self) -> GraphQuery {
+        let mut graph_query = GraphQuery {
+            root_query_id: self.query_id(),
+            node_property_queries: Default::default(),
+            edge_filters: Default::default(),
+            edge_map: Default::default(),
+            limits: Default::default(),
+        };
+
+        self.add_to(&mut graph_query);
+
+        graph_query
+    }
+
+    fn add_to(self, graph_query: &mut GraphQuery) {
+        let query_id = self.query_id();
+        graph_query.merge_node(self.query);
+
+        for (edge_name, reverse_edge_name, neighbor) in self.edges {
+            let neighbor_query_id = neighbor.query_id();
+
+            graph_query
+                .edge_filters
+                .entry((query_id, edge_name.clone()))
+                .or_insert_with(Default::default)
+                .insert(neighbor_query_id);
+            graph_query
+                .edge_filters
+                .entry((neighbor_query_id, reverse_edge_name.clone()))
+                .or_insert_with(Default::default)
+                .insert(query_id);
+            graph_query
+                .edge_map
+                .insert(edge_name.clone(), reverse_edge_name.clone());
+            graph_query.edge_map.insert(reverse_edge_name, edge_name);
+
+            neighbor.add_to(graph_query);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages::StringCmp;
+
+    use super::*;
+
+    #[test]
+    fn build_graph_query() -> Result<(), Box<dyn std::error::Error>> {
+        let process_type = NodeType::try_from("Process")?;
+        let process_name = PropertyName::try_from("process_name")?;
+        let children = EdgeName::try_from("children")?;
+        let parent = EdgeName::try_from("parent")?;
+
+        let child = NodeQuery::new(process_type.clone())
+            .with_string_filters(process_name.clone(), vec![StringCmp::eq("cmd.exe", false)]);
+        let child_id = child.query_id();
+
+        let root = NodeQuery::new(process_type)
+            .with_string_filters(process_name, vec![StringCmp::eq("svchost.exe", false)])
+            .with_edge_to(children.clone(), parent.clone(), child);
+        let root_id = root.query_id();
+
+        let graph_query = root.build();
+
+        assert_eq!(graph_query.root_query_id, root_id);
+        assert_eq!(graph_query.node_property_queries.len(), 2);
+        assert!(graph_query.edge_filters[&(root_id, children.clone())].contains(&child_id));
+        assert!(graph_query.edge_filters[&(child_id, parent.clone())].contains(&root_id));
+        assert_eq!(graph_query.edge_map[&children], parent);
+
+        Ok(())
+    }
+}
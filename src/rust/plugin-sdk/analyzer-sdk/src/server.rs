@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use clap::Parser;
+use rust_proto::{
+    graplinc::grapl::api::plugin_sdk::analyzers::v1beta1::server::{
+        AnalyzerApi,
+        AnalyzerServer,
+    },
+    protocol::healthcheck::HealthcheckStatus,
+};
+use tokio::net::TcpListener;
+
+#[derive(clap::Parser, Debug)]
+pub struct AnalyzerServiceConfig {
+    #[clap(long, env = "PLUGIN_BIND_ADDRESS")]
+    pub bind_address: std::net::SocketAddr,
+}
+impl AnalyzerServiceConfig {
+    /// An alias for clap::parse, so that consumers don't need to
+    /// declare a dependency on clap
+    pub fn from_env_vars() -> Self {
+        Self::parse()
+    }
+}
+
+pub async fn exec_service(
+    analyzer: impl AnalyzerApi + Send + Sync + 'static,
+    config: AnalyzerServiceConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let healthcheck_polling_interval_ms = 5000; // TODO: un-hardcode
+    let (server, _shutdown_tx) = AnalyzerServer::new(
+        analyzer,
+        TcpListener::bind(config.bind_address.clone()).await?,
+        || async { Ok(HealthcheckStatus::Serving) }, // FIXME: this is garbage
+        Duration::from_millis(healthcheck_polling_interval_ms),
+    );
+    tracing::info!(
+        message = "starting gRPC server",
+        socket_address = %config.bind_address,
+    );
+
+    Ok(server.serve().await?)
+}
@@ -0,0 +1,401 @@
+use std::collections::HashSet;
+
+use rust_proto::{
+    client_factory::{
+        build_grpc_client,
+        services::AnalyzerClientConfig,
+    },
+    graplinc::{
+        common::v1beta1::Duration,
+        grapl::{
+            api::{
+                graph_query_service::v1beta1::messages::{
+                    GraphView,
+                    IntPropertyValue,
+                },
+                plugin_sdk::analyzers::v1beta1::{
+                    client::AnalyzerServiceClient,
+                    messages::{
+                        EdgeUpdate,
+                        ExecutionHit,
+                        ExecutionResult,
+                        Int64PropertyUpdate,
+                        RunAnalyzerRequest,
+                        StringPropertyUpdate,
+                        UInt64PropertyUpdate,
+                        Update,
+                    },
+                    server::{
+                        AnalyzerApi,
+                        AnalyzerServer,
+                    },
+                },
+            },
+            common::v1beta1::types::{
+                EdgeName,
+                Uid,
+            },
+        },
+    },
+    protocol::{
+        error::{
+            GrpcClientError,
+            ServeError,
+        },
+        healthcheck::{
+            client::HealthcheckClient,
+            HealthcheckStatus,
+        },
+    },
+};
+use test_context::{
+    futures::channel::oneshot::Sender,
+    AsyncTestContext,
+};
+use tokio::{
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+// See generator-sdk's GeneratorTestContext for why the Internals are split out.
+struct AnalyzerTestContextInternals {
+    client: AnalyzerServiceClient,
+    server_handle: JoinHandle<Result<(), ServeError>>,
+    shutdown_tx: Sender<()>,
+}
+impl AnalyzerTestContextInternals {
+    async fn new(analyzer_api: impl AnalyzerApi + Send + Sync + 'static) -> Self {
+        // binding the tcp listener on port 0 tells the operating system to
+        // reserve an unused, ephemeral port
+        let tcp_listener = TcpListener::bind("0.0.0.0:0")
+            .await
+            .expect("failed to bind tcp listener");
+
+        // determine the actual port which was bound
+        let socket_address = tcp_listener
+            .local_addr()
+            .expect("failed to obtain socket address");
+
+        // construct an http URI clients can use to connect to server bound to
+        // the port.
+        let endpoint = format!("http://{}:{}", socket_address.ip(), socket_address.port());
+
+        let (server, shutdown_tx) = AnalyzerServer::new(
+            analyzer_api,
+            tcp_listener,
+            || async { Ok(HealthcheckStatus::Serving) },
+            Duration::from_millis(50),
+        );
+
+        let service_name = server.service_name();
+
+        let server_handle = tokio::task::spawn(server.serve());
+
+        HealthcheckClient::wait_until_healthy(
+            endpoint.clone(),
+            service_name,
+            Duration::from_millis(250),
+            Duration::from_millis(10),
+        )
+        .await
+        .expect("Analyzer never reported healthy");
+
+        let client_config = AnalyzerClientConfig {
+            analyzer_client_address: endpoint,
+        };
+        let client = build_grpc_client(client_config).await.unwrap();
+
+        AnalyzerTestContextInternals {
+            client,
+            server_handle,
+            shutdown_tx,
+        }
+    }
+
+    async fn teardown(self) {
+        self.shutdown_tx
+            .send(())
+            .expect("failed to shutdown server");
+        self.server_handle
+            .await
+            .expect("failed to join server task")
+            .expect("server configuration failed");
+    }
+}
+
+pub struct AnalyzerTestContext {
+    internals: Option<AnalyzerTestContextInternals>,
+}
+
+#[async_trait::async_trait]
+impl AsyncTestContext for AnalyzerTestContext {
+    async fn setup() -> Self {
+        Self { internals: None }
+    }
+    async fn teardown(self) {
+        if let Some(i) = self.internals {
+            i.teardown().await;
+        }
+    }
+}
+impl AnalyzerTestContext {
+    pub async fn get_client(
+        &mut self,
+        analyzer_api: impl AnalyzerApi + Send + Sync + 'static,
+    ) -> AnalyzerServiceClient {
+        if let None = self.internals {
+            self.internals = Some(AnalyzerTestContextInternals::new(analyzer_api).await)
+        }
+
+        self.internals.as_ref().expect("internals").client.clone()
+    }
+}
+
+/// Runs the analyzer against every update in a canned graph, as if each of its string
+/// properties and edges had just been written, and returns the hits.
+///
+/// An `EdgeUpdate` needs the reverse edge name, so each edge is paired with an edge going the
+/// other way between the same two nodes and sent once; edges without one aren't sent. When
+/// several edges run between the same two nodes, they're paired in order of their names.
+pub async fn run_against_graph(
+    client: &mut AnalyzerServiceClient,
+    tenant_id: uuid::Uuid,
+    graph: &GraphView,
+) -> Result<Vec<ExecutionHit>, GrpcClientError> {
+    let mut execution_hits = Vec::new();
+    for update in updates_from_graph(graph) {
+        let response = client
+            .run_analyzer(RunAnalyzerRequest { tenant_id, update })
+            .await?;
+
+        if let ExecutionResult::ExecutionHit(execution_hit) = response.execution_result {
+            execution_hits.push(execution_hit);
+        }
+    }
+
+    Ok(execution_hits)
+}
+
+fn updates_from_graph(graph: &GraphView) -> Vec<Update> {
+    let mut updates = Vec::new();
+
+    for (uid, node) in graph.get_nodes() {
+        for property_name in node.string_properties.prop_map.keys() {
+            updates.push(Update::StringProperty(StringPropertyUpdate {
+                uid: *uid,
+                property_name: property_name.clone(),
+            }));
+        }
+        for (property_name, value) in node.int_properties.prop_map.iter() {
+            updates.push(match value {
+                IntPropertyValue::I64(_) => Update::Int64Property(Int64PropertyUpdate {
+                    uid: *uid,
+                    property_name: property_name.clone(),
+                }),
+                IntPropertyValue::U64(_) => Update::Uint64Property(UInt64PropertyUpdate {
+                    uid: *uid,
+                    property_name: property_name.clone(),
+                }),
+            });
+        }
+    }
+
+    // sorted, so that the pairing doesn't depend on hash map order
+    let mut edges: Vec<(Uid, &EdgeName, Uid)> = graph
+        .edges
+        .iter()
+        .flat_map(|((src_uid, edge_name), dst_uids)| {
+            dst_uids
+                .iter()
+                .map(move |dst_uid| (*src_uid, edge_name, *dst_uid))
+        })
+        .collect();
+    edges.sort_by(|a, b| (a.0, &a.1.value, a.2).cmp(&(b.0, &b.1.value, b.2)));
+
+    let mut paired = HashSet::new();
+    for edge in edges.iter() {
+        if paired.contains(edge) {
+            continue;
+        }
+        let (src_uid, forward_edge_name, dst_uid) = *edge;
+
+        let reverse_edge = edges.iter().find(|reverse_edge| {
+            reverse_edge.0 == dst_uid
+                && reverse_edge.2 == src_uid
+                && *reverse_edge != edge
+                && !paired.contains(*reverse_edge)
+        });
+
+        if let Some(reverse_edge) = reverse_edge {
+            paired.insert(*edge);
+            paired.insert(*reverse_edge);
+
+            updates.push(Update::Edge(EdgeUpdate {
+                src_uid,
+                dst_uid,
+                forward_edge_name: forward_edge_name.clone(),
+                reverse_edge_name: reverse_edge.1.clone(),
+            }));
+        }
+    }
+
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use rust_proto::graplinc::grapl::api::plugin_sdk::analyzers::v1beta1::messages::{
+        ExecutionMiss,
+        RunAnalyzerResponse,
+    };
+    use test_context::test_context;
+
+    use super::*;
+    use crate::execution_hit::ExecutionHitBuilder;
+
+    fn uid(value: u64) -> Uid {
+        Uid::from_u64(value).unwrap()
+    }
+
+    fn edge_name(value: &'static str) -> EdgeName {
+        EdgeName::try_from(value).unwrap()
+    }
+
+    fn process_graph() -> GraphView {
+        let mut graph = GraphView::default();
+        graph
+            .new_node(uid(1), "Process".try_into().unwrap())
+            .add_string_property("process_name".try_into().unwrap(), "cmd.exe".to_owned());
+        graph.new_node(uid(2), "Process".try_into().unwrap());
+        graph.add_edge(uid(1), edge_name("children"), uid(2));
+        graph.add_edge(uid(2), edge_name("parent"), uid(1));
+        graph
+    }
+
+    fn edge_updates(updates: &[Update]) -> Vec<&EdgeUpdate> {
+        updates
+            .iter()
+            .filter_map(|update| match update {
+                Update::Edge(edge_update) => Some(edge_update),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edge_and_its_reverse_are_sent_once() {
+        let updates = updates_from_graph(&process_graph());
+
+        assert_eq!(
+            edge_updates(&updates),
+            vec![&EdgeUpdate {
+                src_uid: uid(1),
+                dst_uid: uid(2),
+                forward_edge_name: edge_name("children"),
+                reverse_edge_name: edge_name("parent"),
+            }]
+        );
+    }
+
+    #[test]
+    fn edges_between_the_same_nodes_are_paired_once_each() {
+        let mut graph = process_graph();
+        graph.add_edge(uid(1), edge_name("injected_into"), uid(2));
+        graph.add_edge(uid(2), edge_name("injected_by"), uid(1));
+
+        let updates = updates_from_graph(&graph);
+
+        // every edge shows up in exactly one update
+        let edges = edge_updates(&updates);
+        assert_eq!(edges.len(), 2);
+        let mut names: Vec<_> = edges
+            .iter()
+            .flat_map(|edge| {
+                [
+                    edge.forward_edge_name.value.as_str(),
+                    edge.reverse_edge_name.value.as_str(),
+                ]
+            })
+            .collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            vec!["children", "injected_by", "injected_into", "parent"]
+        );
+    }
+
+    #[test]
+    fn edge_without_a_reverse_is_not_sent() {
+        let mut graph = GraphView::default();
+        graph.new_node(uid(1), "Process".try_into().unwrap());
+        graph.new_node(uid(2), "File".try_into().unwrap());
+        graph.add_edge(uid(1), edge_name("created_files"), uid(2));
+
+        assert!(edge_updates(&updates_from_graph(&graph)).is_empty());
+    }
+
+    /// Records every update it's run against, and hits on edges.
+    #[derive(Default)]
+    struct RecordingAnalyzer {
+        updates: std::sync::Arc<Mutex<Vec<Update>>>,
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("unreachable")]
+    struct RecordingAnalyzerError;
+
+    impl From<RecordingAnalyzerError> for rust_proto::protocol::status::Status {
+        fn from(e: RecordingAnalyzerError) -> Self {
+            rust_proto::protocol::status::Status::unknown(e.to_string())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AnalyzerApi for RecordingAnalyzer {
+        type Error = RecordingAnalyzerError;
+
+        async fn run_analyzer(
+            &self,
+            request: RunAnalyzerRequest,
+        ) -> Result<RunAnalyzerResponse, Self::Error> {
+            self.updates.lock().unwrap().push(request.update.clone());
+
+            let execution_result = match request.update {
+                Update::Edge(edge_update) => {
+                    let mut graph_view = GraphView::default();
+                    graph_view.new_node(edge_update.src_uid, "Process".try_into().unwrap());
+                    graph_view.new_node(edge_update.dst_uid, "Process".try_into().unwrap());
+                    ExecutionResult::ExecutionHit(
+                        ExecutionHitBuilder::new("recording-analyzer", graph_view).build(),
+                    )
+                }
+                _ => ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+            };
+
+            Ok(RunAnalyzerResponse { execution_result })
+        }
+    }
+
+    #[test_context(AnalyzerTestContext)]
+    #[tokio::test]
+    async fn run_against_graph_sends_every_update(ctx: &mut AnalyzerTestContext) {
+        let analyzer = RecordingAnalyzer::default();
+        let recorded_updates = analyzer.updates.clone();
+        let mut client = ctx.get_client(analyzer).await;
+
+        let execution_hits = run_against_graph(&mut client, uuid::Uuid::new_v4(), &process_graph())
+            .await
+            .expect("run_against_graph");
+
+        // one string property and one edge
+        let recorded_updates = recorded_updates.lock().unwrap();
+        assert_eq!(recorded_updates.len(), 2);
+        assert_eq!(edge_updates(&recorded_updates).len(), 1);
+
+        // only the edge is a hit
+        assert_eq!(execution_hits.len(), 1);
+        assert_eq!(execution_hits[0].analyzer_name.value, "recording-analyzer");
+    }
+}
@@ -0,0 +1,45 @@
+-- Executions that used up all of their attempts are moved out of the
+-- (partitioned, short-lived) execution tables and into these tables, where
+-- they are kept until they're explicitly requeued.
+CREATE TABLE IF NOT EXISTS plugin_work_queue.generator_dead_letter_executions
+(
+    -- `execution_key` is the key the execution had in `generator_plugin_executions`
+    execution_key      bigint      NOT NULL PRIMARY KEY,
+    plugin_id          uuid        NOT NULL,
+    pipeline_message   bytea       NOT NULL,
+    tenant_id          uuid        NOT NULL,
+    trace_id           uuid        NOT NULL,
+    event_source_id    uuid        NOT NULL,
+    -- `attempts` is how many times the execution was handed out before it was dead-lettered
+    attempts           integer     NOT NULL,
+    -- `creation_time` is when the execution was originally enqueued
+    creation_time      timestamptz NOT NULL,
+    dead_lettered_time timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS plugin_work_queue.analyzer_dead_letter_executions
+(
+    -- `execution_key` is the key the execution had in `analyzer_plugin_executions`
+    execution_key      bigint      NOT NULL PRIMARY KEY,
+    plugin_id          uuid        NOT NULL,
+    pipeline_message   bytea       NOT NULL,
+    tenant_id          uuid        NOT NULL,
+    trace_id           uuid        NOT NULL,
+    event_source_id    uuid        NOT NULL,
+    -- `attempts` is how many times the execution was handed out before it was dead-lettered
+    attempts           integer     NOT NULL,
+    -- `creation_time` is when the execution was originally enqueued
+    creation_time      timestamptz NOT NULL,
+    dead_lettered_time timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS generator_dead_letter_plugin_id_ix ON plugin_work_queue.generator_dead_letter_executions (plugin_id);
+CREATE INDEX IF NOT EXISTS analyzer_dead_letter_plugin_id_ix ON plugin_work_queue.analyzer_dead_letter_executions (plugin_id);
+
+-- Per-plugin overrides of the service-wide maximum number of attempts
+CREATE TABLE IF NOT EXISTS plugin_work_queue.plugin_max_attempts
+(
+    plugin_id    uuid    NOT NULL PRIMARY KEY,
+    max_attempts integer NOT NULL,
+    CHECK (max_attempts > 0)
+);
@@ -0,0 +1,16 @@
+-- `lease_id` identifies the most recent hand-out of an execution. It's
+-- regenerated every time the execution is handed out, so a consumer whose
+-- visibility timeout ran out can no longer acknowledge or extend it.
+ALTER TABLE plugin_work_queue.generator_plugin_executions
+    ADD COLUMN IF NOT EXISTS lease_id uuid;
+
+ALTER TABLE plugin_work_queue.analyzer_plugin_executions
+    ADD COLUMN IF NOT EXISTS lease_id uuid;
+
+-- Per-plugin overrides of the service-wide visibility timeout
+CREATE TABLE IF NOT EXISTS plugin_work_queue.plugin_visibility_timeouts
+(
+    plugin_id                  uuid    NOT NULL PRIMARY KEY,
+    visibility_timeout_seconds integer NOT NULL,
+    CHECK (visibility_timeout_seconds > 0)
+);
@@ -0,0 +1,23 @@
+DO $$ BEGIN
+    IF to_regtype('plugin_work_queue.scheduling_mode') IS NULL THEN
+        CREATE TYPE plugin_work_queue.scheduling_mode AS ENUM ('oldest_first', 'round_robin');
+    END IF;
+END $$;
+
+-- Per-plugin overrides of the default scheduling, which hands out the oldest
+-- executions first regardless of tenant. Plugins without a row here use the
+-- default.
+CREATE TABLE IF NOT EXISTS plugin_work_queue.plugin_scheduling_policies
+(
+    plugin_id                uuid                              NOT NULL PRIMARY KEY,
+    scheduling_mode          plugin_work_queue.scheduling_mode NOT NULL,
+    -- `max_in_flight_per_tenant` caps how many of a single tenant's executions
+    -- may be handed out and not yet visible again. NULL means no cap.
+    max_in_flight_per_tenant integer,
+    CHECK (max_in_flight_per_tenant > 0)
+);
+
+-- Lets the scheduled dequeue read only the oldest few visible executions of
+-- each tenant, instead of ranking a plugin's whole backlog.
+CREATE INDEX IF NOT EXISTS generator_tenant_creation_time_ix ON plugin_work_queue.generator_plugin_executions (plugin_id, tenant_id, creation_time);
+CREATE INDEX IF NOT EXISTS analyzer_tenant_creation_time_ix ON plugin_work_queue.analyzer_plugin_executions (plugin_id, tenant_id, creation_time);
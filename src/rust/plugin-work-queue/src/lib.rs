@@ -22,6 +22,15 @@ pub struct PluginWorkQueueServiceConfig {
     pub plugin_work_queue_bind_address: SocketAddr,
     #[clap(long, env)]
     pub plugin_work_queue_healthcheck_polling_interval_ms: u64,
+    /// How many times an execution is handed out before it's dead-lettered.
+    /// Can be overridden per plugin with the SetMaxAttempts RPC.
+    #[clap(long, env)]
+    pub plugin_work_queue_max_attempts: u32,
+    /// How many seconds an execution is hidden from other consumers after it's
+    /// first handed out; later attempts back off exponentially from it.
+    /// Can be overridden per plugin with the SetVisibilityTimeout RPC.
+    #[clap(long, env)]
+    pub plugin_work_queue_visibility_timeout_seconds: u32,
 }
 
 #[derive(clap::Parser, Clone, Debug)]
@@ -1,4 +1,8 @@
 use bytes::Bytes;
+use chrono::{
+    DateTime,
+    Utc,
+};
 use grapl_config::PostgresClient;
 use sqlx::{
     Pool,
@@ -9,6 +13,11 @@ use uuid::Uuid;
 
 use crate::PluginWorkQueueDbConfig;
 
+/// The most executions a single dequeue moves into the dead-letter table.
+/// Any remaining exhausted executions are moved by later dequeues, so that a
+/// large backlog of them doesn't hold locks for one long transaction.
+const MAX_DEAD_LETTERED_PER_DEQUEUE: i64 = 1000;
+
 #[derive(Clone, Debug, Eq, PartialEq, sqlx::Type)]
 #[sqlx(type_name = "status", rename_all = "lowercase")]
 pub enum Status {
@@ -43,6 +52,62 @@ pub struct NextExecutionRequest {
     pub event_source_id: Uuid,
 }
 
+/// An execution that used up all of its attempts, as stored in one of the
+/// dead-letter tables. The payload is omitted; see `DeadLetteredMessage`.
+#[derive(Clone, Debug)]
+pub struct DeadLetteredExecution {
+    pub execution_key: ExecutionId,
+    pub plugin_id: Uuid,
+    pub tenant_id: Uuid,
+    pub trace_id: Uuid,
+    pub event_source_id: Uuid,
+    pub attempts: i32,
+    pub creation_time: DateTime<Utc>,
+    pub dead_lettered_time: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeadLetteredMessage {
+    pub execution: DeadLetteredExecution,
+    pub pipeline_message: Vec<u8>,
+}
+
+/// How many of a tenant's executions, created within the last day, are in each
+/// state. An execution is in flight if it has been handed out and neither
+/// acknowledged nor past its visibility timeout.
+#[derive(Clone, Debug)]
+pub struct TenantExecutionCounts {
+    pub tenant_id: Uuid,
+    pub enqueued: i64,
+    pub in_flight: i64,
+    pub failed: i64,
+    pub processed: i64,
+    pub oldest_enqueued_time: Option<DateTime<Utc>>,
+}
+
+/// How many of a tenant's enqueued and in-flight executions have been handed
+/// out `attempts` times
+#[derive(Clone, Debug)]
+pub struct TenantRetryCount {
+    pub tenant_id: Uuid,
+    pub attempts: i32,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct TenantDeadLetterCount {
+    pub tenant_id: Uuid,
+    pub count: i64,
+}
+
+/// The raw numbers behind a plugin's queue stats, broken down by tenant
+#[derive(Clone, Debug)]
+pub struct QueueStats {
+    pub execution_counts: Vec<TenantExecutionCounts>,
+    pub retry_counts: Vec<TenantRetryCount>,
+    pub dead_letter_counts: Vec<TenantDeadLetterCount>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum PsqlQueueError {
     #[error("Sqlx {0}")]
@@ -52,6 +117,45 @@ pub enum PsqlQueueError {
 #[derive(Clone, Debug)]
 pub struct Message {
     pub request: NextExecutionRequest,
+    pub lease_id: Uuid,
+}
+
+/// Identifies one hand-out of an execution. An execution can only be
+/// acknowledged or have its visibility timeout extended with the lease it was
+/// most recently handed out with. Consumers that predate leases acknowledge
+/// without a `lease_id`, which isn't checked against the current lease.
+#[derive(Clone, Copy, Debug)]
+pub struct Lease {
+    pub execution_key: ExecutionId,
+    pub lease_id: Option<Uuid>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "scheduling_mode", rename_all = "snake_case")]
+pub enum SchedulingMode {
+    /// Hand out the oldest executions first, whichever tenant they belong to
+    OldestFirst,
+    /// Take turns between tenants, handing out each tenant's oldest executions
+    /// first
+    RoundRobin,
+}
+
+/// How a plugin's executions are handed out. Plugins without a policy of
+/// their own are scheduled oldest first, without a cap.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SchedulingPolicy {
+    pub scheduling_mode: SchedulingMode,
+    /// How many of a single tenant's executions may be in flight at once
+    pub max_in_flight_per_tenant: Option<i32>,
+}
+
+impl Default for SchedulingPolicy {
+    fn default() -> Self {
+        Self {
+            scheduling_mode: SchedulingMode::OldestFirst,
+            max_in_flight_per_tenant: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -144,37 +248,135 @@ impl PsqlQueue {
     }
 
     #[instrument(skip(self), err)]
-    pub async fn get_generator_message(
+    pub async fn get_generator_messages(
         &self,
         plugin_id: Uuid,
-    ) -> Result<Option<Message>, PsqlQueueError> {
+        default_max_attempts: i32,
+        default_visibility_timeout: i32,
+        max_messages: i64,
+    ) -> Result<Vec<Message>, PsqlQueueError> {
         // This function does a few things
-        // 1. It attempts to get a message from the queue
+        // 1. It moves up to `MAX_DEAD_LETTERED_PER_DEQUEUE` executions that have
+        //    used up all of their attempts, and whose last attempt has timed
+        //    out, into the dead-letter table, skipping any locked by another
+        //    transaction
+        // 2. It attempts to get up to `max_messages` messages from the queue
         //      -> Where that message isn't over a day old
         //      -> Where that message is "visible"
         //      -> Where that message isn't currently being evaluated by another transaction
         //      -> Where that message is in the 'enqueued' state
-        // 2. Updates the `try_count`
-        // 3. Updates the `visible_after`
+        //      -> Where that message has attempts left
+        //      -> Where that message's tenant is under the plugin's in-flight cap, if any
+        //      -> Taking turns between tenants, if the plugin is scheduled round-robin
+        // 3. Updates the `try_count`
+        // 4. Updates the `visible_after`
+        // 5. Leases the messages out under a new `lease_id`
 
         // Note that:
-        // * messages are invisible for 10 seconds *after* each select
-        //      * The 10 second timeout is arbitrary but reasonable.
+        // * messages are invisible for the plugin's visibility timeout *after*
+        //   the first select, doubling with every further select up to 64
+        //   times that
+        //      * The lease can be extended while the message is being processed
         // * messages are immediately visible after their insert
         // * messages 'expire' after one day
-        // * messages currently do not have a maximum retry limit
         // * The one day expiration matches our 1 day partitioning strategy
+        // * Plugins with a scheduling policy are dequeued by one transaction at
+        //   a time, which keeps the in-flight caps from being overshot by
+        //   concurrent consumers. Plugins without one skip locked rows instead.
+        //      * Only each tenant's oldest `max_messages` visible messages are
+        //        ranked, so the cost grows with the number of tenants rather
+        //        than with the size of the backlog
+        //      * Candidates aren't locked, so the update rechecks that they're
+        //        still 'enqueued' and visible in case they were acknowledged
+        //        in the meantime
 
-        // In the future we can leverage a maximum retry limit as well as a batch version of this query
-        // A more dynamic visibility strategy would also be reasonable
-        let request: Option<NextExecutionRequest> = sqlx::query_as!(
-            NextExecutionRequest,
-            r#"
+        let mut transaction = self.pool.begin().await?;
+
+        let max_attempts = Self::max_attempts(&mut transaction, plugin_id)
+            .await?
+            .unwrap_or(default_max_attempts);
+        let visibility_timeout = Self::visibility_timeout(&mut transaction, plugin_id)
+            .await?
+            .unwrap_or(default_visibility_timeout);
+        let lease_id = Uuid::new_v4();
+        let scheduling_policy = Self::scheduling_policy(&mut transaction, plugin_id).await?;
+
+        let dead_lettered = sqlx::query!(
+            r"
+            WITH exhausted AS (
+                DELETE FROM plugin_work_queue.generator_plugin_executions
+                WHERE plugin_id = $1
+                  AND execution_key IN (
+                    SELECT execution_key
+                    FROM plugin_work_queue.generator_plugin_executions
+                    WHERE plugin_id = $1
+                      AND current_status = 'enqueued'
+                      AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                      AND visible_after <= CURRENT_TIMESTAMP
+                      AND try_count + 1 >= $2
+                    LIMIT $3
+                    FOR UPDATE SKIP LOCKED
+                  )
+                RETURNING
+                    execution_key,
+                    plugin_id,
+                    pipeline_message,
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                    try_count,
+                    creation_time
+            )
+            INSERT INTO plugin_work_queue.generator_dead_letter_executions (
+                execution_key,
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time
+            )
+            SELECT
+                execution_key,
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                try_count + 1,
+                creation_time
+            FROM exhausted
+        ",
+            plugin_id,
+            max_attempts,
+            MAX_DEAD_LETTERED_PER_DEQUEUE,
+        )
+        .execute(&mut transaction)
+        .await?
+        .rows_affected();
+
+        if dead_lettered > 0 {
+            tracing::warn!(
+                message = "dead-lettered generator executions",
+                plugin_id =% plugin_id,
+                count = dead_lettered,
+                max_attempts = max_attempts,
+            );
+        }
+
+        let requests: Vec<NextExecutionRequest> = match scheduling_policy {
+            None => sqlx::query_as!(
+                NextExecutionRequest,
+                r#"
             UPDATE plugin_work_queue.generator_plugin_executions
             SET
-                try_count  = try_count + 1,
+                try_count  = next_execution.try_count + 1,
                 last_updated = CURRENT_TIMESTAMP,
-                visible_after  = CURRENT_TIMESTAMP + INTERVAL '10 seconds'
+                visible_after  = CURRENT_TIMESTAMP
+                    + INTERVAL '1 second' * $4::integer
+                        * power(2, LEAST(next_execution.try_count + 1, 6)),
+                lease_id = $5
             FROM (
                  SELECT
                      execution_key,
@@ -185,15 +387,17 @@ impl PsqlQueue {
                      event_source_id,
                      current_status,
                      creation_time,
-                     visible_after
+                     visible_after,
+                     try_count
                  FROM plugin_work_queue.generator_plugin_executions
                  WHERE plugin_id = $1
                    AND current_status = 'enqueued'
                    AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
                    AND visible_after <= CURRENT_TIMESTAMP
+                   AND try_count + 1 < $2
                  ORDER BY creation_time ASC
                  FOR UPDATE SKIP LOCKED
-                 LIMIT 1
+                 LIMIT $3
              ) AS next_execution
              WHERE plugin_work_queue.generator_plugin_executions.execution_key = next_execution.execution_key
              RETURNING
@@ -204,46 +408,234 @@ impl PsqlQueue {
                  next_execution.trace_id,
                  next_execution.event_source_id
         "#,
-            plugin_id
-        )
-            .fetch_optional(&self.pool)
-            .await?;
+                plugin_id,
+                max_attempts,
+                max_messages,
+                visibility_timeout,
+                lease_id,
+            )
+            .fetch_all(&mut transaction)
+            .await?,
+            Some(scheduling_policy) => sqlx::query_as!(
+                NextExecutionRequest,
+                r#"
+                UPDATE plugin_work_queue.generator_plugin_executions AS execution
+                SET try_count = execution.try_count + 1,
+                    last_updated = CURRENT_TIMESTAMP,
+                    visible_after = CURRENT_TIMESTAMP
+                        + INTERVAL '1 second' * $4::integer
+                            * power(2, LEAST(execution.try_count + 1, 6)),
+                    lease_id = $5
+                FROM (
+                    SELECT candidate.execution_key
+                    FROM (
+                        SELECT
+                            oldest.execution_key,
+                            oldest.tenant_id,
+                            oldest.creation_time,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY oldest.tenant_id
+                                ORDER BY oldest.creation_time ASC
+                            ) AS tenant_rank
+                        FROM (
+                            SELECT DISTINCT tenant_id
+                            FROM plugin_work_queue.generator_plugin_executions
+                            WHERE plugin_id = $1
+                              AND current_status = 'enqueued'
+                              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                              AND visible_after <= CURRENT_TIMESTAMP
+                              AND try_count + 1 < $2
+                        ) AS tenant
+                        CROSS JOIN LATERAL (
+                            SELECT execution_key, tenant_id, creation_time
+                            FROM plugin_work_queue.generator_plugin_executions
+                            WHERE plugin_id = $1
+                              AND tenant_id = tenant.tenant_id
+                              AND current_status = 'enqueued'
+                              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                              AND visible_after <= CURRENT_TIMESTAMP
+                              AND try_count + 1 < $2
+                            ORDER BY creation_time ASC
+                            LIMIT $3
+                        ) AS oldest
+                    ) AS candidate
+                    LEFT JOIN (
+                        SELECT tenant_id, COUNT(*) AS in_flight
+                        FROM plugin_work_queue.generator_plugin_executions
+                        WHERE plugin_id = $1
+                          AND current_status = 'enqueued'
+                          AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                          AND visible_after > CURRENT_TIMESTAMP
+                        GROUP BY tenant_id
+                    ) AS tenant_in_flight ON tenant_in_flight.tenant_id = candidate.tenant_id
+                    WHERE $7::integer IS NULL
+                       OR candidate.tenant_rank + COALESCE(tenant_in_flight.in_flight, 0) <= $7::integer
+                    ORDER BY
+                        CASE WHEN $6::boolean THEN candidate.tenant_rank ELSE 1 END ASC,
+                        candidate.creation_time ASC
+                    LIMIT $3
+                ) AS next_execution
+                WHERE execution.execution_key = next_execution.execution_key
+                  AND execution.current_status = 'enqueued'
+                  AND execution.visible_after <= CURRENT_TIMESTAMP
+                RETURNING
+                    execution.execution_key AS "execution_key!: ExecutionId",
+                    execution.plugin_id,
+                    execution.pipeline_message,
+                    execution.tenant_id,
+                    execution.trace_id,
+                    execution.event_source_id
+            "#,
+                plugin_id,
+                max_attempts,
+                max_messages,
+                visibility_timeout,
+                lease_id,
+                scheduling_policy.scheduling_mode == SchedulingMode::RoundRobin,
+                scheduling_policy.max_in_flight_per_tenant,
+            )
+            .fetch_all(&mut transaction)
+            .await?,
+        };
+
+        transaction.commit().await?;
 
-        Ok(request.map(|request| Message { request }))
+        Ok(requests
+            .into_iter()
+            .map(|request| Message { request, lease_id })
+            .collect())
     }
 
     #[instrument(skip(self), err)]
-    pub async fn get_analyzer_message(
+    pub async fn get_analyzer_messages(
         &self,
         plugin_id: Uuid,
-    ) -> Result<Option<Message>, PsqlQueueError> {
+        default_max_attempts: i32,
+        default_visibility_timeout: i32,
+        max_messages: i64,
+    ) -> Result<Vec<Message>, PsqlQueueError> {
         // `get_message` does a few things
-        // 1. It attempts to get a message from the queue
+        // 1. It moves up to `MAX_DEAD_LETTERED_PER_DEQUEUE` executions that have
+        //    used up all of their attempts, and whose last attempt has timed
+        //    out, into the dead-letter table, skipping any locked by another
+        //    transaction
+        // 2. It attempts to get up to `max_messages` messages from the queue
         //      -> Where that message isn't over a day old
         //      -> Where that message is "visible"
         //      -> Where that message isn't currently being evaluated by another transaction
         //      -> Where that message is in the 'enqueued' state
-        // 2. Updates the `try_count`
-        // 3. Updates the `visible_after`
+        //      -> Where that message has attempts left
+        //      -> Where that message's tenant is under the plugin's in-flight cap, if any
+        //      -> Taking turns between tenants, if the plugin is scheduled round-robin
+        // 3. Updates the `try_count`
+        // 4. Updates the `visible_after`
+        // 5. Leases the messages out under a new `lease_id`
 
         // Note that:
-        // * messages are invisible for 10 seconds *after* each select
-        //      * The 10 second timeout is arbitrary but reasonable.
+        // * messages are invisible for the plugin's visibility timeout *after*
+        //   the first select, doubling with every further select up to 64
+        //   times that
+        //      * The lease can be extended while the message is being processed
         // * messages are immediately visible after their insert
         // * messages 'expire' after one day
-        // * messages currently do not have a maximum retry limit
         // * The one day expiration matches our 1 day partitioning strategy
+        // * Plugins with a scheduling policy are dequeued by one transaction at
+        //   a time, which keeps the in-flight caps from being overshot by
+        //   concurrent consumers. Plugins without one skip locked rows instead.
+        //      * Only each tenant's oldest `max_messages` visible messages are
+        //        ranked, so the cost grows with the number of tenants rather
+        //        than with the size of the backlog
+        //      * Candidates aren't locked, so the update rechecks that they're
+        //        still 'enqueued' and visible in case they were acknowledged
+        //        in the meantime
 
-        // In the future we can leverage a maximum retry limit as well as a batch version of this query
-        // A more dynamic visibility strategy would also be reasonable
-        let request: Option<NextExecutionRequest> = sqlx::query_as!(
-            NextExecutionRequest,
-            r#"
+        let mut transaction = self.pool.begin().await?;
+
+        let max_attempts = Self::max_attempts(&mut transaction, plugin_id)
+            .await?
+            .unwrap_or(default_max_attempts);
+        let visibility_timeout = Self::visibility_timeout(&mut transaction, plugin_id)
+            .await?
+            .unwrap_or(default_visibility_timeout);
+        let lease_id = Uuid::new_v4();
+        let scheduling_policy = Self::scheduling_policy(&mut transaction, plugin_id).await?;
+
+        let dead_lettered = sqlx::query!(
+            r"
+            WITH exhausted AS (
+                DELETE FROM plugin_work_queue.analyzer_plugin_executions
+                WHERE plugin_id = $1
+                  AND execution_key IN (
+                    SELECT execution_key
+                    FROM plugin_work_queue.analyzer_plugin_executions
+                    WHERE plugin_id = $1
+                      AND current_status = 'enqueued'
+                      AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                      AND visible_after <= CURRENT_TIMESTAMP
+                      AND try_count + 1 >= $2
+                    LIMIT $3
+                    FOR UPDATE SKIP LOCKED
+                  )
+                RETURNING
+                    execution_key,
+                    plugin_id,
+                    pipeline_message,
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                    try_count,
+                    creation_time
+            )
+            INSERT INTO plugin_work_queue.analyzer_dead_letter_executions (
+                execution_key,
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time
+            )
+            SELECT
+                execution_key,
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                try_count + 1,
+                creation_time
+            FROM exhausted
+        ",
+            plugin_id,
+            max_attempts,
+            MAX_DEAD_LETTERED_PER_DEQUEUE,
+        )
+        .execute(&mut transaction)
+        .await?
+        .rows_affected();
+
+        if dead_lettered > 0 {
+            tracing::warn!(
+                message = "dead-lettered analyzer executions",
+                plugin_id =% plugin_id,
+                count = dead_lettered,
+                max_attempts = max_attempts,
+            );
+        }
+
+        let requests: Vec<NextExecutionRequest> = match scheduling_policy {
+            None => sqlx::query_as!(
+                NextExecutionRequest,
+                r#"
             UPDATE plugin_work_queue.analyzer_plugin_executions
             SET
-                try_count  = plugin_work_queue.analyzer_plugin_executions.try_count + 1,
+                try_count  = next_execution.try_count + 1,
                 last_updated = CURRENT_TIMESTAMP,
-                visible_after  = CURRENT_TIMESTAMP + INTERVAL '10 seconds'
+                visible_after  = CURRENT_TIMESTAMP
+                    + INTERVAL '1 second' * $4::integer
+                        * power(2, LEAST(next_execution.try_count + 1, 6)),
+                lease_id = $5
             FROM (
                  SELECT
                      execution_key,
@@ -254,15 +646,17 @@ impl PsqlQueue {
                      event_source_id,
                      current_status,
                      creation_time,
-                     visible_after
+                     visible_after,
+                     try_count
                  FROM plugin_work_queue.analyzer_plugin_executions
                  WHERE plugin_id = $1
                    AND current_status = 'enqueued'
                    AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
                    AND visible_after <= CURRENT_TIMESTAMP
+                   AND try_count + 1 < $2
                  ORDER BY creation_time ASC
                  FOR UPDATE SKIP LOCKED
-                 LIMIT 1
+                 LIMIT $3
              ) AS next_execution
              WHERE plugin_work_queue.analyzer_plugin_executions.execution_key = next_execution.execution_key
              RETURNING
@@ -273,61 +667,1040 @@ impl PsqlQueue {
                  next_execution.trace_id,
                  next_execution.event_source_id
         "#,
-            plugin_id,
-        )
-            .fetch_optional(&self.pool)
-            .await?;
+                plugin_id,
+                max_attempts,
+                max_messages,
+                visibility_timeout,
+                lease_id,
+            )
+            .fetch_all(&mut transaction)
+            .await?,
+            Some(scheduling_policy) => sqlx::query_as!(
+                NextExecutionRequest,
+                r#"
+                UPDATE plugin_work_queue.analyzer_plugin_executions AS execution
+                SET try_count = execution.try_count + 1,
+                    last_updated = CURRENT_TIMESTAMP,
+                    visible_after = CURRENT_TIMESTAMP
+                        + INTERVAL '1 second' * $4::integer
+                            * power(2, LEAST(execution.try_count + 1, 6)),
+                    lease_id = $5
+                FROM (
+                    SELECT candidate.execution_key
+                    FROM (
+                        SELECT
+                            oldest.execution_key,
+                            oldest.tenant_id,
+                            oldest.creation_time,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY oldest.tenant_id
+                                ORDER BY oldest.creation_time ASC
+                            ) AS tenant_rank
+                        FROM (
+                            SELECT DISTINCT tenant_id
+                            FROM plugin_work_queue.analyzer_plugin_executions
+                            WHERE plugin_id = $1
+                              AND current_status = 'enqueued'
+                              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                              AND visible_after <= CURRENT_TIMESTAMP
+                              AND try_count + 1 < $2
+                        ) AS tenant
+                        CROSS JOIN LATERAL (
+                            SELECT execution_key, tenant_id, creation_time
+                            FROM plugin_work_queue.analyzer_plugin_executions
+                            WHERE plugin_id = $1
+                              AND tenant_id = tenant.tenant_id
+                              AND current_status = 'enqueued'
+                              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                              AND visible_after <= CURRENT_TIMESTAMP
+                              AND try_count + 1 < $2
+                            ORDER BY creation_time ASC
+                            LIMIT $3
+                        ) AS oldest
+                    ) AS candidate
+                    LEFT JOIN (
+                        SELECT tenant_id, COUNT(*) AS in_flight
+                        FROM plugin_work_queue.analyzer_plugin_executions
+                        WHERE plugin_id = $1
+                          AND current_status = 'enqueued'
+                          AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+                          AND visible_after > CURRENT_TIMESTAMP
+                        GROUP BY tenant_id
+                    ) AS tenant_in_flight ON tenant_in_flight.tenant_id = candidate.tenant_id
+                    WHERE $7::integer IS NULL
+                       OR candidate.tenant_rank + COALESCE(tenant_in_flight.in_flight, 0) <= $7::integer
+                    ORDER BY
+                        CASE WHEN $6::boolean THEN candidate.tenant_rank ELSE 1 END ASC,
+                        candidate.creation_time ASC
+                    LIMIT $3
+                ) AS next_execution
+                WHERE execution.execution_key = next_execution.execution_key
+                  AND execution.current_status = 'enqueued'
+                  AND execution.visible_after <= CURRENT_TIMESTAMP
+                RETURNING
+                    execution.execution_key AS "execution_key!: ExecutionId",
+                    execution.plugin_id,
+                    execution.pipeline_message,
+                    execution.tenant_id,
+                    execution.trace_id,
+                    execution.event_source_id
+            "#,
+                plugin_id,
+                max_attempts,
+                max_messages,
+                visibility_timeout,
+                lease_id,
+                scheduling_policy.scheduling_mode == SchedulingMode::RoundRobin,
+                scheduling_policy.max_in_flight_per_tenant,
+            )
+            .fetch_all(&mut transaction)
+            .await?,
+        };
+
+        transaction.commit().await?;
 
-        Ok(request.map(|request| Message { request }))
+        Ok(requests
+            .into_iter()
+            .map(|request| Message { request, lease_id })
+            .collect())
     }
 
+    /// Sets the status of an execution, provided `lease` is still current.
+    /// Returns whether it was. A failed execution is also copied to the
+    /// dead-letter table, since retrying a failure the plugin reported won't
+    /// change its outcome.
     #[instrument(skip(self), err)]
     pub async fn ack_generator(
         &self,
-        execution_key: ExecutionId,
+        lease: Lease,
         current_status: Status,
-    ) -> Result<(), PsqlQueueError> {
-        sqlx::query!(
+    ) -> Result<bool, PsqlQueueError> {
+        let acknowledged = sqlx::query!(
             r#"
-                UPDATE plugin_work_queue.generator_plugin_executions
-                SET current_status = $2,
-                    last_updated = CASE
-                        WHEN current_status != 'processed'
-                            THEN CURRENT_TIMESTAMP
-                            ELSE last_updated
-                        END
-                WHERE execution_key = $1
+                WITH acknowledged AS (
+                    UPDATE plugin_work_queue.generator_plugin_executions
+                    SET current_status = $3,
+                        last_updated = CURRENT_TIMESTAMP
+                    WHERE execution_key = $1
+                      AND ($2::uuid IS NULL OR lease_id = $2)
+                      AND current_status = 'enqueued'
+                    RETURNING
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count,
+                        creation_time,
+                        current_status
+                ),
+                dead_lettered AS (
+                    INSERT INTO plugin_work_queue.generator_dead_letter_executions (
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        attempts,
+                        creation_time
+                    )
+                    SELECT
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count + 1,
+                        creation_time
+                    FROM acknowledged
+                    WHERE current_status = 'failed'
+                )
+                SELECT execution_key FROM acknowledged
             "#,
-            execution_key.0,
+            lease.execution_key.0,
+            lease.lease_id,
             current_status as _,
         )
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
-        Ok(())
+
+        Ok(acknowledged.is_some())
+    }
+
+    /// Marks the `processed` executions as processed and the `failed` ones
+    /// as failed, in a single statement, copying the failed ones to the
+    /// dead-letter table. Executions whose lease is no longer current are left
+    /// alone; the keys of the others are returned.
+    #[instrument(skip(self, processed, failed), err)]
+    pub async fn ack_generator_batch(
+        &self,
+        processed: &[Lease],
+        failed: &[Lease],
+    ) -> Result<Vec<ExecutionId>, PsqlQueueError> {
+        let acknowledgements: Vec<(&Lease, bool)> = processed
+            .iter()
+            .map(|lease| (lease, true))
+            .chain(failed.iter().map(|lease| (lease, false)))
+            .collect();
+        let execution_keys: Vec<i64> = acknowledgements
+            .iter()
+            .map(|(lease, _)| lease.execution_key.0)
+            .collect();
+        let lease_ids: Vec<Option<Uuid>> = acknowledgements
+            .iter()
+            .map(|(lease, _)| lease.lease_id)
+            .collect();
+        let processed: Vec<bool> = acknowledgements
+            .iter()
+            .map(|(_, processed)| *processed)
+            .collect();
+
+        let acknowledged = sqlx::query!(
+            r#"
+                WITH acknowledged AS (
+                    UPDATE plugin_work_queue.generator_plugin_executions AS execution
+                    SET current_status = CASE
+                            WHEN acknowledgement.processed
+                                THEN 'processed'::plugin_work_queue.status
+                                ELSE 'failed'::plugin_work_queue.status
+                            END,
+                        last_updated = CURRENT_TIMESTAMP
+                    FROM UNNEST($1::bigint[], $2::uuid[], $3::boolean[])
+                        AS acknowledgement(execution_key, lease_id, processed)
+                    WHERE execution.execution_key = acknowledgement.execution_key
+                      AND (
+                          acknowledgement.lease_id IS NULL
+                          OR execution.lease_id = acknowledgement.lease_id
+                      )
+                      AND execution.current_status = 'enqueued'
+                    RETURNING
+                        execution.execution_key,
+                        execution.plugin_id,
+                        execution.pipeline_message,
+                        execution.tenant_id,
+                        execution.trace_id,
+                        execution.event_source_id,
+                        execution.try_count,
+                        execution.creation_time,
+                        execution.current_status
+                ),
+                dead_lettered AS (
+                    INSERT INTO plugin_work_queue.generator_dead_letter_executions (
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        attempts,
+                        creation_time
+                    )
+                    SELECT
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count + 1,
+                        creation_time
+                    FROM acknowledged
+                    WHERE current_status = 'failed'
+                )
+                SELECT execution_key AS "execution_key!: ExecutionId"
+                FROM acknowledged
+            "#,
+            &execution_keys[..],
+            &lease_ids[..] as _,
+            &processed[..],
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(acknowledged
+            .into_iter()
+            .map(|row| row.execution_key)
+            .collect())
+    }
+
+    /// Pushes back the visibility timeout of every execution in `leases`
+    /// whose lease is still current, by the visibility timeout of the plugin
+    /// it belongs to. Returns the keys of the extended executions.
+    #[instrument(skip(self, leases), err)]
+    pub async fn extend_generator_leases(
+        &self,
+        leases: &[Lease],
+        default_visibility_timeout: i32,
+    ) -> Result<Vec<ExecutionId>, PsqlQueueError> {
+        let execution_keys: Vec<i64> = leases.iter().map(|lease| lease.execution_key.0).collect();
+        let lease_ids: Vec<Option<Uuid>> = leases.iter().map(|lease| lease.lease_id).collect();
+
+        let extended = sqlx::query!(
+            r#"
+                UPDATE plugin_work_queue.generator_plugin_executions AS execution
+                SET visible_after = GREATEST(
+                        execution.visible_after,
+                        CURRENT_TIMESTAMP + INTERVAL '1 second' * COALESCE(
+                            (
+                                SELECT visibility_timeout_seconds
+                                FROM plugin_work_queue.plugin_visibility_timeouts
+                                WHERE plugin_id = execution.plugin_id
+                            ),
+                            $3::integer
+                        )
+                    ),
+                    last_updated = CURRENT_TIMESTAMP
+                FROM UNNEST($1::bigint[], $2::uuid[]) AS lease(execution_key, lease_id)
+                WHERE execution.execution_key = lease.execution_key
+                  AND (lease.lease_id IS NULL OR execution.lease_id = lease.lease_id)
+                  AND execution.current_status = 'enqueued'
+                RETURNING execution.execution_key AS "execution_key!: ExecutionId"
+            "#,
+            &execution_keys[..],
+            &lease_ids[..] as _,
+            default_visibility_timeout,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(extended.into_iter().map(|row| row.execution_key).collect())
     }
 
+    /// Sets the status of an execution, provided `lease` is still current.
+    /// Returns whether it was. A failed execution is also copied to the
+    /// dead-letter table, since retrying a failure the plugin reported won't
+    /// change its outcome.
     #[instrument(skip(self), err)]
     pub async fn ack_analyzer(
         &self,
-        execution_key: ExecutionId,
+        lease: Lease,
         current_status: Status,
-    ) -> Result<(), PsqlQueueError> {
-        sqlx::query!(
+    ) -> Result<bool, PsqlQueueError> {
+        let acknowledged = sqlx::query!(
             r#"
-                UPDATE plugin_work_queue.analyzer_plugin_executions
-                SET current_status = $2,
-                    last_updated = CASE
-                        WHEN current_status != 'processed'
-                            THEN CURRENT_TIMESTAMP
-                            ELSE last_updated
-                        END
-                WHERE execution_key = $1
+                WITH acknowledged AS (
+                    UPDATE plugin_work_queue.analyzer_plugin_executions
+                    SET current_status = $3,
+                        last_updated = CURRENT_TIMESTAMP
+                    WHERE execution_key = $1
+                      AND ($2::uuid IS NULL OR lease_id = $2)
+                      AND current_status = 'enqueued'
+                    RETURNING
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count,
+                        creation_time,
+                        current_status
+                ),
+                dead_lettered AS (
+                    INSERT INTO plugin_work_queue.analyzer_dead_letter_executions (
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        attempts,
+                        creation_time
+                    )
+                    SELECT
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count + 1,
+                        creation_time
+                    FROM acknowledged
+                    WHERE current_status = 'failed'
+                )
+                SELECT execution_key FROM acknowledged
             "#,
-            execution_key.0,
+            lease.execution_key.0,
+            lease.lease_id,
             current_status as _,
         )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(acknowledged.is_some())
+    }
+
+    /// Marks the `processed` executions as processed and the `failed` ones
+    /// as failed, in a single statement, copying the failed ones to the
+    /// dead-letter table. Executions whose lease is no longer current are left
+    /// alone; the keys of the others are returned.
+    #[instrument(skip(self, processed, failed), err)]
+    pub async fn ack_analyzer_batch(
+        &self,
+        processed: &[Lease],
+        failed: &[Lease],
+    ) -> Result<Vec<ExecutionId>, PsqlQueueError> {
+        let acknowledgements: Vec<(&Lease, bool)> = processed
+            .iter()
+            .map(|lease| (lease, true))
+            .chain(failed.iter().map(|lease| (lease, false)))
+            .collect();
+        let execution_keys: Vec<i64> = acknowledgements
+            .iter()
+            .map(|(lease, _)| lease.execution_key.0)
+            .collect();
+        let lease_ids: Vec<Option<Uuid>> = acknowledgements
+            .iter()
+            .map(|(lease, _)| lease.lease_id)
+            .collect();
+        let processed: Vec<bool> = acknowledgements
+            .iter()
+            .map(|(_, processed)| *processed)
+            .collect();
+
+        let acknowledged = sqlx::query!(
+            r#"
+                WITH acknowledged AS (
+                    UPDATE plugin_work_queue.analyzer_plugin_executions AS execution
+                    SET current_status = CASE
+                            WHEN acknowledgement.processed
+                                THEN 'processed'::plugin_work_queue.status
+                                ELSE 'failed'::plugin_work_queue.status
+                            END,
+                        last_updated = CURRENT_TIMESTAMP
+                    FROM UNNEST($1::bigint[], $2::uuid[], $3::boolean[])
+                        AS acknowledgement(execution_key, lease_id, processed)
+                    WHERE execution.execution_key = acknowledgement.execution_key
+                      AND (
+                          acknowledgement.lease_id IS NULL
+                          OR execution.lease_id = acknowledgement.lease_id
+                      )
+                      AND execution.current_status = 'enqueued'
+                    RETURNING
+                        execution.execution_key,
+                        execution.plugin_id,
+                        execution.pipeline_message,
+                        execution.tenant_id,
+                        execution.trace_id,
+                        execution.event_source_id,
+                        execution.try_count,
+                        execution.creation_time,
+                        execution.current_status
+                ),
+                dead_lettered AS (
+                    INSERT INTO plugin_work_queue.analyzer_dead_letter_executions (
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        attempts,
+                        creation_time
+                    )
+                    SELECT
+                        execution_key,
+                        plugin_id,
+                        pipeline_message,
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        try_count + 1,
+                        creation_time
+                    FROM acknowledged
+                    WHERE current_status = 'failed'
+                )
+                SELECT execution_key AS "execution_key!: ExecutionId"
+                FROM acknowledged
+            "#,
+            &execution_keys[..],
+            &lease_ids[..] as _,
+            &processed[..],
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(acknowledged
+            .into_iter()
+            .map(|row| row.execution_key)
+            .collect())
+    }
+
+    /// Pushes back the visibility timeout of every execution in `leases`
+    /// whose lease is still current, by the visibility timeout of the plugin
+    /// it belongs to. Returns the keys of the extended executions.
+    #[instrument(skip(self, leases), err)]
+    pub async fn extend_analyzer_leases(
+        &self,
+        leases: &[Lease],
+        default_visibility_timeout: i32,
+    ) -> Result<Vec<ExecutionId>, PsqlQueueError> {
+        let execution_keys: Vec<i64> = leases.iter().map(|lease| lease.execution_key.0).collect();
+        let lease_ids: Vec<Option<Uuid>> = leases.iter().map(|lease| lease.lease_id).collect();
+
+        let extended = sqlx::query!(
+            r#"
+                UPDATE plugin_work_queue.analyzer_plugin_executions AS execution
+                SET visible_after = GREATEST(
+                        execution.visible_after,
+                        CURRENT_TIMESTAMP + INTERVAL '1 second' * COALESCE(
+                            (
+                                SELECT visibility_timeout_seconds
+                                FROM plugin_work_queue.plugin_visibility_timeouts
+                                WHERE plugin_id = execution.plugin_id
+                            ),
+                            $3::integer
+                        )
+                    ),
+                    last_updated = CURRENT_TIMESTAMP
+                FROM UNNEST($1::bigint[], $2::uuid[]) AS lease(execution_key, lease_id)
+                WHERE execution.execution_key = lease.execution_key
+                  AND (lease.lease_id IS NULL OR execution.lease_id = lease.lease_id)
+                  AND execution.current_status = 'enqueued'
+                RETURNING execution.execution_key AS "execution_key!: ExecutionId"
+            "#,
+            &execution_keys[..],
+            &lease_ids[..] as _,
+            default_visibility_timeout,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(extended.into_iter().map(|row| row.execution_key).collect())
+    }
+
+    /// Overrides the maximum number of attempts for `plugin_id`'s executions.
+    #[instrument(skip(self), err)]
+    pub async fn set_max_attempts(
+        &self,
+        plugin_id: Uuid,
+        max_attempts: i32,
+    ) -> Result<(), PsqlQueueError> {
+        sqlx::query!(
+            r"
+            INSERT INTO plugin_work_queue.plugin_max_attempts (plugin_id, max_attempts)
+            VALUES ($1, $2)
+            ON CONFLICT (plugin_id) DO UPDATE
+            SET max_attempts = excluded.max_attempts
+        ",
+            plugin_id,
+            max_attempts,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Overrides the visibility timeout, in seconds, for `plugin_id`'s
+    /// executions.
+    #[instrument(skip(self), err)]
+    pub async fn set_visibility_timeout(
+        &self,
+        plugin_id: Uuid,
+        visibility_timeout: i32,
+    ) -> Result<(), PsqlQueueError> {
+        sqlx::query!(
+            r"
+            INSERT INTO plugin_work_queue.plugin_visibility_timeouts (plugin_id, visibility_timeout_seconds)
+            VALUES ($1, $2)
+            ON CONFLICT (plugin_id) DO UPDATE
+            SET visibility_timeout_seconds = excluded.visibility_timeout_seconds
+        ",
+            plugin_id,
+            visibility_timeout,
+        )
         .execute(&self.pool)
         .await?;
         Ok(())
     }
+
+    /// Sets how `plugin_id`'s executions are handed out. Setting the default
+    /// policy removes the plugin's own.
+    #[instrument(skip(self), err)]
+    pub async fn set_scheduling_policy(
+        &self,
+        plugin_id: Uuid,
+        scheduling_policy: SchedulingPolicy,
+    ) -> Result<(), PsqlQueueError> {
+        if scheduling_policy == SchedulingPolicy::default() {
+            sqlx::query!(
+                r"
+                DELETE FROM plugin_work_queue.plugin_scheduling_policies
+                WHERE plugin_id = $1
+            ",
+                plugin_id,
+            )
+            .execute(&self.pool)
+            .await?;
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r"
+            INSERT INTO plugin_work_queue.plugin_scheduling_policies (plugin_id, scheduling_mode, max_in_flight_per_tenant)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (plugin_id) DO UPDATE
+            SET scheduling_mode = excluded.scheduling_mode,
+                max_in_flight_per_tenant = excluded.max_in_flight_per_tenant
+        ",
+            plugin_id,
+            scheduling_policy.scheduling_mode as _,
+            scheduling_policy.max_in_flight_per_tenant,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Looks up `plugin_id`'s scheduling policy, locking it until the
+    /// transaction ends
+    async fn scheduling_policy(
+        transaction: &mut sqlx::Transaction<'_, Postgres>,
+        plugin_id: Uuid,
+    ) -> Result<Option<SchedulingPolicy>, PsqlQueueError> {
+        let scheduling_policy = sqlx::query_as!(
+            SchedulingPolicy,
+            r#"
+            SELECT
+                scheduling_mode AS "scheduling_mode: SchedulingMode",
+                max_in_flight_per_tenant
+            FROM plugin_work_queue.plugin_scheduling_policies
+            WHERE plugin_id = $1
+            FOR UPDATE
+        "#,
+            plugin_id,
+        )
+        .fetch_optional(transaction)
+        .await?;
+
+        Ok(scheduling_policy)
+    }
+
+    async fn visibility_timeout(
+        transaction: &mut sqlx::Transaction<'_, Postgres>,
+        plugin_id: Uuid,
+    ) -> Result<Option<i32>, PsqlQueueError> {
+        let visibility_timeout = sqlx::query!(
+            r"
+            SELECT visibility_timeout_seconds
+            FROM plugin_work_queue.plugin_visibility_timeouts
+            WHERE plugin_id = $1
+        ",
+            plugin_id,
+        )
+        .fetch_optional(transaction)
+        .await?;
+
+        Ok(visibility_timeout.map(|row| row.visibility_timeout_seconds))
+    }
+
+    async fn max_attempts(
+        transaction: &mut sqlx::Transaction<'_, Postgres>,
+        plugin_id: Uuid,
+    ) -> Result<Option<i32>, PsqlQueueError> {
+        let max_attempts = sqlx::query!(
+            r"
+            SELECT max_attempts
+            FROM plugin_work_queue.plugin_max_attempts
+            WHERE plugin_id = $1
+        ",
+            plugin_id,
+        )
+        .fetch_optional(transaction)
+        .await?;
+
+        Ok(max_attempts.map(|row| row.max_attempts))
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn list_generator_dead_letters(
+        &self,
+        plugin_id: Uuid,
+    ) -> Result<Vec<DeadLetteredExecution>, PsqlQueueError> {
+        let executions = sqlx::query_as!(
+            DeadLetteredExecution,
+            r#"
+            SELECT
+                execution_key AS "execution_key!: ExecutionId",
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time,
+                dead_lettered_time
+            FROM plugin_work_queue.generator_dead_letter_executions
+            WHERE plugin_id = $1
+            ORDER BY dead_lettered_time ASC
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(executions)
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn get_generator_dead_letter(
+        &self,
+        plugin_id: Uuid,
+        execution_key: ExecutionId,
+    ) -> Result<Option<DeadLetteredMessage>, PsqlQueueError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                execution_key AS "execution_key!: ExecutionId",
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time,
+                dead_lettered_time
+            FROM plugin_work_queue.generator_dead_letter_executions
+            WHERE plugin_id = $1
+              AND execution_key = $2
+            "#,
+            plugin_id,
+            execution_key.0,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| DeadLetteredMessage {
+            execution: DeadLetteredExecution {
+                execution_key: row.execution_key,
+                plugin_id: row.plugin_id,
+                tenant_id: row.tenant_id,
+                trace_id: row.trace_id,
+                event_source_id: row.event_source_id,
+                attempts: row.attempts,
+                creation_time: row.creation_time,
+                dead_lettered_time: row.dead_lettered_time,
+            },
+            pipeline_message: row.pipeline_message,
+        }))
+    }
+
+    /// Moves a dead-lettered execution back onto the queue as a new execution,
+    /// with a fresh set of attempts. Returns the new execution's key, or
+    /// `None` if there was no such dead-lettered execution.
+    #[instrument(skip(self), err)]
+    pub async fn requeue_generator_dead_letter(
+        &self,
+        plugin_id: Uuid,
+        execution_key: ExecutionId,
+    ) -> Result<Option<ExecutionId>, PsqlQueueError> {
+        let requeued = sqlx::query!(
+            r#"
+            WITH requeued AS (
+                DELETE FROM plugin_work_queue.generator_dead_letter_executions
+                WHERE plugin_id = $1
+                  AND execution_key = $2
+                RETURNING
+                    plugin_id,
+                    pipeline_message,
+                    tenant_id,
+                    trace_id,
+                    event_source_id
+            )
+            INSERT INTO plugin_work_queue.generator_plugin_executions (
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                current_status,
+                try_count
+            )
+            SELECT
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                'enqueued',
+                -1
+            FROM requeued
+            RETURNING execution_key AS "execution_key!: ExecutionId"
+            "#,
+            plugin_id,
+            execution_key.0,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(requeued.map(|row| row.execution_key))
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn generator_queue_stats(
+        &self,
+        plugin_id: Uuid,
+    ) -> Result<QueueStats, PsqlQueueError> {
+        let execution_counts = sqlx::query_as!(
+            TenantExecutionCounts,
+            r#"
+            SELECT
+                tenant_id,
+                COUNT(*) FILTER (
+                    WHERE current_status = 'enqueued' AND visible_after <= CURRENT_TIMESTAMP
+                ) AS "enqueued!",
+                COUNT(*) FILTER (
+                    WHERE current_status = 'enqueued' AND visible_after > CURRENT_TIMESTAMP
+                ) AS "in_flight!",
+                COUNT(*) FILTER (WHERE current_status = 'failed') AS "failed!",
+                COUNT(*) FILTER (WHERE current_status = 'processed') AS "processed!",
+                MIN(creation_time) FILTER (
+                    WHERE current_status = 'enqueued'
+                ) AS oldest_enqueued_time
+            FROM plugin_work_queue.generator_plugin_executions
+            WHERE plugin_id = $1
+              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+            GROUP BY tenant_id
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let retry_counts = sqlx::query_as!(
+            TenantRetryCount,
+            r#"
+            SELECT
+                tenant_id,
+                try_count + 1 AS "attempts!",
+                COUNT(*) AS "count!"
+            FROM plugin_work_queue.generator_plugin_executions
+            WHERE plugin_id = $1
+              AND current_status = 'enqueued'
+              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+            GROUP BY tenant_id, try_count
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let dead_letter_counts = sqlx::query_as!(
+            TenantDeadLetterCount,
+            r#"
+            SELECT
+                tenant_id,
+                COUNT(*) AS "count!"
+            FROM plugin_work_queue.generator_dead_letter_executions
+            WHERE plugin_id = $1
+            GROUP BY tenant_id
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(QueueStats {
+            execution_counts,
+            retry_counts,
+            dead_letter_counts,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn list_analyzer_dead_letters(
+        &self,
+        plugin_id: Uuid,
+    ) -> Result<Vec<DeadLetteredExecution>, PsqlQueueError> {
+        let executions = sqlx::query_as!(
+            DeadLetteredExecution,
+            r#"
+            SELECT
+                execution_key AS "execution_key!: ExecutionId",
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time,
+                dead_lettered_time
+            FROM plugin_work_queue.analyzer_dead_letter_executions
+            WHERE plugin_id = $1
+            ORDER BY dead_lettered_time ASC
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(executions)
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn get_analyzer_dead_letter(
+        &self,
+        plugin_id: Uuid,
+        execution_key: ExecutionId,
+    ) -> Result<Option<DeadLetteredMessage>, PsqlQueueError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                execution_key AS "execution_key!: ExecutionId",
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time,
+                dead_lettered_time
+            FROM plugin_work_queue.analyzer_dead_letter_executions
+            WHERE plugin_id = $1
+              AND execution_key = $2
+            "#,
+            plugin_id,
+            execution_key.0,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| DeadLetteredMessage {
+            execution: DeadLetteredExecution {
+                execution_key: row.execution_key,
+                plugin_id: row.plugin_id,
+                tenant_id: row.tenant_id,
+                trace_id: row.trace_id,
+                event_source_id: row.event_source_id,
+                attempts: row.attempts,
+                creation_time: row.creation_time,
+                dead_lettered_time: row.dead_lettered_time,
+            },
+            pipeline_message: row.pipeline_message,
+        }))
+    }
+
+    /// Moves a dead-lettered execution back onto the queue as a new execution,
+    /// with a fresh set of attempts. Returns the new execution's key, or
+    /// `None` if there was no such dead-lettered execution.
+    #[instrument(skip(self), err)]
+    pub async fn requeue_analyzer_dead_letter(
+        &self,
+        plugin_id: Uuid,
+        execution_key: ExecutionId,
+    ) -> Result<Option<ExecutionId>, PsqlQueueError> {
+        let requeued = sqlx::query!(
+            r#"
+            WITH requeued AS (
+                DELETE FROM plugin_work_queue.analyzer_dead_letter_executions
+                WHERE plugin_id = $1
+                  AND execution_key = $2
+                RETURNING
+                    plugin_id,
+                    pipeline_message,
+                    tenant_id,
+                    trace_id,
+                    event_source_id
+            )
+            INSERT INTO plugin_work_queue.analyzer_plugin_executions (
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                current_status,
+                try_count
+            )
+            SELECT
+                plugin_id,
+                pipeline_message,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                'enqueued',
+                -1
+            FROM requeued
+            RETURNING execution_key AS "execution_key!: ExecutionId"
+            "#,
+            plugin_id,
+            execution_key.0,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(requeued.map(|row| row.execution_key))
+    }
+
+    #[instrument(skip(self), err)]
+    pub async fn analyzer_queue_stats(
+        &self,
+        plugin_id: Uuid,
+    ) -> Result<QueueStats, PsqlQueueError> {
+        let execution_counts = sqlx::query_as!(
+            TenantExecutionCounts,
+            r#"
+            SELECT
+                tenant_id,
+                COUNT(*) FILTER (
+                    WHERE current_status = 'enqueued' AND visible_after <= CURRENT_TIMESTAMP
+                ) AS "enqueued!",
+                COUNT(*) FILTER (
+                    WHERE current_status = 'enqueued' AND visible_after > CURRENT_TIMESTAMP
+                ) AS "in_flight!",
+                COUNT(*) FILTER (WHERE current_status = 'failed') AS "failed!",
+                COUNT(*) FILTER (WHERE current_status = 'processed') AS "processed!",
+                MIN(creation_time) FILTER (
+                    WHERE current_status = 'enqueued'
+                ) AS oldest_enqueued_time
+            FROM plugin_work_queue.analyzer_plugin_executions
+            WHERE plugin_id = $1
+              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+            GROUP BY tenant_id
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let retry_counts = sqlx::query_as!(
+            TenantRetryCount,
+            r#"
+            SELECT
+                tenant_id,
+                try_count + 1 AS "attempts!",
+                COUNT(*) AS "count!"
+            FROM plugin_work_queue.analyzer_plugin_executions
+            WHERE plugin_id = $1
+              AND current_status = 'enqueued'
+              AND creation_time >= (CURRENT_TIMESTAMP - INTERVAL '1 day')
+            GROUP BY tenant_id, try_count
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let dead_letter_counts = sqlx::query_as!(
+            TenantDeadLetterCount,
+            r#"
+            SELECT
+                tenant_id,
+                COUNT(*) AS "count!"
+            FROM plugin_work_queue.analyzer_dead_letter_executions
+            WHERE plugin_id = $1
+            GROUP BY tenant_id
+            "#,
+            plugin_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(QueueStats {
+            execution_counts,
+            retry_counts,
+            dead_letter_counts,
+        })
+    }
 }
@@ -1,5 +1,18 @@
-use std::time::Duration;
+use std::{
+    collections::{
+        BTreeMap,
+        HashSet,
+    },
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
 
+use chrono::{
+    DateTime,
+    Utc,
+};
 use grapl_config::PostgresClient;
 use kafka::{
     Producer,
@@ -9,6 +22,7 @@ use rust_proto::{
     graplinc::grapl::{
         api::{
             graph::v1beta1::GraphDescription,
+            plugin_registry::v1beta1::PluginType,
             plugin_work_queue::{
                 v1beta1,
                 v1beta1::{
@@ -30,12 +44,18 @@ use tokio::net::TcpListener;
 use crate::{
     psql_queue::{
         self,
+        ExecutionId,
+        Lease,
         PsqlQueue,
         PsqlQueueError,
     },
     ConfigUnion,
 };
 
+/// The most jobs handed out by a single GetExecuteGenerator or
+/// GetExecuteAnalyzer call
+const MAX_JOBS_PER_REQUEST: u32 = 100;
+
 #[derive(Debug, thiserror::Error)]
 pub enum PluginWorkQueueError {
     #[error("PsqlQueueError {0}")]
@@ -44,6 +64,19 @@ pub enum PluginWorkQueueError {
     DeserializationError(#[from] SerDeError),
     #[error("KafkaProducerError {0}")]
     KafkaProducerError(#[from] ProducerError),
+    #[error("max attempts {0} is out of range")]
+    InvalidMaxAttempts(u32),
+    #[error("no dead-lettered execution {request_id} for plugin {plugin_id}")]
+    DeadLetteredExecutionNotFound {
+        plugin_id: uuid::Uuid,
+        request_id: i64,
+    },
+    #[error("visibility timeout {0:?} is out of range")]
+    InvalidVisibilityTimeout(Duration),
+    #[error("lease on execution {request_id} was lost")]
+    LeaseLost { request_id: i64 },
+    #[error("max in-flight per tenant {0} is out of range")]
+    InvalidMaxInFlightPerTenant(u32),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -52,6 +85,10 @@ pub enum PluginWorkQueueInitError {
     DbInit(#[from] grapl_config::PostgresDbInitError),
     #[error("Kafka {0}")]
     Kafka(#[from] kafka::ConfigurationError),
+    #[error("max attempts {0} is out of range")]
+    InvalidMaxAttempts(u32),
+    #[error("visibility timeout of {0} seconds is out of range")]
+    InvalidVisibilityTimeout(u32),
 }
 
 impl From<PluginWorkQueueError> for Status {
@@ -62,6 +99,19 @@ impl From<PluginWorkQueueError> for Status {
             PluginWorkQueueError::DeserializationError(_) => {
                 Status::invalid_argument("Invalid argument")
             }
+            PluginWorkQueueError::InvalidMaxAttempts(_) => {
+                Status::invalid_argument(err.to_string())
+            }
+            PluginWorkQueueError::DeadLetteredExecutionNotFound { .. } => {
+                Status::not_found(err.to_string())
+            }
+            PluginWorkQueueError::InvalidVisibilityTimeout(_) => {
+                Status::invalid_argument(err.to_string())
+            }
+            PluginWorkQueueError::LeaseLost { .. } => Status::failed_precondition(err.to_string()),
+            PluginWorkQueueError::InvalidMaxInFlightPerTenant(_) => {
+                Status::invalid_argument(err.to_string())
+            }
         }
     }
 }
@@ -70,18 +120,175 @@ impl From<PluginWorkQueueError> for Status {
 pub struct PluginWorkQueue {
     queue: PsqlQueue,
     generator_producer: Producer<GraphDescription>,
+    // The number of attempts an execution gets before it's dead-lettered,
+    // unless overridden for its plugin
+    max_attempts: i32,
+    // How many seconds an execution is leased for when it's first handed out,
+    // unless overridden for its plugin
+    visibility_timeout: i32,
 }
 
 impl PluginWorkQueue {
     pub async fn try_from(configs: &ConfigUnion) -> Result<Self, PluginWorkQueueInitError> {
+        let max_attempts = configs.service_config.plugin_work_queue_max_attempts;
+        let max_attempts = match i32::try_from(max_attempts) {
+            Ok(max_attempts) if max_attempts > 0 => max_attempts,
+            _ => return Err(PluginWorkQueueInitError::InvalidMaxAttempts(max_attempts)),
+        };
+        let visibility_timeout = configs
+            .service_config
+            .plugin_work_queue_visibility_timeout_seconds;
+        let visibility_timeout = match i32::try_from(visibility_timeout) {
+            Ok(visibility_timeout) if visibility_timeout > 0 => visibility_timeout,
+            _ => {
+                return Err(PluginWorkQueueInitError::InvalidVisibilityTimeout(
+                    visibility_timeout,
+                ))
+            }
+        };
         let psql_queue = PsqlQueue::init_with_config(configs.db_config.clone()).await?;
         let generator_producer = Producer::new(configs.generator_producer_config.clone())?;
         Ok(Self {
             queue: psql_queue,
             generator_producer,
+            max_attempts,
+            visibility_timeout,
         })
     }
 }
+
+impl From<v1beta1::SchedulingMode> for psql_queue::SchedulingMode {
+    fn from(scheduling_mode: v1beta1::SchedulingMode) -> Self {
+        match scheduling_mode {
+            v1beta1::SchedulingMode::OldestFirst => Self::OldestFirst,
+            v1beta1::SchedulingMode::RoundRobin => Self::RoundRobin,
+        }
+    }
+}
+
+impl From<psql_queue::DeadLetteredExecution> for v1beta1::DeadLetteredExecution {
+    fn from(execution: psql_queue::DeadLetteredExecution) -> Self {
+        Self {
+            request_id: execution.execution_key.into(),
+            plugin_id: execution.plugin_id,
+            tenant_id: execution.tenant_id,
+            trace_id: execution.trace_id,
+            event_source_id: execution.event_source_id,
+            attempts: execution.attempts as u32,
+            creation_time: SystemTime::from(execution.creation_time),
+            dead_lettered_time: SystemTime::from(execution.dead_lettered_time),
+        }
+    }
+}
+
+/// Running totals for the executions of one tenant, or of all tenants
+#[derive(Default)]
+struct QueueTotals {
+    enqueued: u64,
+    in_flight: u64,
+    failed: u64,
+    processed: u64,
+    dead_lettered: u64,
+    oldest_enqueued_time: Option<DateTime<Utc>>,
+    retry_histogram: BTreeMap<u32, u64>,
+}
+
+impl QueueTotals {
+    fn add(&mut self, other: &QueueTotals) {
+        self.enqueued += other.enqueued;
+        self.in_flight += other.in_flight;
+        self.failed += other.failed;
+        self.processed += other.processed;
+        self.dead_lettered += other.dead_lettered;
+        self.oldest_enqueued_time = match (self.oldest_enqueued_time, other.oldest_enqueued_time) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        for (attempts, count) in &other.retry_histogram {
+            *self.retry_histogram.entry(*attempts).or_default() += count;
+        }
+    }
+
+    fn into_stats(self, now: DateTime<Utc>) -> v1beta1::QueueStats {
+        v1beta1::QueueStats {
+            enqueued: self.enqueued,
+            in_flight: self.in_flight,
+            failed: self.failed,
+            processed: self.processed,
+            dead_lettered: self.dead_lettered,
+            // Clock skew between us and the database can't make an age negative
+            oldest_enqueued_age: self
+                .oldest_enqueued_time
+                .map(|oldest| (now - oldest).to_std().unwrap_or_default()),
+            retry_histogram: self
+                .retry_histogram
+                .into_iter()
+                .map(|(attempts, count)| v1beta1::RetryHistogramBucket { attempts, count })
+                .collect(),
+        }
+    }
+}
+
+/// Folds the per-tenant numbers from the database into stats for each tenant
+/// and for the plugin as a whole
+fn queue_stats_response(
+    stats: psql_queue::QueueStats,
+    now: DateTime<Utc>,
+) -> v1beta1::GetQueueStatsResponse {
+    let mut tenants: BTreeMap<uuid::Uuid, QueueTotals> = BTreeMap::new();
+
+    for counts in stats.execution_counts {
+        let totals = tenants.entry(counts.tenant_id).or_default();
+        totals.enqueued = counts.enqueued as u64;
+        totals.in_flight = counts.in_flight as u64;
+        totals.failed = counts.failed as u64;
+        totals.processed = counts.processed as u64;
+        totals.oldest_enqueued_time = counts.oldest_enqueued_time;
+    }
+    for retries in stats.retry_counts {
+        let totals = tenants.entry(retries.tenant_id).or_default();
+        *totals
+            .retry_histogram
+            .entry(retries.attempts as u32)
+            .or_default() += retries.count as u64;
+    }
+    for dead_letters in stats.dead_letter_counts {
+        tenants
+            .entry(dead_letters.tenant_id)
+            .or_default()
+            .dead_lettered = dead_letters.count as u64;
+    }
+
+    let mut overall = QueueTotals::default();
+    for totals in tenants.values() {
+        overall.add(totals);
+    }
+
+    v1beta1::GetQueueStatsResponse {
+        stats: overall.into_stats(now),
+        tenant_stats: tenants
+            .into_iter()
+            .map(|(tenant_id, totals)| v1beta1::TenantQueueStats {
+                tenant_id,
+                stats: totals.into_stats(now),
+            })
+            .collect(),
+    }
+}
+
+/// The request ids of the `requested` executions that aren't among `granted`
+fn request_ids_not_in(
+    requested: impl IntoIterator<Item = ExecutionId>,
+    granted: &[ExecutionId],
+) -> Vec<i64> {
+    let granted: HashSet<i64> = granted.iter().map(|id| i64::from(*id)).collect();
+    requested
+        .into_iter()
+        .map(i64::from)
+        .filter(|request_id| !granted.contains(request_id))
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl PluginWorkQueueApi for PluginWorkQueue {
     type Error = PluginWorkQueueError;
@@ -146,40 +353,53 @@ impl PluginWorkQueueApi for PluginWorkQueue {
         request: v1beta1::GetExecuteGeneratorRequest,
     ) -> Result<v1beta1::GetExecuteGeneratorResponse, PluginWorkQueueError> {
         let plugin_id = request.plugin_id();
-        let message = self.queue.get_generator_message(plugin_id).await?;
-        let message = match message {
-            Some(message) => message,
-            None => {
-                tracing::warn!(
-                    message = "found no generator executions",
+        let max_jobs = request.max_jobs().clamp(1, MAX_JOBS_PER_REQUEST);
+        let messages = self
+            .queue
+            .get_generator_messages(
+                plugin_id,
+                self.max_attempts,
+                self.visibility_timeout,
+                i64::from(max_jobs),
+            )
+            .await?;
+
+        if messages.is_empty() {
+            tracing::warn!(
+                message = "found no generator executions",
+                plugin_id =% plugin_id,
+            );
+        }
+
+        let jobs = messages
+            .into_iter()
+            .map(|message| {
+                let tenant_id = message.request.tenant_id;
+                let trace_id = message.request.trace_id;
+                let event_source_id = message.request.event_source_id;
+
+                tracing::debug!(
+                    message = "retrieving generator execution",
+                    tenant_id =% tenant_id,
+                    trace_id =% trace_id,
+                    event_source_id =% event_source_id,
                     plugin_id =% plugin_id,
                 );
-                return Ok(v1beta1::GetExecuteGeneratorResponse::new(None, 0));
-            }
-        };
-
-        let tenant_id = message.request.tenant_id;
-        let trace_id = message.request.trace_id;
-        let event_source_id = message.request.event_source_id;
 
-        tracing::debug!(
-            message = "retrieving generator execution",
-            tenant_id =% tenant_id,
-            trace_id =% trace_id,
-            event_source_id =% event_source_id,
-            plugin_id =% plugin_id,
-        );
+                v1beta1::AcquiredExecutionJob {
+                    execution_job: v1beta1::ExecutionJob::new(
+                        message.request.pipeline_message.into(),
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                    ),
+                    request_id: message.request.execution_key.into(),
+                    lease_id: message.lease_id,
+                }
+            })
+            .collect();
 
-        let execution_job = v1beta1::ExecutionJob::new(
-            message.request.pipeline_message.into(),
-            tenant_id,
-            trace_id,
-            event_source_id,
-        );
-        Ok(v1beta1::GetExecuteGeneratorResponse::new(
-            Some(execution_job),
-            message.request.execution_key.into(),
-        ))
+        Ok(v1beta1::GetExecuteGeneratorResponse::new(jobs))
     }
 
     #[tracing::instrument(skip(self, request), err)]
@@ -187,40 +407,54 @@ impl PluginWorkQueueApi for PluginWorkQueue {
         &self,
         request: v1beta1::GetExecuteAnalyzerRequest,
     ) -> Result<v1beta1::GetExecuteAnalyzerResponse, PluginWorkQueueError> {
-        let message = self.queue.get_analyzer_message(request.plugin_id).await?;
-        let message = match message {
-            Some(message) => message,
-            None => {
-                tracing::warn!(
-                    message = "found no analyzer executions",
-                    plugin_id =% request.plugin_id,
-                );
-                return Ok(v1beta1::GetExecuteAnalyzerResponse::new(None, 0));
-            }
-        };
+        let plugin_id = request.plugin_id;
+        let max_jobs = request.max_jobs.clamp(1, MAX_JOBS_PER_REQUEST);
+        let messages = self
+            .queue
+            .get_analyzer_messages(
+                plugin_id,
+                self.max_attempts,
+                self.visibility_timeout,
+                i64::from(max_jobs),
+            )
+            .await?;
 
-        let tenant_id = message.request.tenant_id;
-        let trace_id = message.request.trace_id;
-        let event_source_id = message.request.event_source_id;
+        if messages.is_empty() {
+            tracing::warn!(
+                message = "found no analyzer executions",
+                plugin_id =% plugin_id,
+            );
+        }
 
-        tracing::debug!(
-            message = "retrieving analyzer execution",
-            tenant_id =% tenant_id,
-            trace_id =% trace_id,
-            event_source_id =% event_source_id,
-            plugin_id =% request.plugin_id,
-        );
+        let jobs = messages
+            .into_iter()
+            .map(|message| {
+                let tenant_id = message.request.tenant_id;
+                let trace_id = message.request.trace_id;
+                let event_source_id = message.request.event_source_id;
 
-        let execution_job = v1beta1::ExecutionJob::new(
-            message.request.pipeline_message.into(),
-            tenant_id,
-            trace_id,
-            event_source_id,
-        );
-        Ok(v1beta1::GetExecuteAnalyzerResponse::new(
-            Some(execution_job),
-            message.request.execution_key.into(),
-        ))
+                tracing::debug!(
+                    message = "retrieving analyzer execution",
+                    tenant_id =% tenant_id,
+                    trace_id =% trace_id,
+                    event_source_id =% event_source_id,
+                    plugin_id =% plugin_id,
+                );
+
+                v1beta1::AcquiredExecutionJob {
+                    execution_job: v1beta1::ExecutionJob::new(
+                        message.request.pipeline_message.into(),
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                    ),
+                    request_id: message.request.execution_key.into(),
+                    lease_id: message.lease_id,
+                }
+            })
+            .collect();
+
+        Ok(v1beta1::GetExecuteAnalyzerResponse::new(jobs))
     }
 
     #[tracing::instrument(skip(self, request), err)]
@@ -233,9 +467,23 @@ impl PluginWorkQueueApi for PluginWorkQueue {
         let event_source_id = request.event_source_id();
         let request_id = request.request_id();
         let plugin_id = request.plugin_id();
+        let lease = Lease {
+            execution_key: request_id.into(),
+            lease_id: request.lease_id(),
+        };
 
         let status = match request.graph_description() {
             Some(graph_description) => {
+                // Make sure the lease is still ours, and won't run out while
+                // we publish, so the result is published at most once
+                let extended = self
+                    .queue
+                    .extend_generator_leases(&[lease], self.visibility_timeout)
+                    .await?;
+                if extended.is_empty() {
+                    return Err(PluginWorkQueueError::LeaseLost { request_id });
+                }
+
                 tracing::debug!(
                     message = "publishing generator execution result",
                     tenant_id =% tenant_id,
@@ -267,7 +515,9 @@ impl PluginWorkQueueApi for PluginWorkQueue {
             status =? status,
         );
 
-        self.queue.ack_generator(request_id.into(), status).await?;
+        if !self.queue.ack_generator(lease, status).await? {
+            return Err(PluginWorkQueueError::LeaseLost { request_id });
+        }
 
         Ok(v1beta1::AcknowledgeGeneratorResponse {})
     }
@@ -296,10 +546,380 @@ impl PluginWorkQueueApi for PluginWorkQueue {
             status =? status,
         );
 
+        let request_id = request.request_id();
+        let lease = Lease {
+            execution_key: request_id.into(),
+            lease_id: request.lease_id(),
+        };
+        if !self.queue.ack_analyzer(lease, status).await? {
+            return Err(PluginWorkQueueError::LeaseLost { request_id });
+        }
+
+        Ok(v1beta1::AcknowledgeAnalyzerResponse {})
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn acknowledge_generator_batch(
+        &self,
+        request: v1beta1::AcknowledgeGeneratorBatchRequest,
+    ) -> Result<v1beta1::AcknowledgeGeneratorBatchResponse, PluginWorkQueueError> {
+        let mut processed = Vec::new();
+        let mut failed = Vec::new();
+        let mut envelopes = Vec::new();
+
+        for acknowledgement in request.acknowledgements {
+            let lease = Lease {
+                execution_key: acknowledgement.request_id().into(),
+                lease_id: acknowledgement.lease_id(),
+            };
+            let tenant_id = acknowledgement.tenant_id();
+            let trace_id = acknowledgement.trace_id();
+            let event_source_id = acknowledgement.event_source_id();
+
+            match acknowledgement.graph_description() {
+                Some(graph_description) => {
+                    envelopes.push(Envelope::new(
+                        tenant_id,
+                        trace_id,
+                        event_source_id,
+                        graph_description,
+                    ));
+                    processed.push(lease);
+                }
+                None => failed.push(lease),
+            }
+        }
+
+        // Only publish the results of executions whose lease is still ours,
+        // extending those leases so they can't run out while we publish
+        let extended: HashSet<i64> = self
+            .queue
+            .extend_generator_leases(&processed, self.visibility_timeout)
+            .await?
+            .into_iter()
+            .map(i64::from)
+            .collect();
+        let (held, envelopes): (Vec<Lease>, Vec<Envelope<GraphDescription>>) = processed
+            .iter()
+            .zip(envelopes)
+            .filter(|(lease, _)| extended.contains(&i64::from(lease.execution_key)))
+            .map(|(lease, envelope)| (*lease, envelope))
+            .unzip();
+
+        tracing::debug!(
+            message = "publishing generator execution results",
+            processed = held.len(),
+            failed = failed.len(),
+        );
+
+        // Every result is published before anything is acknowledged, so a
+        // failed publish leaves the whole batch to be retried.
+        futures::future::try_join_all(
+            envelopes
+                .into_iter()
+                .map(|envelope| self.generator_producer.send(envelope)),
+        )
+        .await?;
+
+        let acknowledged = self.queue.ack_generator_batch(&held, &failed).await?;
+        let rejected_request_ids = request_ids_not_in(
+            processed
+                .iter()
+                .chain(failed.iter())
+                .map(|lease| lease.execution_key),
+            &acknowledged,
+        );
+
+        if !rejected_request_ids.is_empty() {
+            tracing::warn!(
+                message = "rejected generator acknowledgements with lost leases",
+                rejected_request_ids =? rejected_request_ids,
+            );
+        }
+
+        Ok(v1beta1::AcknowledgeGeneratorBatchResponse {
+            rejected_request_ids,
+        })
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn acknowledge_analyzer_batch(
+        &self,
+        request: v1beta1::AcknowledgeAnalyzerBatchRequest,
+    ) -> Result<v1beta1::AcknowledgeAnalyzerBatchResponse, PluginWorkQueueError> {
+        let (processed, failed): (Vec<_>, Vec<_>) = request
+            .acknowledgements
+            .iter()
+            .partition(|acknowledgement| acknowledgement.success());
+        let lease = |acknowledgement: &v1beta1::AcknowledgeAnalyzerRequest| Lease {
+            execution_key: acknowledgement.request_id().into(),
+            lease_id: acknowledgement.lease_id(),
+        };
+        let processed: Vec<Lease> = processed.into_iter().map(lease).collect();
+        let failed: Vec<Lease> = failed.into_iter().map(lease).collect();
+
+        tracing::debug!(
+            message = "acknowledging analyzer executions",
+            processed = processed.len(),
+            failed = failed.len(),
+        );
+
+        let acknowledged = self.queue.ack_analyzer_batch(&processed, &failed).await?;
+        let rejected_request_ids = request_ids_not_in(
+            processed
+                .iter()
+                .chain(failed.iter())
+                .map(|lease| lease.execution_key),
+            &acknowledged,
+        );
+
+        if !rejected_request_ids.is_empty() {
+            tracing::warn!(
+                message = "rejected analyzer acknowledgements with lost leases",
+                rejected_request_ids =? rejected_request_ids,
+            );
+        }
+
+        Ok(v1beta1::AcknowledgeAnalyzerBatchResponse {
+            rejected_request_ids,
+        })
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn set_max_attempts(
+        &self,
+        request: v1beta1::SetMaxAttemptsRequest,
+    ) -> Result<v1beta1::SetMaxAttemptsResponse, PluginWorkQueueError> {
+        let max_attempts = match i32::try_from(request.max_attempts) {
+            Ok(max_attempts) if max_attempts > 0 => max_attempts,
+            _ => {
+                return Err(PluginWorkQueueError::InvalidMaxAttempts(
+                    request.max_attempts,
+                ))
+            }
+        };
+
+        tracing::info!(
+            message = "setting max attempts",
+            plugin_id =% request.plugin_id,
+            max_attempts = max_attempts,
+        );
+
         self.queue
-            .ack_analyzer(request.request_id().into(), status)
+            .set_max_attempts(request.plugin_id, max_attempts)
             .await?;
-        Ok(v1beta1::AcknowledgeAnalyzerResponse {})
+
+        Ok(v1beta1::SetMaxAttemptsResponse {})
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn set_visibility_timeout(
+        &self,
+        request: v1beta1::SetVisibilityTimeoutRequest,
+    ) -> Result<v1beta1::SetVisibilityTimeoutResponse, PluginWorkQueueError> {
+        // Timeouts are kept in whole seconds, so anything under a second
+        // would leave the execution visible the moment it's handed out
+        let visibility_timeout = match i32::try_from(request.visibility_timeout.as_secs()) {
+            Ok(visibility_timeout) if visibility_timeout > 0 => visibility_timeout,
+            _ => {
+                return Err(PluginWorkQueueError::InvalidVisibilityTimeout(
+                    request.visibility_timeout,
+                ))
+            }
+        };
+
+        tracing::info!(
+            message = "setting visibility timeout",
+            plugin_id =% request.plugin_id,
+            visibility_timeout = visibility_timeout,
+        );
+
+        self.queue
+            .set_visibility_timeout(request.plugin_id, visibility_timeout)
+            .await?;
+
+        Ok(v1beta1::SetVisibilityTimeoutResponse {})
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn set_scheduling_policy(
+        &self,
+        request: v1beta1::SetSchedulingPolicyRequest,
+    ) -> Result<v1beta1::SetSchedulingPolicyResponse, PluginWorkQueueError> {
+        let max_in_flight_per_tenant = request
+            .max_in_flight_per_tenant
+            .map(|max_in_flight| match i32::try_from(max_in_flight) {
+                Ok(max_in_flight) if max_in_flight > 0 => Ok(max_in_flight),
+                _ => Err(PluginWorkQueueError::InvalidMaxInFlightPerTenant(
+                    max_in_flight,
+                )),
+            })
+            .transpose()?;
+
+        tracing::info!(
+            message = "setting scheduling policy",
+            plugin_id =% request.plugin_id,
+            scheduling_mode =? request.scheduling_mode,
+            max_in_flight_per_tenant =? max_in_flight_per_tenant,
+        );
+
+        self.queue
+            .set_scheduling_policy(
+                request.plugin_id,
+                psql_queue::SchedulingPolicy {
+                    scheduling_mode: request.scheduling_mode.into(),
+                    max_in_flight_per_tenant,
+                },
+            )
+            .await?;
+
+        Ok(v1beta1::SetSchedulingPolicyResponse {})
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn extend_lease(
+        &self,
+        request: v1beta1::ExtendLeaseRequest,
+    ) -> Result<v1beta1::ExtendLeaseResponse, PluginWorkQueueError> {
+        let leases: Vec<Lease> = request
+            .leases
+            .iter()
+            .map(|lease| Lease {
+                execution_key: lease.request_id.into(),
+                lease_id: Some(lease.lease_id),
+            })
+            .collect();
+
+        let extended = match request.plugin_type {
+            PluginType::Generator => {
+                self.queue
+                    .extend_generator_leases(&leases, self.visibility_timeout)
+                    .await?
+            }
+            PluginType::Analyzer => {
+                self.queue
+                    .extend_analyzer_leases(&leases, self.visibility_timeout)
+                    .await?
+            }
+        };
+        let lost_request_ids =
+            request_ids_not_in(leases.iter().map(|lease| lease.execution_key), &extended);
+
+        if !lost_request_ids.is_empty() {
+            tracing::warn!(
+                message = "could not extend lost leases",
+                plugin_id =% request.plugin_id,
+                plugin_type = request.plugin_type.type_name(),
+                lost_request_ids =? lost_request_ids,
+            );
+        }
+
+        Ok(v1beta1::ExtendLeaseResponse { lost_request_ids })
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn list_dead_lettered_executions(
+        &self,
+        request: v1beta1::ListDeadLetteredExecutionsRequest,
+    ) -> Result<v1beta1::ListDeadLetteredExecutionsResponse, PluginWorkQueueError> {
+        let executions = match request.plugin_type {
+            PluginType::Generator => {
+                self.queue
+                    .list_generator_dead_letters(request.plugin_id)
+                    .await?
+            }
+            PluginType::Analyzer => {
+                self.queue
+                    .list_analyzer_dead_letters(request.plugin_id)
+                    .await?
+            }
+        };
+
+        Ok(v1beta1::ListDeadLetteredExecutionsResponse {
+            executions: executions.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn get_dead_lettered_execution(
+        &self,
+        request: v1beta1::GetDeadLetteredExecutionRequest,
+    ) -> Result<v1beta1::GetDeadLetteredExecutionResponse, PluginWorkQueueError> {
+        let plugin_id = request.plugin_id;
+        let request_id = request.request_id;
+        let execution_key = ExecutionId::from(request_id);
+
+        let message = match request.plugin_type {
+            PluginType::Generator => {
+                self.queue
+                    .get_generator_dead_letter(plugin_id, execution_key)
+                    .await?
+            }
+            PluginType::Analyzer => {
+                self.queue
+                    .get_analyzer_dead_letter(plugin_id, execution_key)
+                    .await?
+            }
+        }
+        .ok_or(PluginWorkQueueError::DeadLetteredExecutionNotFound {
+            plugin_id,
+            request_id,
+        })?;
+
+        Ok(v1beta1::GetDeadLetteredExecutionResponse {
+            execution: message.execution.into(),
+            data: message.pipeline_message.into(),
+        })
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn requeue_dead_lettered_execution(
+        &self,
+        request: v1beta1::RequeueDeadLetteredExecutionRequest,
+    ) -> Result<v1beta1::RequeueDeadLetteredExecutionResponse, PluginWorkQueueError> {
+        let plugin_id = request.plugin_id;
+        let request_id = request.request_id;
+        let execution_key = ExecutionId::from(request_id);
+
+        let requeued = match request.plugin_type {
+            PluginType::Generator => {
+                self.queue
+                    .requeue_generator_dead_letter(plugin_id, execution_key)
+                    .await?
+            }
+            PluginType::Analyzer => {
+                self.queue
+                    .requeue_analyzer_dead_letter(plugin_id, execution_key)
+                    .await?
+            }
+        }
+        .ok_or(PluginWorkQueueError::DeadLetteredExecutionNotFound {
+            plugin_id,
+            request_id,
+        })?;
+
+        tracing::info!(
+            message = "requeued dead-lettered execution",
+            plugin_id =% plugin_id,
+            plugin_type = request.plugin_type.type_name(),
+            dead_lettered_request_id = request_id,
+            request_id = i64::from(requeued),
+        );
+
+        Ok(v1beta1::RequeueDeadLetteredExecutionResponse {})
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn get_queue_stats(
+        &self,
+        request: v1beta1::GetQueueStatsRequest,
+    ) -> Result<v1beta1::GetQueueStatsResponse, PluginWorkQueueError> {
+        let stats = match request.plugin_type {
+            PluginType::Generator => self.queue.generator_queue_stats(request.plugin_id).await?,
+            PluginType::Analyzer => self.queue.analyzer_queue_stats(request.plugin_id).await?,
+        };
+
+        Ok(queue_stats_response(stats, Utc::now()))
     }
 }
 
@@ -331,3 +951,185 @@ pub async fn exec_service(configs: ConfigUnion) -> Result<(), Box<dyn std::error
 
     Ok(server.serve().await?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution_counts(
+        tenant_id: uuid::Uuid,
+        enqueued: i64,
+        oldest_enqueued_time: Option<DateTime<Utc>>,
+    ) -> psql_queue::TenantExecutionCounts {
+        psql_queue::TenantExecutionCounts {
+            tenant_id,
+            enqueued,
+            in_flight: 1,
+            failed: 2,
+            processed: 3,
+            oldest_enqueued_time,
+        }
+    }
+
+    fn retry_count(
+        tenant_id: uuid::Uuid,
+        attempts: i32,
+        count: i64,
+    ) -> psql_queue::TenantRetryCount {
+        psql_queue::TenantRetryCount {
+            tenant_id,
+            attempts,
+            count,
+        }
+    }
+
+    fn tenant_stats(
+        response: &v1beta1::GetQueueStatsResponse,
+        tenant_id: uuid::Uuid,
+    ) -> &v1beta1::QueueStats {
+        &response
+            .tenant_stats
+            .iter()
+            .find(|tenant_stats| tenant_stats.tenant_id == tenant_id)
+            .expect("tenant missing from the response")
+            .stats
+    }
+
+    #[test]
+    fn test_add_keeps_the_oldest_enqueued_time() {
+        let now = Utc::now();
+        let older = now - chrono::Duration::seconds(30);
+
+        let mut totals = QueueTotals {
+            oldest_enqueued_time: Some(now),
+            ..Default::default()
+        };
+        totals.add(&QueueTotals {
+            oldest_enqueued_time: Some(older),
+            ..Default::default()
+        });
+        assert_eq!(totals.oldest_enqueued_time, Some(older));
+
+        // A tenant with nothing enqueued doesn't clear the oldest time
+        totals.add(&QueueTotals::default());
+        assert_eq!(totals.oldest_enqueued_time, Some(older));
+
+        let mut totals = QueueTotals::default();
+        totals.add(&QueueTotals {
+            oldest_enqueued_time: Some(now),
+            ..Default::default()
+        });
+        assert_eq!(totals.oldest_enqueued_time, Some(now));
+    }
+
+    #[test]
+    fn test_queue_stats_response_merges_tenants() {
+        let now = Utc::now();
+        let tenant_a = uuid::Uuid::new_v4();
+        let tenant_b = uuid::Uuid::new_v4();
+        let tenant_c = uuid::Uuid::new_v4();
+
+        let stats = psql_queue::QueueStats {
+            execution_counts: vec![
+                execution_counts(tenant_a, 4, Some(now - chrono::Duration::seconds(10))),
+                execution_counts(tenant_b, 6, Some(now - chrono::Duration::seconds(60))),
+            ],
+            retry_counts: vec![
+                retry_count(tenant_a, 1, 3),
+                retry_count(tenant_a, 2, 1),
+                retry_count(tenant_b, 1, 5),
+                retry_count(tenant_b, 3, 1),
+            ],
+            // Tenant c only has dead-lettered executions
+            dead_letter_counts: vec![
+                psql_queue::TenantDeadLetterCount {
+                    tenant_id: tenant_b,
+                    count: 2,
+                },
+                psql_queue::TenantDeadLetterCount {
+                    tenant_id: tenant_c,
+                    count: 7,
+                },
+            ],
+        };
+
+        let response = queue_stats_response(stats, now);
+
+        assert_eq!(response.tenant_stats.len(), 3);
+        assert_eq!(
+            response.stats,
+            v1beta1::QueueStats {
+                enqueued: 10,
+                in_flight: 2,
+                failed: 4,
+                processed: 6,
+                dead_lettered: 9,
+                oldest_enqueued_age: Some(Duration::from_secs(60)),
+                retry_histogram: vec![
+                    v1beta1::RetryHistogramBucket {
+                        attempts: 1,
+                        count: 8,
+                    },
+                    v1beta1::RetryHistogramBucket {
+                        attempts: 2,
+                        count: 1,
+                    },
+                    v1beta1::RetryHistogramBucket {
+                        attempts: 3,
+                        count: 1,
+                    },
+                ],
+            }
+        );
+
+        assert_eq!(
+            tenant_stats(&response, tenant_a),
+            &v1beta1::QueueStats {
+                enqueued: 4,
+                in_flight: 1,
+                failed: 2,
+                processed: 3,
+                dead_lettered: 0,
+                oldest_enqueued_age: Some(Duration::from_secs(10)),
+                retry_histogram: vec![
+                    v1beta1::RetryHistogramBucket {
+                        attempts: 1,
+                        count: 3,
+                    },
+                    v1beta1::RetryHistogramBucket {
+                        attempts: 2,
+                        count: 1,
+                    },
+                ],
+            }
+        );
+
+        assert_eq!(
+            tenant_stats(&response, tenant_c),
+            &v1beta1::QueueStats {
+                dead_lettered: 7,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_queue_stats_response_clamps_future_enqueued_time() {
+        let now = Utc::now();
+        let tenant_id = uuid::Uuid::new_v4();
+
+        let stats = psql_queue::QueueStats {
+            execution_counts: vec![execution_counts(
+                tenant_id,
+                1,
+                Some(now + chrono::Duration::seconds(5)),
+            )],
+            retry_counts: vec![],
+            dead_letter_counts: vec![],
+        };
+
+        let response = queue_stats_response(stats, now);
+
+        assert_eq!(response.stats.oldest_enqueued_age, Some(Duration::ZERO));
+    }
+}
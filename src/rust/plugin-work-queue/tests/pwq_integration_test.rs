@@ -3,18 +3,62 @@
 use std::time::Duration;
 
 use clap::Parser;
+use kafka::{
+    config::ConsumerConfig,
+    test_utils::topic_scanner::KafkaTopicScanner,
+};
 use rust_proto::{
     client_factory::{
         build_grpc_client,
         services::PluginWorkQueueClientConfig,
     },
-    graplinc::grapl::api::plugin_work_queue::v1beta1::{
-        ExecutionJob,
-        GetExecuteGeneratorRequest,
-        PushExecuteGeneratorRequest,
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::GraphDescription,
+            plugin_registry::v1beta1::PluginType,
+            plugin_work_queue::v1beta1::{
+                AcknowledgeAnalyzerBatchRequest,
+                AcknowledgeAnalyzerRequest,
+                AcknowledgeGeneratorBatchRequest,
+                AcknowledgeGeneratorRequest,
+                AcquiredExecutionJob,
+                ExecutionJob,
+                ExecutionLease,
+                ExtendLeaseRequest,
+                GetDeadLetteredExecutionRequest,
+                GetExecuteAnalyzerRequest,
+                GetExecuteGeneratorRequest,
+                GetExecuteGeneratorResponse,
+                GetQueueStatsRequest,
+                ListDeadLetteredExecutionsRequest,
+                PushExecuteAnalyzerRequest,
+                PushExecuteGeneratorRequest,
+                RequeueDeadLetteredExecutionRequest,
+                SchedulingMode,
+                SetMaxAttemptsRequest,
+                SetSchedulingPolicyRequest,
+                SetVisibilityTimeoutRequest,
+            },
+        },
+        pipeline::v1beta1::Envelope,
+    },
+    protocol::{
+        error::GrpcClientError,
+        status::Code,
     },
 };
 
+/// The job handed out in response to a `max_jobs: 1` request, if any
+fn only_acquired_job(response: GetExecuteGeneratorResponse) -> Option<AcquiredExecutionJob> {
+    let mut jobs = response.jobs();
+    assert!(jobs.len() <= 1, "expected at most one job: {:?}", jobs);
+    jobs.pop()
+}
+
+fn single_job(response: GetExecuteGeneratorResponse) -> Option<ExecutionJob> {
+    only_acquired_job(response).map(|job| job.execution_job)
+}
+
 #[tokio::test]
 async fn test_push_and_get_execute_generator() -> eyre::Result<()> {
     let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
@@ -65,40 +109,40 @@ async fn test_push_and_get_execute_generator() -> eyre::Result<()> {
     // GetExecuteGenerator doesn't pay attention to the plugin_id *at all*.
 
     let retrieve_job_for_plugin_id_2 = pwq_client
-        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2))
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2, 1))
         .await?;
 
     assert_eq!(
-        retrieve_job_for_plugin_id_2.execution_job(),
+        single_job(retrieve_job_for_plugin_id_2),
         Some(job_2.execution_job())
     );
 
     // Fetch for plugin_id 1
     let retrieve_job_for_plugin_id_1 = pwq_client
-        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_1))
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_1, 1))
         .await?;
 
     assert_eq!(
-        retrieve_job_for_plugin_id_1.execution_job(),
+        single_job(retrieve_job_for_plugin_id_1),
         Some(job_1.execution_job())
     );
 
     // Fetch for plugin_id 2 again, we should get Job 3
     let retrieve_job_3_for_plugin_id_2 = pwq_client
-        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2))
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2, 1))
         .await?;
 
     assert_eq!(
-        retrieve_job_3_for_plugin_id_2.execution_job(),
+        single_job(retrieve_job_3_for_plugin_id_2),
         Some(job_3.execution_job())
     );
 
     // Fetch one more time, we should be out of work
     let retrieve_job_none_for_plugin_id_2 = pwq_client
-        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2))
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id_2, 1))
         .await?;
 
-    assert_eq!(retrieve_job_none_for_plugin_id_2.execution_job(), None);
+    assert_eq!(single_job(retrieve_job_none_for_plugin_id_2), None);
 
     Ok(())
 }
@@ -124,14 +168,14 @@ async fn test_message_available_after_failure() -> eyre::Result<()> {
         let mut pwq_client = pwq_client.clone();
         async move {
             pwq_client
-                .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id))
+                .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
                 .await
                 .map_err(|e| eyre::eyre!(e))
         }
     };
 
     // Get the job
-    let retrieved_job = retrieve_job().await?.execution_job();
+    let retrieved_job = single_job(retrieve_job().await?);
     eyre::ensure!(
         retrieved_job == Some(job.clone().execution_job()),
         "Expected job equality: {:?}, {:?}",
@@ -140,7 +184,7 @@ async fn test_message_available_after_failure() -> eyre::Result<()> {
     );
 
     // If we get the job again, it should be None this time.
-    let retrieved_job = retrieve_job().await?.execution_job();
+    let retrieved_job = single_job(retrieve_job().await?);
     eyre::ensure!(
         retrieved_job == None,
         "Expected None job: {:?}",
@@ -150,7 +194,7 @@ async fn test_message_available_after_failure() -> eyre::Result<()> {
     // If we haven't acknowledged it for 10 seconds, it becomes visible again
     tokio::time::sleep(Duration::from_millis(10_500)).await;
 
-    let retrieved_job = retrieve_job().await?.execution_job();
+    let retrieved_job = single_job(retrieve_job().await?);
     eyre::ensure!(
         retrieved_job == Some(job.clone().execution_job()),
         "Expected job equality: {:?}, {:?}",
@@ -160,3 +204,712 @@ async fn test_message_available_after_failure() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_message_dead_lettered_after_max_attempts() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    // A plugin must allow at least one attempt
+    match pwq_client
+        .set_max_attempts(SetMaxAttemptsRequest {
+            plugin_id,
+            max_attempts: 0,
+        })
+        .await
+    {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument)
+        }
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    // Only allow a single attempt for this plugin
+    pwq_client
+        .set_max_attempts(SetMaxAttemptsRequest {
+            plugin_id,
+            max_attempts: 1,
+        })
+        .await?;
+
+    let job = PushExecuteGeneratorRequest::new(
+        ExecutionJob::new("poison".into(), tenant_id, trace_id, event_source_id),
+        plugin_id,
+    );
+    pwq_client.push_execute_generator(job.clone()).await?;
+
+    // Get the job, and never acknowledge it
+    let retrieved = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+        .await?;
+    let retrieved = only_acquired_job(retrieved).expect("expected a job");
+    let request_id = retrieved.request_id;
+    assert_eq!(retrieved.execution_job, job.clone().execution_job());
+
+    // Once its only attempt times out, it's dead-lettered instead of retried
+    tokio::time::sleep(Duration::from_millis(10_500)).await;
+
+    let retrieved = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+        .await?;
+    assert_eq!(single_job(retrieved), None);
+
+    let dead_letters = pwq_client
+        .list_dead_lettered_executions(ListDeadLetteredExecutionsRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+        })
+        .await?
+        .executions;
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].request_id, request_id);
+    assert_eq!(dead_letters[0].attempts, 1);
+    assert_eq!(dead_letters[0].tenant_id, tenant_id);
+
+    let dead_letter = pwq_client
+        .get_dead_lettered_execution(GetDeadLetteredExecutionRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+            request_id,
+        })
+        .await?;
+    assert_eq!(dead_letter.data, job.clone().execution_job().data());
+
+    // Requeueing makes it available again
+    pwq_client
+        .requeue_dead_lettered_execution(RequeueDeadLetteredExecutionRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+            request_id,
+        })
+        .await?;
+
+    let retrieved = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+        .await?;
+    assert_eq!(single_job(retrieved), Some(job.execution_job()));
+
+    let dead_letters = pwq_client
+        .list_dead_lettered_executions(ListDeadLetteredExecutionsRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+        })
+        .await?
+        .executions;
+    assert!(dead_letters.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_get_and_acknowledge_generator() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    let jobs: Vec<_> = (0..3)
+        .map(|i| {
+            PushExecuteGeneratorRequest::new(
+                ExecutionJob::new(
+                    format!("job {i}").into(),
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                ),
+                plugin_id,
+            )
+        })
+        .collect();
+
+    for job in jobs.iter() {
+        pwq_client.push_execute_generator(job.clone()).await?;
+    }
+
+    // A batch is capped at max_jobs
+    let first_batch = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 2))
+        .await?
+        .jobs();
+    assert_eq!(first_batch.len(), 2);
+
+    // and only contains jobs that weren't already handed out
+    let second_batch = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 2))
+        .await?
+        .jobs();
+    assert_eq!(second_batch.len(), 1);
+
+    let mut retrieved: Vec<_> = first_batch
+        .iter()
+        .chain(second_batch.iter())
+        .map(|job| job.execution_job.clone())
+        .collect();
+    retrieved.sort_by_key(|job| job.clone().data());
+    let expected: Vec<_> = jobs.into_iter().map(|job| job.execution_job()).collect();
+    assert_eq!(retrieved, expected);
+
+    let third_batch = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 2))
+        .await?
+        .jobs();
+    assert!(third_batch.is_empty());
+
+    // Mark them all as failed, so nothing is published
+    let acknowledgements = first_batch
+        .into_iter()
+        .chain(second_batch.into_iter())
+        .map(|job| {
+            AcknowledgeGeneratorRequest::new(
+                job.request_id,
+                Some(job.lease_id),
+                None,
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+            )
+        })
+        .collect();
+    pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest { acknowledgements })
+        .await?;
+
+    // Failures aren't retried, so every job is dead-lettered straight away
+    let dead_letters = pwq_client
+        .list_dead_lettered_executions(ListDeadLetteredExecutionsRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+        })
+        .await?
+        .executions;
+    assert_eq!(dead_letters.len(), 3);
+    assert!(dead_letters
+        .iter()
+        .all(|dead_letter| dead_letter.attempts == 1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_acknowledge_processed_generator_publishes() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    let kafka_scanner = KafkaTopicScanner::new(
+        ConsumerConfig::with_topic("generated-graphs"),
+        Duration::from_secs(60),
+        Envelope::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            GraphDescription::new(),
+        ),
+    );
+    let handle = kafka_scanner
+        .scan_for_tenant(tenant_id, 2, |_: GraphDescription| true)
+        .await;
+
+    for i in 0..2 {
+        pwq_client
+            .push_execute_generator(PushExecuteGeneratorRequest::new(
+                ExecutionJob::new(
+                    format!("job {i}").into(),
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                ),
+                plugin_id,
+            ))
+            .await?;
+    }
+
+    let batch = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 2))
+        .await?
+        .jobs();
+    assert_eq!(batch.len(), 2);
+
+    // Mark them all as processed, so each result is published
+    let acknowledgements: Vec<_> = batch
+        .iter()
+        .map(|job| {
+            AcknowledgeGeneratorRequest::new(
+                job.request_id,
+                Some(job.lease_id),
+                Some(GraphDescription::new()),
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+            )
+        })
+        .collect();
+    let response = pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest {
+            acknowledgements: acknowledgements.clone(),
+        })
+        .await?;
+    assert!(response.rejected_request_ids.is_empty());
+
+    let envelopes = tokio::time::timeout(Duration::from_secs(60), handle).await??;
+    assert_eq!(envelopes.len(), 2);
+    assert!(envelopes
+        .iter()
+        .all(|envelope| envelope.trace_id() == trace_id
+            && envelope.event_source_id() == event_source_id));
+
+    // The executions are settled, so acknowledging them again is rejected
+    let response = pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest { acknowledgements })
+        .await?;
+    let mut rejected_request_ids = response.rejected_request_ids;
+    rejected_request_ids.sort();
+    let mut request_ids: Vec<_> = batch.iter().map(|job| job.request_id).collect();
+    request_ids.sort();
+    assert_eq!(rejected_request_ids, request_ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_get_and_acknowledge_analyzer() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    for i in 0..3 {
+        pwq_client
+            .push_execute_analyzer(PushExecuteAnalyzerRequest::new(
+                ExecutionJob::new(
+                    format!("job {i}").into(),
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                ),
+                plugin_id,
+            ))
+            .await?;
+    }
+
+    let batch = pwq_client
+        .get_execute_analyzer(GetExecuteAnalyzerRequest::new(plugin_id, 3))
+        .await?
+        .jobs();
+    assert_eq!(batch.len(), 3);
+
+    // One processed, the rest failed
+    let acknowledgements: Vec<_> = batch
+        .iter()
+        .enumerate()
+        .map(|(i, job)| {
+            AcknowledgeAnalyzerRequest::new(
+                job.request_id,
+                Some(job.lease_id),
+                i == 0,
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+            )
+        })
+        .collect();
+    let response = pwq_client
+        .acknowledge_analyzer_batch(AcknowledgeAnalyzerBatchRequest {
+            acknowledgements: acknowledgements.clone(),
+        })
+        .await?;
+    assert!(response.rejected_request_ids.is_empty());
+
+    // Nothing is left to hand out
+    let remaining = pwq_client
+        .get_execute_analyzer(GetExecuteAnalyzerRequest::new(plugin_id, 3))
+        .await?
+        .jobs();
+    assert!(remaining.is_empty());
+
+    // and the executions are settled, so acknowledging them again is rejected
+    let response = pwq_client
+        .acknowledge_analyzer_batch(AcknowledgeAnalyzerBatchRequest { acknowledgements })
+        .await?;
+    let mut rejected_request_ids = response.rejected_request_ids;
+    rejected_request_ids.sort();
+    let mut request_ids: Vec<_> = batch.iter().map(|job| job.request_id).collect();
+    request_ids.sort();
+    assert_eq!(rejected_request_ids, request_ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_queue_stats() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let (tenant_id_1, tenant_id_2) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    // Two jobs for the first tenant, one for the second
+    for tenant_id in [tenant_id_1, tenant_id_1, tenant_id_2] {
+        pwq_client
+            .push_execute_generator(PushExecuteGeneratorRequest::new(
+                ExecutionJob::new("stats".into(), tenant_id, trace_id, event_source_id),
+                plugin_id,
+            ))
+            .await?;
+    }
+
+    // Hand one job out and fail it, and hand out another without
+    // acknowledging it
+    let failed = only_acquired_job(
+        pwq_client
+            .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+            .await?,
+    )
+    .expect("a job");
+    pwq_client
+        .acknowledge_generator(AcknowledgeGeneratorRequest::new(
+            failed.request_id,
+            Some(failed.lease_id),
+            None,
+            plugin_id,
+            failed.execution_job.tenant_id(),
+            trace_id,
+            event_source_id,
+        ))
+        .await?;
+    only_acquired_job(
+        pwq_client
+            .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+            .await?,
+    )
+    .expect("a job");
+
+    let response = pwq_client
+        .get_queue_stats(GetQueueStatsRequest {
+            plugin_type: PluginType::Generator,
+            plugin_id,
+        })
+        .await?;
+
+    let stats = response.stats;
+    assert_eq!(stats.enqueued, 1);
+    assert_eq!(stats.in_flight, 1);
+    assert_eq!(stats.failed, 1);
+    assert_eq!(stats.processed, 0);
+    // The failed job was also dead-lettered
+    assert_eq!(stats.dead_lettered, 1);
+    assert!(stats.oldest_enqueued_age.is_some());
+
+    // The waiting job has never been handed out; the in-flight one once
+    let histogram: Vec<_> = stats
+        .retry_histogram
+        .iter()
+        .map(|bucket| (bucket.attempts, bucket.count))
+        .collect();
+    assert_eq!(histogram, vec![(0, 1), (1, 1)]);
+
+    let mut tenant_ids: Vec<_> = response
+        .tenant_stats
+        .iter()
+        .map(|tenant| tenant.tenant_id)
+        .collect();
+    tenant_ids.sort();
+    let mut expected_tenant_ids = vec![tenant_id_1, tenant_id_2];
+    expected_tenant_ids.sort();
+    assert_eq!(tenant_ids, expected_tenant_ids);
+
+    let total: u64 = response
+        .tenant_stats
+        .iter()
+        .map(|tenant| {
+            tenant.stats.enqueued
+                + tenant.stats.in_flight
+                + tenant.stats.failed
+                + tenant.stats.processed
+        })
+        .sum();
+    assert_eq!(total, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lost_lease_rejects_acknowledgement() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    // Timeouts are kept in whole seconds, so a sub-second one is refused
+    match pwq_client
+        .set_visibility_timeout(SetVisibilityTimeoutRequest {
+            plugin_id,
+            visibility_timeout: Duration::from_millis(500),
+        })
+        .await
+    {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument)
+        }
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    pwq_client
+        .set_visibility_timeout(SetVisibilityTimeoutRequest {
+            plugin_id,
+            visibility_timeout: Duration::from_secs(1),
+        })
+        .await?;
+
+    pwq_client
+        .push_execute_generator(PushExecuteGeneratorRequest::new(
+            ExecutionJob::new("slow".into(), tenant_id, trace_id, event_source_id),
+            plugin_id,
+        ))
+        .await?;
+
+    let retrieve_job = {
+        let pwq_client = pwq_client.clone();
+        move || {
+            let mut pwq_client = pwq_client.clone();
+            async move {
+                pwq_client
+                    .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+                    .await
+                    .map(only_acquired_job)
+                    .map_err(|e| eyre::eyre!(e))
+            }
+        }
+    };
+    let extend = |job: &AcquiredExecutionJob| ExtendLeaseRequest {
+        plugin_type: PluginType::Generator,
+        plugin_id,
+        leases: vec![ExecutionLease {
+            request_id: job.request_id,
+            lease_id: job.lease_id,
+        }],
+    };
+    let acknowledge = |job: &AcquiredExecutionJob| {
+        AcknowledgeGeneratorRequest::new(
+            job.request_id,
+            Some(job.lease_id),
+            None,
+            plugin_id,
+            tenant_id,
+            trace_id,
+            event_source_id,
+        )
+    };
+
+    let first = retrieve_job().await?.expect("a job");
+
+    // Extending the lease keeps the job from being handed out again
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let response = pwq_client.extend_lease(extend(&first)).await?;
+        assert!(response.lost_request_ids.is_empty());
+    }
+    assert!(retrieve_job().await?.is_none());
+
+    // Once the lease runs out, the job is handed out under a new lease
+    tokio::time::sleep(Duration::from_millis(1_500)).await;
+    let second = retrieve_job().await?.expect("the job, again");
+    assert_eq!(second.request_id, first.request_id);
+    assert_ne!(second.lease_id, first.lease_id);
+
+    // and the first lease can neither be extended nor acknowledged
+    let response = pwq_client.extend_lease(extend(&first)).await?;
+    assert_eq!(response.lost_request_ids, vec![first.request_id]);
+    assert!(pwq_client
+        .acknowledge_generator(acknowledge(&first))
+        .await
+        .is_err());
+
+    let response = pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest {
+            acknowledgements: vec![acknowledge(&first)],
+        })
+        .await?;
+    assert_eq!(response.rejected_request_ids, vec![first.request_id]);
+
+    // while the current lease can
+    let response = pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest {
+            acknowledgements: vec![acknowledge(&second)],
+        })
+        .await?;
+    assert!(response.rejected_request_ids.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_acknowledgement_without_lease() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    pwq_client
+        .push_execute_generator(PushExecuteGeneratorRequest::new(
+            ExecutionJob::new("legacy".into(), tenant_id, trace_id, event_source_id),
+            plugin_id,
+        ))
+        .await?;
+
+    let job = only_acquired_job(
+        pwq_client
+            .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, 1))
+            .await?,
+    )
+    .expect("a job");
+
+    // Consumers that predate leases acknowledge without one
+    let acknowledge = || {
+        AcknowledgeGeneratorRequest::new(
+            job.request_id,
+            None,
+            None,
+            plugin_id,
+            tenant_id,
+            trace_id,
+            event_source_id,
+        )
+    };
+    pwq_client.acknowledge_generator(acknowledge()).await?;
+
+    // but an execution is still only acknowledged once
+    assert!(pwq_client
+        .acknowledge_generator(acknowledge())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_round_robin_scheduling_with_in_flight_cap() -> eyre::Result<()> {
+    let mut pwq_client = build_grpc_client(PluginWorkQueueClientConfig::parse()).await?;
+
+    let (noisy_tenant_id, quiet_tenant_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let plugin_id = uuid::Uuid::new_v4();
+
+    // A cap of zero would starve every tenant
+    match pwq_client
+        .set_scheduling_policy(SetSchedulingPolicyRequest {
+            plugin_id,
+            scheduling_mode: SchedulingMode::RoundRobin,
+            max_in_flight_per_tenant: Some(0),
+        })
+        .await
+    {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument)
+        }
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    pwq_client
+        .set_scheduling_policy(SetSchedulingPolicyRequest {
+            plugin_id,
+            scheduling_mode: SchedulingMode::RoundRobin,
+            max_in_flight_per_tenant: Some(2),
+        })
+        .await?;
+
+    // The noisy tenant's jobs are all older than the quiet tenant's
+    for tenant_id in [
+        noisy_tenant_id,
+        noisy_tenant_id,
+        noisy_tenant_id,
+        noisy_tenant_id,
+        quiet_tenant_id,
+    ] {
+        pwq_client
+            .push_execute_generator(PushExecuteGeneratorRequest::new(
+                ExecutionJob::new("job".into(), tenant_id, trace_id, event_source_id),
+                plugin_id,
+            ))
+            .await?;
+    }
+
+    let retrieve_jobs = {
+        let pwq_client = pwq_client.clone();
+        move |max_jobs| {
+            let mut pwq_client = pwq_client.clone();
+            async move {
+                pwq_client
+                    .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id, max_jobs))
+                    .await
+                    .map(|response| response.jobs())
+                    .map_err(|e| eyre::eyre!(e))
+            }
+        }
+    };
+    let tenant_ids = |jobs: &[AcquiredExecutionJob]| {
+        let mut tenant_ids: Vec<uuid::Uuid> = jobs
+            .iter()
+            .map(|job| job.execution_job.tenant_id())
+            .collect();
+        tenant_ids.sort();
+        tenant_ids
+    };
+
+    // Both tenants get a turn, even though the noisy tenant was first
+    let first = retrieve_jobs(2).await?;
+    let mut expected = vec![noisy_tenant_id, quiet_tenant_id];
+    expected.sort();
+    assert_eq!(tenant_ids(&first), expected);
+
+    // The noisy tenant is capped at 2 jobs in flight
+    let second = retrieve_jobs(10).await?;
+    assert_eq!(tenant_ids(&second), vec![noisy_tenant_id]);
+    assert!(retrieve_jobs(10).await?.is_empty());
+
+    // Acknowledging its jobs makes room for the rest
+    let acknowledgements = first
+        .iter()
+        .chain(second.iter())
+        .map(|job| {
+            AcknowledgeGeneratorRequest::new(
+                job.request_id,
+                Some(job.lease_id),
+                None,
+                plugin_id,
+                job.execution_job.tenant_id(),
+                trace_id,
+                event_source_id,
+            )
+        })
+        .collect();
+    let response = pwq_client
+        .acknowledge_generator_batch(AcknowledgeGeneratorBatchRequest { acknowledgements })
+        .await?;
+    assert!(response.rejected_request_ids.is_empty());
+
+    let third = retrieve_jobs(10).await?;
+    assert_eq!(tenant_ids(&third), vec![noisy_tenant_id, noisy_tenant_id]);
+
+    Ok(())
+}
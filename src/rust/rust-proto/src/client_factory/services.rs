@@ -1,3 +1,6 @@
+mod analyzer;
+pub use analyzer::AnalyzerClientConfig;
+
 mod event_source;
 pub use event_source::EventSourceClientConfig;
 
@@ -0,0 +1,26 @@
+use crate::{
+    client_factory::grpc_client_config::{
+        GenericGrpcClientConfig,
+        GrpcClientConfig,
+    },
+    graplinc::grapl::api::plugin_sdk::analyzers::v1beta1::client::AnalyzerServiceClient,
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct AnalyzerClientConfig {
+    // Intentionally not marked with Clap macros; you'll rarely/never have to
+    // construct an AnalyzerClientConfig from environment variables.
+    pub analyzer_client_address: String,
+}
+
+impl From<AnalyzerClientConfig> for GenericGrpcClientConfig {
+    fn from(val: AnalyzerClientConfig) -> Self {
+        GenericGrpcClientConfig {
+            address: val.analyzer_client_address,
+        }
+    }
+}
+
+impl GrpcClientConfig for AnalyzerClientConfig {
+    type Client = AnalyzerServiceClient;
+}
@@ -92,4 +92,17 @@ impl GraphMutationClient {
             RpcConfig::default(),
         )
     }
+    pub async fn mutate_graph(
+        &mut self,
+        request: native::MutateGraphRequest,
+    ) -> Result<native::MutateGraphResponse, GraphMutationClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            mutate_graph,
+            proto::MutateGraphRequest,
+            native::MutateGraphResponse,
+            RpcConfig::default(),
+        )
+    }
 }
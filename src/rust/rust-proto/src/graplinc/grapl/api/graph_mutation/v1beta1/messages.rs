@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{
     graplinc::grapl::{
         api::graph::v1beta1::NodeProperty,
@@ -9,11 +11,20 @@ use crate::{
         },
     },
     protobufs::graplinc::grapl::api::graph_mutation::v1beta1::{
+        node_ref::Node as NodeRefNodeProto,
         CreateEdgeRequest as CreateEdgeRequestProto,
         CreateEdgeResponse as CreateEdgeResponseProto,
         CreateNodeRequest as CreateNodeRequestProto,
         CreateNodeResponse as CreateNodeResponseProto,
+        EdgeMutation as EdgeMutationProto,
+        ExistingNode as ExistingNodeProto,
+        ExistingNodeMutation as ExistingNodeMutationProto,
+        MutateGraphRequest as MutateGraphRequestProto,
+        MutateGraphResponse as MutateGraphResponseProto,
         MutationRedundancy as MutationRedundancyProto,
+        NodeMutation as NodeMutationProto,
+        NodePropertyMutation as NodePropertyMutationProto,
+        NodeRef as NodeRefProto,
         SetNodePropertyRequest as SetNodePropertyRequestProto,
         SetNodePropertyResponse as SetNodePropertyResponseProto,
     },
@@ -273,6 +284,296 @@ impl From<CreateNodeResponse> for CreateNodeResponseProto {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePropertyMutation {
+    pub property_name: PropertyName,
+    pub property: NodeProperty,
+}
+
+impl TryFrom<NodePropertyMutationProto> for NodePropertyMutation {
+    type Error = SerDeError;
+    fn try_from(proto: NodePropertyMutationProto) -> Result<Self, Self::Error> {
+        let property_name = proto
+            .property_name
+            .ok_or(SerDeError::MissingField("property_name"))?
+            .try_into()?;
+        let property = proto
+            .property
+            .ok_or(SerDeError::MissingField("property"))?
+            .try_into()?;
+        Ok(Self {
+            property_name,
+            property,
+        })
+    }
+}
+
+impl From<NodePropertyMutation> for NodePropertyMutationProto {
+    fn from(value: NodePropertyMutation) -> Self {
+        Self {
+            property_name: Some(value.property_name.into()),
+            property: Some(value.property.into()),
+        }
+    }
+}
+
+/// A node to create as part of a `MutateGraphRequest`. The `node_key` is only
+/// meaningful within that request, where edges use it to refer to the node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMutation {
+    pub node_key: String,
+    pub node_type: NodeType,
+    pub properties: Vec<NodePropertyMutation>,
+}
+
+impl TryFrom<NodeMutationProto> for NodeMutation {
+    type Error = SerDeError;
+    fn try_from(proto: NodeMutationProto) -> Result<Self, Self::Error> {
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        let properties = proto
+            .properties
+            .into_iter()
+            .map(NodePropertyMutation::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            node_key: proto.node_key,
+            node_type,
+            properties,
+        })
+    }
+}
+
+impl From<NodeMutation> for NodeMutationProto {
+    fn from(value: NodeMutation) -> Self {
+        Self {
+            node_key: value.node_key,
+            node_type: Some(value.node_type.into()),
+            properties: value.properties.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A node that was created before the `MutateGraphRequest` referring to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistingNode {
+    pub uid: Uid,
+    pub node_type: NodeType,
+}
+
+impl TryFrom<ExistingNodeProto> for ExistingNode {
+    type Error = SerDeError;
+    fn try_from(proto: ExistingNodeProto) -> Result<Self, Self::Error> {
+        let uid = proto
+            .uid
+            .ok_or(SerDeError::MissingField("uid"))?
+            .try_into()?;
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        Ok(Self { uid, node_type })
+    }
+}
+
+impl From<ExistingNode> for ExistingNodeProto {
+    fn from(value: ExistingNode) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            node_type: Some(value.node_type.into()),
+        }
+    }
+}
+
+/// Refers to either a node created by the same `MutateGraphRequest`, by its
+/// `node_key`, or to a node that already exists.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeRef {
+    NodeKey(String),
+    ExistingNode(ExistingNode),
+}
+
+impl TryFrom<NodeRefProto> for NodeRef {
+    type Error = SerDeError;
+    fn try_from(proto: NodeRefProto) -> Result<Self, Self::Error> {
+        match proto.node {
+            Some(NodeRefNodeProto::NodeKey(node_key)) => Ok(NodeRef::NodeKey(node_key)),
+            Some(NodeRefNodeProto::ExistingNode(existing_node)) => {
+                Ok(NodeRef::ExistingNode(existing_node.try_into()?))
+            }
+            None => Err(SerDeError::MissingField("node")),
+        }
+    }
+}
+
+impl From<NodeRef> for NodeRefProto {
+    fn from(value: NodeRef) -> Self {
+        let node = match value {
+            NodeRef::NodeKey(node_key) => NodeRefNodeProto::NodeKey(node_key),
+            NodeRef::ExistingNode(existing_node) => {
+                NodeRefNodeProto::ExistingNode(existing_node.into())
+            }
+        };
+        Self { node: Some(node) }
+    }
+}
+
+/// Properties to set on a node that already exists, as part of a
+/// `MutateGraphRequest`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistingNodeMutation {
+    pub node: ExistingNode,
+    pub properties: Vec<NodePropertyMutation>,
+}
+
+impl TryFrom<ExistingNodeMutationProto> for ExistingNodeMutation {
+    type Error = SerDeError;
+    fn try_from(proto: ExistingNodeMutationProto) -> Result<Self, Self::Error> {
+        let node = proto
+            .node
+            .ok_or(SerDeError::MissingField("node"))?
+            .try_into()?;
+        let properties = proto
+            .properties
+            .into_iter()
+            .map(NodePropertyMutation::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { node, properties })
+    }
+}
+
+impl From<ExistingNodeMutation> for ExistingNodeMutationProto {
+    fn from(value: ExistingNodeMutation) -> Self {
+        Self {
+            node: Some(value.node.into()),
+            properties: value.properties.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeMutation {
+    pub edge_name: EdgeName,
+    pub from: NodeRef,
+    pub to: NodeRef,
+}
+
+impl TryFrom<EdgeMutationProto> for EdgeMutation {
+    type Error = SerDeError;
+    fn try_from(proto: EdgeMutationProto) -> Result<Self, Self::Error> {
+        let edge_name = proto
+            .edge_name
+            .ok_or(SerDeError::MissingField("edge_name"))?
+            .try_into()?;
+        let from = proto
+            .from
+            .ok_or(SerDeError::MissingField("from"))?
+            .try_into()?;
+        let to = proto.to.ok_or(SerDeError::MissingField("to"))?.try_into()?;
+        Ok(Self {
+            edge_name,
+            from,
+            to,
+        })
+    }
+}
+
+impl From<EdgeMutation> for EdgeMutationProto {
+    fn from(value: EdgeMutation) -> Self {
+        Self {
+            edge_name: Some(value.edge_name.into()),
+            from: Some(value.from.into()),
+            to: Some(value.to.into()),
+        }
+    }
+}
+
+/// Creates nodes, sets properties and creates edges in a single request. Each
+/// node is allocated a new uid whenever the request is handled, so retrying
+/// a request that failed partway through writes its nodes again, under new
+/// uids.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutateGraphRequest {
+    pub tenant_id: uuid::Uuid,
+    pub nodes: Vec<NodeMutation>,
+    pub edges: Vec<EdgeMutation>,
+    pub existing_nodes: Vec<ExistingNodeMutation>,
+}
+
+impl TryFrom<MutateGraphRequestProto> for MutateGraphRequest {
+    type Error = SerDeError;
+    fn try_from(proto: MutateGraphRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+        let nodes = proto
+            .nodes
+            .into_iter()
+            .map(NodeMutation::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let edges = proto
+            .edges
+            .into_iter()
+            .map(EdgeMutation::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let existing_nodes = proto
+            .existing_nodes
+            .into_iter()
+            .map(ExistingNodeMutation::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            tenant_id,
+            nodes,
+            edges,
+            existing_nodes,
+        })
+    }
+}
+
+impl From<MutateGraphRequest> for MutateGraphRequestProto {
+    fn from(value: MutateGraphRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            nodes: value.nodes.into_iter().map(Into::into).collect(),
+            edges: value.edges.into_iter().map(Into::into).collect(),
+            existing_nodes: value.existing_nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutateGraphResponse {
+    /// The uid allocated for each node, keyed by its `node_key`
+    pub uids: HashMap<String, Uid>,
+}
+
+impl TryFrom<MutateGraphResponseProto> for MutateGraphResponse {
+    type Error = SerDeError;
+    fn try_from(proto: MutateGraphResponseProto) -> Result<Self, Self::Error> {
+        let uids = proto
+            .uids
+            .into_iter()
+            .map(|(node_key, uid)| Ok((node_key, uid.try_into()?)))
+            .collect::<Result<HashMap<_, _>, SerDeError>>()?;
+        Ok(Self { uids })
+    }
+}
+
+impl From<MutateGraphResponse> for MutateGraphResponseProto {
+    fn from(value: MutateGraphResponse) -> Self {
+        Self {
+            uids: value
+                .uids
+                .into_iter()
+                .map(|(node_key, uid)| (node_key, uid.into()))
+                .collect(),
+        }
+    }
+}
+
 impl serde_impl::ProtobufSerializable for SetNodePropertyRequest {
     type ProtobufMessage = SetNodePropertyRequestProto;
 }
@@ -327,3 +628,21 @@ impl type_url::TypeUrl for CreateNodeResponse {
         "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.CreateNodeResponse\
     ";
 }
+
+impl serde_impl::ProtobufSerializable for MutateGraphRequest {
+    type ProtobufMessage = MutateGraphRequestProto;
+}
+
+impl type_url::TypeUrl for MutateGraphRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.MutateGraphRequest";
+}
+
+impl serde_impl::ProtobufSerializable for MutateGraphResponse {
+    type ProtobufMessage = MutateGraphResponseProto;
+}
+
+impl type_url::TypeUrl for MutateGraphResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.MutateGraphResponse";
+}
@@ -61,6 +61,10 @@ pub trait GraphMutationApi {
         &self,
         request: native::CreateEdgeRequest,
     ) -> Result<native::CreateEdgeResponse, Self::Error>;
+    async fn mutate_graph(
+        &self,
+        request: native::MutateGraphRequest,
+    ) -> Result<native::MutateGraphResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -91,6 +95,14 @@ where
     ) -> Result<tonic::Response<proto::CreateEdgeResponse>, tonic::Status> {
         execute_rpc!(self, request, create_edge)
     }
+    /// MutateGraph creates every node, property and edge in the request,
+    /// returning the uids allocated for each node keyed by its node_key.
+    async fn mutate_graph(
+        &self,
+        request: tonic::Request<proto::MutateGraphRequest>,
+    ) -> Result<tonic::Response<proto::MutateGraphResponse>, tonic::Status> {
+        execute_rpc!(self, request, mutate_graph)
+    }
 }
 
 /**
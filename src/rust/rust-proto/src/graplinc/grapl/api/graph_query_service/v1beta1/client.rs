@@ -79,4 +79,18 @@ impl GraphQueryClient {
             RpcConfig::default(),
         )
     }
+
+    pub async fn query_graph_by_type(
+        &mut self,
+        request: native::QueryGraphByTypeRequest,
+    ) -> Result<native::QueryGraphByTypeResponse, GraphQueryClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            query_graph_by_type,
+            proto::QueryGraphByTypeRequest,
+            native::QueryGraphByTypeResponse,
+            RpcConfig::default(),
+        )
+    }
 }
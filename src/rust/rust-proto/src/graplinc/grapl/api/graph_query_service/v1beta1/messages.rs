@@ -6,11 +6,14 @@ use rustc_hash::{
 };
 
 use crate::{
-    graplinc::grapl::common::v1beta1::types::{
-        EdgeName,
-        NodeType,
-        PropertyName,
-        Uid,
+    graplinc::{
+        common::v1beta1::Duration,
+        grapl::common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
     },
     protobufs::graplinc::grapl::api::graph_query_service::v1beta1 as proto,
     SerDeError,
@@ -527,6 +530,10 @@ impl NodePropertyQuery {
         debug_assert_eq!(self.query_id, other.query_id);
         debug_assert_eq!(self.node_type, other.node_type);
         self.string_filters.extend(other.string_filters);
+        self.int_filters.extend(other.int_filters);
+        self.uid_filters
+            .uid_filters
+            .extend(other.uid_filters.uid_filters);
     }
 
     pub fn with_string_filters(
@@ -626,12 +633,45 @@ impl From<NodePropertyQuery> for proto::NodePropertyQuery {
     }
 }
 
+/// Bounds on how much of the graph a `GraphQuery` may traverse. `None` means
+/// no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryLimits {
+    pub max_edges_per_hop: Option<u32>,
+    pub max_depth: Option<u32>,
+    pub max_nodes: Option<u32>,
+    pub timeout: Option<Duration>,
+}
+
+impl From<proto::QueryLimits> for QueryLimits {
+    fn from(value: proto::QueryLimits) -> Self {
+        Self {
+            max_edges_per_hop: value.max_edges_per_hop,
+            max_depth: value.max_depth,
+            max_nodes: value.max_nodes,
+            timeout: value.timeout.map(Duration::from),
+        }
+    }
+}
+
+impl From<QueryLimits> for proto::QueryLimits {
+    fn from(value: QueryLimits) -> Self {
+        Self {
+            max_edges_per_hop: value.max_edges_per_hop,
+            max_depth: value.max_depth,
+            max_nodes: value.max_nodes,
+            timeout: value.timeout.map(Into::into),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GraphQuery {
     pub root_query_id: QueryId,
     pub node_property_queries: FxHashMap<QueryId, NodePropertyQuery>,
     pub edge_filters: FxHashMap<(QueryId, EdgeName), FxHashSet<QueryId>>,
     pub edge_map: FxHashMap<EdgeName, EdgeName>,
+    pub limits: QueryLimits,
 }
 
 impl GraphQuery {
@@ -735,6 +775,7 @@ impl TryFrom<proto::GraphQuery> for GraphQuery {
             node_property_queries,
             edge_filters,
             edge_map,
+            limits: value.limits.map(QueryLimits::from).unwrap_or_default(),
         })
     }
 }
@@ -779,6 +820,7 @@ impl From<GraphQuery> for proto::GraphQuery {
             node_property_queries,
             edge_filters,
             edge_map,
+            limits: Some(value.limits.into()),
         }
     }
 }
@@ -831,11 +873,81 @@ impl From<StringProperties> for proto::StringProperties {
     }
 }
 
+/// The value of an int property, keeping the signedness of its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntPropertyValue {
+    I64(i64),
+    U64(u64),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IntProperties {
+    pub prop_map: FxHashMap<PropertyName, IntPropertyValue>,
+}
+
+impl IntProperties {
+    pub fn merge(&mut self, other: Self) {
+        self.prop_map.extend(other.prop_map);
+    }
+
+    pub fn add_int_property(&mut self, property_name: PropertyName, value: IntPropertyValue) {
+        self.prop_map.insert(property_name, value);
+    }
+}
+
+impl TryFrom<proto::IntProperties> for IntProperties {
+    type Error = SerDeError;
+    fn try_from(value: proto::IntProperties) -> Result<Self, Self::Error> {
+        let mut prop_map = FxHashMap::default();
+        prop_map.reserve(value.properties.len());
+
+        for int_property in value.properties {
+            let property_name = int_property
+                .property_name
+                .ok_or_else(|| SerDeError::MissingField("property_name"))?;
+            let property_value = match int_property
+                .property_value
+                .ok_or_else(|| SerDeError::MissingField("property_value"))?
+            {
+                proto::int_property::PropertyValue::I64Value(value) => IntPropertyValue::I64(value),
+                proto::int_property::PropertyValue::U64Value(value) => IntPropertyValue::U64(value),
+            };
+            prop_map.insert(property_name.try_into()?, property_value);
+        }
+
+        Ok(Self { prop_map })
+    }
+}
+
+impl From<IntProperties> for proto::IntProperties {
+    fn from(value: IntProperties) -> Self {
+        let props_as_vec: Vec<proto::IntProperty> = value
+            .prop_map
+            .into_iter()
+            .map(|(k, v)| proto::IntProperty {
+                property_name: Some(k.into()),
+                property_value: Some(match v {
+                    IntPropertyValue::I64(value) => {
+                        proto::int_property::PropertyValue::I64Value(value)
+                    }
+                    IntPropertyValue::U64(value) => {
+                        proto::int_property::PropertyValue::U64Value(value)
+                    }
+                }),
+            })
+            .collect();
+        proto::IntProperties {
+            properties: props_as_vec,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NodePropertiesView {
     pub uid: Uid,
     pub node_type: NodeType,
     pub string_properties: StringProperties,
+    pub int_properties: IntProperties,
 }
 
 impl NodePropertiesView {
@@ -844,6 +956,7 @@ impl NodePropertiesView {
             uid,
             node_type,
             string_properties,
+            int_properties: IntProperties::default(),
         }
     }
 
@@ -851,12 +964,17 @@ impl NodePropertiesView {
         debug_assert_eq!(self.uid, other.uid);
         debug_assert_eq!(self.node_type, other.node_type);
         self.string_properties.merge(other.string_properties);
+        self.int_properties.merge(other.int_properties);
     }
 
     pub fn add_string_property(&mut self, property_name: PropertyName, value: String) {
         self.string_properties
             .add_string_property(property_name, value);
     }
+
+    pub fn add_int_property(&mut self, property_name: PropertyName, value: IntPropertyValue) {
+        self.int_properties.add_int_property(property_name, value);
+    }
 }
 
 impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
@@ -868,6 +986,13 @@ impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
 
         let string_properties = StringProperties::try_from(proto_string_properties)?;
 
+        // Older servers don't send int properties at all
+        let int_properties = value
+            .int_properties
+            .map(IntProperties::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Self {
             uid: value
                 .uid
@@ -878,6 +1003,7 @@ impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
                 .ok_or(SerDeError::MissingField("node_type"))?
                 .try_into()?,
             string_properties,
+            int_properties,
         })
     }
 }
@@ -885,11 +1011,13 @@ impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
 impl From<NodePropertiesView> for proto::NodePropertiesView {
     fn from(value: NodePropertiesView) -> Self {
         let string_properties = proto::StringProperties::from(value.string_properties);
+        let int_properties = proto::IntProperties::from(value.int_properties);
 
         Self {
             uid: Some(value.uid.into()),
             node_type: Some(value.node_type.into()),
             string_properties: Some(string_properties),
+            int_properties: Some(int_properties),
         }
     }
 }
@@ -1053,6 +1181,7 @@ impl From<EdgeViewMap> for proto::EdgeViewMap {
 pub struct GraphView {
     pub nodes: FxHashMap<Uid, NodePropertiesView>,
     pub edges: FxHashMap<(Uid, EdgeName), FxHashSet<Uid>>,
+    pub truncated: bool,
 }
 
 impl GraphView {
@@ -1106,6 +1235,8 @@ impl GraphView {
         for ((src_uid, edge_name), dst_uids) in other.edges.into_iter() {
             self.add_edges(src_uid.clone(), edge_name.clone(), dst_uids.clone());
         }
+
+        self.truncated |= other.truncated;
     }
 
     pub fn get_nodes(&self) -> &FxHashMap<Uid, NodePropertiesView> {
@@ -1128,6 +1259,7 @@ impl TryFrom<proto::GraphView> for GraphView {
         Ok(Self {
             nodes: nodes.entries,
             edges: edges.entries,
+            truncated: value.truncated,
         })
     }
 }
@@ -1145,6 +1277,7 @@ impl From<GraphView> for proto::GraphView {
         Self {
             nodes: Some(nodes),
             edges: Some(edges),
+            truncated: value.truncated,
         }
     }
 }
@@ -1354,3 +1487,74 @@ impl From<QueryGraphFromUidResponse> for proto::QueryGraphFromUidResponse {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct QueryGraphByTypeRequest {
+    pub tenant_id: uuid::Uuid,
+    pub graph_query: GraphQuery,
+    pub page_size: u32,
+    pub page_token: Option<Uid>,
+}
+
+impl TryFrom<proto::QueryGraphByTypeRequest> for QueryGraphByTypeRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::QueryGraphByTypeRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            graph_query: value
+                .graph_query
+                .ok_or(SerDeError::MissingField("graph_query"))?
+                .try_into()?,
+            page_size: value.page_size,
+            page_token: value.page_token.map(Uid::try_from).transpose()?,
+        })
+    }
+}
+
+impl From<QueryGraphByTypeRequest> for proto::QueryGraphByTypeRequest {
+    fn from(value: QueryGraphByTypeRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            graph_query: Some(value.graph_query.into()),
+            page_size: value.page_size,
+            page_token: value.page_token.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryGraphByTypeResponse {
+    pub matched_graphs: Vec<MatchedGraphWithUid>,
+    pub next_page_token: Option<Uid>,
+}
+
+impl TryFrom<proto::QueryGraphByTypeResponse> for QueryGraphByTypeResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::QueryGraphByTypeResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            matched_graphs: value
+                .matched_graphs
+                .into_iter()
+                .map(MatchedGraphWithUid::try_from)
+                .collect::<Result<_, _>>()?,
+            next_page_token: value.next_page_token.map(Uid::try_from).transpose()?,
+        })
+    }
+}
+
+impl From<QueryGraphByTypeResponse> for proto::QueryGraphByTypeResponse {
+    fn from(value: QueryGraphByTypeResponse) -> Self {
+        Self {
+            matched_graphs: value
+                .matched_graphs
+                .into_iter()
+                .map(proto::MatchedGraphWithUid::from)
+                .collect(),
+            next_page_token: value.next_page_token.map(Into::into),
+        }
+    }
+}
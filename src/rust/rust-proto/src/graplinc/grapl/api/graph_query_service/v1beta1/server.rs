@@ -22,6 +22,8 @@ use tonic::transport::{
 use crate::{
     execute_rpc,
     graplinc::grapl::api::graph_query_service::v1beta1::messages::{
+        QueryGraphByTypeRequest,
+        QueryGraphByTypeResponse,
         QueryGraphFromUidRequest,
         QueryGraphFromUidResponse,
         QueryGraphWithUidRequest,
@@ -32,6 +34,8 @@ use crate::{
             GraphQueryService as GraphQueryServiceProto,
             GraphQueryServiceServer as GraphQueryServiceServerProto,
         },
+        QueryGraphByTypeRequest as QueryGraphByTypeRequestProto,
+        QueryGraphByTypeResponse as QueryGraphByTypeResponseProto,
         QueryGraphFromUidRequest as QueryGraphFromUidRequestProto,
         QueryGraphFromUidResponse as QueryGraphFromUidResponseProto,
         QueryGraphWithUidRequest as QueryGraphWithUidRequestProto,
@@ -68,6 +72,10 @@ pub trait GraphQueryApi {
         &self,
         request: QueryGraphFromUidRequest,
     ) -> Result<QueryGraphFromUidResponse, Self::Error>;
+    async fn query_graph_by_type(
+        &self,
+        request: QueryGraphByTypeRequest,
+    ) -> Result<QueryGraphByTypeResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -88,6 +96,13 @@ where
     ) -> Result<tonic::Response<QueryGraphFromUidResponseProto>, tonic::Status> {
         execute_rpc!(self, request, query_graph_from_uid)
     }
+
+    async fn query_graph_by_type(
+        &self,
+        request: tonic::Request<QueryGraphByTypeRequestProto>,
+    ) -> Result<tonic::Response<QueryGraphByTypeResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, query_graph_by_type)
+    }
 }
 
 /**
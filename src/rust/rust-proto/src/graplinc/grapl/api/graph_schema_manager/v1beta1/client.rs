@@ -81,4 +81,74 @@ impl GraphSchemaManagerClient {
             RpcConfig::default(),
         )
     }
+
+    pub async fn get_node_identity_algorithm(
+        &mut self,
+        request: native::GetNodeIdentityAlgorithmRequest,
+    ) -> Result<native::GetNodeIdentityAlgorithmResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_node_identity_algorithm,
+            proto::GetNodeIdentityAlgorithmRequest,
+            native::GetNodeIdentityAlgorithmResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    pub async fn get_node_schema(
+        &mut self,
+        request: native::GetNodeSchemaRequest,
+    ) -> Result<native::GetNodeSchemaResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_node_schema,
+            proto::GetNodeSchemaRequest,
+            native::GetNodeSchemaResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    pub async fn list_node_schemas(
+        &mut self,
+        request: native::ListNodeSchemasRequest,
+    ) -> Result<native::ListNodeSchemasResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            list_node_schemas,
+            proto::ListNodeSchemasRequest,
+            native::ListNodeSchemasResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    pub async fn get_raw_schema(
+        &mut self,
+        request: native::GetRawSchemaRequest,
+    ) -> Result<native::GetRawSchemaResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_raw_schema,
+            proto::GetRawSchemaRequest,
+            native::GetRawSchemaResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    pub async fn diff_schemas(
+        &mut self,
+        request: native::DiffSchemasRequest,
+    ) -> Result<native::DiffSchemasResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            diff_schemas,
+            proto::DiffSchemasRequest,
+            native::DiffSchemasResponse,
+            RpcConfig::default(),
+        )
+    }
 }
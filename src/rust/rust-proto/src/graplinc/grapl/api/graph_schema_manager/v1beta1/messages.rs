@@ -1,17 +1,45 @@
+use std::time::SystemTime;
+
 use bytes::Bytes;
 
 use crate::{
     graplinc::grapl::common::v1beta1::types::{
         EdgeName,
         NodeType,
+        PropertyName,
     },
     protobufs::graplinc::grapl::api::graph_schema_manager::v1beta1::{
+        get_node_identity_algorithm_response::IdentityAlgorithm as IdentityAlgorithmProto,
         DeploySchemaRequest as DeploySchemaRequestProto,
         DeploySchemaResponse as DeploySchemaResponseProto,
+        DiffSchemasRequest as DiffSchemasRequestProto,
+        DiffSchemasResponse as DiffSchemasResponseProto,
         EdgeCardinality as EdgeCardinalityProto,
+        EdgeSchema as EdgeSchemaProto,
+        EdgeSchemaChange as EdgeSchemaChangeProto,
         GetEdgeSchemaRequest as GetEdgeSchemaRequestProto,
         GetEdgeSchemaResponse as GetEdgeSchemaResponseProto,
+        GetNodeIdentityAlgorithmRequest as GetNodeIdentityAlgorithmRequestProto,
+        GetNodeIdentityAlgorithmResponse as GetNodeIdentityAlgorithmResponseProto,
+        GetNodeSchemaRequest as GetNodeSchemaRequestProto,
+        GetNodeSchemaResponse as GetNodeSchemaResponseProto,
+        GetRawSchemaRequest as GetRawSchemaRequestProto,
+        GetRawSchemaResponse as GetRawSchemaResponseProto,
+        ListNodeSchemasRequest as ListNodeSchemasRequestProto,
+        ListNodeSchemasResponse as ListNodeSchemasResponseProto,
+        NodeSchemaDiff as NodeSchemaDiffProto,
+        NodeSchemaVersion as NodeSchemaVersionProto,
+        NodeValidationReport as NodeValidationReportProto,
+        PropertySchema as PropertySchemaProto,
+        PropertySchemaChange as PropertySchemaChangeProto,
+        PropertyType as PropertyTypeProto,
+        SchemaCompatibilityViolation as SchemaCompatibilityViolationProto,
+        SchemaCompatibilityViolationKind as SchemaCompatibilityViolationKindProto,
         SchemaType as SchemaTypeProto,
+        SchemaViolation as SchemaViolationProto,
+        SchemaViolationKind as SchemaViolationKindProto,
+        SessionIdentityArguments as SessionIdentityArgumentsProto,
+        StaticIdentityArguments as StaticIdentityArgumentsProto,
     },
     serde_impl,
     type_url,
@@ -53,6 +81,8 @@ pub struct DeploySchemaRequest {
     pub schema: Bytes,
     pub schema_type: SchemaType,
     pub schema_version: u32,
+    /// Deploy even if the schema is incompatible with the previous version
+    pub force: bool,
 }
 
 impl TryFrom<DeploySchemaRequestProto> for DeploySchemaRequest {
@@ -78,6 +108,7 @@ impl TryFrom<DeploySchemaRequestProto> for DeploySchemaRequest {
             schema,
             schema_type,
             schema_version: value.schema_version,
+            force: value.force,
         })
     }
 }
@@ -90,6 +121,7 @@ impl From<DeploySchemaRequest> for DeploySchemaRequestProto {
             schema_type: schema_type as i32,
             schema: value.schema,
             schema_version: value.schema_version,
+            force: value.force,
         }
     }
 }
@@ -103,22 +135,141 @@ impl serde_impl::ProtobufSerializable for DeploySchemaRequest {
     type ProtobufMessage = DeploySchemaRequestProto;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaCompatibilityViolationKind {
+    PropertyTypeChanged,
+    IdentityAlgorithmChanged,
+    ReverseEdgeRemoved,
+    EdgeCardinalityChanged,
+    ReverseEdgeRenamed,
+    EdgeRemoved,
+}
+
+impl TryFrom<SchemaCompatibilityViolationKindProto> for SchemaCompatibilityViolationKind {
+    type Error = SerDeError;
+
+    fn try_from(proto: SchemaCompatibilityViolationKindProto) -> Result<Self, Self::Error> {
+        match proto {
+            SchemaCompatibilityViolationKindProto::PropertyTypeChanged => {
+                Ok(SchemaCompatibilityViolationKind::PropertyTypeChanged)
+            }
+            SchemaCompatibilityViolationKindProto::IdentityAlgorithmChanged => {
+                Ok(SchemaCompatibilityViolationKind::IdentityAlgorithmChanged)
+            }
+            SchemaCompatibilityViolationKindProto::ReverseEdgeRemoved => {
+                Ok(SchemaCompatibilityViolationKind::ReverseEdgeRemoved)
+            }
+            SchemaCompatibilityViolationKindProto::EdgeCardinalityChanged => {
+                Ok(SchemaCompatibilityViolationKind::EdgeCardinalityChanged)
+            }
+            SchemaCompatibilityViolationKindProto::ReverseEdgeRenamed => {
+                Ok(SchemaCompatibilityViolationKind::ReverseEdgeRenamed)
+            }
+            SchemaCompatibilityViolationKindProto::EdgeRemoved => {
+                Ok(SchemaCompatibilityViolationKind::EdgeRemoved)
+            }
+            SchemaCompatibilityViolationKindProto::Unspecified => Err(SerDeError::UnknownVariant(
+                "SchemaCompatibilityViolationKind",
+            )),
+        }
+    }
+}
+
+impl From<SchemaCompatibilityViolationKind> for SchemaCompatibilityViolationKindProto {
+    fn from(kind: SchemaCompatibilityViolationKind) -> Self {
+        match kind {
+            SchemaCompatibilityViolationKind::PropertyTypeChanged => {
+                SchemaCompatibilityViolationKindProto::PropertyTypeChanged
+            }
+            SchemaCompatibilityViolationKind::IdentityAlgorithmChanged => {
+                SchemaCompatibilityViolationKindProto::IdentityAlgorithmChanged
+            }
+            SchemaCompatibilityViolationKind::ReverseEdgeRemoved => {
+                SchemaCompatibilityViolationKindProto::ReverseEdgeRemoved
+            }
+            SchemaCompatibilityViolationKind::EdgeCardinalityChanged => {
+                SchemaCompatibilityViolationKindProto::EdgeCardinalityChanged
+            }
+            SchemaCompatibilityViolationKind::ReverseEdgeRenamed => {
+                SchemaCompatibilityViolationKindProto::ReverseEdgeRenamed
+            }
+            SchemaCompatibilityViolationKind::EdgeRemoved => {
+                SchemaCompatibilityViolationKindProto::EdgeRemoved
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaCompatibilityViolation {
+    pub node_type: NodeType,
+    pub kind: SchemaCompatibilityViolationKind,
+    pub description: String,
+}
+
+impl TryFrom<SchemaCompatibilityViolationProto> for SchemaCompatibilityViolation {
+    type Error = SerDeError;
+
+    fn try_from(proto: SchemaCompatibilityViolationProto) -> Result<Self, Self::Error> {
+        let kind = proto.kind().try_into()?;
+
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField(
+                "SchemaCompatibilityViolation.node_type",
+            ))?
+            .try_into()?;
+
+        Ok(SchemaCompatibilityViolation {
+            node_type,
+            kind,
+            description: proto.description,
+        })
+    }
+}
+
+impl From<SchemaCompatibilityViolation> for SchemaCompatibilityViolationProto {
+    fn from(violation: SchemaCompatibilityViolation) -> Self {
+        let kind: SchemaCompatibilityViolationKindProto = violation.kind.into();
+        SchemaCompatibilityViolationProto {
+            node_type: Some(violation.node_type.into()),
+            kind: kind as i32,
+            description: violation.description,
+        }
+    }
+}
+
+impl type_url::TypeUrl for SchemaCompatibilityViolation {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.SchemaCompatibilityViolation";
+}
+
+impl serde_impl::ProtobufSerializable for SchemaCompatibilityViolation {
+    type ProtobufMessage = SchemaCompatibilityViolationProto;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct DeploySchemaResponse {}
+pub struct DeploySchemaResponse {
+    /// Incompatible changes relative to the previously deployed version,
+    /// which were deployed because `force` was set
+    pub violations: Vec<SchemaCompatibilityViolation>,
+}
 
 impl TryFrom<DeploySchemaResponseProto> for DeploySchemaResponse {
     type Error = SerDeError;
 
     fn try_from(response_proto: DeploySchemaResponseProto) -> Result<Self, Self::Error> {
-        let DeploySchemaResponseProto {} = response_proto;
-        Ok(DeploySchemaResponse {})
+        Ok(DeploySchemaResponse {
+            violations: try_from_all(response_proto.violations)?,
+        })
     }
 }
 
 impl From<DeploySchemaResponse> for DeploySchemaResponseProto {
     fn from(response: DeploySchemaResponse) -> Self {
-        let DeploySchemaResponse {} = response;
-        DeploySchemaResponseProto {}
+        DeploySchemaResponseProto {
+            violations: into_all(response.violations),
+        }
     }
 }
 
@@ -265,3 +416,988 @@ impl type_url::TypeUrl for EdgeCardinality {
     const TYPE_URL: &'static str =
         "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.EdgeCardinality";
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeIdentityAlgorithmRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+}
+
+impl TryFrom<GetNodeIdentityAlgorithmRequestProto> for GetNodeIdentityAlgorithmRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: GetNodeIdentityAlgorithmRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField(
+                "GetNodeIdentityAlgorithmRequest.tenant_id",
+            ))?
+            .into();
+
+        let node_type = request_proto
+            .node_type
+            .ok_or(SerDeError::MissingField(
+                "GetNodeIdentityAlgorithmRequest.node_type",
+            ))?
+            .try_into()?;
+
+        Ok(GetNodeIdentityAlgorithmRequest {
+            tenant_id,
+            node_type,
+        })
+    }
+}
+
+impl From<GetNodeIdentityAlgorithmRequest> for GetNodeIdentityAlgorithmRequestProto {
+    fn from(request: GetNodeIdentityAlgorithmRequest) -> Self {
+        GetNodeIdentityAlgorithmRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            node_type: Some(request.node_type.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeIdentityAlgorithmRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeIdentityAlgorithmRequest";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeIdentityAlgorithmRequest {
+    type ProtobufMessage = GetNodeIdentityAlgorithmRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionIdentityArguments {
+    pub pseudo_key_properties: Vec<PropertyName>,
+    pub creation_timestamp_property: PropertyName,
+    pub last_seen_timestamp_property: PropertyName,
+    pub termination_timestamp_property: PropertyName,
+}
+
+impl TryFrom<SessionIdentityArgumentsProto> for SessionIdentityArguments {
+    type Error = SerDeError;
+
+    fn try_from(args_proto: SessionIdentityArgumentsProto) -> Result<Self, Self::Error> {
+        let pseudo_key_properties = args_proto
+            .pseudo_key_properties
+            .into_iter()
+            .map(PropertyName::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let creation_timestamp_property = args_proto
+            .creation_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentityArguments.creation_timestamp_property",
+            ))?
+            .try_into()?;
+
+        let last_seen_timestamp_property = args_proto
+            .last_seen_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentityArguments.last_seen_timestamp_property",
+            ))?
+            .try_into()?;
+
+        let termination_timestamp_property = args_proto
+            .termination_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentityArguments.termination_timestamp_property",
+            ))?
+            .try_into()?;
+
+        Ok(SessionIdentityArguments {
+            pseudo_key_properties,
+            creation_timestamp_property,
+            last_seen_timestamp_property,
+            termination_timestamp_property,
+        })
+    }
+}
+
+impl From<SessionIdentityArguments> for SessionIdentityArgumentsProto {
+    fn from(args: SessionIdentityArguments) -> Self {
+        SessionIdentityArgumentsProto {
+            pseudo_key_properties: args
+                .pseudo_key_properties
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            creation_timestamp_property: Some(args.creation_timestamp_property.into()),
+            last_seen_timestamp_property: Some(args.last_seen_timestamp_property.into()),
+            termination_timestamp_property: Some(args.termination_timestamp_property.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StaticIdentityArguments {
+    pub static_key_properties: Vec<PropertyName>,
+}
+
+impl TryFrom<StaticIdentityArgumentsProto> for StaticIdentityArguments {
+    type Error = SerDeError;
+
+    fn try_from(args_proto: StaticIdentityArgumentsProto) -> Result<Self, Self::Error> {
+        let static_key_properties = args_proto
+            .static_key_properties
+            .into_iter()
+            .map(PropertyName::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(StaticIdentityArguments {
+            static_key_properties,
+        })
+    }
+}
+
+impl From<StaticIdentityArguments> for StaticIdentityArgumentsProto {
+    fn from(args: StaticIdentityArguments) -> Self {
+        StaticIdentityArgumentsProto {
+            static_key_properties: args
+                .static_key_properties
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IdentityAlgorithm {
+    Session(SessionIdentityArguments),
+    Static(StaticIdentityArguments),
+}
+
+impl TryFrom<IdentityAlgorithmProto> for IdentityAlgorithm {
+    type Error = SerDeError;
+
+    fn try_from(algorithm_proto: IdentityAlgorithmProto) -> Result<Self, Self::Error> {
+        match algorithm_proto {
+            IdentityAlgorithmProto::Session(args) => {
+                Ok(IdentityAlgorithm::Session(args.try_into()?))
+            }
+            IdentityAlgorithmProto::Static(args) => Ok(IdentityAlgorithm::Static(args.try_into()?)),
+        }
+    }
+}
+
+impl From<IdentityAlgorithm> for IdentityAlgorithmProto {
+    fn from(algorithm: IdentityAlgorithm) -> Self {
+        match algorithm {
+            IdentityAlgorithm::Session(args) => IdentityAlgorithmProto::Session(args.into()),
+            IdentityAlgorithm::Static(args) => IdentityAlgorithmProto::Static(args.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeIdentityAlgorithmResponse {
+    pub identity_algorithm: IdentityAlgorithm,
+    pub schema_version: u32,
+}
+
+impl TryFrom<GetNodeIdentityAlgorithmResponseProto> for GetNodeIdentityAlgorithmResponse {
+    type Error = SerDeError;
+
+    fn try_from(
+        response_proto: GetNodeIdentityAlgorithmResponseProto,
+    ) -> Result<Self, Self::Error> {
+        let identity_algorithm = response_proto
+            .identity_algorithm
+            .ok_or(SerDeError::MissingField(
+                "GetNodeIdentityAlgorithmResponse.identity_algorithm",
+            ))?
+            .try_into()?;
+
+        Ok(GetNodeIdentityAlgorithmResponse {
+            identity_algorithm,
+            schema_version: response_proto.schema_version,
+        })
+    }
+}
+
+impl From<GetNodeIdentityAlgorithmResponse> for GetNodeIdentityAlgorithmResponseProto {
+    fn from(response: GetNodeIdentityAlgorithmResponse) -> Self {
+        GetNodeIdentityAlgorithmResponseProto {
+            identity_algorithm: Some(response.identity_algorithm.into()),
+            schema_version: response.schema_version,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeIdentityAlgorithmResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeIdentityAlgorithmResponse";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeIdentityAlgorithmResponse {
+    type ProtobufMessage = GetNodeIdentityAlgorithmResponseProto;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    ImmutableString,
+    ImmutableI64,
+    MaxI64,
+    MinI64,
+    ImmutableU64,
+    MaxU64,
+    MinU64,
+}
+
+impl TryFrom<PropertyTypeProto> for PropertyType {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: PropertyTypeProto) -> Result<Self, Self::Error> {
+        match response_proto {
+            PropertyTypeProto::ImmutableString => Ok(PropertyType::ImmutableString),
+            PropertyTypeProto::ImmutableI64 => Ok(PropertyType::ImmutableI64),
+            PropertyTypeProto::MaxI64 => Ok(PropertyType::MaxI64),
+            PropertyTypeProto::MinI64 => Ok(PropertyType::MinI64),
+            PropertyTypeProto::ImmutableU64 => Ok(PropertyType::ImmutableU64),
+            PropertyTypeProto::MaxU64 => Ok(PropertyType::MaxU64),
+            PropertyTypeProto::MinU64 => Ok(PropertyType::MinU64),
+            PropertyTypeProto::Unspecified => Err(SerDeError::UnknownVariant("PropertyType")),
+        }
+    }
+}
+
+impl From<PropertyType> for PropertyTypeProto {
+    fn from(response: PropertyType) -> Self {
+        match response {
+            PropertyType::ImmutableString => PropertyTypeProto::ImmutableString,
+            PropertyType::ImmutableI64 => PropertyTypeProto::ImmutableI64,
+            PropertyType::MaxI64 => PropertyTypeProto::MaxI64,
+            PropertyType::MinI64 => PropertyTypeProto::MinI64,
+            PropertyType::ImmutableU64 => PropertyTypeProto::ImmutableU64,
+            PropertyType::MaxU64 => PropertyTypeProto::MaxU64,
+            PropertyType::MinU64 => PropertyTypeProto::MinU64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeSchemaRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+    /// Defaults to the most recently deployed version
+    pub schema_version: Option<u32>,
+}
+
+impl TryFrom<GetNodeSchemaRequestProto> for GetNodeSchemaRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: GetNodeSchemaRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("GetNodeSchemaRequest.tenant_id"))?
+            .into();
+
+        let node_type = request_proto
+            .node_type
+            .ok_or(SerDeError::MissingField("GetNodeSchemaRequest.node_type"))?
+            .try_into()?;
+
+        Ok(GetNodeSchemaRequest {
+            tenant_id,
+            node_type,
+            schema_version: request_proto.schema_version,
+        })
+    }
+}
+
+impl From<GetNodeSchemaRequest> for GetNodeSchemaRequestProto {
+    fn from(request: GetNodeSchemaRequest) -> Self {
+        GetNodeSchemaRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            node_type: Some(request.node_type.into()),
+            schema_version: request.schema_version,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeSchemaRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeSchemaRequest";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeSchemaRequest {
+    type ProtobufMessage = GetNodeSchemaRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertySchema {
+    pub property_name: PropertyName,
+    pub property_type: PropertyType,
+    pub identity_only: bool,
+}
+
+impl TryFrom<PropertySchemaProto> for PropertySchema {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: PropertySchemaProto) -> Result<Self, Self::Error> {
+        let property_type = response_proto.property_type().try_into()?;
+
+        let property_name = response_proto
+            .property_name
+            .ok_or(SerDeError::MissingField("PropertySchema.property_name"))?
+            .try_into()?;
+
+        Ok(PropertySchema {
+            property_name,
+            property_type,
+            identity_only: response_proto.identity_only,
+        })
+    }
+}
+
+impl From<PropertySchema> for PropertySchemaProto {
+    fn from(value: PropertySchema) -> Self {
+        let property_type: PropertyTypeProto = value.property_type.into();
+        PropertySchemaProto {
+            property_name: Some(value.property_name.into()),
+            property_type: property_type as i32,
+            identity_only: value.identity_only,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EdgeSchema {
+    pub edge_name: EdgeName,
+    pub reverse_edge_name: EdgeName,
+    pub cardinality: EdgeCardinality,
+    pub reverse_cardinality: EdgeCardinality,
+}
+
+impl TryFrom<EdgeSchemaProto> for EdgeSchema {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: EdgeSchemaProto) -> Result<Self, Self::Error> {
+        let cardinality = response_proto.cardinality().try_into()?;
+        let reverse_cardinality = response_proto.reverse_cardinality().try_into()?;
+
+        let edge_name = response_proto
+            .edge_name
+            .ok_or(SerDeError::MissingField("EdgeSchema.edge_name"))?
+            .try_into()?;
+
+        let reverse_edge_name = response_proto
+            .reverse_edge_name
+            .ok_or(SerDeError::MissingField("EdgeSchema.reverse_edge_name"))?
+            .try_into()?;
+
+        Ok(EdgeSchema {
+            edge_name,
+            reverse_edge_name,
+            cardinality,
+            reverse_cardinality,
+        })
+    }
+}
+
+impl From<EdgeSchema> for EdgeSchemaProto {
+    fn from(value: EdgeSchema) -> Self {
+        let cardinality: EdgeCardinalityProto = value.cardinality.into();
+        let reverse_cardinality: EdgeCardinalityProto = value.reverse_cardinality.into();
+        EdgeSchemaProto {
+            edge_name: Some(value.edge_name.into()),
+            reverse_edge_name: Some(value.reverse_edge_name.into()),
+            cardinality: cardinality as i32,
+            reverse_cardinality: reverse_cardinality as i32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeSchemaResponse {
+    pub properties: Vec<PropertySchema>,
+    pub edges: Vec<EdgeSchema>,
+    pub schema_version: u32,
+}
+
+impl TryFrom<GetNodeSchemaResponseProto> for GetNodeSchemaResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: GetNodeSchemaResponseProto) -> Result<Self, Self::Error> {
+        let properties = response_proto
+            .properties
+            .into_iter()
+            .map(PropertySchema::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let edges = response_proto
+            .edges
+            .into_iter()
+            .map(EdgeSchema::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(GetNodeSchemaResponse {
+            properties,
+            edges,
+            schema_version: response_proto.schema_version,
+        })
+    }
+}
+
+impl From<GetNodeSchemaResponse> for GetNodeSchemaResponseProto {
+    fn from(response: GetNodeSchemaResponse) -> Self {
+        GetNodeSchemaResponseProto {
+            properties: response.properties.into_iter().map(Into::into).collect(),
+            edges: response.edges.into_iter().map(Into::into).collect(),
+            schema_version: response.schema_version,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeSchemaResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeSchemaResponse";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeSchemaResponse {
+    type ProtobufMessage = GetNodeSchemaResponseProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListNodeSchemasRequest {
+    pub tenant_id: uuid::Uuid,
+}
+
+impl TryFrom<ListNodeSchemasRequestProto> for ListNodeSchemasRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: ListNodeSchemasRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("ListNodeSchemasRequest.tenant_id"))?
+            .into();
+
+        Ok(ListNodeSchemasRequest { tenant_id })
+    }
+}
+
+impl From<ListNodeSchemasRequest> for ListNodeSchemasRequestProto {
+    fn from(request: ListNodeSchemasRequest) -> Self {
+        ListNodeSchemasRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for ListNodeSchemasRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.ListNodeSchemasRequest";
+}
+
+impl serde_impl::ProtobufSerializable for ListNodeSchemasRequest {
+    type ProtobufMessage = ListNodeSchemasRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeSchemaVersion {
+    pub node_type: NodeType,
+    pub schema_version: u32,
+    pub deployment_timestamp: SystemTime,
+}
+
+impl TryFrom<NodeSchemaVersionProto> for NodeSchemaVersion {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: NodeSchemaVersionProto) -> Result<Self, Self::Error> {
+        let node_type = response_proto
+            .node_type
+            .ok_or(SerDeError::MissingField("NodeSchemaVersion.node_type"))?
+            .try_into()?;
+
+        let deployment_timestamp = response_proto
+            .deployment_timestamp
+            .ok_or(SerDeError::MissingField(
+                "NodeSchemaVersion.deployment_timestamp",
+            ))?
+            .try_into()?;
+
+        Ok(NodeSchemaVersion {
+            node_type,
+            schema_version: response_proto.schema_version,
+            deployment_timestamp,
+        })
+    }
+}
+
+impl TryFrom<NodeSchemaVersion> for NodeSchemaVersionProto {
+    type Error = SerDeError;
+
+    fn try_from(value: NodeSchemaVersion) -> Result<Self, Self::Error> {
+        Ok(NodeSchemaVersionProto {
+            node_type: Some(value.node_type.into()),
+            schema_version: value.schema_version,
+            deployment_timestamp: Some(value.deployment_timestamp.try_into()?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListNodeSchemasResponse {
+    pub node_schemas: Vec<NodeSchemaVersion>,
+}
+
+impl TryFrom<ListNodeSchemasResponseProto> for ListNodeSchemasResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: ListNodeSchemasResponseProto) -> Result<Self, Self::Error> {
+        let node_schemas = response_proto
+            .node_schemas
+            .into_iter()
+            .map(NodeSchemaVersion::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ListNodeSchemasResponse { node_schemas })
+    }
+}
+
+impl TryFrom<ListNodeSchemasResponse> for ListNodeSchemasResponseProto {
+    type Error = SerDeError;
+
+    fn try_from(response: ListNodeSchemasResponse) -> Result<Self, Self::Error> {
+        let node_schemas = response
+            .node_schemas
+            .into_iter()
+            .map(NodeSchemaVersionProto::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ListNodeSchemasResponseProto { node_schemas })
+    }
+}
+
+impl type_url::TypeUrl for ListNodeSchemasResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.ListNodeSchemasResponse";
+}
+
+impl serde_impl::ProtobufSerializable for ListNodeSchemasResponse {
+    type ProtobufMessage = ListNodeSchemasResponseProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetRawSchemaRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+    pub schema_version: u32,
+}
+
+impl TryFrom<GetRawSchemaRequestProto> for GetRawSchemaRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: GetRawSchemaRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("GetRawSchemaRequest.tenant_id"))?
+            .into();
+
+        let node_type = request_proto
+            .node_type
+            .ok_or(SerDeError::MissingField("GetRawSchemaRequest.node_type"))?
+            .try_into()?;
+
+        Ok(GetRawSchemaRequest {
+            tenant_id,
+            node_type,
+            schema_version: request_proto.schema_version,
+        })
+    }
+}
+
+impl From<GetRawSchemaRequest> for GetRawSchemaRequestProto {
+    fn from(request: GetRawSchemaRequest) -> Self {
+        GetRawSchemaRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            node_type: Some(request.node_type.into()),
+            schema_version: request.schema_version,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetRawSchemaRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetRawSchemaRequest";
+}
+
+impl serde_impl::ProtobufSerializable for GetRawSchemaRequest {
+    type ProtobufMessage = GetRawSchemaRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetRawSchemaResponse {
+    pub schema: Bytes,
+    pub schema_type: SchemaType,
+}
+
+impl TryFrom<GetRawSchemaResponseProto> for GetRawSchemaResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: GetRawSchemaResponseProto) -> Result<Self, Self::Error> {
+        let schema_type = response_proto.schema_type().try_into()?;
+
+        Ok(GetRawSchemaResponse {
+            schema: response_proto.schema,
+            schema_type,
+        })
+    }
+}
+
+impl From<GetRawSchemaResponse> for GetRawSchemaResponseProto {
+    fn from(response: GetRawSchemaResponse) -> Self {
+        let schema_type: SchemaTypeProto = response.schema_type.into();
+        GetRawSchemaResponseProto {
+            schema: response.schema,
+            schema_type: schema_type as i32,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetRawSchemaResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetRawSchemaResponse";
+}
+
+impl serde_impl::ProtobufSerializable for GetRawSchemaResponse {
+    type ProtobufMessage = GetRawSchemaResponseProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiffSchemasRequest {
+    pub tenant_id: uuid::Uuid,
+    pub from_schema_version: u32,
+    pub to_schema_version: u32,
+}
+
+impl TryFrom<DiffSchemasRequestProto> for DiffSchemasRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: DiffSchemasRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("DiffSchemasRequest.tenant_id"))?
+            .into();
+
+        Ok(DiffSchemasRequest {
+            tenant_id,
+            from_schema_version: request_proto.from_schema_version,
+            to_schema_version: request_proto.to_schema_version,
+        })
+    }
+}
+
+impl From<DiffSchemasRequest> for DiffSchemasRequestProto {
+    fn from(request: DiffSchemasRequest) -> Self {
+        DiffSchemasRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            from_schema_version: request.from_schema_version,
+            to_schema_version: request.to_schema_version,
+        }
+    }
+}
+
+impl type_url::TypeUrl for DiffSchemasRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.DiffSchemasRequest";
+}
+
+impl serde_impl::ProtobufSerializable for DiffSchemasRequest {
+    type ProtobufMessage = DiffSchemasRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertySchemaChange {
+    pub before: PropertySchema,
+    pub after: PropertySchema,
+}
+
+impl TryFrom<PropertySchemaChangeProto> for PropertySchemaChange {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: PropertySchemaChangeProto) -> Result<Self, Self::Error> {
+        let before = response_proto
+            .before
+            .ok_or(SerDeError::MissingField("PropertySchemaChange.before"))?
+            .try_into()?;
+
+        let after = response_proto
+            .after
+            .ok_or(SerDeError::MissingField("PropertySchemaChange.after"))?
+            .try_into()?;
+
+        Ok(PropertySchemaChange { before, after })
+    }
+}
+
+impl From<PropertySchemaChange> for PropertySchemaChangeProto {
+    fn from(value: PropertySchemaChange) -> Self {
+        PropertySchemaChangeProto {
+            before: Some(value.before.into()),
+            after: Some(value.after.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EdgeSchemaChange {
+    pub before: EdgeSchema,
+    pub after: EdgeSchema,
+}
+
+impl TryFrom<EdgeSchemaChangeProto> for EdgeSchemaChange {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: EdgeSchemaChangeProto) -> Result<Self, Self::Error> {
+        let before = response_proto
+            .before
+            .ok_or(SerDeError::MissingField("EdgeSchemaChange.before"))?
+            .try_into()?;
+
+        let after = response_proto
+            .after
+            .ok_or(SerDeError::MissingField("EdgeSchemaChange.after"))?
+            .try_into()?;
+
+        Ok(EdgeSchemaChange { before, after })
+    }
+}
+
+impl From<EdgeSchemaChange> for EdgeSchemaChangeProto {
+    fn from(value: EdgeSchemaChange) -> Self {
+        EdgeSchemaChangeProto {
+            before: Some(value.before.into()),
+            after: Some(value.after.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeSchemaDiff {
+    pub node_type: NodeType,
+    pub added_properties: Vec<PropertySchema>,
+    pub removed_properties: Vec<PropertySchema>,
+    pub changed_properties: Vec<PropertySchemaChange>,
+    pub added_edges: Vec<EdgeSchema>,
+    pub removed_edges: Vec<EdgeSchema>,
+    pub changed_edges: Vec<EdgeSchemaChange>,
+    pub identity_algorithm_changed: bool,
+}
+
+impl TryFrom<NodeSchemaDiffProto> for NodeSchemaDiff {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: NodeSchemaDiffProto) -> Result<Self, Self::Error> {
+        let node_type = response_proto
+            .node_type
+            .ok_or(SerDeError::MissingField("NodeSchemaDiff.node_type"))?
+            .try_into()?;
+
+        Ok(NodeSchemaDiff {
+            node_type,
+            added_properties: try_from_all(response_proto.added_properties)?,
+            removed_properties: try_from_all(response_proto.removed_properties)?,
+            changed_properties: try_from_all(response_proto.changed_properties)?,
+            added_edges: try_from_all(response_proto.added_edges)?,
+            removed_edges: try_from_all(response_proto.removed_edges)?,
+            changed_edges: try_from_all(response_proto.changed_edges)?,
+            identity_algorithm_changed: response_proto.identity_algorithm_changed,
+        })
+    }
+}
+
+impl From<NodeSchemaDiff> for NodeSchemaDiffProto {
+    fn from(value: NodeSchemaDiff) -> Self {
+        NodeSchemaDiffProto {
+            node_type: Some(value.node_type.into()),
+            added_properties: into_all(value.added_properties),
+            removed_properties: into_all(value.removed_properties),
+            changed_properties: into_all(value.changed_properties),
+            added_edges: into_all(value.added_edges),
+            removed_edges: into_all(value.removed_edges),
+            changed_edges: into_all(value.changed_edges),
+            identity_algorithm_changed: value.identity_algorithm_changed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiffSchemasResponse {
+    pub added_node_types: Vec<NodeType>,
+    pub removed_node_types: Vec<NodeType>,
+    pub changed_node_types: Vec<NodeSchemaDiff>,
+}
+
+impl TryFrom<DiffSchemasResponseProto> for DiffSchemasResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: DiffSchemasResponseProto) -> Result<Self, Self::Error> {
+        Ok(DiffSchemasResponse {
+            added_node_types: try_from_all(response_proto.added_node_types)?,
+            removed_node_types: try_from_all(response_proto.removed_node_types)?,
+            changed_node_types: try_from_all(response_proto.changed_node_types)?,
+        })
+    }
+}
+
+impl From<DiffSchemasResponse> for DiffSchemasResponseProto {
+    fn from(response: DiffSchemasResponse) -> Self {
+        DiffSchemasResponseProto {
+            added_node_types: into_all(response.added_node_types),
+            removed_node_types: into_all(response.removed_node_types),
+            changed_node_types: into_all(response.changed_node_types),
+        }
+    }
+}
+
+impl type_url::TypeUrl for DiffSchemasResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.DiffSchemasResponse";
+}
+
+impl serde_impl::ProtobufSerializable for DiffSchemasResponse {
+    type ProtobufMessage = DiffSchemasResponseProto;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaViolationKind {
+    UnknownNodeType,
+    UnknownProperty,
+    PropertyTypeMismatch,
+    UndeclaredEdge,
+    EdgeCardinalityViolation,
+}
+
+impl TryFrom<SchemaViolationKindProto> for SchemaViolationKind {
+    type Error = SerDeError;
+
+    fn try_from(proto: SchemaViolationKindProto) -> Result<Self, Self::Error> {
+        match proto {
+            SchemaViolationKindProto::UnknownNodeType => Ok(SchemaViolationKind::UnknownNodeType),
+            SchemaViolationKindProto::UnknownProperty => Ok(SchemaViolationKind::UnknownProperty),
+            SchemaViolationKindProto::PropertyTypeMismatch => {
+                Ok(SchemaViolationKind::PropertyTypeMismatch)
+            }
+            SchemaViolationKindProto::UndeclaredEdge => Ok(SchemaViolationKind::UndeclaredEdge),
+            SchemaViolationKindProto::EdgeCardinalityViolation => {
+                Ok(SchemaViolationKind::EdgeCardinalityViolation)
+            }
+            SchemaViolationKindProto::Unspecified => {
+                Err(SerDeError::UnknownVariant("SchemaViolationKind"))
+            }
+        }
+    }
+}
+
+impl From<SchemaViolationKind> for SchemaViolationKindProto {
+    fn from(kind: SchemaViolationKind) -> Self {
+        match kind {
+            SchemaViolationKind::UnknownNodeType => SchemaViolationKindProto::UnknownNodeType,
+            SchemaViolationKind::UnknownProperty => SchemaViolationKindProto::UnknownProperty,
+            SchemaViolationKind::PropertyTypeMismatch => {
+                SchemaViolationKindProto::PropertyTypeMismatch
+            }
+            SchemaViolationKind::UndeclaredEdge => SchemaViolationKindProto::UndeclaredEdge,
+            SchemaViolationKind::EdgeCardinalityViolation => {
+                SchemaViolationKindProto::EdgeCardinalityViolation
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaViolation {
+    pub kind: SchemaViolationKind,
+    pub description: String,
+}
+
+impl TryFrom<SchemaViolationProto> for SchemaViolation {
+    type Error = SerDeError;
+
+    fn try_from(proto: SchemaViolationProto) -> Result<Self, Self::Error> {
+        Ok(SchemaViolation {
+            kind: proto.kind().try_into()?,
+            description: proto.description,
+        })
+    }
+}
+
+impl From<SchemaViolation> for SchemaViolationProto {
+    fn from(violation: SchemaViolation) -> Self {
+        let kind: SchemaViolationKindProto = violation.kind.into();
+        SchemaViolationProto {
+            kind: kind as i32,
+            description: violation.description,
+        }
+    }
+}
+
+impl type_url::TypeUrl for SchemaViolation {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.SchemaViolation";
+}
+
+impl serde_impl::ProtobufSerializable for SchemaViolation {
+    type ProtobufMessage = SchemaViolationProto;
+}
+
+/// A generated node that was rejected because it violates its type's
+/// deployed schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeValidationReport {
+    pub node_key: String,
+    pub node_type: NodeType,
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl TryFrom<NodeValidationReportProto> for NodeValidationReport {
+    type Error = SerDeError;
+
+    fn try_from(proto: NodeValidationReportProto) -> Result<Self, Self::Error> {
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("NodeValidationReport.node_type"))?
+            .try_into()?;
+
+        Ok(NodeValidationReport {
+            node_key: proto.node_key,
+            node_type,
+            violations: try_from_all(proto.violations)?,
+        })
+    }
+}
+
+impl From<NodeValidationReport> for NodeValidationReportProto {
+    fn from(report: NodeValidationReport) -> Self {
+        NodeValidationReportProto {
+            node_key: report.node_key,
+            node_type: Some(report.node_type.into()),
+            violations: into_all(report.violations),
+        }
+    }
+}
+
+impl type_url::TypeUrl for NodeValidationReport {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.NodeValidationReport";
+}
+
+impl serde_impl::ProtobufSerializable for NodeValidationReport {
+    type ProtobufMessage = NodeValidationReportProto;
+}
+
+fn try_from_all<P, T>(protos: Vec<P>) -> Result<Vec<T>, SerDeError>
+where
+    T: TryFrom<P, Error = SerDeError>,
+{
+    protos.into_iter().map(T::try_from).collect()
+}
+
+fn into_all<T, P>(values: Vec<T>) -> Vec<P>
+where
+    T: Into<P>,
+{
+    values.into_iter().map(Into::into).collect()
+}
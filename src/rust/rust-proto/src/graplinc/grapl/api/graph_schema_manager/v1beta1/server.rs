@@ -22,8 +22,18 @@ use crate::{
     graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
         DeploySchemaRequest,
         DeploySchemaResponse,
+        DiffSchemasRequest,
+        DiffSchemasResponse,
         GetEdgeSchemaRequest,
         GetEdgeSchemaResponse,
+        GetNodeIdentityAlgorithmRequest,
+        GetNodeIdentityAlgorithmResponse,
+        GetNodeSchemaRequest,
+        GetNodeSchemaResponse,
+        GetRawSchemaRequest,
+        GetRawSchemaResponse,
+        ListNodeSchemasRequest,
+        ListNodeSchemasResponse,
     },
     protobufs::graplinc::grapl::api::graph_schema_manager::{
         v1beta1 as proto,
@@ -64,6 +74,31 @@ pub trait GraphSchemaManagerApi {
         &self,
         request: GetEdgeSchemaRequest,
     ) -> Result<GetEdgeSchemaResponse, Self::Error>;
+
+    async fn get_node_identity_algorithm(
+        &self,
+        request: GetNodeIdentityAlgorithmRequest,
+    ) -> Result<GetNodeIdentityAlgorithmResponse, Self::Error>;
+
+    async fn get_node_schema(
+        &self,
+        request: GetNodeSchemaRequest,
+    ) -> Result<GetNodeSchemaResponse, Self::Error>;
+
+    async fn list_node_schemas(
+        &self,
+        request: ListNodeSchemasRequest,
+    ) -> Result<ListNodeSchemasResponse, Self::Error>;
+
+    async fn get_raw_schema(
+        &self,
+        request: GetRawSchemaRequest,
+    ) -> Result<GetRawSchemaResponse, Self::Error>;
+
+    async fn diff_schemas(
+        &self,
+        request: DiffSchemasRequest,
+    ) -> Result<DiffSchemasResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -84,6 +119,41 @@ where
     ) -> Result<tonic::Response<proto::GetEdgeSchemaResponse>, tonic::Status> {
         execute_rpc!(self, request, get_edge_schema)
     }
+
+    async fn get_node_identity_algorithm(
+        &self,
+        request: tonic::Request<proto::GetNodeIdentityAlgorithmRequest>,
+    ) -> Result<tonic::Response<proto::GetNodeIdentityAlgorithmResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_node_identity_algorithm)
+    }
+
+    async fn get_node_schema(
+        &self,
+        request: tonic::Request<proto::GetNodeSchemaRequest>,
+    ) -> Result<tonic::Response<proto::GetNodeSchemaResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_node_schema)
+    }
+
+    async fn list_node_schemas(
+        &self,
+        request: tonic::Request<proto::ListNodeSchemasRequest>,
+    ) -> Result<tonic::Response<proto::ListNodeSchemasResponse>, tonic::Status> {
+        execute_rpc!(self, request, list_node_schemas)
+    }
+
+    async fn get_raw_schema(
+        &self,
+        request: tonic::Request<proto::GetRawSchemaRequest>,
+    ) -> Result<tonic::Response<proto::GetRawSchemaResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_raw_schema)
+    }
+
+    async fn diff_schemas(
+        &self,
+        request: tonic::Request<proto::DiffSchemasRequest>,
+    ) -> Result<tonic::Response<proto::DiffSchemasResponse>, tonic::Status> {
+        execute_rpc!(self, request, diff_schemas)
+    }
 }
 
 /**
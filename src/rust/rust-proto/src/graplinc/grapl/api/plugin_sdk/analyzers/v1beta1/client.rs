@@ -33,7 +33,8 @@ pub struct AnalyzerServiceClient {
 
 #[async_trait::async_trait]
 impl Connectable for AnalyzerServiceClient {
-    const SERVICE_NAME: &'static str = "graplinc.grapl.api.plugin_registry.v1beta1.AnalyzerService";
+    const SERVICE_NAME: &'static str =
+        "graplinc.grapl.api.plugin_sdk.analyzers.v1beta1.AnalyzerService";
 
     #[tracing::instrument(err)]
     async fn connect(endpoint: Endpoint) -> Result<Self, ConnectError> {
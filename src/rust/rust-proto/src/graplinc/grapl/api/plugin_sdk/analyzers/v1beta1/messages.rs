@@ -312,6 +312,15 @@ impl From<ExecutionHit> for ExecutionHitProto {
     }
 }
 
+impl type_url::TypeUrl for ExecutionHit {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_sdk.analyzers.v1beta1.ExecutionHit";
+}
+
+impl ProtobufSerializable for ExecutionHit {
+    type ProtobufMessage = ExecutionHitProto;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExecutionMiss {}
 
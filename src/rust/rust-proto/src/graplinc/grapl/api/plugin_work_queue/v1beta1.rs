@@ -1,6 +1,12 @@
 #![allow(unused_variables)]
 
-use std::fmt::Formatter;
+use std::{
+    fmt::Formatter,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
 
 use bytes::Bytes;
 use proto::{
@@ -20,8 +26,14 @@ pub use crate::graplinc::grapl::api::plugin_work_queue::{
     },
 };
 use crate::{
-    graplinc::grapl::api::graph::v1beta1::GraphDescription,
-    protobufs::graplinc::grapl::api::plugin_work_queue::v1beta1 as proto,
+    graplinc::grapl::api::{
+        graph::v1beta1::GraphDescription,
+        plugin_registry::v1beta1::PluginType,
+    },
+    protobufs::graplinc::grapl::api::{
+        plugin_registry::v1beta1::PluginType as PluginTypeProto,
+        plugin_work_queue::v1beta1 as proto,
+    },
     serde_impl::ProtobufSerializable,
     type_url,
     SerDeError,
@@ -115,9 +127,59 @@ impl type_url::TypeUrl for ExecutionJob {
         "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ExecutionJob";
 }
 
+/// A job handed out by the queue, with the `request_id` it must be
+/// acknowledged with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcquiredExecutionJob {
+    pub execution_job: ExecutionJob,
+    pub request_id: i64,
+    pub lease_id: Uuid,
+}
+
+impl TryFrom<proto::AcquiredExecutionJob> for AcquiredExecutionJob {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AcquiredExecutionJob) -> Result<Self, Self::Error> {
+        let execution_job = value
+            .execution_job
+            .ok_or(Self::Error::MissingField("execution_job"))?
+            .try_into()?;
+        let lease_id = value
+            .lease_id
+            .ok_or(Self::Error::MissingField("lease_id"))?
+            .into();
+
+        Ok(Self {
+            execution_job,
+            request_id: value.request_id,
+            lease_id,
+        })
+    }
+}
+
+impl From<AcquiredExecutionJob> for proto::AcquiredExecutionJob {
+    fn from(value: AcquiredExecutionJob) -> Self {
+        Self {
+            execution_job: Some(value.execution_job.into()),
+            request_id: value.request_id,
+            lease_id: Some(value.lease_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for AcquiredExecutionJob {
+    type ProtobufMessage = proto::AcquiredExecutionJob;
+}
+
+impl type_url::TypeUrl for AcquiredExecutionJob {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcquiredExecutionJob";
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AcknowledgeGeneratorRequest {
     request_id: i64,
+    lease_id: Option<Uuid>,
     graph_description: Option<GraphDescription>,
     plugin_id: Uuid,
     tenant_id: Uuid,
@@ -128,6 +190,7 @@ pub struct AcknowledgeGeneratorRequest {
 impl AcknowledgeGeneratorRequest {
     pub fn new(
         request_id: i64,
+        lease_id: Option<Uuid>,
         graph_description: Option<GraphDescription>,
         plugin_id: Uuid,
         tenant_id: Uuid,
@@ -136,6 +199,7 @@ impl AcknowledgeGeneratorRequest {
     ) -> Self {
         Self {
             request_id,
+            lease_id,
             graph_description,
             plugin_id,
             tenant_id,
@@ -148,6 +212,12 @@ impl AcknowledgeGeneratorRequest {
         self.request_id
     }
 
+    /// The lease the job was handed out with, or `None` if the consumer
+    /// predates leases.
+    pub fn lease_id(&self) -> Option<Uuid> {
+        self.lease_id
+    }
+
     pub fn graph_description(self) -> Option<GraphDescription> {
         self.graph_description
     }
@@ -191,9 +261,12 @@ impl TryFrom<proto::AcknowledgeGeneratorRequest> for AcknowledgeGeneratorRequest
             .event_source_id
             .ok_or(Self::Error::MissingField("event_source_id"))?
             .into();
+        // Consumers that predate leases don't send one
+        let lease_id = value.lease_id.map(Into::into);
 
         Ok(Self {
             request_id,
+            lease_id,
             graph_description,
             plugin_id,
             tenant_id,
@@ -212,6 +285,7 @@ impl From<AcknowledgeGeneratorRequest> for proto::AcknowledgeGeneratorRequest {
             tenant_id: Some(value.tenant_id.into()),
             trace_id: Some(value.trace_id.into()),
             event_source_id: Some(value.event_source_id.into()),
+            lease_id: value.lease_id.map(Into::into),
         }
     }
 }
@@ -254,6 +328,7 @@ impl type_url::TypeUrl for AcknowledgeGeneratorResponse {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AcknowledgeAnalyzerRequest {
     request_id: i64,
+    lease_id: Option<Uuid>,
     success: bool,
     plugin_id: Uuid,
     tenant_id: Uuid,
@@ -264,6 +339,7 @@ pub struct AcknowledgeAnalyzerRequest {
 impl AcknowledgeAnalyzerRequest {
     pub fn new(
         request_id: i64,
+        lease_id: Option<Uuid>,
         success: bool,
         plugin_id: Uuid,
         tenant_id: Uuid,
@@ -272,6 +348,7 @@ impl AcknowledgeAnalyzerRequest {
     ) -> Self {
         Self {
             request_id,
+            lease_id,
             success,
             plugin_id,
             tenant_id,
@@ -284,6 +361,12 @@ impl AcknowledgeAnalyzerRequest {
         self.request_id
     }
 
+    /// The lease the job was handed out with, or `None` if the consumer
+    /// predates leases.
+    pub fn lease_id(&self) -> Option<Uuid> {
+        self.lease_id
+    }
+
     pub fn success(&self) -> bool {
         self.success
     }
@@ -327,9 +410,12 @@ impl TryFrom<proto::AcknowledgeAnalyzerRequest> for AcknowledgeAnalyzerRequest {
             .event_source_id
             .ok_or(Self::Error::MissingField("event_source_id"))?
             .into();
+        // Consumers that predate leases don't send one
+        let lease_id = value.lease_id.map(Into::into);
 
         Ok(Self {
             request_id,
+            lease_id,
             success,
             plugin_id,
             tenant_id,
@@ -348,6 +434,7 @@ impl From<AcknowledgeAnalyzerRequest> for proto::AcknowledgeAnalyzerRequest {
             tenant_id: Some(value.tenant_id.into()),
             trace_id: Some(value.trace_id.into()),
             event_source_id: Some(value.event_source_id.into()),
+            lease_id: value.lease_id.map(Into::into),
         }
     }
 }
@@ -387,9 +474,146 @@ impl type_url::TypeUrl for AcknowledgeAnalyzerResponse {
         "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeAnalyzerResponse";
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcknowledgeGeneratorBatchRequest {
+    pub acknowledgements: Vec<AcknowledgeGeneratorRequest>,
+}
+
+impl TryFrom<proto::AcknowledgeGeneratorBatchRequest> for AcknowledgeGeneratorBatchRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AcknowledgeGeneratorBatchRequest) -> Result<Self, Self::Error> {
+        let acknowledgements = value
+            .acknowledgements
+            .into_iter()
+            .map(AcknowledgeGeneratorRequest::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { acknowledgements })
+    }
+}
+
+impl From<AcknowledgeGeneratorBatchRequest> for proto::AcknowledgeGeneratorBatchRequest {
+    fn from(value: AcknowledgeGeneratorBatchRequest) -> Self {
+        Self {
+            acknowledgements: value.acknowledgements.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl ProtobufSerializable for AcknowledgeGeneratorBatchRequest {
+    type ProtobufMessage = proto::AcknowledgeGeneratorBatchRequest;
+}
+
+impl type_url::TypeUrl for AcknowledgeGeneratorBatchRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeGeneratorBatchRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcknowledgeGeneratorBatchResponse {
+    pub rejected_request_ids: Vec<i64>,
+}
+
+impl TryFrom<proto::AcknowledgeGeneratorBatchResponse> for AcknowledgeGeneratorBatchResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AcknowledgeGeneratorBatchResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rejected_request_ids: value.rejected_request_ids,
+        })
+    }
+}
+
+impl From<AcknowledgeGeneratorBatchResponse> for proto::AcknowledgeGeneratorBatchResponse {
+    fn from(value: AcknowledgeGeneratorBatchResponse) -> Self {
+        Self {
+            rejected_request_ids: value.rejected_request_ids,
+        }
+    }
+}
+
+impl ProtobufSerializable for AcknowledgeGeneratorBatchResponse {
+    type ProtobufMessage = proto::AcknowledgeGeneratorBatchResponse;
+}
+
+impl type_url::TypeUrl for AcknowledgeGeneratorBatchResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeGeneratorBatchResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcknowledgeAnalyzerBatchRequest {
+    pub acknowledgements: Vec<AcknowledgeAnalyzerRequest>,
+}
+
+impl TryFrom<proto::AcknowledgeAnalyzerBatchRequest> for AcknowledgeAnalyzerBatchRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AcknowledgeAnalyzerBatchRequest) -> Result<Self, Self::Error> {
+        let acknowledgements = value
+            .acknowledgements
+            .into_iter()
+            .map(AcknowledgeAnalyzerRequest::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { acknowledgements })
+    }
+}
+
+impl From<AcknowledgeAnalyzerBatchRequest> for proto::AcknowledgeAnalyzerBatchRequest {
+    fn from(value: AcknowledgeAnalyzerBatchRequest) -> Self {
+        Self {
+            acknowledgements: value.acknowledgements.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl ProtobufSerializable for AcknowledgeAnalyzerBatchRequest {
+    type ProtobufMessage = proto::AcknowledgeAnalyzerBatchRequest;
+}
+
+impl type_url::TypeUrl for AcknowledgeAnalyzerBatchRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeAnalyzerBatchRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcknowledgeAnalyzerBatchResponse {
+    pub rejected_request_ids: Vec<i64>,
+}
+
+impl TryFrom<proto::AcknowledgeAnalyzerBatchResponse> for AcknowledgeAnalyzerBatchResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AcknowledgeAnalyzerBatchResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rejected_request_ids: value.rejected_request_ids,
+        })
+    }
+}
+
+impl From<AcknowledgeAnalyzerBatchResponse> for proto::AcknowledgeAnalyzerBatchResponse {
+    fn from(value: AcknowledgeAnalyzerBatchResponse) -> Self {
+        Self {
+            rejected_request_ids: value.rejected_request_ids,
+        }
+    }
+}
+
+impl ProtobufSerializable for AcknowledgeAnalyzerBatchResponse {
+    type ProtobufMessage = proto::AcknowledgeAnalyzerBatchResponse;
+}
+
+impl type_url::TypeUrl for AcknowledgeAnalyzerBatchResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeAnalyzerBatchResponse";
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetExecuteAnalyzerRequest {
     pub plugin_id: Uuid,
+    pub max_jobs: u32,
 }
 
 impl TryFrom<proto::GetExecuteAnalyzerRequest> for GetExecuteAnalyzerRequest {
@@ -400,7 +624,10 @@ impl TryFrom<proto::GetExecuteAnalyzerRequest> for GetExecuteAnalyzerRequest {
             .plugin_id
             .ok_or(Self::Error::MissingField("plugin_id"))?
             .into();
-        Ok(Self { plugin_id })
+        Ok(Self {
+            plugin_id,
+            max_jobs: value.max_jobs,
+        })
     }
 }
 
@@ -408,6 +635,7 @@ impl From<GetExecuteAnalyzerRequest> for proto::GetExecuteAnalyzerRequest {
     fn from(value: GetExecuteAnalyzerRequest) -> Self {
         Self {
             plugin_id: Some(value.plugin_id.into()),
+            max_jobs: value.max_jobs,
         }
     }
 }
@@ -423,24 +651,16 @@ impl type_url::TypeUrl for GetExecuteAnalyzerRequest {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetExecuteAnalyzerResponse {
-    execution_job: Option<ExecutionJob>,
-    request_id: i64,
+    jobs: Vec<AcquiredExecutionJob>,
 }
 
 impl GetExecuteAnalyzerResponse {
-    pub fn new(execution_job: Option<ExecutionJob>, request_id: i64) -> Self {
-        Self {
-            execution_job,
-            request_id,
-        }
+    pub fn new(jobs: Vec<AcquiredExecutionJob>) -> Self {
+        Self { jobs }
     }
 
-    pub fn execution_job(self) -> Option<ExecutionJob> {
-        self.execution_job
-    }
-
-    pub fn request_id(&self) -> i64 {
-        self.request_id
+    pub fn jobs(self) -> Vec<AcquiredExecutionJob> {
+        self.jobs
     }
 }
 
@@ -448,26 +668,32 @@ impl TryFrom<proto::GetExecuteAnalyzerResponse> for GetExecuteAnalyzerResponse {
     type Error = SerDeError;
 
     fn try_from(value: proto::GetExecuteAnalyzerResponse) -> Result<Self, Self::Error> {
-        let request_id = value.request_id;
-        let maybe_job = value.maybe_job.ok_or(Self::Error::MissingField(
-            "GetExecuteAnalyzerResponse.maybe_job",
-        ))?;
-        let execution_job: Option<ExecutionJob> = maybe_job.try_into()?;
+        let jobs = value
+            .jobs
+            .into_iter()
+            .map(AcquiredExecutionJob::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Self {
-            request_id,
-            execution_job,
-        })
+        Ok(Self { jobs })
     }
 }
 
 impl From<GetExecuteAnalyzerResponse> for proto::GetExecuteAnalyzerResponse {
     fn from(value: GetExecuteAnalyzerResponse) -> Self {
-        let execution_job = value.execution_job.into();
-        let request_id = value.request_id;
-        proto::GetExecuteAnalyzerResponse {
-            request_id,
-            maybe_job: Some(execution_job),
+        let first_job = value.jobs.first();
+        Self {
+            maybe_job: Some(first_job.map(|job| job.execution_job.clone()).into()),
+            request_id: first_job.map(|job| job.request_id).unwrap_or_default(),
+            jobs: value.jobs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Option<ExecutionJob>> for get_execute_analyzer_response::MaybeJob {
+    fn from(job: Option<ExecutionJob>) -> Self {
+        match job {
+            None => get_execute_analyzer_response::MaybeJob::NoJobs(proto::NoAvailableJobs {}),
+            Some(job) => get_execute_analyzer_response::MaybeJob::Job(job.into()),
         }
     }
 }
@@ -484,16 +710,24 @@ impl type_url::TypeUrl for GetExecuteAnalyzerResponse {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetExecuteGeneratorRequest {
     plugin_id: Uuid,
+    max_jobs: u32,
 }
 
 impl GetExecuteGeneratorRequest {
-    pub fn new(plugin_id: Uuid) -> Self {
-        Self { plugin_id }
+    pub fn new(plugin_id: Uuid, max_jobs: u32) -> Self {
+        Self {
+            plugin_id,
+            max_jobs,
+        }
     }
 
     pub fn plugin_id(&self) -> Uuid {
         self.plugin_id
     }
+
+    pub fn max_jobs(&self) -> u32 {
+        self.max_jobs
+    }
 }
 
 impl TryFrom<proto::GetExecuteGeneratorRequest> for GetExecuteGeneratorRequest {
@@ -504,7 +738,10 @@ impl TryFrom<proto::GetExecuteGeneratorRequest> for GetExecuteGeneratorRequest {
             .plugin_id
             .ok_or(Self::Error::MissingField("plugin_id"))?
             .into();
-        Ok(Self { plugin_id })
+        Ok(Self {
+            plugin_id,
+            max_jobs: value.max_jobs,
+        })
     }
 }
 
@@ -512,6 +749,7 @@ impl From<GetExecuteGeneratorRequest> for proto::GetExecuteGeneratorRequest {
     fn from(value: GetExecuteGeneratorRequest) -> Self {
         Self {
             plugin_id: Some(value.plugin_id.into()),
+            max_jobs: value.max_jobs,
         }
     }
 }
@@ -527,24 +765,16 @@ impl type_url::TypeUrl for GetExecuteGeneratorRequest {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetExecuteGeneratorResponse {
-    execution_job: Option<ExecutionJob>,
-    request_id: i64,
+    jobs: Vec<AcquiredExecutionJob>,
 }
 
 impl GetExecuteGeneratorResponse {
-    pub fn new(execution_job: Option<ExecutionJob>, request_id: i64) -> Self {
-        Self {
-            execution_job,
-            request_id,
-        }
-    }
-
-    pub fn execution_job(self) -> Option<ExecutionJob> {
-        self.execution_job
+    pub fn new(jobs: Vec<AcquiredExecutionJob>) -> Self {
+        Self { jobs }
     }
 
-    pub fn request_id(&self) -> i64 {
-        self.request_id
+    pub fn jobs(self) -> Vec<AcquiredExecutionJob> {
+        self.jobs
     }
 }
 
@@ -552,26 +782,32 @@ impl TryFrom<proto::GetExecuteGeneratorResponse> for GetExecuteGeneratorResponse
     type Error = SerDeError;
 
     fn try_from(value: proto::GetExecuteGeneratorResponse) -> Result<Self, Self::Error> {
-        let request_id = value.request_id;
-        let maybe_job = value.maybe_job.ok_or(Self::Error::MissingField(
-            "proto::GetExecuteGeneratorResponse.maybe_job",
-        ))?;
-        let execution_job: Option<ExecutionJob> = maybe_job.try_into()?;
+        let jobs = value
+            .jobs
+            .into_iter()
+            .map(AcquiredExecutionJob::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Self {
-            request_id,
-            execution_job,
-        })
+        Ok(Self { jobs })
     }
 }
 
 impl From<GetExecuteGeneratorResponse> for proto::GetExecuteGeneratorResponse {
     fn from(value: GetExecuteGeneratorResponse) -> Self {
-        let execution_job = value.execution_job.into();
-        let request_id = value.request_id;
-        proto::GetExecuteGeneratorResponse {
-            request_id,
-            maybe_job: Some(execution_job),
+        let first_job = value.jobs.first();
+        Self {
+            maybe_job: Some(first_job.map(|job| job.execution_job.clone()).into()),
+            request_id: first_job.map(|job| job.request_id).unwrap_or_default(),
+            jobs: value.jobs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Option<ExecutionJob>> for get_execute_generator_response::MaybeJob {
+    fn from(job: Option<ExecutionJob>) -> Self {
+        match job {
+            None => get_execute_generator_response::MaybeJob::NoJobs(proto::NoAvailableJobs {}),
+            Some(job) => get_execute_generator_response::MaybeJob::Job(job.into()),
         }
     }
 }
@@ -761,44 +997,941 @@ impl type_url::TypeUrl for PushExecuteGeneratorResponse {
         "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.PushExecuteGeneratorResponse";
 }
 
-impl From<Option<ExecutionJob>> for get_execute_generator_response::MaybeJob {
-    fn from(execution_job: Option<ExecutionJob>) -> Self {
-        match execution_job {
-            None => get_execute_generator_response::MaybeJob::NoJobs(proto::NoAvailableJobs {}),
-            Some(job) => get_execute_generator_response::MaybeJob::Job(job.into()),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetMaxAttemptsRequest {
+    pub plugin_id: Uuid,
+    pub max_attempts: u32,
+}
+
+impl TryFrom<proto::SetMaxAttemptsRequest> for SetMaxAttemptsRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::SetMaxAttemptsRequest) -> Result<Self, Self::Error> {
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        if value.max_attempts == 0 {
+            return Err(SerDeError::InvalidField {
+                field_name: "max_attempts",
+                assertion: "must be positive".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            plugin_id,
+            max_attempts: value.max_attempts,
+        })
+    }
+}
+
+impl From<SetMaxAttemptsRequest> for proto::SetMaxAttemptsRequest {
+    fn from(value: SetMaxAttemptsRequest) -> Self {
+        Self {
+            plugin_id: Some(value.plugin_id.into()),
+            max_attempts: value.max_attempts,
         }
     }
 }
 
-impl TryFrom<get_execute_analyzer_response::MaybeJob> for Option<ExecutionJob> {
+impl ProtobufSerializable for SetMaxAttemptsRequest {
+    type ProtobufMessage = proto::SetMaxAttemptsRequest;
+}
+
+impl type_url::TypeUrl for SetMaxAttemptsRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetMaxAttemptsRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetMaxAttemptsResponse {}
+
+impl TryFrom<proto::SetMaxAttemptsResponse> for SetMaxAttemptsResponse {
     type Error = SerDeError;
 
-    fn try_from(maybe_job: get_execute_analyzer_response::MaybeJob) -> Result<Self, Self::Error> {
-        let maybe_job = match maybe_job {
-            get_execute_analyzer_response::MaybeJob::Job(job) => Some(job.try_into()?),
-            get_execute_analyzer_response::MaybeJob::NoJobs(_) => None,
-        };
-        Ok(maybe_job)
+    fn try_from(_value: proto::SetMaxAttemptsResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
     }
 }
 
-impl From<Option<ExecutionJob>> for get_execute_analyzer_response::MaybeJob {
-    fn from(execution_job: Option<ExecutionJob>) -> Self {
-        match execution_job {
-            None => get_execute_analyzer_response::MaybeJob::NoJobs(proto::NoAvailableJobs {}),
-            Some(job) => get_execute_analyzer_response::MaybeJob::Job(job.into()),
+impl From<SetMaxAttemptsResponse> for proto::SetMaxAttemptsResponse {
+    fn from(_value: SetMaxAttemptsResponse) -> Self {
+        Self {}
+    }
+}
+
+impl ProtobufSerializable for SetMaxAttemptsResponse {
+    type ProtobufMessage = proto::SetMaxAttemptsResponse;
+}
+
+impl type_url::TypeUrl for SetMaxAttemptsResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetMaxAttemptsResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetVisibilityTimeoutRequest {
+    pub plugin_id: Uuid,
+    pub visibility_timeout: Duration,
+}
+
+impl TryFrom<proto::SetVisibilityTimeoutRequest> for SetVisibilityTimeoutRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::SetVisibilityTimeoutRequest) -> Result<Self, Self::Error> {
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+        let visibility_timeout: Duration = value
+            .visibility_timeout
+            .ok_or(Self::Error::MissingField("visibility_timeout"))?
+            .into();
+
+        if visibility_timeout.as_secs() == 0 || visibility_timeout.subsec_nanos() != 0 {
+            return Err(SerDeError::InvalidField {
+                field_name: "visibility_timeout",
+                assertion: "must be a positive number of whole seconds".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            plugin_id,
+            visibility_timeout,
+        })
+    }
+}
+
+impl From<SetVisibilityTimeoutRequest> for proto::SetVisibilityTimeoutRequest {
+    fn from(value: SetVisibilityTimeoutRequest) -> Self {
+        Self {
+            plugin_id: Some(value.plugin_id.into()),
+            visibility_timeout: Some(value.visibility_timeout.into()),
         }
     }
 }
 
-impl TryFrom<get_execute_generator_response::MaybeJob> for Option<ExecutionJob> {
+impl ProtobufSerializable for SetVisibilityTimeoutRequest {
+    type ProtobufMessage = proto::SetVisibilityTimeoutRequest;
+}
+
+impl type_url::TypeUrl for SetVisibilityTimeoutRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetVisibilityTimeoutRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetVisibilityTimeoutResponse {}
+
+impl TryFrom<proto::SetVisibilityTimeoutResponse> for SetVisibilityTimeoutResponse {
     type Error = SerDeError;
 
-    fn try_from(maybe_job: get_execute_generator_response::MaybeJob) -> Result<Self, Self::Error> {
-        let maybe_job = match maybe_job {
-            get_execute_generator_response::MaybeJob::Job(job) => Some(job.try_into()?),
-            get_execute_generator_response::MaybeJob::NoJobs(_) => None,
-        };
-        Ok(maybe_job)
+    fn try_from(_value: proto::SetVisibilityTimeoutResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<SetVisibilityTimeoutResponse> for proto::SetVisibilityTimeoutResponse {
+    fn from(_value: SetVisibilityTimeoutResponse) -> Self {
+        Self {}
     }
 }
+
+impl ProtobufSerializable for SetVisibilityTimeoutResponse {
+    type ProtobufMessage = proto::SetVisibilityTimeoutResponse;
+}
+
+impl type_url::TypeUrl for SetVisibilityTimeoutResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetVisibilityTimeoutResponse";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingMode {
+    OldestFirst,
+    RoundRobin,
+}
+
+impl TryFrom<proto::SchedulingMode> for SchedulingMode {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::SchedulingMode) -> Result<Self, Self::Error> {
+        match value {
+            proto::SchedulingMode::Unspecified => Err(SerDeError::UnknownVariant("SchedulingMode")),
+            proto::SchedulingMode::OldestFirst => Ok(SchedulingMode::OldestFirst),
+            proto::SchedulingMode::RoundRobin => Ok(SchedulingMode::RoundRobin),
+        }
+    }
+}
+
+impl From<SchedulingMode> for proto::SchedulingMode {
+    fn from(value: SchedulingMode) -> Self {
+        match value {
+            SchedulingMode::OldestFirst => proto::SchedulingMode::OldestFirst,
+            SchedulingMode::RoundRobin => proto::SchedulingMode::RoundRobin,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetSchedulingPolicyRequest {
+    pub plugin_id: Uuid,
+    pub scheduling_mode: SchedulingMode,
+    pub max_in_flight_per_tenant: Option<u32>,
+}
+
+impl TryFrom<proto::SetSchedulingPolicyRequest> for SetSchedulingPolicyRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::SetSchedulingPolicyRequest) -> Result<Self, Self::Error> {
+        let scheduling_mode = value.scheduling_mode().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        if value.max_in_flight_per_tenant == Some(0) {
+            return Err(SerDeError::InvalidField {
+                field_name: "max_in_flight_per_tenant",
+                assertion: "must be positive".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            plugin_id,
+            scheduling_mode,
+            max_in_flight_per_tenant: value.max_in_flight_per_tenant,
+        })
+    }
+}
+
+impl From<SetSchedulingPolicyRequest> for proto::SetSchedulingPolicyRequest {
+    fn from(value: SetSchedulingPolicyRequest) -> Self {
+        let scheduling_mode: proto::SchedulingMode = value.scheduling_mode.into();
+        Self {
+            plugin_id: Some(value.plugin_id.into()),
+            scheduling_mode: scheduling_mode as i32,
+            max_in_flight_per_tenant: value.max_in_flight_per_tenant,
+        }
+    }
+}
+
+impl ProtobufSerializable for SetSchedulingPolicyRequest {
+    type ProtobufMessage = proto::SetSchedulingPolicyRequest;
+}
+
+impl type_url::TypeUrl for SetSchedulingPolicyRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetSchedulingPolicyRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetSchedulingPolicyResponse {}
+
+impl TryFrom<proto::SetSchedulingPolicyResponse> for SetSchedulingPolicyResponse {
+    type Error = SerDeError;
+
+    fn try_from(_value: proto::SetSchedulingPolicyResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<SetSchedulingPolicyResponse> for proto::SetSchedulingPolicyResponse {
+    fn from(_value: SetSchedulingPolicyResponse) -> Self {
+        Self {}
+    }
+}
+
+impl ProtobufSerializable for SetSchedulingPolicyResponse {
+    type ProtobufMessage = proto::SetSchedulingPolicyResponse;
+}
+
+impl type_url::TypeUrl for SetSchedulingPolicyResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.SetSchedulingPolicyResponse";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionLease {
+    pub request_id: i64,
+    pub lease_id: Uuid,
+}
+
+impl TryFrom<proto::ExecutionLease> for ExecutionLease {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::ExecutionLease) -> Result<Self, Self::Error> {
+        let lease_id = value
+            .lease_id
+            .ok_or(Self::Error::MissingField("lease_id"))?
+            .into();
+
+        Ok(Self {
+            request_id: value.request_id,
+            lease_id,
+        })
+    }
+}
+
+impl From<ExecutionLease> for proto::ExecutionLease {
+    fn from(value: ExecutionLease) -> Self {
+        Self {
+            request_id: value.request_id,
+            lease_id: Some(value.lease_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for ExecutionLease {
+    type ProtobufMessage = proto::ExecutionLease;
+}
+
+impl type_url::TypeUrl for ExecutionLease {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ExecutionLease";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendLeaseRequest {
+    pub plugin_type: PluginType,
+    pub plugin_id: Uuid,
+    pub leases: Vec<ExecutionLease>,
+}
+
+impl TryFrom<proto::ExtendLeaseRequest> for ExtendLeaseRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::ExtendLeaseRequest) -> Result<Self, Self::Error> {
+        let plugin_type = value.plugin_type().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+        let leases = value
+            .leases
+            .into_iter()
+            .map(ExecutionLease::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            plugin_type,
+            plugin_id,
+            leases,
+        })
+    }
+}
+
+impl From<ExtendLeaseRequest> for proto::ExtendLeaseRequest {
+    fn from(value: ExtendLeaseRequest) -> Self {
+        let plugin_type: PluginTypeProto = value.plugin_type.into();
+        Self {
+            plugin_type: plugin_type as i32,
+            plugin_id: Some(value.plugin_id.into()),
+            leases: value
+                .leases
+                .into_iter()
+                .map(proto::ExecutionLease::from)
+                .collect(),
+        }
+    }
+}
+
+impl ProtobufSerializable for ExtendLeaseRequest {
+    type ProtobufMessage = proto::ExtendLeaseRequest;
+}
+
+impl type_url::TypeUrl for ExtendLeaseRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ExtendLeaseRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendLeaseResponse {
+    pub lost_request_ids: Vec<i64>,
+}
+
+impl TryFrom<proto::ExtendLeaseResponse> for ExtendLeaseResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::ExtendLeaseResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            lost_request_ids: value.lost_request_ids,
+        })
+    }
+}
+
+impl From<ExtendLeaseResponse> for proto::ExtendLeaseResponse {
+    fn from(value: ExtendLeaseResponse) -> Self {
+        Self {
+            lost_request_ids: value.lost_request_ids,
+        }
+    }
+}
+
+impl ProtobufSerializable for ExtendLeaseResponse {
+    type ProtobufMessage = proto::ExtendLeaseResponse;
+}
+
+impl type_url::TypeUrl for ExtendLeaseResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ExtendLeaseResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetteredExecution {
+    pub request_id: i64,
+    pub plugin_id: Uuid,
+    pub tenant_id: Uuid,
+    pub trace_id: Uuid,
+    pub event_source_id: Uuid,
+    pub attempts: u32,
+    pub creation_time: SystemTime,
+    pub dead_lettered_time: SystemTime,
+}
+
+impl TryFrom<proto::DeadLetteredExecution> for DeadLetteredExecution {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::DeadLetteredExecution) -> Result<Self, Self::Error> {
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+        let tenant_id = value
+            .tenant_id
+            .ok_or(Self::Error::MissingField("tenant_id"))?
+            .into();
+        let trace_id = value
+            .trace_id
+            .ok_or(Self::Error::MissingField("trace_id"))?
+            .into();
+        let event_source_id = value
+            .event_source_id
+            .ok_or(Self::Error::MissingField("event_source_id"))?
+            .into();
+        let creation_time = value
+            .creation_time
+            .ok_or(Self::Error::MissingField("creation_time"))?
+            .try_into()?;
+        let dead_lettered_time = value
+            .dead_lettered_time
+            .ok_or(Self::Error::MissingField("dead_lettered_time"))?
+            .try_into()?;
+
+        Ok(Self {
+            request_id: value.request_id,
+            plugin_id,
+            tenant_id,
+            trace_id,
+            event_source_id,
+            attempts: value.attempts,
+            creation_time,
+            dead_lettered_time,
+        })
+    }
+}
+
+impl TryFrom<DeadLetteredExecution> for proto::DeadLetteredExecution {
+    type Error = SerDeError;
+
+    fn try_from(value: DeadLetteredExecution) -> Result<Self, Self::Error> {
+        Ok(Self {
+            request_id: value.request_id,
+            plugin_id: Some(value.plugin_id.into()),
+            tenant_id: Some(value.tenant_id.into()),
+            trace_id: Some(value.trace_id.into()),
+            event_source_id: Some(value.event_source_id.into()),
+            attempts: value.attempts,
+            creation_time: Some(value.creation_time.try_into()?),
+            dead_lettered_time: Some(value.dead_lettered_time.try_into()?),
+        })
+    }
+}
+
+impl ProtobufSerializable for DeadLetteredExecution {
+    type ProtobufMessage = proto::DeadLetteredExecution;
+}
+
+impl type_url::TypeUrl for DeadLetteredExecution {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.DeadLetteredExecution";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListDeadLetteredExecutionsRequest {
+    pub plugin_type: PluginType,
+    pub plugin_id: Uuid,
+}
+
+impl TryFrom<proto::ListDeadLetteredExecutionsRequest> for ListDeadLetteredExecutionsRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::ListDeadLetteredExecutionsRequest) -> Result<Self, Self::Error> {
+        let plugin_type = value.plugin_type().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        Ok(Self {
+            plugin_type,
+            plugin_id,
+        })
+    }
+}
+
+impl From<ListDeadLetteredExecutionsRequest> for proto::ListDeadLetteredExecutionsRequest {
+    fn from(value: ListDeadLetteredExecutionsRequest) -> Self {
+        let plugin_type: PluginTypeProto = value.plugin_type.into();
+        Self {
+            plugin_type: plugin_type as i32,
+            plugin_id: Some(value.plugin_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for ListDeadLetteredExecutionsRequest {
+    type ProtobufMessage = proto::ListDeadLetteredExecutionsRequest;
+}
+
+impl type_url::TypeUrl for ListDeadLetteredExecutionsRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ListDeadLetteredExecutionsRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListDeadLetteredExecutionsResponse {
+    pub executions: Vec<DeadLetteredExecution>,
+}
+
+impl TryFrom<proto::ListDeadLetteredExecutionsResponse> for ListDeadLetteredExecutionsResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::ListDeadLetteredExecutionsResponse) -> Result<Self, Self::Error> {
+        let executions = value
+            .executions
+            .into_iter()
+            .map(DeadLetteredExecution::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { executions })
+    }
+}
+
+impl TryFrom<ListDeadLetteredExecutionsResponse> for proto::ListDeadLetteredExecutionsResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: ListDeadLetteredExecutionsResponse) -> Result<Self, Self::Error> {
+        let executions = value
+            .executions
+            .into_iter()
+            .map(proto::DeadLetteredExecution::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { executions })
+    }
+}
+
+impl ProtobufSerializable for ListDeadLetteredExecutionsResponse {
+    type ProtobufMessage = proto::ListDeadLetteredExecutionsResponse;
+}
+
+impl type_url::TypeUrl for ListDeadLetteredExecutionsResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.ListDeadLetteredExecutionsResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetDeadLetteredExecutionRequest {
+    pub plugin_type: PluginType,
+    pub plugin_id: Uuid,
+    pub request_id: i64,
+}
+
+impl TryFrom<proto::GetDeadLetteredExecutionRequest> for GetDeadLetteredExecutionRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::GetDeadLetteredExecutionRequest) -> Result<Self, Self::Error> {
+        let plugin_type = value.plugin_type().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        Ok(Self {
+            plugin_type,
+            plugin_id,
+            request_id: value.request_id,
+        })
+    }
+}
+
+impl From<GetDeadLetteredExecutionRequest> for proto::GetDeadLetteredExecutionRequest {
+    fn from(value: GetDeadLetteredExecutionRequest) -> Self {
+        let plugin_type: PluginTypeProto = value.plugin_type.into();
+        Self {
+            plugin_type: plugin_type as i32,
+            plugin_id: Some(value.plugin_id.into()),
+            request_id: value.request_id,
+        }
+    }
+}
+
+impl ProtobufSerializable for GetDeadLetteredExecutionRequest {
+    type ProtobufMessage = proto::GetDeadLetteredExecutionRequest;
+}
+
+impl type_url::TypeUrl for GetDeadLetteredExecutionRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.GetDeadLetteredExecutionRequest";
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetDeadLetteredExecutionResponse {
+    pub execution: DeadLetteredExecution,
+    pub data: Bytes,
+}
+
+impl std::fmt::Debug for GetDeadLetteredExecutionResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GetDeadLetteredExecutionResponse")
+            .field("execution", &self.execution)
+            .field("data.len", &self.data.len())
+            .finish()
+    }
+}
+
+impl TryFrom<proto::GetDeadLetteredExecutionResponse> for GetDeadLetteredExecutionResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::GetDeadLetteredExecutionResponse) -> Result<Self, Self::Error> {
+        let execution = value
+            .execution
+            .ok_or(Self::Error::MissingField("execution"))?
+            .try_into()?;
+
+        Ok(Self {
+            execution,
+            data: value.data,
+        })
+    }
+}
+
+impl TryFrom<GetDeadLetteredExecutionResponse> for proto::GetDeadLetteredExecutionResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: GetDeadLetteredExecutionResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            execution: Some(value.execution.try_into()?),
+            data: value.data,
+        })
+    }
+}
+
+impl ProtobufSerializable for GetDeadLetteredExecutionResponse {
+    type ProtobufMessage = proto::GetDeadLetteredExecutionResponse;
+}
+
+impl type_url::TypeUrl for GetDeadLetteredExecutionResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.GetDeadLetteredExecutionResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequeueDeadLetteredExecutionRequest {
+    pub plugin_type: PluginType,
+    pub plugin_id: Uuid,
+    pub request_id: i64,
+}
+
+impl TryFrom<proto::RequeueDeadLetteredExecutionRequest> for RequeueDeadLetteredExecutionRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::RequeueDeadLetteredExecutionRequest) -> Result<Self, Self::Error> {
+        let plugin_type = value.plugin_type().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        Ok(Self {
+            plugin_type,
+            plugin_id,
+            request_id: value.request_id,
+        })
+    }
+}
+
+impl From<RequeueDeadLetteredExecutionRequest> for proto::RequeueDeadLetteredExecutionRequest {
+    fn from(value: RequeueDeadLetteredExecutionRequest) -> Self {
+        let plugin_type: PluginTypeProto = value.plugin_type.into();
+        Self {
+            plugin_type: plugin_type as i32,
+            plugin_id: Some(value.plugin_id.into()),
+            request_id: value.request_id,
+        }
+    }
+}
+
+impl ProtobufSerializable for RequeueDeadLetteredExecutionRequest {
+    type ProtobufMessage = proto::RequeueDeadLetteredExecutionRequest;
+}
+
+impl type_url::TypeUrl for RequeueDeadLetteredExecutionRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.RequeueDeadLetteredExecutionRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequeueDeadLetteredExecutionResponse {}
+
+impl TryFrom<proto::RequeueDeadLetteredExecutionResponse> for RequeueDeadLetteredExecutionResponse {
+    type Error = SerDeError;
+
+    fn try_from(_value: proto::RequeueDeadLetteredExecutionResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<RequeueDeadLetteredExecutionResponse> for proto::RequeueDeadLetteredExecutionResponse {
+    fn from(_value: RequeueDeadLetteredExecutionResponse) -> Self {
+        Self {}
+    }
+}
+
+impl ProtobufSerializable for RequeueDeadLetteredExecutionResponse {
+    type ProtobufMessage = proto::RequeueDeadLetteredExecutionResponse;
+}
+
+impl type_url::TypeUrl for RequeueDeadLetteredExecutionResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.RequeueDeadLetteredExecutionResponse";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryHistogramBucket {
+    pub attempts: u32,
+    pub count: u64,
+}
+
+impl TryFrom<proto::RetryHistogramBucket> for RetryHistogramBucket {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::RetryHistogramBucket) -> Result<Self, Self::Error> {
+        Ok(Self {
+            attempts: value.attempts,
+            count: value.count,
+        })
+    }
+}
+
+impl From<RetryHistogramBucket> for proto::RetryHistogramBucket {
+    fn from(value: RetryHistogramBucket) -> Self {
+        Self {
+            attempts: value.attempts,
+            count: value.count,
+        }
+    }
+}
+
+impl ProtobufSerializable for RetryHistogramBucket {
+    type ProtobufMessage = proto::RetryHistogramBucket;
+}
+
+impl type_url::TypeUrl for RetryHistogramBucket {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.RetryHistogramBucket";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QueueStats {
+    pub enqueued: u64,
+    pub in_flight: u64,
+    pub failed: u64,
+    pub processed: u64,
+    pub dead_lettered: u64,
+    pub oldest_enqueued_age: Option<Duration>,
+    pub retry_histogram: Vec<RetryHistogramBucket>,
+}
+
+impl TryFrom<proto::QueueStats> for QueueStats {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::QueueStats) -> Result<Self, Self::Error> {
+        let retry_histogram = value
+            .retry_histogram
+            .into_iter()
+            .map(RetryHistogramBucket::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            enqueued: value.enqueued,
+            in_flight: value.in_flight,
+            failed: value.failed,
+            processed: value.processed,
+            dead_lettered: value.dead_lettered,
+            oldest_enqueued_age: value.oldest_enqueued_age.map(Duration::from),
+            retry_histogram,
+        })
+    }
+}
+
+impl From<QueueStats> for proto::QueueStats {
+    fn from(value: QueueStats) -> Self {
+        Self {
+            enqueued: value.enqueued,
+            in_flight: value.in_flight,
+            failed: value.failed,
+            processed: value.processed,
+            dead_lettered: value.dead_lettered,
+            oldest_enqueued_age: value.oldest_enqueued_age.map(Into::into),
+            retry_histogram: value
+                .retry_histogram
+                .into_iter()
+                .map(proto::RetryHistogramBucket::from)
+                .collect(),
+        }
+    }
+}
+
+impl ProtobufSerializable for QueueStats {
+    type ProtobufMessage = proto::QueueStats;
+}
+
+impl type_url::TypeUrl for QueueStats {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.QueueStats";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantQueueStats {
+    pub tenant_id: Uuid,
+    pub stats: QueueStats,
+}
+
+impl TryFrom<proto::TenantQueueStats> for TenantQueueStats {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::TenantQueueStats) -> Result<Self, Self::Error> {
+        let tenant_id = value
+            .tenant_id
+            .ok_or(Self::Error::MissingField("tenant_id"))?
+            .into();
+        let stats = value
+            .stats
+            .ok_or(Self::Error::MissingField("stats"))?
+            .try_into()?;
+
+        Ok(Self { tenant_id, stats })
+    }
+}
+
+impl From<TenantQueueStats> for proto::TenantQueueStats {
+    fn from(value: TenantQueueStats) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            stats: Some(value.stats.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for TenantQueueStats {
+    type ProtobufMessage = proto::TenantQueueStats;
+}
+
+impl type_url::TypeUrl for TenantQueueStats {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.TenantQueueStats";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetQueueStatsRequest {
+    pub plugin_type: PluginType,
+    pub plugin_id: Uuid,
+}
+
+impl TryFrom<proto::GetQueueStatsRequest> for GetQueueStatsRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::GetQueueStatsRequest) -> Result<Self, Self::Error> {
+        let plugin_type = value.plugin_type().try_into()?;
+        let plugin_id = value
+            .plugin_id
+            .ok_or(Self::Error::MissingField("plugin_id"))?
+            .into();
+
+        Ok(Self {
+            plugin_type,
+            plugin_id,
+        })
+    }
+}
+
+impl From<GetQueueStatsRequest> for proto::GetQueueStatsRequest {
+    fn from(value: GetQueueStatsRequest) -> Self {
+        let plugin_type: PluginTypeProto = value.plugin_type.into();
+        Self {
+            plugin_type: plugin_type as i32,
+            plugin_id: Some(value.plugin_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for GetQueueStatsRequest {
+    type ProtobufMessage = proto::GetQueueStatsRequest;
+}
+
+impl type_url::TypeUrl for GetQueueStatsRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.GetQueueStatsRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetQueueStatsResponse {
+    pub stats: QueueStats,
+    pub tenant_stats: Vec<TenantQueueStats>,
+}
+
+impl TryFrom<proto::GetQueueStatsResponse> for GetQueueStatsResponse {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::GetQueueStatsResponse) -> Result<Self, Self::Error> {
+        let stats = value
+            .stats
+            .ok_or(Self::Error::MissingField("stats"))?
+            .try_into()?;
+        let tenant_stats = value
+            .tenant_stats
+            .into_iter()
+            .map(TenantQueueStats::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            stats,
+            tenant_stats,
+        })
+    }
+}
+
+impl From<GetQueueStatsResponse> for proto::GetQueueStatsResponse {
+    fn from(value: GetQueueStatsResponse) -> Self {
+        Self {
+            stats: Some(value.stats.into()),
+            tenant_stats: value
+                .tenant_stats
+                .into_iter()
+                .map(proto::TenantQueueStats::from)
+                .collect(),
+        }
+    }
+}
+
+impl ProtobufSerializable for GetQueueStatsResponse {
+    type ProtobufMessage = proto::GetQueueStatsResponse;
+}
+
+impl type_url::TypeUrl for GetQueueStatsResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.GetQueueStatsResponse";
+}
@@ -84,7 +84,7 @@ impl PluginWorkQueueServiceClient {
         )
     }
 
-    /// Retrieves a new execution job for a generator
+    /// Retrieves new execution jobs for a generator
     #[tracing::instrument(skip(self, request), err)]
     pub async fn get_execute_generator(
         &mut self,
@@ -100,7 +100,7 @@ impl PluginWorkQueueServiceClient {
         )
     }
 
-    /// Retrieves a new execution job for an analyzer
+    /// Retrieves new execution jobs for an analyzer
     #[tracing::instrument(skip(self, request), err)]
     pub async fn get_execute_analyzer(
         &mut self,
@@ -147,4 +147,165 @@ impl PluginWorkQueueServiceClient {
             RpcConfig::default(),
         )
     }
+
+    /// Acknowledges the completion of many generator jobs at once
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn acknowledge_generator_batch(
+        &mut self,
+        request: native::AcknowledgeGeneratorBatchRequest,
+    ) -> Result<native::AcknowledgeGeneratorBatchResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            acknowledge_generator_batch,
+            proto::AcknowledgeGeneratorBatchRequest,
+            native::AcknowledgeGeneratorBatchResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Acknowledges the completion of many analyzer jobs at once
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn acknowledge_analyzer_batch(
+        &mut self,
+        request: native::AcknowledgeAnalyzerBatchRequest,
+    ) -> Result<native::AcknowledgeAnalyzerBatchResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            acknowledge_analyzer_batch,
+            proto::AcknowledgeAnalyzerBatchRequest,
+            native::AcknowledgeAnalyzerBatchResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Overrides the number of attempts a plugin's jobs get before they are dead-lettered
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn set_max_attempts(
+        &mut self,
+        request: native::SetMaxAttemptsRequest,
+    ) -> Result<native::SetMaxAttemptsResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            set_max_attempts,
+            proto::SetMaxAttemptsRequest,
+            native::SetMaxAttemptsResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Overrides how long a plugin's jobs are leased for when handed out
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn set_visibility_timeout(
+        &mut self,
+        request: native::SetVisibilityTimeoutRequest,
+    ) -> Result<native::SetVisibilityTimeoutResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            set_visibility_timeout,
+            proto::SetVisibilityTimeoutRequest,
+            native::SetVisibilityTimeoutResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Overrides how a plugin's jobs are shared out between tenants
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn set_scheduling_policy(
+        &mut self,
+        request: native::SetSchedulingPolicyRequest,
+    ) -> Result<native::SetSchedulingPolicyResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            set_scheduling_policy,
+            proto::SetSchedulingPolicyRequest,
+            native::SetSchedulingPolicyResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Extends the leases of jobs that are still being worked on
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn extend_lease(
+        &mut self,
+        request: native::ExtendLeaseRequest,
+    ) -> Result<native::ExtendLeaseResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            extend_lease,
+            proto::ExtendLeaseRequest,
+            native::ExtendLeaseResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Lists the dead-lettered jobs of a plugin
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn list_dead_lettered_executions(
+        &mut self,
+        request: native::ListDeadLetteredExecutionsRequest,
+    ) -> Result<native::ListDeadLetteredExecutionsResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            list_dead_lettered_executions,
+            proto::ListDeadLetteredExecutionsRequest,
+            native::ListDeadLetteredExecutionsResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Retrieves a single dead-lettered job, including its payload
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn get_dead_lettered_execution(
+        &mut self,
+        request: native::GetDeadLetteredExecutionRequest,
+    ) -> Result<native::GetDeadLetteredExecutionResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_dead_lettered_execution,
+            proto::GetDeadLetteredExecutionRequest,
+            native::GetDeadLetteredExecutionResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Moves a dead-lettered job back onto the queue with a fresh attempt count
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn requeue_dead_lettered_execution(
+        &mut self,
+        request: native::RequeueDeadLetteredExecutionRequest,
+    ) -> Result<native::RequeueDeadLetteredExecutionResponse, PluginWorkQueueServiceClientError>
+    {
+        execute_client_rpc!(
+            self,
+            request,
+            requeue_dead_lettered_execution,
+            proto::RequeueDeadLetteredExecutionRequest,
+            native::RequeueDeadLetteredExecutionResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    /// Reports how much work is waiting for a plugin, overall and per tenant
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn get_queue_stats(
+        &mut self,
+        request: native::GetQueueStatsRequest,
+    ) -> Result<native::GetQueueStatsResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_queue_stats,
+            proto::GetQueueStatsRequest,
+            native::GetQueueStatsResponse,
+            RpcConfig::default(),
+        )
+    }
 }
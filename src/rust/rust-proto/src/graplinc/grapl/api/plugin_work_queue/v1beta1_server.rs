@@ -69,6 +69,56 @@ pub trait PluginWorkQueueApi {
         &self,
         request: native::AcknowledgeAnalyzerRequest,
     ) -> Result<native::AcknowledgeAnalyzerResponse, Self::Error>;
+
+    async fn acknowledge_generator_batch(
+        &self,
+        request: native::AcknowledgeGeneratorBatchRequest,
+    ) -> Result<native::AcknowledgeGeneratorBatchResponse, Self::Error>;
+
+    async fn acknowledge_analyzer_batch(
+        &self,
+        request: native::AcknowledgeAnalyzerBatchRequest,
+    ) -> Result<native::AcknowledgeAnalyzerBatchResponse, Self::Error>;
+
+    async fn set_max_attempts(
+        &self,
+        request: native::SetMaxAttemptsRequest,
+    ) -> Result<native::SetMaxAttemptsResponse, Self::Error>;
+
+    async fn set_visibility_timeout(
+        &self,
+        request: native::SetVisibilityTimeoutRequest,
+    ) -> Result<native::SetVisibilityTimeoutResponse, Self::Error>;
+
+    async fn set_scheduling_policy(
+        &self,
+        request: native::SetSchedulingPolicyRequest,
+    ) -> Result<native::SetSchedulingPolicyResponse, Self::Error>;
+
+    async fn extend_lease(
+        &self,
+        request: native::ExtendLeaseRequest,
+    ) -> Result<native::ExtendLeaseResponse, Self::Error>;
+
+    async fn list_dead_lettered_executions(
+        &self,
+        request: native::ListDeadLetteredExecutionsRequest,
+    ) -> Result<native::ListDeadLetteredExecutionsResponse, Self::Error>;
+
+    async fn get_dead_lettered_execution(
+        &self,
+        request: native::GetDeadLetteredExecutionRequest,
+    ) -> Result<native::GetDeadLetteredExecutionResponse, Self::Error>;
+
+    async fn requeue_dead_lettered_execution(
+        &self,
+        request: native::RequeueDeadLetteredExecutionRequest,
+    ) -> Result<native::RequeueDeadLetteredExecutionResponse, Self::Error>;
+
+    async fn get_queue_stats(
+        &self,
+        request: native::GetQueueStatsRequest,
+    ) -> Result<native::GetQueueStatsResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -117,6 +167,76 @@ where
     ) -> Result<tonic::Response<proto::AcknowledgeAnalyzerResponse>, tonic::Status> {
         execute_rpc!(self, request, acknowledge_analyzer)
     }
+
+    async fn acknowledge_generator_batch(
+        &self,
+        request: tonic::Request<proto::AcknowledgeGeneratorBatchRequest>,
+    ) -> Result<tonic::Response<proto::AcknowledgeGeneratorBatchResponse>, tonic::Status> {
+        execute_rpc!(self, request, acknowledge_generator_batch)
+    }
+
+    async fn acknowledge_analyzer_batch(
+        &self,
+        request: tonic::Request<proto::AcknowledgeAnalyzerBatchRequest>,
+    ) -> Result<tonic::Response<proto::AcknowledgeAnalyzerBatchResponse>, tonic::Status> {
+        execute_rpc!(self, request, acknowledge_analyzer_batch)
+    }
+
+    async fn set_max_attempts(
+        &self,
+        request: tonic::Request<proto::SetMaxAttemptsRequest>,
+    ) -> Result<tonic::Response<proto::SetMaxAttemptsResponse>, tonic::Status> {
+        execute_rpc!(self, request, set_max_attempts)
+    }
+
+    async fn set_visibility_timeout(
+        &self,
+        request: tonic::Request<proto::SetVisibilityTimeoutRequest>,
+    ) -> Result<tonic::Response<proto::SetVisibilityTimeoutResponse>, tonic::Status> {
+        execute_rpc!(self, request, set_visibility_timeout)
+    }
+
+    async fn set_scheduling_policy(
+        &self,
+        request: tonic::Request<proto::SetSchedulingPolicyRequest>,
+    ) -> Result<tonic::Response<proto::SetSchedulingPolicyResponse>, tonic::Status> {
+        execute_rpc!(self, request, set_scheduling_policy)
+    }
+
+    async fn extend_lease(
+        &self,
+        request: tonic::Request<proto::ExtendLeaseRequest>,
+    ) -> Result<tonic::Response<proto::ExtendLeaseResponse>, tonic::Status> {
+        execute_rpc!(self, request, extend_lease)
+    }
+
+    async fn list_dead_lettered_executions(
+        &self,
+        request: tonic::Request<proto::ListDeadLetteredExecutionsRequest>,
+    ) -> Result<tonic::Response<proto::ListDeadLetteredExecutionsResponse>, tonic::Status> {
+        execute_rpc!(self, request, list_dead_lettered_executions)
+    }
+
+    async fn get_dead_lettered_execution(
+        &self,
+        request: tonic::Request<proto::GetDeadLetteredExecutionRequest>,
+    ) -> Result<tonic::Response<proto::GetDeadLetteredExecutionResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_dead_lettered_execution)
+    }
+
+    async fn requeue_dead_lettered_execution(
+        &self,
+        request: tonic::Request<proto::RequeueDeadLetteredExecutionRequest>,
+    ) -> Result<tonic::Response<proto::RequeueDeadLetteredExecutionResponse>, tonic::Status> {
+        execute_rpc!(self, request, requeue_dead_lettered_execution)
+    }
+
+    async fn get_queue_stats(
+        &self,
+        request: tonic::Request<proto::GetQueueStatsRequest>,
+    ) -> Result<tonic::Response<proto::GetQueueStatsResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_queue_stats)
+    }
 }
 
 /**
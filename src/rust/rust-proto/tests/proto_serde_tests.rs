@@ -467,6 +467,31 @@ mod plugin_work_queue {
             check_encode_decode_invariant(value)
         }
 
+        #[test]
+        fn test_acknowledge_generator_batch_requests(value in pwq_strats::acknowledge_generator_batch_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_acknowledge_generator_batch_responses(value in pwq_strats::acknowledge_generator_batch_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_acknowledge_analyzer_batch_requests(value in pwq_strats::acknowledge_analyzer_batch_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_acknowledge_analyzer_batch_responses(value in pwq_strats::acknowledge_analyzer_batch_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_acquired_execution_jobs(value in pwq_strats::acquired_execution_jobs()) {
+            check_encode_decode_invariant(value)
+        }
+
 
         #[test]
         fn test_get_execute_analyzer_requests(value in pwq_strats::get_execute_analyzer_requests()) {
@@ -512,6 +537,106 @@ mod plugin_work_queue {
         fn test_push_execute_generator_responses(value in pwq_strats::push_execute_generator_responses()) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_set_max_attempts_requests(value in pwq_strats::set_max_attempts_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_set_max_attempts_responses(value in pwq_strats::set_max_attempts_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_set_visibility_timeout_requests(value in pwq_strats::set_visibility_timeout_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_set_visibility_timeout_responses(value in pwq_strats::set_visibility_timeout_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_set_scheduling_policy_requests(value in pwq_strats::set_scheduling_policy_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_set_scheduling_policy_responses(value in pwq_strats::set_scheduling_policy_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_execution_leases(value in pwq_strats::execution_leases()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_extend_lease_requests(value in pwq_strats::extend_lease_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_extend_lease_responses(value in pwq_strats::extend_lease_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_dead_lettered_executions(value in pwq_strats::dead_lettered_executions()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_list_dead_lettered_executions_requests(value in pwq_strats::list_dead_lettered_executions_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_list_dead_lettered_executions_responses(value in pwq_strats::list_dead_lettered_executions_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_dead_lettered_execution_requests(value in pwq_strats::get_dead_lettered_execution_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_dead_lettered_execution_responses(value in pwq_strats::get_dead_lettered_execution_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_requeue_dead_lettered_execution_requests(value in pwq_strats::requeue_dead_lettered_execution_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_requeue_dead_lettered_execution_responses(value in pwq_strats::requeue_dead_lettered_execution_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_queue_stats(value in pwq_strats::queue_stats()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_tenant_queue_stats(value in pwq_strats::tenant_queue_stats()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_queue_stats_requests(value in pwq_strats::get_queue_stats_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_queue_stats_responses(value in pwq_strats::get_queue_stats_responses()) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -526,10 +651,70 @@ mod graph_schema_manager {
             check_encode_decode_invariant(value)
         }
 
+        #[test]
+        fn test_deploy_schema_responses(value in sm_strats::deploy_schema_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
         #[test]
         fn test_get_edge_schema_responses(value in sm_strats::get_edge_schema_responses()) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_get_node_identity_algorithm_requests(value in sm_strats::get_node_identity_algorithm_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_node_identity_algorithm_responses(value in sm_strats::get_node_identity_algorithm_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_node_schema_requests(value in sm_strats::get_node_schema_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_node_schema_responses(value in sm_strats::get_node_schema_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_list_node_schemas_requests(value in sm_strats::list_node_schemas_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_list_node_schemas_responses(value in sm_strats::list_node_schemas_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_raw_schema_requests(value in sm_strats::get_raw_schema_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_raw_schema_responses(value in sm_strats::get_raw_schema_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_diff_schemas_requests(value in sm_strats::diff_schemas_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_diff_schemas_responses(value in sm_strats::diff_schemas_responses()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_node_validation_reports(value in sm_strats::node_validation_reports()) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -1,4 +1,7 @@
-use std::time::SystemTime;
+use std::time::{
+    Duration,
+    SystemTime,
+};
 
 use bytes::Bytes;
 use proptest::prelude::*;
@@ -77,7 +80,9 @@ pub mod pipeline {
 
 pub mod common {
 
-    use rust_proto::graplinc::grapl::common::v1beta1::types::{self as native,};
+    use rust_proto::graplinc::grapl::common::v1beta1::types::{
+        self as native,
+    };
 
     use super::*;
     prop_compose! {
@@ -987,6 +992,7 @@ pub mod plugin_work_queue {
     prop_compose! {
         pub fn acknowledge_generator_requests()(
             request_id in any::<i64>(),
+            lease_id in proptest::option::of(uuids()),
             graph_description in proptest::option::of(graph::graph_descriptions()),
             plugin_id in uuids(),
             tenant_id in uuids(),
@@ -995,6 +1001,7 @@ pub mod plugin_work_queue {
         ) -> native::AcknowledgeGeneratorRequest {
             native::AcknowledgeGeneratorRequest::new(
                 request_id,
+                lease_id,
                 graph_description,
                 plugin_id,
                 tenant_id,
@@ -1012,6 +1019,7 @@ pub mod plugin_work_queue {
     prop_compose! {
         pub fn acknowledge_analyzer_requests()(
             request_id in any::<i64>(),
+            lease_id in proptest::option::of(uuids()),
             success in any::<bool>(),
             plugin_id in uuids(),
             tenant_id in uuids(),
@@ -1020,6 +1028,7 @@ pub mod plugin_work_queue {
         ) -> native::AcknowledgeAnalyzerRequest {
             native::AcknowledgeAnalyzerRequest::new(
                 request_id,
+                lease_id,
                 success,
                 plugin_id,
                 tenant_id,
@@ -1034,51 +1043,97 @@ pub mod plugin_work_queue {
         Just(native::AcknowledgeAnalyzerResponse {})
     }
 
-    pub fn maybe_jobs() -> impl Strategy<Value = Option<native::ExecutionJob>> {
-        proptest::option::of(execution_jobs())
+    prop_compose! {
+        pub fn acknowledge_generator_batch_requests()(
+            acknowledgements in proptest::collection::vec(acknowledge_generator_requests(), 0..4),
+        ) -> native::AcknowledgeGeneratorBatchRequest {
+            native::AcknowledgeGeneratorBatchRequest {
+                acknowledgements,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn acknowledge_generator_batch_responses()(
+            rejected_request_ids in proptest::collection::vec(any::<i64>(), 0..10),
+        ) -> native::AcknowledgeGeneratorBatchResponse {
+            native::AcknowledgeGeneratorBatchResponse {
+                rejected_request_ids,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn acknowledge_analyzer_batch_requests()(
+            acknowledgements in proptest::collection::vec(acknowledge_analyzer_requests(), 0..10),
+        ) -> native::AcknowledgeAnalyzerBatchRequest {
+            native::AcknowledgeAnalyzerBatchRequest {
+                acknowledgements,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn acknowledge_analyzer_batch_responses()(
+            rejected_request_ids in proptest::collection::vec(any::<i64>(), 0..10),
+        ) -> native::AcknowledgeAnalyzerBatchResponse {
+            native::AcknowledgeAnalyzerBatchResponse {
+                rejected_request_ids,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn acquired_execution_jobs()(
+            execution_job in execution_jobs(),
+            request_id in any::<i64>(),
+            lease_id in uuids(),
+        ) -> native::AcquiredExecutionJob {
+            native::AcquiredExecutionJob {
+                execution_job,
+                request_id,
+                lease_id,
+            }
+        }
     }
 
     prop_compose! {
         pub fn get_execute_analyzer_requests()(
             plugin_id in uuids(),
+            max_jobs in any::<u32>(),
         ) -> native::GetExecuteAnalyzerRequest {
             native::GetExecuteAnalyzerRequest {
                 plugin_id,
+                max_jobs,
             }
         }
     }
 
     prop_compose! {
         pub fn get_execute_analyzer_responses()(
-            execution_job in maybe_jobs(),
-            request_id in any::<i64>(),
+            jobs in proptest::collection::vec(acquired_execution_jobs(), 0..10),
         ) -> native::GetExecuteAnalyzerResponse {
-            native::GetExecuteAnalyzerResponse::new(
-                execution_job,
-                request_id,
-            )
+            native::GetExecuteAnalyzerResponse::new(jobs)
         }
     }
 
     prop_compose! {
         pub fn get_execute_generator_requests()(
             plugin_id in uuids(),
+            max_jobs in any::<u32>(),
         ) -> native::GetExecuteGeneratorRequest {
             native::GetExecuteGeneratorRequest::new(
                 plugin_id,
+                max_jobs,
             )
         }
     }
 
     prop_compose! {
         pub fn get_execute_generator_responses()(
-            execution_job in maybe_jobs(),
-            request_id in any::<i64>(),
+            jobs in proptest::collection::vec(acquired_execution_jobs(), 0..10),
         ) -> native::GetExecuteGeneratorResponse {
-            native::GetExecuteGeneratorResponse::new(
-                execution_job,
-                request_id,
-            )
+            native::GetExecuteGeneratorResponse::new(jobs)
         }
     }
 
@@ -1115,6 +1170,255 @@ pub mod plugin_work_queue {
     ) -> impl Strategy<Value = native::PushExecuteGeneratorResponse> {
         Just(native::PushExecuteGeneratorResponse {})
     }
+
+    prop_compose! {
+        pub fn set_max_attempts_requests()(
+            plugin_id in uuids(),
+            max_attempts in 1..=u32::MAX,
+        ) -> native::SetMaxAttemptsRequest {
+            native::SetMaxAttemptsRequest {
+                plugin_id,
+                max_attempts,
+            }
+        }
+    }
+
+    pub fn set_max_attempts_responses() -> impl Strategy<Value = native::SetMaxAttemptsResponse> {
+        Just(native::SetMaxAttemptsResponse {})
+    }
+
+    prop_compose! {
+        pub fn set_visibility_timeout_requests()(
+            plugin_id in uuids(),
+            seconds in 1..=u64::MAX,
+        ) -> native::SetVisibilityTimeoutRequest {
+            native::SetVisibilityTimeoutRequest {
+                plugin_id,
+                visibility_timeout: Duration::from_secs(seconds),
+            }
+        }
+    }
+
+    pub fn set_visibility_timeout_responses(
+    ) -> impl Strategy<Value = native::SetVisibilityTimeoutResponse> {
+        Just(native::SetVisibilityTimeoutResponse {})
+    }
+
+    pub fn scheduling_modes() -> BoxedStrategy<native::SchedulingMode> {
+        prop_oneof![
+            Just(native::SchedulingMode::OldestFirst),
+            Just(native::SchedulingMode::RoundRobin),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn set_scheduling_policy_requests()(
+            plugin_id in uuids(),
+            scheduling_mode in scheduling_modes(),
+            max_in_flight_per_tenant in proptest::option::of(1..=u32::MAX),
+        ) -> native::SetSchedulingPolicyRequest {
+            native::SetSchedulingPolicyRequest {
+                plugin_id,
+                scheduling_mode,
+                max_in_flight_per_tenant,
+            }
+        }
+    }
+
+    pub fn set_scheduling_policy_responses(
+    ) -> impl Strategy<Value = native::SetSchedulingPolicyResponse> {
+        Just(native::SetSchedulingPolicyResponse {})
+    }
+
+    prop_compose! {
+        pub fn execution_leases()(
+            request_id in any::<i64>(),
+            lease_id in uuids(),
+        ) -> native::ExecutionLease {
+            native::ExecutionLease {
+                request_id,
+                lease_id,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn extend_lease_requests()(
+            plugin_type in plugin_registry::plugin_types(),
+            plugin_id in uuids(),
+            leases in proptest::collection::vec(execution_leases(), 0..10),
+        ) -> native::ExtendLeaseRequest {
+            native::ExtendLeaseRequest {
+                plugin_type,
+                plugin_id,
+                leases,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn extend_lease_responses()(
+            lost_request_ids in proptest::collection::vec(any::<i64>(), 0..10),
+        ) -> native::ExtendLeaseResponse {
+            native::ExtendLeaseResponse { lost_request_ids }
+        }
+    }
+
+    prop_compose! {
+        pub fn dead_lettered_executions()(
+            request_id in any::<i64>(),
+            plugin_id in uuids(),
+            tenant_id in uuids(),
+            trace_id in uuids(),
+            event_source_id in uuids(),
+            attempts in any::<u32>(),
+            creation_time in any::<SystemTime>(),
+            dead_lettered_time in any::<SystemTime>(),
+        ) -> native::DeadLetteredExecution {
+            native::DeadLetteredExecution {
+                request_id,
+                plugin_id,
+                tenant_id,
+                trace_id,
+                event_source_id,
+                attempts,
+                creation_time,
+                dead_lettered_time,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn list_dead_lettered_executions_requests()(
+            plugin_type in plugin_registry::plugin_types(),
+            plugin_id in uuids(),
+        ) -> native::ListDeadLetteredExecutionsRequest {
+            native::ListDeadLetteredExecutionsRequest {
+                plugin_type,
+                plugin_id,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn list_dead_lettered_executions_responses()(
+            executions in proptest::collection::vec(dead_lettered_executions(), 10),
+        ) -> native::ListDeadLetteredExecutionsResponse {
+            native::ListDeadLetteredExecutionsResponse {
+                executions,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_dead_lettered_execution_requests()(
+            plugin_type in plugin_registry::plugin_types(),
+            plugin_id in uuids(),
+            request_id in any::<i64>(),
+        ) -> native::GetDeadLetteredExecutionRequest {
+            native::GetDeadLetteredExecutionRequest {
+                plugin_type,
+                plugin_id,
+                request_id,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_dead_lettered_execution_responses()(
+            execution in dead_lettered_executions(),
+            data in bytes(1024),
+        ) -> native::GetDeadLetteredExecutionResponse {
+            native::GetDeadLetteredExecutionResponse {
+                execution,
+                data,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn requeue_dead_lettered_execution_requests()(
+            plugin_type in plugin_registry::plugin_types(),
+            plugin_id in uuids(),
+            request_id in any::<i64>(),
+        ) -> native::RequeueDeadLetteredExecutionRequest {
+            native::RequeueDeadLetteredExecutionRequest {
+                plugin_type,
+                plugin_id,
+                request_id,
+            }
+        }
+    }
+
+    pub fn requeue_dead_lettered_execution_responses(
+    ) -> impl Strategy<Value = native::RequeueDeadLetteredExecutionResponse> {
+        Just(native::RequeueDeadLetteredExecutionResponse {})
+    }
+
+    prop_compose! {
+        pub fn retry_histogram_buckets()(
+            attempts in any::<u32>(),
+            count in any::<u64>(),
+        ) -> native::RetryHistogramBucket {
+            native::RetryHistogramBucket { attempts, count }
+        }
+    }
+
+    prop_compose! {
+        pub fn queue_stats()(
+            enqueued in any::<u64>(),
+            in_flight in any::<u64>(),
+            failed in any::<u64>(),
+            processed in any::<u64>(),
+            dead_lettered in any::<u64>(),
+            oldest_enqueued_age in proptest::option::of(any::<Duration>()),
+            retry_histogram in proptest::collection::vec(retry_histogram_buckets(), 0..5),
+        ) -> native::QueueStats {
+            native::QueueStats {
+                enqueued,
+                in_flight,
+                failed,
+                processed,
+                dead_lettered,
+                oldest_enqueued_age,
+                retry_histogram,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn tenant_queue_stats()(
+            tenant_id in uuids(),
+            stats in queue_stats(),
+        ) -> native::TenantQueueStats {
+            native::TenantQueueStats { tenant_id, stats }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_queue_stats_requests()(
+            plugin_type in plugin_registry::plugin_types(),
+            plugin_id in uuids(),
+        ) -> native::GetQueueStatsRequest {
+            native::GetQueueStatsRequest {
+                plugin_type,
+                plugin_id,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_queue_stats_responses()(
+            stats in queue_stats(),
+            tenant_stats in proptest::collection::vec(tenant_queue_stats(), 0..5),
+        ) -> native::GetQueueStatsResponse {
+            native::GetQueueStatsResponse {
+                stats,
+                tenant_stats,
+            }
+        }
+    }
 }
 
 pub mod graph_schema_manager {
@@ -1161,12 +1465,338 @@ pub mod graph_schema_manager {
             schema in bytes(32),
             schema_type in schema_types(),
             schema_version in any::<u32>(),
+            force in any::<bool>(),
         ) -> native::DeploySchemaRequest {
             native::DeploySchemaRequest{
                 tenant_id,
                 schema,
                 schema_type,
-                schema_version
+                schema_version,
+                force,
+            }
+        }
+    }
+
+    pub fn schema_compatibility_violation_kinds(
+    ) -> BoxedStrategy<native::SchemaCompatibilityViolationKind> {
+        prop_oneof![
+            Just(native::SchemaCompatibilityViolationKind::PropertyTypeChanged),
+            Just(native::SchemaCompatibilityViolationKind::IdentityAlgorithmChanged),
+            Just(native::SchemaCompatibilityViolationKind::ReverseEdgeRemoved),
+            Just(native::SchemaCompatibilityViolationKind::EdgeCardinalityChanged),
+            Just(native::SchemaCompatibilityViolationKind::ReverseEdgeRenamed),
+            Just(native::SchemaCompatibilityViolationKind::EdgeRemoved),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn schema_compatibility_violations()(
+            node_type in common::node_types(),
+            kind in schema_compatibility_violation_kinds(),
+            description in any::<String>(),
+        ) -> native::SchemaCompatibilityViolation {
+            native::SchemaCompatibilityViolation {
+                node_type,
+                kind,
+                description,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn deploy_schema_responses()(
+            violations in proptest::collection::vec(schema_compatibility_violations(), 0..5),
+        ) -> native::DeploySchemaResponse {
+            native::DeploySchemaResponse { violations }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_node_identity_algorithm_requests()(
+            tenant_id in uuids(),
+            node_type in common::node_types(),
+        ) -> native::GetNodeIdentityAlgorithmRequest {
+            native::GetNodeIdentityAlgorithmRequest {
+                tenant_id,
+                node_type,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn session_identity_arguments()(
+            pseudo_key_properties in proptest::collection::vec(common::property_names(), 0..5),
+            creation_timestamp_property in common::property_names(),
+            last_seen_timestamp_property in common::property_names(),
+            termination_timestamp_property in common::property_names(),
+        ) -> native::SessionIdentityArguments {
+            native::SessionIdentityArguments {
+                pseudo_key_properties,
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn static_identity_arguments()(
+            static_key_properties in proptest::collection::vec(common::property_names(), 0..5),
+        ) -> native::StaticIdentityArguments {
+            native::StaticIdentityArguments {
+                static_key_properties,
+            }
+        }
+    }
+
+    pub fn identity_algorithms() -> BoxedStrategy<native::IdentityAlgorithm> {
+        prop_oneof![
+            session_identity_arguments().prop_map(native::IdentityAlgorithm::Session),
+            static_identity_arguments().prop_map(native::IdentityAlgorithm::Static),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn get_node_identity_algorithm_responses()(
+            identity_algorithm in identity_algorithms(),
+            schema_version in any::<u32>(),
+        ) -> native::GetNodeIdentityAlgorithmResponse {
+            native::GetNodeIdentityAlgorithmResponse {
+                identity_algorithm,
+                schema_version,
+            }
+        }
+    }
+
+    pub fn property_types() -> BoxedStrategy<native::PropertyType> {
+        prop_oneof![
+            Just(native::PropertyType::ImmutableString),
+            Just(native::PropertyType::ImmutableI64),
+            Just(native::PropertyType::MaxI64),
+            Just(native::PropertyType::MinI64),
+            Just(native::PropertyType::ImmutableU64),
+            Just(native::PropertyType::MaxU64),
+            Just(native::PropertyType::MinU64),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn get_node_schema_requests()(
+            tenant_id in uuids(),
+            node_type in common::node_types(),
+            schema_version in proptest::option::of(any::<u32>()),
+        ) -> native::GetNodeSchemaRequest {
+            native::GetNodeSchemaRequest {
+                tenant_id,
+                node_type,
+                schema_version,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn property_schemas()(
+            property_name in common::property_names(),
+            property_type in property_types(),
+            identity_only in any::<bool>(),
+        ) -> native::PropertySchema {
+            native::PropertySchema {
+                property_name,
+                property_type,
+                identity_only,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn edge_schemas()(
+            edge_name in common::edge_names(),
+            reverse_edge_name in common::edge_names(),
+            cardinality in edge_cardinalities(),
+            reverse_cardinality in edge_cardinalities(),
+        ) -> native::EdgeSchema {
+            native::EdgeSchema {
+                edge_name,
+                reverse_edge_name,
+                cardinality,
+                reverse_cardinality,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_node_schema_responses()(
+            properties in proptest::collection::vec(property_schemas(), 0..5),
+            edges in proptest::collection::vec(edge_schemas(), 0..5),
+            schema_version in any::<u32>(),
+        ) -> native::GetNodeSchemaResponse {
+            native::GetNodeSchemaResponse {
+                properties,
+                edges,
+                schema_version,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn list_node_schemas_requests()(
+            tenant_id in uuids(),
+        ) -> native::ListNodeSchemasRequest {
+            native::ListNodeSchemasRequest { tenant_id }
+        }
+    }
+
+    prop_compose! {
+        pub fn node_schema_versions()(
+            node_type in common::node_types(),
+            schema_version in any::<u32>(),
+            deployment_timestamp in any::<SystemTime>(),
+        ) -> native::NodeSchemaVersion {
+            native::NodeSchemaVersion {
+                node_type,
+                schema_version,
+                deployment_timestamp,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn list_node_schemas_responses()(
+            node_schemas in proptest::collection::vec(node_schema_versions(), 0..5),
+        ) -> native::ListNodeSchemasResponse {
+            native::ListNodeSchemasResponse { node_schemas }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_raw_schema_requests()(
+            tenant_id in uuids(),
+            node_type in common::node_types(),
+            schema_version in any::<u32>(),
+        ) -> native::GetRawSchemaRequest {
+            native::GetRawSchemaRequest {
+                tenant_id,
+                node_type,
+                schema_version,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_raw_schema_responses()(
+            schema in bytes(32),
+            schema_type in schema_types(),
+        ) -> native::GetRawSchemaResponse {
+            native::GetRawSchemaResponse {
+                schema,
+                schema_type,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn diff_schemas_requests()(
+            tenant_id in uuids(),
+            from_schema_version in any::<u32>(),
+            to_schema_version in any::<u32>(),
+        ) -> native::DiffSchemasRequest {
+            native::DiffSchemasRequest {
+                tenant_id,
+                from_schema_version,
+                to_schema_version,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn property_schema_changes()(
+            before in property_schemas(),
+            after in property_schemas(),
+        ) -> native::PropertySchemaChange {
+            native::PropertySchemaChange { before, after }
+        }
+    }
+
+    prop_compose! {
+        pub fn edge_schema_changes()(
+            before in edge_schemas(),
+            after in edge_schemas(),
+        ) -> native::EdgeSchemaChange {
+            native::EdgeSchemaChange { before, after }
+        }
+    }
+
+    prop_compose! {
+        pub fn node_schema_diffs()(
+            node_type in common::node_types(),
+            added_properties in proptest::collection::vec(property_schemas(), 0..3),
+            removed_properties in proptest::collection::vec(property_schemas(), 0..3),
+            changed_properties in proptest::collection::vec(property_schema_changes(), 0..3),
+            added_edges in proptest::collection::vec(edge_schemas(), 0..3),
+            removed_edges in proptest::collection::vec(edge_schemas(), 0..3),
+            changed_edges in proptest::collection::vec(edge_schema_changes(), 0..3),
+            identity_algorithm_changed in any::<bool>(),
+        ) -> native::NodeSchemaDiff {
+            native::NodeSchemaDiff {
+                node_type,
+                added_properties,
+                removed_properties,
+                changed_properties,
+                added_edges,
+                removed_edges,
+                changed_edges,
+                identity_algorithm_changed,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn diff_schemas_responses()(
+            added_node_types in proptest::collection::vec(common::node_types(), 0..3),
+            removed_node_types in proptest::collection::vec(common::node_types(), 0..3),
+            changed_node_types in proptest::collection::vec(node_schema_diffs(), 0..3),
+        ) -> native::DiffSchemasResponse {
+            native::DiffSchemasResponse {
+                added_node_types,
+                removed_node_types,
+                changed_node_types,
+            }
+        }
+    }
+
+    pub fn schema_violation_kinds() -> BoxedStrategy<native::SchemaViolationKind> {
+        prop_oneof![
+            Just(native::SchemaViolationKind::UnknownNodeType),
+            Just(native::SchemaViolationKind::UnknownProperty),
+            Just(native::SchemaViolationKind::PropertyTypeMismatch),
+            Just(native::SchemaViolationKind::UndeclaredEdge),
+            Just(native::SchemaViolationKind::EdgeCardinalityViolation),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn schema_violations()(
+            kind in schema_violation_kinds(),
+            description in any::<String>(),
+        ) -> native::SchemaViolation {
+            native::SchemaViolation { kind, description }
+        }
+    }
+
+    prop_compose! {
+        pub fn node_validation_reports()(
+            node_key in any::<String>(),
+            node_type in common::node_types(),
+            violations in proptest::collection::vec(schema_violations(), 0..5),
+        ) -> native::NodeValidationReport {
+            native::NodeValidationReport {
+                node_key,
+                node_type,
+                violations,
             }
         }
     }
@@ -18,6 +18,8 @@ use rust_proto::{
     },
 };
 use scylla::{
+    cql_to_rust::FromRowError,
+    query::Query,
     transport::errors::QueryError,
     Session,
 };
@@ -27,7 +29,15 @@ use crate::{
     config::ScyllaProvisionerServiceConfig,
     table_names::{
         tenant_keyspace_name,
+        IMM_I_64_TABLE_NAME,
         IMM_STRING_TABLE_NAME,
+        IMM_U_64_TABLE_NAME,
+        MAX_I_64_TABLE_NAME,
+        MAX_U_64_TABLE_NAME,
+        MIN_I_64_TABLE_NAME,
+        MIN_U_64_TABLE_NAME,
+        NODES_BY_TYPE_TABLE_NAME,
+        SESSIONS_TABLE_NAME,
     },
 };
 
@@ -35,12 +45,53 @@ use crate::{
 pub enum ScyllaProvisionerError {
     #[error("Scylla Error {0}")]
     ScyllaError(#[from] QueryError),
+    #[error("Row was invalid {0}")]
+    FromRowError(#[from] FromRowError),
 }
 
 impl From<ScyllaProvisionerError> for Status {
     fn from(error: ScyllaProvisionerError) -> Self {
         match error {
             ScyllaProvisionerError::ScyllaError(error) => Status::unknown(error.to_string()),
+            ScyllaProvisionerError::FromRowError(error) => Status::internal(error.to_string()),
+        }
+    }
+}
+
+/// How many `node_type` rows are read at once while backfilling
+const BACKFILL_PAGE_SIZE: i32 = 1000;
+
+/// Copies every row of `node_type` into `nodes_by_type`, for tenants that were
+/// provisioned before `nodes_by_type` existed. Both tables hold the same
+/// (uid, node_type) pairs, so this is idempotent and safe to run while
+/// graph-mutation is writing to them.
+async fn backfill_nodes_by_type(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    let mut select = Query::new(format!("SELECT uid, node_type FROM {tenant_ks}.node_type"));
+    select.set_page_size(BACKFILL_PAGE_SIZE);
+
+    let insert = session
+        .prepare(format!(
+            "INSERT INTO {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME} (uid, node_type) VALUES (?, ?)"
+        ))
+        .await?;
+
+    let mut paging_state = None;
+    loop {
+        let page = session
+            .query_paged(select.clone(), &(), paging_state)
+            .await?;
+        paging_state = page.paging_state.clone();
+
+        for row in page.rows_typed_or_empty::<(i64, String)>() {
+            let (uid, node_type) = row?;
+            session.execute(&insert, &(uid, node_type)).await?;
+        }
+
+        if paging_state.is_none() {
+            return Ok(());
         }
     }
 }
@@ -70,7 +121,16 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
             &[]
         ).await?;
 
-        let property_table_names = [(IMM_STRING_TABLE_NAME, "text")];
+        let property_table_names = [
+            (IMM_STRING_TABLE_NAME, "text"),
+            (IMM_I_64_TABLE_NAME, "bigint"),
+            (MAX_I_64_TABLE_NAME, "bigint"),
+            (MIN_I_64_TABLE_NAME, "bigint"),
+            // u64 values are stored as their i64 bit pattern
+            (IMM_U_64_TABLE_NAME, "bigint"),
+            (MAX_U_64_TABLE_NAME, "bigint"),
+            (MIN_U_64_TABLE_NAME, "bigint"),
+        ];
 
         for (table_name, value_type) in property_table_names.into_iter() {
             session
@@ -100,6 +160,23 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
                 &(),
             )
             .await?;
+        // Partitioned by node type, so that the nodes of one type can be
+        // paged through in uid order without scanning every node.
+        session
+            .query(
+                format!(
+                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{NODES_BY_TYPE_TABLE_NAME} (
+                        node_type text,
+                        uid bigint,
+                        PRIMARY KEY (node_type, uid)
+                    ) WITH CLUSTERING ORDER BY (uid ASC)"
+                ),
+                &(),
+            )
+            .await?;
+        // Provisioning again is how tenants from before `nodes_by_type` are
+        // migrated; for a new tenant `node_type` is empty and this is a no-op.
+        backfill_nodes_by_type(session, &tenant_ks).await?;
         session
             .query(
                 format!(
@@ -115,6 +192,24 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
             )
             .await?;
 
+        session
+            .query(
+                format!(
+                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{SESSIONS_TABLE_NAME} (
+                        pseudo_key text,
+                        create_time bigint,
+                        session_id text,
+                        end_time bigint,
+                        is_create_canon boolean,
+                        is_end_canon boolean,
+                        version bigint,
+                        PRIMARY KEY (pseudo_key, create_time)
+                    ) WITH CLUSTERING ORDER BY (create_time ASC)"
+                ),
+                &(),
+            )
+            .await?;
+
         session.await_schema_agreement().await?;
 
         Ok(native::ProvisionGraphForTenantResponse {})
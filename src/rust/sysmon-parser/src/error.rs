@@ -49,11 +49,41 @@ pub enum Error {
     ParseSysmon { message: String, position: usize },
     #[error("unexpected end of stream")]
     UnexpectedEndOfStream,
+    #[error("invalid UTF-8 at position `{position}` with `{source}`")]
+    Utf8 {
+        position: usize,
+        source: std::str::Utf8Error,
+    },
+    #[error("failed to read input with `{kind:?}`: {message}")]
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
     // these errors are useful just as they are
     #[error(transparent)]
     XmlError(#[from] xmlparser::Error),
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::Io {
+            kind: e.kind(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// An error for a single Sysmon event, returned by the recovering event iterators.
+///
+/// The `position` is the byte offset of the start of the event's `<Event>` element in the input,
+/// which can be used to locate the malformed event.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("failed to parse Sysmon event at position `{position}`: {source}")]
+pub struct EventError {
+    pub position: usize,
+    pub source: Error,
+}
+
 impl From<std::convert::Infallible> for Error {
     fn from(_: std::convert::Infallible) -> Error {
         unreachable!()
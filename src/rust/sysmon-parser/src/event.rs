@@ -103,21 +103,72 @@ pub(crate) fn from_tokenizer<'a, 'input: 'a>(
     let system = System::try_from(tokenizer)?;
 
     let event_data = match system.event_id {
+        EventId::ClipboardChange => {
+            EventData::ClipboardChange(event_data::ClipboardChangeEventData::try_from(tokenizer)?)
+        }
+        EventId::CreateRemoteThread => EventData::CreateRemoteThread(
+            event_data::CreateRemoteThreadEventData::try_from(tokenizer)?,
+        ),
+        EventId::DnsQuery => {
+            EventData::DnsQuery(event_data::DnsQueryEventData::try_from(tokenizer)?)
+        }
         EventId::FileCreate => {
             EventData::FileCreate(event_data::FileCreateEventData::try_from(tokenizer)?)
         }
         EventId::FileCreateStreamHash => EventData::FileCreateStreamHash(
             event_data::FileCreateStreamHashEventData::try_from(tokenizer)?,
         ),
+        EventId::FileDelete => {
+            EventData::FileDelete(event_data::FileDeleteEventData::try_from(tokenizer)?)
+        }
+        EventId::FileDeleteDetected => {
+            EventData::FileDeleteDetected(event_data::FileDeleteEventData::try_from(tokenizer)?)
+        }
+        EventId::ImageLoaded => {
+            EventData::ImageLoad(event_data::ImageLoadEventData::try_from(tokenizer)?)
+        }
         EventId::NetworkConnection => {
             EventData::NetworkConnect(event_data::NetworkConnectionEventData::try_from(tokenizer)?)
         }
+        EventId::PipeConnected => {
+            EventData::PipeConnected(event_data::PipeEventData::try_from(tokenizer)?)
+        }
+        EventId::PipeCreated => {
+            EventData::PipeCreated(event_data::PipeEventData::try_from(tokenizer)?)
+        }
+        EventId::ProcessAccess => {
+            EventData::ProcessAccess(event_data::ProcessAccessEventData::try_from(tokenizer)?)
+        }
         EventId::ProcessCreation => {
             EventData::ProcessCreate(event_data::ProcessCreateEventData::try_from(tokenizer)?)
         }
         EventId::ProcessTerminated => EventData::ProcessTerminate(
             event_data::ProcessTerminatedEventData::try_from(tokenizer)?,
         ),
+        EventId::ProcessTampering => {
+            EventData::ProcessTampering(event_data::ProcessTamperingEventData::try_from(tokenizer)?)
+        }
+        EventId::RawAccessRead => {
+            EventData::RawAccessRead(event_data::RawAccessReadEventData::try_from(tokenizer)?)
+        }
+        EventId::RegistryCreateOrDelete => EventData::RegistryAddOrDelete(
+            event_data::RegistryAddOrDeleteEventData::try_from(tokenizer)?,
+        ),
+        EventId::RegistryKeyValueRename => {
+            EventData::RegistryRename(event_data::RegistryRenameEventData::try_from(tokenizer)?)
+        }
+        EventId::RegistryValueSet => {
+            EventData::RegistryValueSet(event_data::RegistryValueSetEventData::try_from(tokenizer)?)
+        }
+        EventId::WmiEventConsumer => {
+            EventData::WmiEventConsumer(event_data::WmiEventConsumerEventData::try_from(tokenizer)?)
+        }
+        EventId::WmiEventConsumerToFilter => EventData::WmiEventConsumerToFilter(
+            event_data::WmiEventConsumerToFilterEventData::try_from(tokenizer)?,
+        ),
+        EventId::WmiEventFilter => {
+            EventData::WmiEventFilter(event_data::WmiEventFilterEventData::try_from(tokenizer)?)
+        }
         _ => EventData::Unsupported,
     };
 
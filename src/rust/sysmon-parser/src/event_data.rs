@@ -1,16 +1,46 @@
 use derive_into_owned::IntoOwned;
 
+mod clipboard_change;
+mod create_remote_thread;
+mod dns_query;
 mod file_create;
 mod file_create_stream_hash;
+mod file_delete;
+mod image_load;
 mod network_connect;
+mod pipe_event;
+mod process_access;
 mod process_creation;
+mod process_tampering;
 mod process_terminated;
+mod raw_access_read;
+mod registry_add_or_delete;
+mod registry_rename;
+mod registry_value_set;
+mod wmi_event_consumer;
+mod wmi_event_consumer_to_filter;
+mod wmi_event_filter;
 
+pub use clipboard_change::ClipboardChangeEventData;
+pub use create_remote_thread::CreateRemoteThreadEventData;
+pub use dns_query::DnsQueryEventData;
 pub use file_create::FileCreateEventData;
 pub use file_create_stream_hash::FileCreateStreamHashEventData;
+pub use file_delete::FileDeleteEventData;
+pub use image_load::ImageLoadEventData;
 pub use network_connect::NetworkConnectionEventData;
+pub use pipe_event::PipeEventData;
+pub use process_access::ProcessAccessEventData;
 pub use process_creation::ProcessCreateEventData;
+pub use process_tampering::ProcessTamperingEventData;
 pub use process_terminated::ProcessTerminatedEventData;
+pub use raw_access_read::RawAccessReadEventData;
+pub use registry_add_or_delete::RegistryAddOrDeleteEventData;
+pub use registry_rename::RegistryRenameEventData;
+pub use registry_value_set::RegistryValueSetEventData;
+pub use wmi_event_consumer::WmiEventConsumerEventData;
+pub use wmi_event_consumer_to_filter::WmiEventConsumerToFilterEventData;
+pub use wmi_event_filter::WmiEventFilterEventData;
 
 pub const UTC_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
 
@@ -19,6 +49,21 @@ pub const UTC_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
 #[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventData<'a> {
+    /// Event ID 24: ClipboardChange (New content in the clipboard)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-24-clipboardchange-new-content-in-the-clipboard>
+    ClipboardChange(ClipboardChangeEventData<'a>),
+
+    /// Event ID 8: CreateRemoteThread
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-8-createremotethread>
+    CreateRemoteThread(CreateRemoteThreadEventData<'a>),
+
+    /// Event ID 22: DNSEvent (DNS query)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-22-dnsevent-dns-query>
+    DnsQuery(DnsQueryEventData<'a>),
+
     /// Event ID 11: FileCreate
     ///
     /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-11-filecreate>
@@ -29,10 +74,41 @@ pub enum EventData<'a> {
     /// https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-15-filecreatestreamhash
     FileCreateStreamHash(FileCreateStreamHashEventData<'a>),
 
+    /// Event ID 23: FileDelete (File Delete archived)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-23-filedelete-file-delete-archived>
+    FileDelete(FileDeleteEventData<'a>),
+
+    /// Event ID 26: FileDeleteDetected (File Delete logged)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-26-filedeletedetected-file-delete-logged>
+    FileDeleteDetected(FileDeleteEventData<'a>),
+
+    /// Event ID 7: Image loaded
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-7-image-loaded>
+    ImageLoad(ImageLoadEventData<'a>),
+
     /// Event ID 3: Network connection
     ///
     /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-3-network-connection>
     NetworkConnect(NetworkConnectionEventData<'a>),
+
+    /// Event ID 18: PipeEvent (Pipe Connected)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-18-pipeevent-pipe-connected>
+    PipeConnected(PipeEventData<'a>),
+
+    /// Event ID 17: PipeEvent (Pipe Created)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-17-pipeevent-pipe-created>
+    PipeCreated(PipeEventData<'a>),
+
+    /// Event ID 10: ProcessAccess
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-10-processaccess>
+    ProcessAccess(ProcessAccessEventData<'a>),
+
     /// Event ID 1: Process creation
     ///
     /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-1-process-creation>
@@ -43,6 +119,46 @@ pub enum EventData<'a> {
     /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-5-process-terminated>
     ProcessTerminate(ProcessTerminatedEventData<'a>),
 
+    /// Event ID 25: ProcessTampering (Process image change)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-25-processtampering-process-image-change>
+    ProcessTampering(ProcessTamperingEventData<'a>),
+
+    /// Event ID 9: RawAccessRead
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-9-rawaccessread>
+    RawAccessRead(RawAccessReadEventData<'a>),
+
+    /// Event ID 12: RegistryEvent (Object create and delete)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-12-registryevent-object-create-and-delete>
+    RegistryAddOrDelete(RegistryAddOrDeleteEventData<'a>),
+
+    /// Event ID 14: RegistryEvent (Key and Value Rename)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-14-registryevent-key-and-value-rename>
+    RegistryRename(RegistryRenameEventData<'a>),
+
+    /// Event ID 13: RegistryEvent (Value Set)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-13-registryevent-value-set>
+    RegistryValueSet(RegistryValueSetEventData<'a>),
+
+    /// Event ID 20: WmiEvent (WmiEventConsumer activity detected)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-20-wmievent-wmieventconsumer-activity-detected>
+    WmiEventConsumer(WmiEventConsumerEventData<'a>),
+
+    /// Event ID 21: WmiEvent (WmiEventConsumerToFilter activity detected)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-21-wmievent-wmieventconsumertofilter-activity-detected>
+    WmiEventConsumerToFilter(WmiEventConsumerToFilterEventData<'a>),
+
+    /// Event ID 19: WmiEvent (WmiEventFilter activity detected)
+    ///
+    /// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-19-wmievent-wmieventfilter-activity-detected>
+    WmiEventFilter(WmiEventFilterEventData<'a>),
+
     /// Unsupported event type
     Unsupported,
 }
@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// This event is generated when the system clipboard contents change.
+///
+/// <event name="SYSMONEVENT_CLIPBOARD" value="24" level="Informational" template="Clipboard changed" rulename="ClipboardChange" ruledefault="include" version="5" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-24-clipboardchange-new-content-in-the-clipboard>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClipboardChangeEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="Session" inType="win:UInt32" />
+    pub session: u32,
+
+    /// <data name="ClientInfo" inType="win:UnicodeString" outType="xs:string" />
+    pub client_info: Cow<'a, str>,
+
+    /// <data name="Hashes" inType="win:UnicodeString" outType="xs:string" />
+    pub hashes: Cow<'a, str>,
+
+    /// <data name="Archived" inType="win:UnicodeString" outType="xs:string" />
+    pub archived: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> ClipboardChangeEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut image = None;
+        let mut session = None;
+        let mut client_info = None;
+        let mut hashes = None;
+        let mut archived = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "Session" => session = Some(util::parse_int::<u32>(value)?),
+                "ClientInfo" => client_info = Some(util::unescape_xml(value)?),
+                "Hashes" => hashes = Some(util::unescape_xml(value)?),
+                "Archived" => archived = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let session = session.ok_or(Error::MissingField("Session"))?;
+        let client_info = client_info.ok_or(Error::MissingField("ClientInfo"))?;
+        let hashes = hashes.ok_or(Error::MissingField("Hashes"))?;
+        let archived = archived.ok_or(Error::MissingField("Archived"))?;
+
+        Ok(ClipboardChangeEventData {
+            rule_name,
+            utc_time,
+            process_guid,
+            process_id,
+            image,
+            session,
+            client_info,
+            hashes,
+            archived,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for ClipboardChangeEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ClipboardChange(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ClipboardChange")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &ClipboardChangeEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ClipboardChange(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ClipboardChange")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_clipboard_change_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 21:12:45.907</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-DB8D-621F-AA00-000000000F00}</Data>
+            <Data Name="ProcessId">7044</Data>
+            <Data Name="Image">C:\Windows\system32\notepad.exe</Data>
+            <Data Name="Session">1</Data>
+            <Data Name="ClientInfo">user: grapltest</Data>
+            <Data Name="Hashes">SHA1=DA39A3EE5E6B4B0D3255BFEF95601890AFD80709</Data>
+            <Data Name="Archived">true</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = ClipboardChangeEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            ClipboardChangeEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:12:45.907", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-DB8D-621F-AA00-000000000F00"
+                ))?,
+                process_id: 7044,
+                image: Cow::Borrowed(r#"C:\Windows\system32\notepad.exe"#),
+                session: 1,
+                client_info: Cow::Borrowed("user: grapltest"),
+                hashes: Cow::Borrowed("SHA1=DA39A3EE5E6B4B0D3255BFEF95601890AFD80709"),
+                archived: Cow::Borrowed("true"),
+                user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
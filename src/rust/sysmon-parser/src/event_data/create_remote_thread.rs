@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// The CreateRemoteThread event detects when a process creates a thread in another process. This
+/// technique is used by malware to inject code and hide in other processes. The event indicates
+/// the source and target process. It gives information on the code that will be run in the new
+/// thread: StartAddress, StartModule and StartFunction.
+///
+/// <event name="SYSMONEVENT_CREATE_REMOTE_THREAD" value="8" level="Informational" template="CreateRemoteThread detected" rulename="CreateRemoteThread" ruledefault="exclude" version="2" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-8-createremotethread>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateRemoteThreadEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="SourceProcessGuid" inType="win:GUID" />
+    pub source_process_guid: uuid::Uuid,
+
+    /// <data name="SourceProcessId" inType="win:UInt32" outType="win:PID" />
+    pub source_process_id: u32,
+
+    /// <data name="SourceImage" inType="win:UnicodeString" outType="xs:string" />
+    pub source_image: Cow<'a, str>,
+
+    /// <data name="TargetProcessGuid" inType="win:GUID" />
+    pub target_process_guid: uuid::Uuid,
+
+    /// <data name="TargetProcessId" inType="win:UInt32" outType="win:PID" />
+    pub target_process_id: u32,
+
+    /// <data name="TargetImage" inType="win:UnicodeString" outType="xs:string" />
+    pub target_image: Cow<'a, str>,
+
+    /// <data name="NewThreadId" inType="win:UInt32" />
+    pub new_thread_id: u32,
+
+    /// <data name="StartAddress" inType="win:HexInt64" />
+    pub start_address: u64,
+
+    /// <data name="StartModule" inType="win:UnicodeString" outType="xs:string" />
+    pub start_module: Option<Cow<'a, str>>,
+
+    /// <data name="StartFunction" inType="win:UnicodeString" outType="xs:string" />
+    pub start_function: Option<Cow<'a, str>>,
+
+    /// <data name="SourceUser" inType="win:UnicodeString" outType="xs:string" />
+    pub source_user: Option<Cow<'a, str>>,
+
+    /// <data name="TargetUser" inType="win:UnicodeString" outType="xs:string" />
+    pub target_user: Option<Cow<'a, str>>,
+}
+
+impl<'a> CreateRemoteThreadEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut source_process_guid = None;
+        let mut source_process_id = None;
+        let mut source_image = None;
+        let mut target_process_guid = None;
+        let mut target_process_id = None;
+        let mut target_image = None;
+        let mut new_thread_id = None;
+        let mut start_address = None;
+        let mut start_module = None;
+        let mut start_function = None;
+        let mut source_user = None;
+        let mut target_user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "SourceProcessGuid" => source_process_guid = Some(util::parse_win_guid_str(value)?),
+                "SourceProcessId" => source_process_id = Some(util::parse_int::<u32>(value)?),
+                "SourceImage" => source_image = Some(util::unescape_xml(value)?),
+                "TargetProcessGuid" => target_process_guid = Some(util::parse_win_guid_str(value)?),
+                "TargetProcessId" => target_process_id = Some(util::parse_int::<u32>(value)?),
+                "TargetImage" => target_image = Some(util::unescape_xml(value)?),
+                "NewThreadId" => new_thread_id = Some(util::parse_int::<u32>(value)?),
+                "StartAddress" => start_address = Some(util::from_zero_or_hex_str(value)?),
+                "StartModule" => start_module = Some(util::unescape_xml(value)?),
+                "StartFunction" => start_function = Some(util::unescape_xml(value)?),
+                "SourceUser" => source_user = Some(util::unescape_xml(value)?),
+                "TargetUser" => target_user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let source_process_guid =
+            source_process_guid.ok_or(Error::MissingField("SourceProcessGuid"))?;
+        let source_process_id = source_process_id.ok_or(Error::MissingField("SourceProcessId"))?;
+        let source_image = source_image.ok_or(Error::MissingField("SourceImage"))?;
+        let target_process_guid =
+            target_process_guid.ok_or(Error::MissingField("TargetProcessGuid"))?;
+        let target_process_id = target_process_id.ok_or(Error::MissingField("TargetProcessId"))?;
+        let target_image = target_image.ok_or(Error::MissingField("TargetImage"))?;
+        let new_thread_id = new_thread_id.ok_or(Error::MissingField("NewThreadId"))?;
+        let start_address = start_address.ok_or(Error::MissingField("StartAddress"))?;
+
+        Ok(CreateRemoteThreadEventData {
+            rule_name,
+            utc_time,
+            source_process_guid,
+            source_process_id,
+            source_image,
+            target_process_guid,
+            target_process_id,
+            target_image,
+            new_thread_id,
+            start_address,
+            start_module,
+            start_function,
+            source_user,
+            target_user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for CreateRemoteThreadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::CreateRemoteThread(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("CreateRemoteThread")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &CreateRemoteThreadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::CreateRemoteThread(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("CreateRemoteThread")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_create_remote_thread_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 20:55:01.112</Data>
+            <Data Name="SourceProcessGuid">{A46BB5E4-D8FD-621F-7700-000000000F00}</Data>
+            <Data Name="SourceProcessId">6212</Data>
+            <Data Name="SourceImage">C:\Users\grapltest\Downloads\injector.exe</Data>
+            <Data Name="TargetProcessGuid">{A46BB5E4-D7B0-621F-0C00-000000000F00}</Data>
+            <Data Name="TargetProcessId">640</Data>
+            <Data Name="TargetImage">C:\Windows\system32\lsass.exe</Data>
+            <Data Name="NewThreadId">7104</Data>
+            <Data Name="StartAddress">0x00007FF8A1B20000</Data>
+            <Data Name="StartModule">-</Data>
+            <Data Name="StartFunction">-</Data>
+            <Data Name="SourceUser">DESKTOP-GRAPL\grapltest</Data>
+            <Data Name="TargetUser">NT AUTHORITY\SYSTEM</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = CreateRemoteThreadEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            CreateRemoteThreadEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 20:55:01.112", UTC_TIME_FORMAT)?,
+                source_process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D8FD-621F-7700-000000000F00"
+                ))?,
+                source_process_id: 6212,
+                source_image: Cow::Borrowed(r#"C:\Users\grapltest\Downloads\injector.exe"#),
+                target_process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D7B0-621F-0C00-000000000F00"
+                ))?,
+                target_process_id: 640,
+                target_image: Cow::Borrowed(r#"C:\Windows\system32\lsass.exe"#),
+                new_thread_id: 7104,
+                start_address: 0x7FF8A1B20000,
+                start_module: Some(Cow::Borrowed("-")),
+                start_function: Some(Cow::Borrowed("-")),
+                source_user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+                target_user: Some(Cow::Borrowed(r#"NT AUTHORITY\SYSTEM"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// This event is generated when a process executes a DNS query, whether the result is successful
+/// or fails, cached or not.
+///
+/// <event name="SYSMONEVENT_DNS_QUERY" value="22" level="Informational" template="Dns query" rulename="DnsQuery" ruledefault="include" version="5" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-22-dnsevent-dns-query>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DnsQueryEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="QueryName" inType="win:UnicodeString" outType="xs:string" />
+    pub query_name: Cow<'a, str>,
+
+    /// <data name="QueryStatus" inType="win:UnicodeString" outType="xs:string" />
+    pub query_status: Cow<'a, str>,
+
+    /// <data name="QueryResults" inType="win:UnicodeString" outType="xs:string" />
+    pub query_results: Option<Cow<'a, str>>,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> DnsQueryEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut query_name = None;
+        let mut query_status = None;
+        let mut query_results = None;
+        let mut image = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "QueryName" => query_name = Some(util::unescape_xml(value)?),
+                "QueryStatus" => query_status = Some(util::unescape_xml(value)?),
+                "QueryResults" => query_results = Some(util::unescape_xml(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let query_name = query_name.ok_or(Error::MissingField("QueryName"))?;
+        let query_status = query_status.ok_or(Error::MissingField("QueryStatus"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+
+        Ok(DnsQueryEventData {
+            rule_name,
+            utc_time,
+            process_guid,
+            process_id,
+            query_name,
+            query_status,
+            query_results,
+            image,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for DnsQueryEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::DnsQuery(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("DnsQuery")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &DnsQueryEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::DnsQuery(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("DnsQuery")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_dns_query_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 21:09:14.771</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-DB2A-621F-A200-000000000F00}</Data>
+            <Data Name="ProcessId">6736</Data>
+            <Data Name="QueryName">www.graplsecurity.com</Data>
+            <Data Name="QueryStatus">0</Data>
+            <Data Name="QueryResults">::ffff:104.21.31.166;::ffff:172.67.176.100;</Data>
+            <Data Name="Image">C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = DnsQueryEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            DnsQueryEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:09:14.771", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-DB2A-621F-A200-000000000F00"
+                ))?,
+                process_id: 6736,
+                query_name: Cow::Borrowed("www.graplsecurity.com"),
+                query_status: Cow::Borrowed("0"),
+                query_results: Some(Cow::Borrowed("::ffff:104.21.31.166;::ffff:172.67.176.100;")),
+                image: Cow::Borrowed(
+                    r#"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe"#
+                ),
+                user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
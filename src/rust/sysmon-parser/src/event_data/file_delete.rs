@@ -0,0 +1,196 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// A file was deleted. Event ID 23 additionally archives the deleted file to the ArchiveDirectory
+/// (`Archived` is set), while event ID 26 (FileDeleteDetected) only logs the deletion. Both
+/// events share the same schema otherwise.
+///
+/// <event name="SYSMONEVENT_FILE_DELETE" value="23" level="Informational" template="File Delete archived" rulename="FileDelete" ruledefault="include" version="5" target="all">
+///
+/// <event name="SYSMONEVENT_FILE_DELETE_DETECTED" value="26" level="Informational" template="File Delete logged" rulename="FileDeleteDetected" ruledefault="include" version="5" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-23-filedelete-file-delete-archived>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileDeleteEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="SequenceNumber" inType="win:UInt64" />
+    pub sequence_number: Option<u64>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="TargetFilename" inType="win:UnicodeString" outType="xs:string" />
+    pub target_filename: Cow<'a, str>,
+
+    /// <data name="Hashes" inType="win:UnicodeString" outType="xs:string" />
+    pub hashes: Option<Cow<'a, str>>,
+
+    /// <data name="IsExecutable" inType="win:Boolean" />
+    pub is_executable: Option<bool>,
+
+    /// <data name="Archived" inType="win:UnicodeString" outType="xs:string" />
+    pub archived: Option<Cow<'a, str>>,
+}
+
+impl<'a> FileDeleteEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut sequence_number = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut user = None;
+        let mut image = None;
+        let mut target_filename = None;
+        let mut hashes = None;
+        let mut is_executable = None;
+        let mut archived = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "SequenceNumber" => sequence_number = Some(util::parse_int::<u64>(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "TargetFilename" => target_filename = Some(util::unescape_xml(value)?),
+                "Hashes" => hashes = Some(util::unescape_xml(value)?),
+                "IsExecutable" => is_executable = Some(util::parse_bool(value)?),
+                "Archived" => archived = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let target_filename = target_filename.ok_or(Error::MissingField("TargetFilename"))?;
+
+        Ok(FileDeleteEventData {
+            rule_name,
+            sequence_number,
+            utc_time,
+            process_guid,
+            process_id,
+            user,
+            image,
+            target_filename,
+            hashes,
+            is_executable,
+            archived,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for FileDeleteEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::FileDelete(event_data) | EventData::FileDeleteDetected(event_data) => {
+                Ok(event_data)
+            }
+            _ => Err(Error::ExpectEventType("FileDelete")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &FileDeleteEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::FileDelete(event_data) | EventData::FileDeleteDetected(event_data) => {
+                Ok(event_data)
+            }
+            _ => Err(Error::ExpectEventType("FileDelete")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_file_delete_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-01-04 19:59:40.221</Data>
+            <Data Name="ProcessGuid">{49e2a5f6-a72c-61d4-ad55-b5ab49560000}</Data>
+            <Data Name="ProcessId">49791</Data>
+            <Data Name="User">root</Data>
+            <Data Name="Image">/usr/bin/rm</Data>
+            <Data Name="TargetFilename">/tmp/payload.sh</Data>
+            <Data Name="Hashes">-</Data>
+            <Data Name="IsExecutable">false</Data>
+            <Data Name="Archived">true</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = FileDeleteEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            FileDeleteEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                sequence_number: None,
+                utc_time: Utc.datetime_from_str("2022-01-04 19:59:40.221", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "49e2a5f6-a72c-61d4-ad55-b5ab49560000"
+                ))?,
+                process_id: 49791,
+                user: Some(Cow::Borrowed("root")),
+                image: Cow::Borrowed("/usr/bin/rm"),
+                target_filename: Cow::Borrowed("/tmp/payload.sh"),
+                hashes: Some(Cow::Borrowed("-")),
+                is_executable: Some(false),
+                archived: Some(Cow::Borrowed("true")),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// The image loaded event logs when a module is loaded in a specific process. This event is
+/// disabled by default and needs to be configured with the "-l" option. It indicates the process
+/// in which the module is loaded, hashes and signature information. The signature is created
+/// asynchronously for performance reasons and indicates if the file was removed after loading.
+///
+/// <event name="SYSMONEVENT_IMAGE_LOAD" value="7" level="Informational" template="Image loaded" rulename="ImageLoad" ruledefault="include" version="3" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-7-image-loaded>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageLoadEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="ImageLoaded" inType="win:UnicodeString" outType="xs:string" />
+    pub image_loaded: Cow<'a, str>,
+
+    /// <data name="FileVersion" inType="win:UnicodeString" outType="xs:string" />
+    pub file_version: Option<Cow<'a, str>>,
+
+    /// <data name="Description" inType="win:UnicodeString" outType="xs:string" />
+    pub description: Option<Cow<'a, str>>,
+
+    /// <data name="Product" inType="win:UnicodeString" outType="xs:string" />
+    pub product: Option<Cow<'a, str>>,
+
+    /// <data name="Company" inType="win:UnicodeString" outType="xs:string" />
+    pub company: Option<Cow<'a, str>>,
+
+    /// <data name="OriginalFileName" inType="win:UnicodeString" outType="xs:string" />
+    pub original_file_name: Option<Cow<'a, str>>,
+
+    /// <data name="Hashes" inType="win:UnicodeString" outType="xs:string" />
+    pub hashes: Cow<'a, str>,
+
+    /// <data name="Signed" inType="win:UnicodeString" outType="xs:string" />
+    pub signed: Cow<'a, str>,
+
+    /// <data name="Signature" inType="win:UnicodeString" outType="xs:string" />
+    pub signature: Cow<'a, str>,
+
+    /// <data name="SignatureStatus" inType="win:UnicodeString" outType="xs:string" />
+    pub signature_status: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> ImageLoadEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut image = None;
+        let mut image_loaded = None;
+        let mut file_version = None;
+        let mut description = None;
+        let mut product = None;
+        let mut company = None;
+        let mut original_file_name = None;
+        let mut hashes = None;
+        let mut signed = None;
+        let mut signature = None;
+        let mut signature_status = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "ImageLoaded" => image_loaded = Some(util::unescape_xml(value)?),
+                "FileVersion" => file_version = Some(util::unescape_xml(value)?),
+                "Description" => description = Some(util::unescape_xml(value)?),
+                "Product" => product = Some(util::unescape_xml(value)?),
+                "Company" => company = Some(util::unescape_xml(value)?),
+                "OriginalFileName" => original_file_name = Some(util::unescape_xml(value)?),
+                "Hashes" => hashes = Some(util::unescape_xml(value)?),
+                "Signed" => signed = Some(util::unescape_xml(value)?),
+                "Signature" => signature = Some(util::unescape_xml(value)?),
+                "SignatureStatus" => signature_status = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let image_loaded = image_loaded.ok_or(Error::MissingField("ImageLoaded"))?;
+        let hashes = hashes.ok_or(Error::MissingField("Hashes"))?;
+        let signed = signed.ok_or(Error::MissingField("Signed"))?;
+        let signature = signature.ok_or(Error::MissingField("Signature"))?;
+        let signature_status = signature_status.ok_or(Error::MissingField("SignatureStatus"))?;
+
+        Ok(ImageLoadEventData {
+            rule_name,
+            utc_time,
+            process_guid,
+            process_id,
+            image,
+            image_loaded,
+            file_version,
+            description,
+            product,
+            company,
+            original_file_name,
+            hashes,
+            signed,
+            signature,
+            signature_status,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for ImageLoadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ImageLoad(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ImageLoad")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &ImageLoadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ImageLoad(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ImageLoad")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_image_load_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 20:49:31.316</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-D7BB-621F-5C00-000000000F00}</Data>
+            <Data Name="ProcessId">5876</Data>
+            <Data Name="Image">C:\Windows\System32\svchost.exe</Data>
+            <Data Name="ImageLoaded">C:\Windows\System32\wbem\wbemprox.dll</Data>
+            <Data Name="FileVersion">10.0.17763.1 (WinBuild.160101.0800)</Data>
+            <Data Name="Description">WMI</Data>
+            <Data Name="Product">Microsoft® Windows® Operating System</Data>
+            <Data Name="Company">Microsoft Corporation</Data>
+            <Data Name="OriginalFileName">wbemprox.dll</Data>
+            <Data Name="Hashes">SHA256=6E0E2D7A1DDBD9D6D84CDA7C0E5C7B5DC0C4D86D8E2B6FA0B6B9D0F8E0A3A6A2</Data>
+            <Data Name="Signed">true</Data>
+            <Data Name="Signature">Microsoft Windows</Data>
+            <Data Name="SignatureStatus">Valid</Data>
+            <Data Name="User">NT AUTHORITY\SYSTEM</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = ImageLoadEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            ImageLoadEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 20:49:31.316", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D7BB-621F-5C00-000000000F00"
+                ))?,
+                process_id: 5876,
+                image: Cow::Borrowed(r#"C:\Windows\System32\svchost.exe"#),
+                image_loaded: Cow::Borrowed(r#"C:\Windows\System32\wbem\wbemprox.dll"#),
+                file_version: Some(Cow::Borrowed("10.0.17763.1 (WinBuild.160101.0800)")),
+                description: Some(Cow::Borrowed("WMI")),
+                product: Some(Cow::Borrowed("Microsoft® Windows® Operating System")),
+                company: Some(Cow::Borrowed("Microsoft Corporation")),
+                original_file_name: Some(Cow::Borrowed("wbemprox.dll")),
+                hashes: Cow::Borrowed(
+                    "SHA256=6E0E2D7A1DDBD9D6D84CDA7C0E5C7B5DC0C4D86D8E2B6FA0B6B9D0F8E0A3A6A2"
+                ),
+                signed: Cow::Borrowed("true"),
+                signature: Cow::Borrowed("Microsoft Windows"),
+                signature_status: Cow::Borrowed("Valid"),
+                user: Some(Cow::Borrowed(r#"NT AUTHORITY\SYSTEM"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// This event generates when a named pipe is created (event ID 17) or when a named pipe
+/// connection is made between a client and a server (event ID 18). Malware often uses named
+/// pipes for interprocess communication. Both events share the same schema.
+///
+/// <event name="SYSMONEVENT_CREATE_NAMEDPIPE" value="17" level="Informational" template="Pipe Created" rulename="PipeEvent" ruledefault="include" version="1" target="windows">
+///
+/// <event name="SYSMONEVENT_CONNECT_NAMEDPIPE" value="18" level="Informational" template="Pipe Connected" rulename="PipeEvent" ruledefault="include" version="1" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-17-pipeevent-pipe-created>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipeEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="EventType" inType="win:UnicodeString" outType="xs:string" />
+    pub event_type: Cow<'a, str>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="PipeName" inType="win:UnicodeString" outType="xs:string" />
+    pub pipe_name: Cow<'a, str>,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> PipeEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut event_type = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut pipe_name = None;
+        let mut image = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "EventType" => event_type = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "PipeName" => pipe_name = Some(util::unescape_xml(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let event_type = event_type.ok_or(Error::MissingField("EventType"))?;
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let pipe_name = pipe_name.ok_or(Error::MissingField("PipeName"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+
+        Ok(PipeEventData {
+            rule_name,
+            event_type,
+            utc_time,
+            process_guid,
+            process_id,
+            pipe_name,
+            image,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for PipeEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::PipeCreated(event_data) | EventData::PipeConnected(event_data) => {
+                Ok(event_data)
+            }
+            _ => Err(Error::ExpectEventType("PipeEvent")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &PipeEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::PipeCreated(event_data) | EventData::PipeConnected(event_data) => {
+                Ok(event_data)
+            }
+            _ => Err(Error::ExpectEventType("PipeEvent")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_pipe_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="EventType">CreatePipe</Data>
+            <Data Name="UtcTime">2022-03-02 21:05:21.889</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-DA69-621F-9800-000000000F00}</Data>
+            <Data Name="ProcessId">4412</Data>
+            <Data Name="PipeName">\MSSE-1234-server</Data>
+            <Data Name="Image">C:\Users\grapltest\Downloads\beacon.exe</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = PipeEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            PipeEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                event_type: Cow::Borrowed("CreatePipe"),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:05:21.889", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-DA69-621F-9800-000000000F00"
+                ))?,
+                process_id: 4412,
+                pipe_name: Cow::Borrowed(r#"\MSSE-1234-server"#),
+                image: Cow::Borrowed(r#"C:\Users\grapltest\Downloads\beacon.exe"#),
+                user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// The process accessed event reports when a process opens another process, an operation that's
+/// often followed by information queries or reading and writing the address space of the target
+/// process. This enables detection of hacking tools that read the memory contents of processes
+/// like Local Security Authority (Lsass.exe) in order to steal credentials for use in
+/// Pass-the-Hash attacks.
+///
+/// <event name="SYSMONEVENT_ACCESS_PROCESS" value="10" level="Informational" template="Process accessed" rulename="ProcessAccess" ruledefault="include" version="3" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-10-processaccess>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessAccessEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="SourceProcessGUID" inType="win:GUID" />
+    pub source_process_guid: uuid::Uuid,
+
+    /// <data name="SourceProcessId" inType="win:UInt32" outType="win:PID" />
+    pub source_process_id: u32,
+
+    /// <data name="SourceThreadId" inType="win:UInt32" />
+    pub source_thread_id: u32,
+
+    /// <data name="SourceImage" inType="win:UnicodeString" outType="xs:string" />
+    pub source_image: Cow<'a, str>,
+
+    /// <data name="TargetProcessGUID" inType="win:GUID" />
+    pub target_process_guid: uuid::Uuid,
+
+    /// <data name="TargetProcessId" inType="win:UInt32" outType="win:PID" />
+    pub target_process_id: u32,
+
+    /// <data name="TargetImage" inType="win:UnicodeString" outType="xs:string" />
+    pub target_image: Cow<'a, str>,
+
+    /// <data name="GrantedAccess" inType="win:HexInt32" />
+    pub granted_access: u32,
+
+    /// <data name="CallTrace" inType="win:UnicodeString" outType="xs:string" />
+    pub call_trace: Option<Cow<'a, str>>,
+
+    /// <data name="SourceUser" inType="win:UnicodeString" outType="xs:string" />
+    pub source_user: Option<Cow<'a, str>>,
+
+    /// <data name="TargetUser" inType="win:UnicodeString" outType="xs:string" />
+    pub target_user: Option<Cow<'a, str>>,
+}
+
+impl<'a> ProcessAccessEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut source_process_guid = None;
+        let mut source_process_id = None;
+        let mut source_thread_id = None;
+        let mut source_image = None;
+        let mut target_process_guid = None;
+        let mut target_process_id = None;
+        let mut target_image = None;
+        let mut granted_access = None;
+        let mut call_trace = None;
+        let mut source_user = None;
+        let mut target_user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "SourceProcessGUID" | "SourceProcessGuid" => {
+                    source_process_guid = Some(util::parse_win_guid_str(value)?)
+                }
+                "SourceProcessId" => source_process_id = Some(util::parse_int::<u32>(value)?),
+                "SourceThreadId" => source_thread_id = Some(util::parse_int::<u32>(value)?),
+                "SourceImage" => source_image = Some(util::unescape_xml(value)?),
+                "TargetProcessGUID" | "TargetProcessGuid" => {
+                    target_process_guid = Some(util::parse_win_guid_str(value)?)
+                }
+                "TargetProcessId" => target_process_id = Some(util::parse_int::<u32>(value)?),
+                "TargetImage" => target_image = Some(util::unescape_xml(value)?),
+                "GrantedAccess" => granted_access = Some(util::from_zero_or_hex_str_u32(value)?),
+                "CallTrace" => call_trace = Some(util::unescape_xml(value)?),
+                "SourceUser" => source_user = Some(util::unescape_xml(value)?),
+                "TargetUser" => target_user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let source_process_guid =
+            source_process_guid.ok_or(Error::MissingField("SourceProcessGUID"))?;
+        let source_process_id = source_process_id.ok_or(Error::MissingField("SourceProcessId"))?;
+        let source_thread_id = source_thread_id.ok_or(Error::MissingField("SourceThreadId"))?;
+        let source_image = source_image.ok_or(Error::MissingField("SourceImage"))?;
+        let target_process_guid =
+            target_process_guid.ok_or(Error::MissingField("TargetProcessGUID"))?;
+        let target_process_id = target_process_id.ok_or(Error::MissingField("TargetProcessId"))?;
+        let target_image = target_image.ok_or(Error::MissingField("TargetImage"))?;
+        let granted_access = granted_access.ok_or(Error::MissingField("GrantedAccess"))?;
+
+        Ok(ProcessAccessEventData {
+            rule_name,
+            utc_time,
+            source_process_guid,
+            source_process_id,
+            source_thread_id,
+            source_image,
+            target_process_guid,
+            target_process_id,
+            target_image,
+            granted_access,
+            call_trace,
+            source_user,
+            target_user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for ProcessAccessEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ProcessAccess(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ProcessAccess")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &ProcessAccessEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ProcessAccess(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ProcessAccess")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_process_access_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 20:56:12.461</Data>
+            <Data Name="SourceProcessGUID">{A46BB5E4-D8FD-621F-7700-000000000F00}</Data>
+            <Data Name="SourceProcessId">6212</Data>
+            <Data Name="SourceThreadId">6348</Data>
+            <Data Name="SourceImage">C:\Users\grapltest\Downloads\procdump64.exe</Data>
+            <Data Name="TargetProcessGUID">{A46BB5E4-D7B0-621F-0C00-000000000F00}</Data>
+            <Data Name="TargetProcessId">640</Data>
+            <Data Name="TargetImage">C:\Windows\system32\lsass.exe</Data>
+            <Data Name="GrantedAccess">0x1FFFFF</Data>
+            <Data Name="CallTrace">C:\Windows\SYSTEM32\ntdll.dll+9c534|C:\Windows\System32\KERNELBASE.dll+2b90e</Data>
+            <Data Name="SourceUser">DESKTOP-GRAPL\grapltest</Data>
+            <Data Name="TargetUser">NT AUTHORITY\SYSTEM</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = ProcessAccessEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            ProcessAccessEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 20:56:12.461", UTC_TIME_FORMAT)?,
+                source_process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D8FD-621F-7700-000000000F00"
+                ))?,
+                source_process_id: 6212,
+                source_thread_id: 6348,
+                source_image: Cow::Borrowed(r#"C:\Users\grapltest\Downloads\procdump64.exe"#),
+                target_process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D7B0-621F-0C00-000000000F00"
+                ))?,
+                target_process_id: 640,
+                target_image: Cow::Borrowed(r#"C:\Windows\system32\lsass.exe"#),
+                granted_access: 0x1fffff,
+                call_trace: Some(Cow::Borrowed(
+                    r#"C:\Windows\SYSTEM32\ntdll.dll+9c534|C:\Windows\System32\KERNELBASE.dll+2b90e"#
+                )),
+                source_user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+                target_user: Some(Cow::Borrowed(r#"NT AUTHORITY\SYSTEM"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// This event is generated when process hiding techniques such as "hollow" or "herpaderp" are
+/// being detected.
+///
+/// <event name="SYSMONEVENT_PROCESS_IMAGE_TAMPERING" value="25" level="Informational" template="Process Tampering" rulename="ProcessTampering" ruledefault="include" version="5" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-25-processtampering-process-image-change>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessTamperingEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="Type" inType="win:UnicodeString" outType="xs:string" />
+    pub tamper_type: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> ProcessTamperingEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut image = None;
+        let mut tamper_type = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "Type" => tamper_type = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let tamper_type = tamper_type.ok_or(Error::MissingField("Type"))?;
+
+        Ok(ProcessTamperingEventData {
+            rule_name,
+            utc_time,
+            process_guid,
+            process_id,
+            image,
+            tamper_type,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for ProcessTamperingEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ProcessTampering(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ProcessTampering")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &ProcessTamperingEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::ProcessTampering(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("ProcessTampering")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_process_tampering_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-03-02 21:14:02.355</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-DBDA-621F-B100-000000000F00}</Data>
+            <Data Name="ProcessId">2480</Data>
+            <Data Name="Image">C:\Windows\SysWOW64\svchost.exe</Data>
+            <Data Name="Type">Image is replaced</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = ProcessTamperingEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            ProcessTamperingEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:14:02.355", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-DBDA-621F-B100-000000000F00"
+                ))?,
+                process_id: 2480,
+                image: Cow::Borrowed(r#"C:\Windows\SysWOW64\svchost.exe"#),
+                tamper_type: Cow::Borrowed("Image is replaced"),
+                user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
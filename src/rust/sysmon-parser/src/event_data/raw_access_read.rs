@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// The RawAccessRead event detects when a process conducts reading operations from the drive
+/// using the `\\.\` denotation. This technique is often used by malware for data exfiltration of
+/// files that are locked for reading, as well as to avoid file access auditing tools.
+///
+/// <event name="SYSMONEVENT_RAWACCESS_READ" value="9" level="Informational" template="RawAccessRead detected" rulename="RawAccessRead" ruledefault="include" version="2" target="all">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-9-rawaccessread>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawAccessReadEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="SequenceNumber" inType="win:UInt64" />
+    pub sequence_number: Option<u64>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="Device" inType="win:UnicodeString" outType="xs:string" />
+    pub device: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> RawAccessReadEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut sequence_number = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut image = None;
+        let mut device = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "SequenceNumber" => sequence_number = Some(util::parse_int::<u64>(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "Device" => device = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let device = device.ok_or(Error::MissingField("Device"))?;
+
+        Ok(RawAccessReadEventData {
+            rule_name,
+            sequence_number,
+            utc_time,
+            process_guid,
+            process_id,
+            image,
+            device,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for RawAccessReadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::RawAccessRead(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("RawAccessRead")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &RawAccessReadEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::RawAccessRead(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("RawAccessRead")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_raw_access_read_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="UtcTime">2022-01-04 19:58:11.053</Data>
+            <Data Name="ProcessGuid">{49e2a5f6-a6d3-61d4-95d8-dd7c52560000}</Data>
+            <Data Name="ProcessId">49731</Data>
+            <Data Name="Image">/usr/bin/dd</Data>
+            <Data Name="Device">/dev/sda1</Data>
+            <Data Name="User">root</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = RawAccessReadEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            RawAccessReadEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                sequence_number: None,
+                utc_time: Utc.datetime_from_str("2022-01-04 19:58:11.053", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "49e2a5f6-a6d3-61d4-95d8-dd7c52560000"
+                ))?,
+                process_id: 49731,
+                image: Cow::Borrowed("/usr/bin/dd"),
+                device: Cow::Borrowed("/dev/sda1"),
+                user: Some(Cow::Borrowed("root")),
+            }
+        );
+
+        Ok(())
+    }
+}
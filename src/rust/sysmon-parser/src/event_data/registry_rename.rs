@@ -0,0 +1,180 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// Registry key and value rename operations map to this event type, recording the new name of
+/// the key or value that was renamed.
+///
+/// <event name="SYSMONEVENT_REG_NAME" value="14" level="Informational" template="Registry object renamed" rulename="RegistryEvent" ruledefault="include" version="2" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-14-registryevent-key-and-value-rename>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegistryRenameEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="EventType" inType="win:UnicodeString" outType="xs:string" />
+    pub event_type: Cow<'a, str>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="ProcessGuid" inType="win:GUID" />
+    pub process_guid: uuid::Uuid,
+
+    /// <data name="ProcessId" inType="win:UInt32" outType="win:PID" />
+    pub process_id: u32,
+
+    /// <data name="Image" inType="win:UnicodeString" outType="xs:string" />
+    pub image: Cow<'a, str>,
+
+    /// <data name="TargetObject" inType="win:UnicodeString" outType="xs:string" />
+    pub target_object: Cow<'a, str>,
+
+    /// <data name="NewName" inType="win:UnicodeString" outType="xs:string" />
+    pub new_name: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Option<Cow<'a, str>>,
+}
+
+impl<'a> RegistryRenameEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut event_type = None;
+        let mut utc_time = None;
+        let mut process_guid = None;
+        let mut process_id = None;
+        let mut image = None;
+        let mut target_object = None;
+        let mut new_name = None;
+        let mut user = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "EventType" => event_type = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "ProcessGuid" => process_guid = Some(util::parse_win_guid_str(value)?),
+                "ProcessId" => process_id = Some(util::parse_int::<u32>(value)?),
+                "Image" => image = Some(util::unescape_xml(value)?),
+                "TargetObject" => target_object = Some(util::unescape_xml(value)?),
+                "NewName" => new_name = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let event_type = event_type.ok_or(Error::MissingField("EventType"))?;
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let process_guid = process_guid.ok_or(Error::MissingField("ProcessGuid"))?;
+        let process_id = process_id.ok_or(Error::MissingField("ProcessId"))?;
+        let image = image.ok_or(Error::MissingField("Image"))?;
+        let target_object = target_object.ok_or(Error::MissingField("TargetObject"))?;
+        let new_name = new_name.ok_or(Error::MissingField("NewName"))?;
+
+        Ok(RegistryRenameEventData {
+            rule_name,
+            event_type,
+            utc_time,
+            process_guid,
+            process_id,
+            image,
+            target_object,
+            new_name,
+            user,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for RegistryRenameEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::RegistryRename(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("RegistryRename")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &RegistryRenameEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::RegistryRename(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("RegistryRename")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use xmlparser::StrSpan;
+
+    use super::*;
+
+    #[test]
+    fn parse_registry_rename_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="EventType">RenameKey</Data>
+            <Data Name="UtcTime">2022-03-02 21:03:40.134</Data>
+            <Data Name="ProcessGuid">{A46BB5E4-D9F8-621F-8F00-000000000F00}</Data>
+            <Data Name="ProcessId">3064</Data>
+            <Data Name="Image">C:\Windows\regedit.exe</Data>
+            <Data Name="TargetObject">HKU\S-1-5-21-1574594750-1263408776-2012057445-1001\Software\New Key #1</Data>
+            <Data Name="NewName">HKU\S-1-5-21-1574594750-1263408776-2012057445-1001\Software\Grapl</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = RegistryRenameEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            RegistryRenameEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                event_type: Cow::Borrowed("RenameKey"),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:03:40.134", UTC_TIME_FORMAT)?,
+                process_guid: util::parse_win_guid_str(&StrSpan::from(
+                    "A46BB5E4-D9F8-621F-8F00-000000000F00"
+                ))?,
+                process_id: 3064,
+                image: Cow::Borrowed(r#"C:\Windows\regedit.exe"#),
+                target_object: Cow::Borrowed(
+                    r#"HKU\S-1-5-21-1574594750-1263408776-2012057445-1001\Software\New Key #1"#
+                ),
+                new_name: Cow::Borrowed(
+                    r#"HKU\S-1-5-21-1574594750-1263408776-2012057445-1001\Software\Grapl"#
+                ),
+                user: Some(Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#)),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// This event logs the registration of WMI consumers, recording the consumer name, log, and
+/// destination.
+///
+/// <event name="SYSMONEVENT_WMI_CONSUMER" value="20" level="Informational" template="WmiEventConsumer activity detected" rulename="WmiEvent" ruledefault="include" version="3" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-20-wmievent-wmieventconsumer-activity-detected>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmiEventConsumerEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="EventType" inType="win:UnicodeString" outType="xs:string" />
+    pub event_type: Cow<'a, str>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="Operation" inType="win:UnicodeString" outType="xs:string" />
+    pub operation: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Cow<'a, str>,
+
+    /// <data name="Name" inType="win:UnicodeString" outType="xs:string" />
+    pub name: Cow<'a, str>,
+
+    /// <data name="Type" inType="win:UnicodeString" outType="xs:string" />
+    pub consumer_type: Cow<'a, str>,
+
+    /// <data name="Destination" inType="win:UnicodeString" outType="xs:string" />
+    pub destination: Cow<'a, str>,
+}
+
+impl<'a> WmiEventConsumerEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut event_type = None;
+        let mut utc_time = None;
+        let mut operation = None;
+        let mut user = None;
+        let mut name = None;
+        let mut consumer_type = None;
+        let mut destination = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "EventType" => event_type = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "Operation" => operation = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                "Name" => name = Some(util::unescape_xml(value)?),
+                "Type" => consumer_type = Some(util::unescape_xml(value)?),
+                "Destination" => destination = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let event_type = event_type.ok_or(Error::MissingField("EventType"))?;
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let operation = operation.ok_or(Error::MissingField("Operation"))?;
+        let user = user.ok_or(Error::MissingField("User"))?;
+        let name = name.ok_or(Error::MissingField("Name"))?;
+        let consumer_type = consumer_type.ok_or(Error::MissingField("Type"))?;
+        let destination = destination.ok_or(Error::MissingField("Destination"))?;
+
+        Ok(WmiEventConsumerEventData {
+            rule_name,
+            event_type,
+            utc_time,
+            operation,
+            user,
+            name,
+            consumer_type,
+            destination,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for WmiEventConsumerEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventConsumer(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventConsumer")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &WmiEventConsumerEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventConsumer(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventConsumer")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parse_wmi_event_consumer_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="EventType">WmiConsumerEvent</Data>
+            <Data Name="UtcTime">2022-03-02 21:07:52.536</Data>
+            <Data Name="Operation">Created</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+            <Data Name="Name">"GraplConsumer"</Data>
+            <Data Name="Type">Command Line</Data>
+            <Data Name="Destination">"C:\\Windows\\System32\\calc.exe"</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = WmiEventConsumerEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            WmiEventConsumerEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                event_type: Cow::Borrowed("WmiConsumerEvent"),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:07:52.536", UTC_TIME_FORMAT)?,
+                operation: Cow::Borrowed("Created"),
+                user: Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#),
+                name: Cow::Borrowed(r#""GraplConsumer""#),
+                consumer_type: Cow::Borrowed("Command Line"),
+                destination: Cow::Borrowed(r#""C:\\Windows\\System32\\calc.exe""#),
+            }
+        );
+
+        Ok(())
+    }
+}
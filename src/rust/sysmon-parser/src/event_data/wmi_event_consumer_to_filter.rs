@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// When a consumer binds to a filter, this event logs the consumer name and filter path.
+///
+/// <event name="SYSMONEVENT_WMI_BINDING" value="21" level="Informational" template="WmiEventConsumerToFilter activity detected" rulename="WmiEvent" ruledefault="include" version="3" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-21-wmievent-wmieventconsumertofilter-activity-detected>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmiEventConsumerToFilterEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="EventType" inType="win:UnicodeString" outType="xs:string" />
+    pub event_type: Cow<'a, str>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="Operation" inType="win:UnicodeString" outType="xs:string" />
+    pub operation: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Cow<'a, str>,
+
+    /// <data name="Consumer" inType="win:UnicodeString" outType="xs:string" />
+    pub consumer: Cow<'a, str>,
+
+    /// <data name="Filter" inType="win:UnicodeString" outType="xs:string" />
+    pub filter: Cow<'a, str>,
+}
+
+impl<'a> WmiEventConsumerToFilterEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut event_type = None;
+        let mut utc_time = None;
+        let mut operation = None;
+        let mut user = None;
+        let mut consumer = None;
+        let mut filter = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "EventType" => event_type = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "Operation" => operation = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                "Consumer" => consumer = Some(util::unescape_xml(value)?),
+                "Filter" => filter = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let event_type = event_type.ok_or(Error::MissingField("EventType"))?;
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let operation = operation.ok_or(Error::MissingField("Operation"))?;
+        let user = user.ok_or(Error::MissingField("User"))?;
+        let consumer = consumer.ok_or(Error::MissingField("Consumer"))?;
+        let filter = filter.ok_or(Error::MissingField("Filter"))?;
+
+        Ok(WmiEventConsumerToFilterEventData {
+            rule_name,
+            event_type,
+            utc_time,
+            operation,
+            user,
+            consumer,
+            filter,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for WmiEventConsumerToFilterEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventConsumerToFilter(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventConsumerToFilter")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &WmiEventConsumerToFilterEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventConsumerToFilter(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventConsumerToFilter")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parse_wmi_event_consumer_to_filter_event(
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="EventType">WmiBindingEvent</Data>
+            <Data Name="UtcTime">2022-03-02 21:07:52.551</Data>
+            <Data Name="Operation">Created</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+            <Data Name="Consumer">"CommandLineEventConsumer.Name=\"GraplConsumer\""</Data>
+            <Data Name="Filter">"__EventFilter.Name=\"GraplFilter\""</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = WmiEventConsumerToFilterEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            WmiEventConsumerToFilterEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                event_type: Cow::Borrowed("WmiBindingEvent"),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:07:52.551", UTC_TIME_FORMAT)?,
+                operation: Cow::Borrowed("Created"),
+                user: Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#),
+                consumer: Cow::Borrowed(r#""CommandLineEventConsumer.Name=\"GraplConsumer\"""#),
+                filter: Cow::Borrowed(r#""__EventFilter.Name=\"GraplFilter\"""#),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use derive_into_owned::IntoOwned;
+
+use super::{
+    EventData,
+    UTC_TIME_FORMAT,
+};
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    util,
+};
+
+/// When a WMI event filter is registered, which is a method used by malware to execute, this
+/// event logs the WMI namespace, filter name and filter expression.
+///
+/// <event name="SYSMONEVENT_WMI_FILTER" value="19" level="Informational" template="WmiEventFilter activity detected" rulename="WmiEvent" ruledefault="include" version="3" target="windows">
+///
+/// <https://docs.microsoft.com/en-us/sysinternals/downloads/sysmon#event-id-19-wmievent-wmieventfilter-activity-detected>
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoOwned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmiEventFilterEventData<'a> {
+    /// <data name="RuleName" inType="win:UnicodeString" outType="xs:string" />
+    pub rule_name: Option<Cow<'a, str>>,
+
+    /// <data name="EventType" inType="win:UnicodeString" outType="xs:string" />
+    pub event_type: Cow<'a, str>,
+
+    /// <data name="UtcTime" inType="win:UnicodeString" outType="xs:string" />
+    pub utc_time: DateTime<Utc>,
+
+    /// <data name="Operation" inType="win:UnicodeString" outType="xs:string" />
+    pub operation: Cow<'a, str>,
+
+    /// <data name="User" inType="win:UnicodeString" outType="xs:string" />
+    pub user: Cow<'a, str>,
+
+    /// <data name="EventNamespace" inType="win:UnicodeString" outType="xs:string" />
+    pub event_namespace: Cow<'a, str>,
+
+    /// <data name="Name" inType="win:UnicodeString" outType="xs:string" />
+    pub name: Cow<'a, str>,
+
+    /// <data name="Query" inType="win:UnicodeString" outType="xs:string" />
+    pub query: Cow<'a, str>,
+}
+
+impl<'a> WmiEventFilterEventData<'a> {
+    pub(crate) fn try_from(tokenizer: &mut xmlparser::Tokenizer<'a>) -> Result<Self> {
+        let mut rule_name = None;
+        let mut event_type = None;
+        let mut utc_time = None;
+        let mut operation = None;
+        let mut user = None;
+        let mut event_namespace = None;
+        let mut name = None;
+        let mut query = None;
+
+        for result in util::EventDataIterator::new(tokenizer)? {
+            let (name, ref value) = result?;
+            match name {
+                "RuleName" => rule_name = Some(util::unescape_xml(value)?),
+                "EventType" => event_type = Some(util::unescape_xml(value)?),
+                "UtcTime" => utc_time = Some(util::parse_utc_from_str(value, UTC_TIME_FORMAT)?),
+                "Operation" => operation = Some(util::unescape_xml(value)?),
+                "User" => user = Some(util::unescape_xml(value)?),
+                "EventNamespace" => event_namespace = Some(util::unescape_xml(value)?),
+                "Name" => name = Some(util::unescape_xml(value)?),
+                "Query" => query = Some(util::unescape_xml(value)?),
+                _ => {}
+            }
+        }
+
+        // expected fields - present in all observed schema versions
+        let event_type = event_type.ok_or(Error::MissingField("EventType"))?;
+        let utc_time = utc_time.ok_or(Error::MissingField("UtcTime"))?;
+        let operation = operation.ok_or(Error::MissingField("Operation"))?;
+        let user = user.ok_or(Error::MissingField("User"))?;
+        let event_namespace = event_namespace.ok_or(Error::MissingField("EventNamespace"))?;
+        let name = name.ok_or(Error::MissingField("Name"))?;
+        let query = query.ok_or(Error::MissingField("Query"))?;
+
+        Ok(WmiEventFilterEventData {
+            rule_name,
+            event_type,
+            utc_time,
+            operation,
+            user,
+            event_namespace,
+            name,
+            query,
+        })
+    }
+}
+
+impl<'a> TryFrom<EventData<'a>> for WmiEventFilterEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventFilter(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventFilter")),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> TryFrom<&'b EventData<'a>> for &WmiEventFilterEventData<'a> {
+    type Error = Error;
+
+    fn try_from(event_data: &'b EventData<'a>) -> Result<Self> {
+        match event_data {
+            EventData::WmiEventFilter(event_data) => Ok(event_data),
+            _ => Err(Error::ExpectEventType("WmiEventFilter")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parse_wmi_event_filter_event() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let xml = r#"
+        <EventData>
+            <Data Name="RuleName">-</Data>
+            <Data Name="EventType">WmiFilterEvent</Data>
+            <Data Name="UtcTime">2022-03-02 21:07:52.520</Data>
+            <Data Name="Operation">Created</Data>
+            <Data Name="User">DESKTOP-GRAPL\grapltest</Data>
+            <Data Name="EventNamespace">"root\\cimv2"</Data>
+            <Data Name="Name">"GraplFilter"</Data>
+            <Data Name="Query">"SELECT * FROM __InstanceModificationEvent WITHIN 60 WHERE TargetInstance ISA Win32_PerfFormattedData_PerfOS_System"</Data>
+        </EventData>"#;
+
+        let mut tokenizer = xmlparser::Tokenizer::from(xml);
+        let event_data = WmiEventFilterEventData::try_from(&mut tokenizer)?;
+
+        assert_eq!(
+            event_data,
+            WmiEventFilterEventData {
+                rule_name: Some(Cow::Borrowed("-")),
+                event_type: Cow::Borrowed("WmiFilterEvent"),
+                utc_time: Utc.datetime_from_str("2022-03-02 21:07:52.520", UTC_TIME_FORMAT)?,
+                operation: Cow::Borrowed("Created"),
+                user: Cow::Borrowed(r#"DESKTOP-GRAPL\grapltest"#),
+                event_namespace: Cow::Borrowed(r#""root\\cimv2""#),
+                name: Cow::Borrowed(r#""GraplFilter""#),
+                query: Cow::Borrowed(
+                    r#""SELECT * FROM __InstanceModificationEvent WITHIN 60 WHERE TargetInstance ISA Win32_PerfFormattedData_PerfOS_System""#
+                ),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,17 @@
 use std::iter::FusedIterator;
 
 use super::event::SysmonEvent;
-use crate::error::Result;
+use crate::{
+    error::{
+        Error,
+        EventError,
+        Result,
+    },
+    util::{
+        self,
+        Boundary,
+    },
+};
 
 /// An iterator over results of parsed Sysmon XML events.
 ///
@@ -57,3 +67,234 @@ impl<'a> Iterator for SysmonEvents<'a> {
 }
 
 impl FusedIterator for SysmonEvents<'_> {}
+
+/// An iterator over results of parsed Sysmon XML events that recovers from malformed events.
+///
+/// This is created by calling [`sysmon_parser::parse_events_recovering`]. See its documentation
+/// for more information.
+pub struct RecoveringSysmonEvents<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> RecoveringSysmonEvents<'a> {
+    pub(super) fn from(input: &'a str) -> Self {
+        RecoveringSysmonEvents { input, position: 0 }
+    }
+}
+
+impl<'a> Iterator for RecoveringSysmonEvents<'a> {
+    type Item = std::result::Result<SysmonEvent<'a>, EventError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+
+        // find the start of the next event, ignoring any stray end-tags
+        let start = loop {
+            match util::next_boundary(bytes, self.position) {
+                Boundary::Start(start) => break start,
+                Boundary::End(end) => self.position = end,
+                Boundary::Incomplete(_) => {
+                    self.position = bytes.len();
+
+                    return None;
+                }
+            }
+        };
+
+        // An event ends at its end-tag. If another event starts first, or the input ends, then
+        // this event was truncated and is skipped.
+        let end = match util::next_boundary(bytes, start + 1) {
+            Boundary::End(end) => end,
+            Boundary::Start(next_start) => {
+                self.position = next_start;
+
+                return Some(Err(EventError {
+                    position: start,
+                    source: Error::UnexpectedEndOfStream,
+                }));
+            }
+            Boundary::Incomplete(_) => {
+                self.position = bytes.len();
+
+                return Some(Err(EventError {
+                    position: start,
+                    source: Error::UnexpectedEndOfStream,
+                }));
+            }
+        };
+        self.position = end;
+
+        let mut tokenizer = xmlparser::Tokenizer::from_fragment(self.input, start..end);
+
+        match super::event::from_tokenizer(&mut tokenizer) {
+            Ok(event) => Some(Ok(event)),
+            Err(source) => Some(Err(EventError {
+                position: start,
+                source,
+            })),
+        }
+    }
+}
+
+impl FusedIterator for RecoveringSysmonEvents<'_> {}
+
+/// The number of bytes requested from the underlying reader at a time.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// An iterator over results of parsed Sysmon XML events read from a [`std::io::Read`].
+///
+/// This is created by calling [`sysmon_parser::parse_events_from_reader`]. See its documentation
+/// for more information.
+pub struct SysmonEventsReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// The position of the start of `buffer` in the input stream
+    offset: usize,
+    eof: bool,
+    done: bool,
+    lenient: bool,
+}
+
+impl<R: std::io::Read> SysmonEventsReader<R> {
+    pub(super) fn from(reader: R) -> Self {
+        SysmonEventsReader {
+            reader,
+            buffer: Vec::with_capacity(READ_CHUNK_SIZE),
+            offset: 0,
+            eof: false,
+            done: false,
+            lenient: false,
+        }
+    }
+
+    /// Escapes characters that are not valid in XML 1.0, such as control characters, before
+    /// parsing each event instead of failing the event. See
+    /// [`sysmon_parser::escape_invalid_xml_chars`] for details.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Reads the next chunk of input into the buffer, setting `eof` when the reader is exhausted.
+    fn fill_buffer(&mut self) -> std::io::Result<()> {
+        let len = self.buffer.len();
+        self.buffer.resize(len + READ_CHUNK_SIZE, 0);
+
+        let result = loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+
+        match result {
+            Ok(read) => {
+                self.buffer.truncate(len + read);
+                self.eof = read == 0;
+
+                Ok(())
+            }
+            Err(e) => {
+                self.buffer.truncate(len);
+
+                Err(e)
+            }
+        }
+    }
+
+    /// Drops the first `count` bytes of the buffer.
+    fn consume(&mut self, count: usize) {
+        self.buffer.drain(..count);
+        self.offset += count;
+    }
+
+    /// Advances the buffer to the start of the next event and returns the position of its end,
+    /// reading more input as needed, along with whether the event was closed by an end-tag.
+    /// Returns `None` if there are no more events.
+    fn next_event_bounds(&mut self) -> std::io::Result<Option<(usize, bool)>> {
+        loop {
+            match util::next_boundary(&self.buffer, 0) {
+                Boundary::Start(start) => {
+                    self.consume(start);
+                    break;
+                }
+                Boundary::End(end) => self.consume(end),
+                Boundary::Incomplete(position) => {
+                    self.consume(position);
+                    if self.eof {
+                        return Ok(None);
+                    }
+                    self.fill_buffer()?;
+                }
+            }
+        }
+
+        let mut position = 1;
+        loop {
+            match util::next_boundary(&self.buffer, position) {
+                Boundary::End(end) => return Ok(Some((end, true))),
+                Boundary::Start(next_start) => return Ok(Some((next_start, false))),
+                Boundary::Incomplete(incomplete) => {
+                    if self.eof {
+                        return Ok(Some((self.buffer.len(), false)));
+                    }
+                    position = incomplete.max(1);
+                    self.fill_buffer()?;
+                }
+            }
+        }
+    }
+}
+
+impl<R: std::io::Read> Iterator for SysmonEventsReader<R> {
+    type Item = std::result::Result<SysmonEvent<'static>, EventError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (end, complete) = match self.next_event_bounds() {
+            Ok(Some(bounds)) => bounds,
+            Ok(None) => {
+                self.done = true;
+
+                return None;
+            }
+            Err(e) => {
+                // there's no way to know if the reader can recover so stop here
+                self.done = true;
+
+                return Some(Err(EventError {
+                    position: self.offset,
+                    source: e.into(),
+                }));
+            }
+        };
+
+        let result = if !complete {
+            // another event started, or the input ended, before this one was closed
+            Err(Error::UnexpectedEndOfStream)
+        } else {
+            match std::str::from_utf8(&self.buffer[..end]) {
+                Ok(xml) if self.lenient => {
+                    let xml = util::escape_invalid_xml_chars(xml);
+                    SysmonEvent::from_str(&xml).map(SysmonEvent::into_owned)
+                }
+                Ok(xml) => SysmonEvent::from_str(xml).map(SysmonEvent::into_owned),
+                Err(source) => Err(Error::Utf8 {
+                    position: source.valid_up_to(),
+                    source,
+                }),
+            }
+        };
+
+        let position = self.offset;
+        self.consume(end);
+
+        Some(result.map_err(|source| EventError { position, source }))
+    }
+}
+
+impl<R: std::io::Read> FusedIterator for SysmonEventsReader<R> {}
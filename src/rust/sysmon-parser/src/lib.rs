@@ -16,11 +16,28 @@
 //! still parse, but the [`SysmonEvent::event_data`] will be the [`EventData::Unsupported`]
 //! variant. The types that currently are supported are:
 //!
+//!   - [ClipboardChange]
+//!   - [CreateRemoteThread]
+//!   - [DnsQuery]
 //!   - [FileCreate]
 //!   - [FileCreateStreamHash]
+//!   - [FileDelete]
+//!   - [FileDeleteDetected]
+//!   - [ImageLoad]
 //!   - [NetworkConnect]
+//!   - [PipeConnected]
+//!   - [PipeCreated]
+//!   - [ProcessAccess]
 //!   - [ProcessCreate]
+//!   - [ProcessTampering]
 //!   - [ProcessTerminate]
+//!   - [RawAccessRead]
+//!   - [RegistryAddOrDelete]
+//!   - [RegistryRename]
+//!   - [RegistryValueSet]
+//!   - [WmiEventConsumer]
+//!   - [WmiEventConsumerToFilter]
+//!   - [WmiEventFilter]
 //!
 //! # Data types
 //!
@@ -40,16 +57,34 @@
 //! 1. [xmlparser] is used for parsing the input XML, which supports XML 1.0 only, and verifies the
 //! XML adheres the to 1.0 spec. However, Sysmon _may_ include characters that are not valid for
 //! the XML 1.0 spec. This includes control characters, which can show up in command line strings
-//! on Linux.
+//! on Linux. Such input can be parsed by first passing it through [`escape_invalid_xml_chars`],
+//! or with [`SysmonEventsReader::lenient`] when parsing from a reader.
 //!
 //!
 //! [Sysmon for Linux]: https://github.com/Sysinternals/SysmonForLinux
 //! [xmlparser]: https://github.com/RazrFalcon/xmlparser
+//! [ClipboardChange]: event_data::ClipboardChangeEventData
+//! [CreateRemoteThread]: event_data::CreateRemoteThreadEventData
+//! [DnsQuery]: event_data::DnsQueryEventData
 //! [FileCreate]: event_data::FileCreateEventData
 //! [FileCreateStreamHash]: event_data::FileCreateStreamHashEventData
+//! [FileDelete]: event_data::FileDeleteEventData
+//! [FileDeleteDetected]: event_data::FileDeleteEventData
+//! [ImageLoad]: event_data::ImageLoadEventData
 //! [NetworkConnect]: event_data::NetworkConnectionEventData
+//! [PipeConnected]: event_data::PipeEventData
+//! [PipeCreated]: event_data::PipeEventData
+//! [ProcessAccess]: event_data::ProcessAccessEventData
 //! [ProcessCreate]: event_data::ProcessCreateEventData
+//! [ProcessTampering]: event_data::ProcessTamperingEventData
 //! [ProcessTerminate]: event_data::ProcessTerminatedEventData
+//! [RawAccessRead]: event_data::RawAccessReadEventData
+//! [RegistryAddOrDelete]: event_data::RegistryAddOrDeleteEventData
+//! [RegistryRename]: event_data::RegistryRenameEventData
+//! [RegistryValueSet]: event_data::RegistryValueSetEventData
+//! [WmiEventConsumer]: event_data::WmiEventConsumerEventData
+//! [WmiEventConsumerToFilter]: event_data::WmiEventConsumerToFilterEventData
+//! [WmiEventFilter]: event_data::WmiEventFilterEventData
 //! [Unsupported]: EventData::UnsupportedEventData
 
 #![allow(
@@ -65,7 +100,10 @@ mod event;
 mod events;
 mod util;
 
-use events::SysmonEvents;
+use events::{
+    RecoveringSysmonEvents,
+    SysmonEvents,
+};
 
 pub mod event_data;
 pub mod system;
@@ -73,14 +111,18 @@ pub mod system;
 #[doc(inline)]
 pub use event_data::EventData;
 
-pub use crate::error::{
-    Error,
-    Result,
-};
 #[doc(inline)]
 pub use crate::event::SysmonEvent;
 #[doc(inline)]
 pub use crate::system::System;
+pub use crate::{
+    error::{
+        Error,
+        EventError,
+        Result,
+    },
+    events::SysmonEventsReader,
+};
 
 /// An iterator over results of parsed Sysmon XML events found in this string slice.
 ///
@@ -129,3 +171,113 @@ pub use crate::system::System;
 pub fn parse_events(input: &str) -> SysmonEvents<'_> {
     SysmonEvents::from(input)
 }
+
+/// An iterator over results of parsed Sysmon XML events found in this string slice, which
+/// continues past malformed events.
+///
+/// Unlike [`parse_events`], which stops after the first error, this skips ahead to the next
+/// `<Event>` start-tag after an error and keeps going. Each error is returned as an [`EventError`]
+/// with the byte offset of the malformed event in `input`.
+///
+/// # Example
+///
+/// ```
+/// let xml = r#"
+/// <Event><System><EventID>5</EventID></System><EventData></EventData></Event>
+/// <Event>
+///   <System>
+///     <Provider Name="Linux-Sysmon" Guid="{ff032593-a8d3-4f13-b0d6-02dc615a6f97}"/>
+///     <EventID>5</EventID>
+///     <Version>3</Version>
+///     <Level>4</Level>
+///     <Task>5</Task>
+///     <Opcode>0</Opcode>
+///     <Keywords>0x8000000000000000</Keywords>
+///     <TimeCreated SystemTime="2022-01-04T19:52:56.313955000Z"/>
+///     <EventRecordID>21</EventRecordID>
+///     <Correlation/>
+///     <Execution ProcessID="49514" ThreadID="49514"/>
+///     <Channel>Linux-Sysmon/Operational</Channel>
+///     <Computer>hostname</Computer>
+///     <Security UserId="0"/>
+///   </System>
+///   <EventData>
+///     <Data Name="RuleName">-</Data>
+///     <Data Name="UtcTime">2022-01-04 19:52:56.319</Data>
+///     <Data Name="ProcessGuid">{49e2a5f6-a598-61d4-5d5a-d1755b550000}</Data>
+///     <Data Name="ProcessId">49529</Data>
+///     <Data Name="Image">/usr/bin/id</Data>
+///     <Data Name="User">root</Data>
+///   </EventData>
+/// </Event>"#;
+///
+/// let mut events = sysmon_parser::parse_events_recovering(xml);
+///
+/// let error = events.next().unwrap().unwrap_err();
+/// assert_eq!(error.position, 1);
+///
+/// let event = events.next().unwrap().unwrap();
+/// assert_eq!(event.system.computer, "hostname");
+///
+/// assert!(events.next().is_none());
+/// ```
+pub fn parse_events_recovering(input: &str) -> RecoveringSysmonEvents<'_> {
+    RecoveringSysmonEvents::from(input)
+}
+
+/// An iterator over results of parsed Sysmon XML events read from `reader`.
+///
+/// Input is read incrementally, holding only about one event in memory at a time, so this is
+/// suitable for exports that are too large to load into a single string for [`parse_events`].
+/// Because events can't borrow from the input they are returned as owned `SysmonEvent<'static>`
+/// values.
+///
+/// Like [`parse_events_recovering`], this continues past malformed events and returns each error
+/// as an [`EventError`] with the byte offset of the malformed event in the stream. Positions
+/// within the wrapped [`Error`] are relative to the start of that event. Iteration stops after an
+/// error from `reader` itself.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::{
+///     fs::File,
+///     io::BufReader,
+/// };
+///
+/// let file = File::open("sysmon-export.xml").unwrap();
+///
+/// for result in sysmon_parser::parse_events_from_reader(BufReader::new(file)) {
+///     match result {
+///         Ok(event) => println!("{:?}", event.system.event_id),
+///         Err(e) => eprintln!("skipping event: {}", e),
+///     }
+/// }
+/// ```
+pub fn parse_events_from_reader<R: std::io::Read>(reader: R) -> SysmonEventsReader<R> {
+    SysmonEventsReader::from(reader)
+}
+
+/// Escapes characters in `input` that are not valid in XML 1.0 as XML character references.
+///
+/// Sysmon for Linux can write control characters, such as those found in command lines, directly
+/// into its XML output, which causes the XML 1.0 parser used by this library to reject the whole
+/// event. Escaping them as character references (ex: `\u{7}` as `&#x7;`) makes the input valid
+/// XML, and because string fields are unescaped when parsed the original characters are preserved
+/// in the parsed events.
+///
+/// Input without any such characters is returned as-is, without allocating.
+///
+/// # Example
+///
+/// ```
+/// let xml = "<Data Name=\"CommandLine\">file \u{7}</Data>";
+///
+/// assert_eq!(
+///     sysmon_parser::escape_invalid_xml_chars(xml),
+///     "<Data Name=\"CommandLine\">file &#x7;</Data>"
+/// );
+/// ```
+pub fn escape_invalid_xml_chars(input: &str) -> std::borrow::Cow<'_, str> {
+    util::escape_invalid_xml_chars(input)
+}
@@ -18,7 +18,12 @@ use crate::error::{
     Result,
 };
 
+mod event_boundary;
 mod eventdata_iterator;
+pub(crate) use event_boundary::{
+    next_boundary,
+    Boundary,
+};
 pub(crate) use eventdata_iterator::EventDataIterator;
 
 pub(crate) fn from_zero_or_hex_str(span: &StrSpan) -> Result<u64> {
@@ -36,6 +41,21 @@ pub(crate) fn from_zero_or_hex_str(span: &StrSpan) -> Result<u64> {
     }
 }
 
+pub(crate) fn from_zero_or_hex_str_u32(span: &StrSpan) -> Result<u32> {
+    let hex_str = span.as_str();
+
+    if hex_str == "0" {
+        Ok(0_u32)
+    } else {
+        let hex_str = hex_str.trim_start_matches("0x");
+        u32::from_str_radix(hex_str, 16).map_err(|source| Error::ParseInt {
+            value: hex_str.to_string(),
+            position: span.start(),
+            source,
+        })
+    }
+}
+
 pub(crate) fn parse_win_guid_str(span: &StrSpan) -> Result<uuid::Uuid> {
     let guid_str = span
         .as_str()
@@ -190,6 +210,41 @@ pub(crate) fn unescape_xml<'a, 'b: 'a>(span: &'a StrSpan<'b>) -> Result<Cow<'b,
     }
 }
 
+/// Returns true for characters allowed in XML 1.0 documents.
+///
+/// `<https://www.w3.org/TR/xml/#charsets>`
+fn is_xml_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{9}'
+            | '\u{A}'
+            | '\u{D}'
+            | '\u{20}'..='\u{D7FF}'
+            | '\u{E000}'..='\u{FFFD}'
+            | '\u{10000}'..='\u{10FFFF}'
+    )
+}
+
+pub(crate) fn escape_invalid_xml_chars(input: &str) -> Cow<'_, str> {
+    let first_invalid = match input.find(|c: char| !is_xml_char(c)) {
+        Some(position) => position,
+        None => return Cow::Borrowed(input),
+    };
+
+    let mut escaped = String::with_capacity(input.len() + 8);
+    escaped.push_str(&input[..first_invalid]);
+
+    for c in input[first_invalid..].chars() {
+        if is_xml_char(c) {
+            escaped.push(c);
+        } else {
+            escaped.push_str(&format!("&#x{:X};", c as u32));
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
 fn get_token_position(token: &Token) -> usize {
     match token {
         Token::Attribute { span, .. }
@@ -388,6 +443,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn escape_invalid_chars() -> Result<()> {
+        assert_eq!(
+            Cow::Borrowed("nothing\tto\nescape"),
+            escape_invalid_xml_chars("nothing\tto\nescape")
+        );
+        assert_eq!(
+            Cow::Owned::<String>("file &#x7;&#x1B;[0m".to_string()),
+            escape_invalid_xml_chars("file \u{7}\u{1b}[0m")
+        );
+
+        // escaped characters are restored when unescaping text
+        assert_eq!(
+            "file \u{7}",
+            unescape_xml(&StrSpan::from(
+                escape_invalid_xml_chars("file \u{7}").as_ref()
+            ))?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn get_element_text() -> Result<()> {
         let xml = r#"<Foo>Bar</Foo>"#;
@@ -0,0 +1,136 @@
+const EVENT_START_TAG: &[u8] = b"<Event";
+const EVENT_END_TAG: &[u8] = b"</Event";
+const COMMENT_START: &[u8] = b"<!--";
+const COMMENT_END: &[u8] = b"-->";
+const CDATA_START: &[u8] = b"<![CDATA[";
+const CDATA_END: &[u8] = b"]]>";
+
+/// The next `<Event>` boundary found in a byte slice by [`next_boundary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Boundary {
+    /// Position of the `<` that begins an `<Event>` start-tag.
+    Start(usize),
+    /// Position immediately after the `>` that ends an `</Event>` end-tag.
+    End(usize),
+    /// No boundary was found. Bytes before this position do not contain a boundary, but the
+    /// remaining bytes may once more input is available (ex: a comment or tag that was cut off).
+    Incomplete(usize),
+}
+
+/// Scans `input` from `from` for the next `<Event>` start-tag or `</Event>` end-tag.
+///
+/// This is a lightweight scan over raw bytes and does not validate the XML in any way. It's used
+/// to find where events begin and end so that a malformed event can be skipped without having to
+/// tokenize it. XML comments and CDATA sections are skipped, so commented-out events are not
+/// reported.
+pub(crate) fn next_boundary(input: &[u8], from: usize) -> Boundary {
+    let mut position = from;
+
+    while let Some(offset) = input.get(position..).and_then(|s| memchr::memchr(b'<', s)) {
+        let start = position + offset;
+        let rest = &input[start..];
+
+        if is_partial_prefix(rest) {
+            return Boundary::Incomplete(start);
+        }
+
+        if rest.starts_with(COMMENT_START) {
+            match memchr::memmem::find(&rest[COMMENT_START.len()..], COMMENT_END) {
+                Some(end) => {
+                    position = start + COMMENT_START.len() + end + COMMENT_END.len();
+                    continue;
+                }
+                None => return Boundary::Incomplete(start),
+            }
+        }
+
+        if rest.starts_with(CDATA_START) {
+            match memchr::memmem::find(&rest[CDATA_START.len()..], CDATA_END) {
+                Some(end) => {
+                    position = start + CDATA_START.len() + end + CDATA_END.len();
+                    continue;
+                }
+                None => return Boundary::Incomplete(start),
+            }
+        }
+
+        if let Some(after_name) = rest.strip_prefix(EVENT_END_TAG) {
+            // `</Event` may be followed by whitespace before the closing `>`
+            match after_name.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(i) if after_name[i] == b'>' => {
+                    return Boundary::End(start + EVENT_END_TAG.len() + i + 1);
+                }
+                Some(_) => {}
+                None => return Boundary::Incomplete(start),
+            }
+        } else if let Some(after_name) = rest.strip_prefix(EVENT_START_TAG) {
+            // make sure this isn't another element that starts with the same name, ex: `<EventData>`
+            match after_name.first() {
+                Some(b) if b.is_ascii_whitespace() || *b == b'>' || *b == b'/' => {
+                    return Boundary::Start(start);
+                }
+                Some(_) => {}
+                None => return Boundary::Incomplete(start),
+            }
+        }
+
+        position = start + 1;
+    }
+
+    Boundary::Incomplete(input.len())
+}
+
+/// Returns true if `rest` is too short to tell whether it begins a comment, CDATA section or
+/// `<Event>` tag.
+fn is_partial_prefix(rest: &[u8]) -> bool {
+    [COMMENT_START, CDATA_START, EVENT_START_TAG, EVENT_END_TAG]
+        .iter()
+        .any(|pattern| rest.len() < pattern.len() && pattern.starts_with(rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_start_and_end() {
+        let input = b"  <Event xmlns='x'><EventData/></Event>\n<Event>";
+
+        assert_eq!(next_boundary(input, 0), Boundary::Start(2));
+        assert_eq!(next_boundary(input, 3), Boundary::End(39));
+        assert_eq!(next_boundary(input, 39), Boundary::Start(40));
+    }
+
+    #[test]
+    fn end_tag_with_whitespace() {
+        let input = b"<Event></Event \n>";
+
+        assert_eq!(next_boundary(input, 1), Boundary::End(input.len()));
+    }
+
+    #[test]
+    fn skips_similar_element_names() {
+        let input = b"<EventData><EventID>1</EventID></EventData>";
+
+        assert_eq!(next_boundary(input, 0), Boundary::Incomplete(input.len()));
+    }
+
+    #[test]
+    fn skips_comments_and_cdata() {
+        let input = b"<!-- <Event></Event> --><![CDATA[<Event>]]><Event/>";
+
+        assert_eq!(next_boundary(input, 0), Boundary::Start(43));
+    }
+
+    #[test]
+    fn incomplete_input() {
+        assert_eq!(next_boundary(b"abc <Eve", 0), Boundary::Incomplete(4));
+        assert_eq!(next_boundary(b"abc <Event", 0), Boundary::Incomplete(4));
+        assert_eq!(next_boundary(b"abc </Event ", 0), Boundary::Incomplete(4));
+        assert_eq!(
+            next_boundary(b"abc <!-- <Event>", 0),
+            Boundary::Incomplete(4)
+        );
+        assert_eq!(next_boundary(b"abc", 0), Boundary::Incomplete(3));
+    }
+}
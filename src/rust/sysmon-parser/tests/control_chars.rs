@@ -10,3 +10,34 @@ fn control_char() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn control_char_escaped() -> eyre::Result<()> {
+    let xml = std::fs::read_to_string("tests/data/event_with_control_char.xml")?;
+    let xml = sysmon_parser::escape_invalid_xml_chars(&xml);
+
+    let events = sysmon_parser::parse_events(&xml).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events.len(), 1);
+
+    let event_data: &sysmon_parser::event_data::ProcessCreateEventData =
+        (&events[0].event_data).try_into()?;
+    assert_eq!(event_data.command_line, "file \u{7}");
+
+    Ok(())
+}
+
+#[test]
+fn control_char_lenient_reader() -> eyre::Result<()> {
+    let file = std::fs::File::open("tests/data/event_with_control_char.xml")?;
+
+    let events = sysmon_parser::parse_events_from_reader(std::io::BufReader::new(file))
+        .lenient(true)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events.len(), 1);
+
+    let event_data: &sysmon_parser::event_data::ProcessCreateEventData =
+        (&events[0].event_data).try_into()?;
+    assert_eq!(event_data.command_line, "file \u{7}");
+
+    Ok(())
+}
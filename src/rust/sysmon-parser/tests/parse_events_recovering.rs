@@ -0,0 +1,114 @@
+use std::io::Read;
+
+const XML: &str = r#"
+<Event><System><Provider Name="Linux-Sysmon" Guid="{ff032593-a8d3-4f13-b0d6-01fc615a0f97}"/><EventID>5</EventID><Version>3</Version><Level>4</Level><Task>5</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime="2022-01-04T19:52:55.677224000Z"/><EventRecordID>9</EventRecordID><Correlation/><Execution ProcessID="49514" ThreadID="49514"/><Channel>Linux-Sysmon/Operational</Channel><Computer>user-VirtualBox</Computer><Security UserId="0"/></System><EventData><Data Name="RuleName">-</Data><Data Name="UtcTime">2022-01-04 19:52:55.682</Data><Data Name="ProcessGuid">{49e2a5f6-a597-61d4-5d6a-98d813560000}</Data><Data Name="ProcessId">49520</Data><Data Name="Image">/usr/bin/systemctl</Data><Data Name="User">user</Data></EventData></Event>
+<!-- <Event></Event> -->
+<Event><System><Provider Name="Linux-Sysmon" Guid="{ff032593-a8d3-4f13-b0d6-01fc615a0f97}"/><EventID>5</EventID><Version>3</Version><Level>4</Level><Task>5</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime="2022-01-04T19:52:55.683744000Z"/><EventRecordID>10</EventRecordID><Correlation/><Execution ProcessID="49514" ThreadID="49514"/><Channel>Linux-Sysmon/Operational</Channel><Computer>user-VirtualBox</Computer><Security UserId="0"/></System><EventData><Data Name="RuleName">-</Data><Data Name="UtcTime">2022-01-04 19:52:55.688</Data><Data Name="ProcessGuid">NOT-A-GUID</Data><Data Name="ProcessId">49521</Data><Data Name="Image">/usr/bin/systemctl</Data><Data Name="User">user</Data></EventData></Event>
+<Event><System><Provider Name="Linux-Sysmon" Guid="{ff032593-a8d3-4f13-b0d6-01fc615a0f97}"/><EventID>5</EventID><Version>3</Version><Level>4</Level><Task>5</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime="2022-01-04T19:52:55.683744000Z"/><EventRecordID>11</EventRecordID><Correlation/><Execution ProcessID="49514" ThreadID="49514"/><Channel>Linux-Sysmon/Operational</Channel><Computer>user-VirtualBox</Computer><Security UserId="0"/></System><EventData><Data Name="RuleName">-</Data><Data Name="UtcTime">2022-01-04 19:52:55.688</Data><Data Name="ProcessGuid">{49e2a5f6-a597-61d4-5d7a-861de5550000}</Data><Data Name="ProcessId">49521</Data><Data Name="Image">/usr/bin/systemctl</Data>
+<Event><System><Provider Name="Linux-Sysmon" Guid="{ff032593-a8d3-4f13-b0d6-01fc615a0f97}"/><EventID>5</EventID><Version>3</Version><Level>4</Level><Task>5</Task><Opcode>0</Opcode><Keywords>0x8000000000000000</Keywords><TimeCreated SystemTime="2022-01-04T19:52:55.683744000Z"/><EventRecordID>12</EventRecordID><Correlation/><Execution ProcessID="49514" ThreadID="49514"/><Channel>Linux-Sysmon/Operational</Channel><Computer>user-VirtualBox</Computer><Security UserId="0"/></System><EventData><Data Name="RuleName">-</Data><Data Name="UtcTime">2022-01-04 19:52:55.688</Data><Data Name="ProcessGuid">{49e2a5f6-a597-61d4-5d7a-861de5550000}</Data><Data Name="ProcessId">49521</Data><Data Name="Image">/usr/bin/systemctl</Data><Data Name="User">user</Data></EventData></Event>
+"#;
+
+/// Returns the `EventRecordID` of each successfully parsed event, or the position of each error.
+fn summarize<'a>(
+    results: impl Iterator<Item = Result<sysmon_parser::SysmonEvent<'a>, sysmon_parser::EventError>>,
+) -> Vec<Result<u64, usize>> {
+    results
+        .map(|result| match result {
+            Ok(event) => Ok(event.system.event_record_id),
+            Err(e) => Err(e.position),
+        })
+        .collect()
+}
+
+/// Returns the position of the `<Event>` start-tag for the event with the given `EventRecordID`.
+fn event_position(event_record_id: u64) -> usize {
+    let record = XML
+        .find(&format!("<EventRecordID>{}<", event_record_id))
+        .unwrap();
+
+    XML[..record].rfind("<Event>").unwrap()
+}
+
+fn expected() -> Vec<Result<u64, usize>> {
+    // the second event has an invalid ProcessGuid, the third is truncated
+    vec![
+        Ok(9),
+        Err(event_position(10)),
+        Err(event_position(11)),
+        Ok(12),
+    ]
+}
+
+#[test]
+fn parse_events_stops_at_first_error() {
+    let results: Vec<_> = sysmon_parser::parse_events(XML).collect();
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn parse_events_recovering() {
+    assert_eq!(
+        summarize(sysmon_parser::parse_events_recovering(XML)),
+        expected()
+    );
+}
+
+/// A reader that returns a single byte per read, to exercise events that span many reads.
+struct OneByteReader<'a>(&'a [u8]);
+
+impl Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(out)) => {
+                *out = *byte;
+                self.0 = rest;
+
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn parse_events_from_reader() {
+    assert_eq!(
+        summarize(sysmon_parser::parse_events_from_reader(XML.as_bytes())),
+        expected()
+    );
+
+    assert_eq!(
+        summarize(sysmon_parser::parse_events_from_reader(OneByteReader(
+            XML.as_bytes()
+        ))),
+        expected()
+    );
+}
+
+#[test]
+fn parse_events_from_reader_io_error() {
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))
+        }
+    }
+
+    let mut events = sysmon_parser::parse_events_from_reader(FailingReader);
+
+    assert!(matches!(
+        events.next(),
+        Some(Err(sysmon_parser::EventError {
+            position: 0,
+            source: sysmon_parser::Error::Io {
+                kind: std::io::ErrorKind::Other,
+                ..
+            },
+        }))
+    ));
+    assert!(events.next().is_none());
+}
@@ -1,4 +1,7 @@
-use dashmap::DashMap;
+use dashmap::{
+    mapref::entry::Entry,
+    DashMap,
+};
 pub use rust_proto::graplinc::grapl::api::uid_allocator::v1beta1::client::UidAllocatorServiceClient;
 use rust_proto::{
     client_factory::{
@@ -13,9 +16,16 @@ use rust_proto::{
             CreateTenantKeyspaceRequest,
         },
     },
-    protocol::service_client::ConnectError,
+    protocol::{
+        service_client::ConnectError,
+        status::Status,
+    },
 };
 
+/// How many empty allocations `allocate_ids` accepts from the allocator
+/// before giving up
+const MAX_EMPTY_ALLOCATIONS: usize = 3;
+
 #[derive(Clone)]
 pub struct CachingUidAllocatorServiceClient {
     pub allocator: UidAllocatorServiceClient,
@@ -63,6 +73,63 @@ impl CachingUidAllocatorServiceClient {
         }
     }
 
+    /// Allocates `count` ids for the tenant, first draining whatever is left of
+    /// its cached allocation. New allocations are at least `self.count` ids, so
+    /// that any left over are cached for subsequent calls.
+    pub async fn allocate_ids(
+        &self,
+        tenant_id: uuid::Uuid,
+        count: usize,
+    ) -> Result<Vec<u64>, UidAllocatorServiceClientError> {
+        let mut ids = Vec::with_capacity(count);
+        if let Some(mut allocation) = self.allocation_map.get_mut(&tenant_id) {
+            ids.extend(allocation.by_ref().take(count));
+        }
+
+        let mut empty_allocations = 0;
+        while ids.len() < count {
+            let remaining = u32::try_from(count - ids.len()).unwrap_or(u32::MAX);
+            let mut allocator = self.allocator.clone();
+            let mut allocation = allocator
+                .allocate_ids(AllocateIdsRequest {
+                    tenant_id: tenant_id.into(),
+                    count: remaining.max(self.count),
+                })
+                .await?
+                .allocation;
+            let allocated = ids.len();
+            ids.extend(allocation.by_ref().take(count - ids.len()));
+            if ids.len() == allocated {
+                empty_allocations += 1;
+                if empty_allocations >= MAX_EMPTY_ALLOCATIONS {
+                    return Err(UidAllocatorServiceClientError::ErrorStatus(
+                        Status::internal("UidAllocator returned an empty allocation"),
+                    ));
+                }
+                continue;
+            }
+
+            self.cache_allocation(tenant_id, allocation);
+        }
+
+        Ok(ids)
+    }
+
+    /// Caches what's left of `allocation`, unless the tenant's cached
+    /// allocation, e.g. one cached by a concurrent call, has more ids left.
+    fn cache_allocation(&self, tenant_id: uuid::Uuid, allocation: Allocation) {
+        match self.allocation_map.entry(tenant_id) {
+            Entry::Occupied(mut cached) => {
+                if remaining_ids(cached.get()) < remaining_ids(&allocation) {
+                    cached.insert(allocation);
+                }
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(allocation);
+            }
+        }
+    }
+
     pub async fn create_tenant_keyspace(
         &mut self,
         request: CreateTenantKeyspaceRequest,
@@ -80,6 +147,11 @@ impl CachingUidAllocatorServiceClient {
     }
 }
 
+/// The number of ids an `Allocation` will still hand out
+fn remaining_ids(allocation: &Allocation) -> u64 {
+    u64::from(allocation.offset).saturating_sub(allocation.start)
+}
+
 impl std::fmt::Debug for CachingUidAllocatorServiceClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut d = f.debug_struct("CachingUidAllocatorServiceClient");
@@ -89,3 +161,23 @@ impl std::fmt::Debug for CachingUidAllocatorServiceClient {
         d.field("count", &self.count).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_ids_matches_allocation() {
+        let mut allocation = Allocation {
+            start: 5,
+            offset: 15,
+        };
+        assert_eq!(remaining_ids(&allocation), 10);
+
+        allocation.by_ref().take(4).for_each(drop);
+        assert_eq!(remaining_ids(&allocation), 6);
+
+        allocation.by_ref().for_each(drop);
+        assert_eq!(remaining_ids(&allocation), 0);
+    }
+}